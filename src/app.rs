@@ -4,17 +4,25 @@
 //! It manages the project tree, command history, tabs, and various UI modes.
 
 use crate::components::{
-    filter::FilterState, palette::CommandPaletteState, progress::ProgressState,
-    settings::SettingsModalState, text_input::TextInputState, updater::UpdateWizardState,
+    disk_usage::DiskUsageState, features::FeatureExplorerState, filter::FilterState,
+    palette::CommandPaletteState, progress::ProgressState, project_details::ProjectDetailsState,
+    reclaim::ReclaimState, settings::SettingsModalState, text_input::TextInputState,
+    toolchains::ToolchainMatrixState, updater::UpdateWizardState,
 };
 use crate::config::Config;
 use crate::events::{Action, Focus, Mode};
-use crate::project::Project;
+use crate::mouse::MouseState;
+use crate::project::{Project, ProjectId};
+use crate::registry::RegistryClient;
+use crate::roots::ScanRoots;
 use crate::runner::UpdateQueue;
-use crate::settings::AppSettings;
+use crate::scan::ScanProgress;
+use crate::settings::{AppSettings, DiscoveryBackend};
+use crate::target_dir::TargetDirs;
 use crate::tree::{FlattenedTree, TreeNode, TreeSelectionState};
 use ratatui::widgets::ListState;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 /// Represents the complete state of the CarWash application
 ///
@@ -26,6 +34,14 @@ pub struct AppState {
     pub should_quit: bool,
     /// Whether the application is currently scanning for projects
     pub is_scanning: bool,
+    /// Progress of the background project scan, while it runs
+    pub scan_progress: Option<ScanProgress>,
+    /// Directories being scanned for projects
+    pub scan_roots: ScanRoots,
+    /// Tag groups collapsed while grouping by tag
+    pub collapsed_tags: HashSet<String>,
+    /// Groups of the project table that are collapsed, by key (`#tag`, `@workspace`, `!status`)
+    pub collapsed_groups: HashSet<String>,
     /// Whether the application is checking for updates
     pub is_checking_updates: bool,
     /// Current application mode
@@ -45,11 +61,13 @@ pub struct AppState {
     /// Set of workspace names that are collapsed in the tree view
     pub collapsed_workspaces: HashSet<String>,
     /// Set of selected project paths
-    pub selected_projects: HashSet<String>,
+    pub selected_projects: HashSet<ProjectId>,
     /// Tab panes for command output
     pub tabs: Vec<Tab>,
     /// Index of the currently active tab
     pub active_tab: usize,
+    /// Scroll offset of the active tab's output
+    pub output_scroll: usize,
     /// History of executed commands
     pub command_history: Vec<String>,
     /// State of the command palette
@@ -72,6 +90,54 @@ pub struct AppState {
     pub progress: Option<ProgressState>,
     /// Currently focused pane
     pub focus: Focus,
+    /// Selection within the dependencies pane
+    pub dependency_list: ListState,
+    /// Scroll offset of the dependency detail view
+    pub dependency_detail_scroll: usize,
+    /// Project details pane, shown in place of the dependencies pane
+    pub details: ProjectDetailsState,
+    /// Feature explorer, feature flags per project and the latest feature matrix
+    pub features: FeatureExplorerState,
+    /// Latest toolchain matrix and failed MSRV checks
+    pub toolchains: ToolchainMatrixState,
+    /// Target directory each project builds into, resolved when sizes are calculated
+    pub target_dirs: TargetDirs,
+    /// Disk reclamation view: breakdowns, sweep criteria and the dry run
+    pub reclaim: ReclaimState,
+    /// Disk usage view of a project: listings per directory and the path descended
+    pub disk_usage: DiskUsageState,
+    /// Where the panes were last drawn, for mouse events
+    pub mouse: MouseState,
+    /// Session saved for the scan roots, restored once their projects are listed
+    pub pending_session: Option<crate::session::Session>,
+    /// Registry client shared by all update checks (global rate limit)
+    pub registry: Arc<RegistryClient>,
+    /// Set while offline: update checks are served from the cache only
+    pub offline: Option<OfflineReason>,
+    /// Discovery backend for this session (setting, or `--cargo-metadata`)
+    pub discovery: DiscoveryBackend,
+}
+
+/// Why the application is running in offline mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfflineReason {
+    /// Requested with `--offline` on the command line
+    Flag,
+    /// Enabled in the persisted settings
+    Setting,
+    /// Turned on automatically after the registry became unreachable
+    Detected,
+}
+
+impl OfflineReason {
+    /// Short label for the status bar
+    pub fn label(&self) -> &'static str {
+        match self {
+            OfflineReason::Flag => "--offline",
+            OfflineReason::Setting => "setting",
+            OfflineReason::Detected => "no network",
+        }
+    }
 }
 
 /// Represents a tab pane for displaying command output
@@ -106,10 +172,19 @@ impl Default for AppState {
         ];
         let mut tree_state = ListState::default();
         tree_state.select(Some(0));
+        let config = Config::load();
+        let registry = Arc::new(RegistryClient::new(&config.checker));
+        let settings = AppSettings::load();
+        let offline = settings.offline.then_some(OfflineReason::Setting);
+        let discovery = settings.discovery;
 
         Self {
             should_quit: false,
             is_scanning: true,
+            scan_progress: None,
+            scan_roots: ScanRoots::default(),
+            collapsed_tags: HashSet::new(),
+            collapsed_groups: HashSet::new(),
             is_checking_updates: false,
             mode: Mode::Loading,
             tree_root: None,
@@ -122,17 +197,31 @@ impl Default for AppState {
             selected_projects: HashSet::new(),
             tabs: Vec::new(),
             active_tab: 0,
+            output_scroll: 0,
             command_history,
             palette: CommandPaletteState::new(),
             updater: UpdateWizardState::new(),
             text_input: TextInputState::new(),
-            update_queue: UpdateQueue::new(),
-            settings: AppSettings::load(),
+            update_queue: UpdateQueue::new(config.checker.concurrency),
+            settings,
             settings_modal: SettingsModalState::new(),
             filter: FilterState::new(),
-            config: Config::load(),
+            config,
             progress: None,
             focus: Focus::default(),
+            dependency_list: ListState::default(),
+            dependency_detail_scroll: 0,
+            details: ProjectDetailsState::default(),
+            features: FeatureExplorerState::default(),
+            toolchains: ToolchainMatrixState::default(),
+            target_dirs: TargetDirs::default(),
+            reclaim: ReclaimState::default(),
+            disk_usage: DiskUsageState::default(),
+            mouse: MouseState::default(),
+            pending_session: None,
+            registry,
+            offline,
+            discovery,
         }
     }
 }
@@ -142,6 +231,11 @@ impl AppState {
         Self::default()
     }
 
+    /// Whether update checks must be served from the cache only
+    pub fn is_offline(&self) -> bool {
+        self.offline.is_some()
+    }
+
     /// Get the current active color scheme based on config
     pub fn current_colors(&self) -> crate::ui::styles::Colors {
         self.config.theme().current_scheme().colors()
@@ -169,18 +263,21 @@ impl AppState {
                 if let crate::tree::TreeNodeType::Project(tree_project) = &node.node_type {
                     // IMPORTANT: The tree contains cloned copies of projects,
                     // but dependencies are updated in all_projects.
-                    // So we need to look up the project by name in all_projects
+                    // So we need to look up the project by id in all_projects
                     // to get the current state with updated dependencies.
-                    return self
-                        .all_projects
-                        .iter()
-                        .find(|p| p.name == tree_project.name);
+                    return self.all_projects.iter().find(|p| p.id == tree_project.id);
                 }
             }
         }
         None
     }
 
+    /// Get the dependency selected in the dependencies pane, if any
+    pub fn get_selected_dependency(&self) -> Option<&crate::project::Dependency> {
+        let index = self.dependency_list.selected()?;
+        self.get_selected_project()?.dependencies.get(index)
+    }
+
     /// Get list of projects that should be visible (excluding collapsed workspace members)
     pub fn get_visible_projects(&self) -> Vec<&Project> {
         let mut last_workspace: Option<&String> = None;
@@ -230,7 +327,7 @@ impl AppState {
                     // Return the actual project from all_projects (has updated dependencies)
                     self.all_projects
                         .iter()
-                        .filter(|p| p.name == tree_project.name)
+                        .filter(|p| p.id == tree_project.id)
                         .collect()
                 }
                 crate::tree::TreeNodeType::Directory { .. } => {
@@ -239,7 +336,7 @@ impl AppState {
                     // Map to actual projects from all_projects (has updated dependencies)
                     tree_projects
                         .into_iter()
-                        .filter_map(|tp| self.all_projects.iter().find(|p| p.name == tp.name))
+                        .filter_map(|tp| self.all_projects.iter().find(|p| p.id == tp.id))
                         .collect()
                 }
             }
@@ -262,36 +359,69 @@ pub fn reducer(state: &mut AppState, action: Action) {
         Action::ShowHelp => handle_show_help(state),
         Action::ShowSettings => handle_show_settings(state),
         Action::CloseSettings => handle_close_settings(state),
-        Action::FinishProjectScan(projects, target_dir) => {
-            handle_finish_project_scan(state, projects, target_dir)
+        Action::FinishProjectScan(projects, roots) => {
+            handle_finish_project_scan(state, projects, roots)
+        }
+        Action::ProjectsDiscovered(projects, progress) => {
+            handle_projects_discovered(state, projects, progress)
         }
+        Action::ProjectsChanged(diff) => handle_projects_changed(state, diff),
         Action::UpdateTextInput(s) => handle_update_text_input(state, s),
+        Action::SubmitTextInput => handle_submit_text_input(state),
         Action::SelectNext => handle_select_next(state),
         Action::SelectPrevious => handle_select_previous(state),
         Action::SelectParent => handle_select_parent(state),
         Action::SelectChild => handle_select_child(state),
         Action::ToggleSelection => handle_toggle_selection(state),
+        Action::SelectRow(index) => handle_select_row(state, index),
+        Action::ToggleRowSelection(index) => {
+            handle_select_row(state, index);
+            handle_toggle_selection(state);
+        }
         Action::ShowCommandPalette => handle_show_command_palette(state),
         Action::UpdatePaletteInput(input) => handle_update_palette_input(state, input),
         Action::PaletteSelectNext => handle_palette_select_next(state),
         Action::PaletteSelectPrevious => handle_palette_select_previous(state),
         Action::StartUpdateWizard => handle_start_update_wizard(state),
+        Action::DependencySelectNext => handle_dependency_select_next(state),
+        Action::DependencySelectPrevious => handle_dependency_select_previous(state),
+        Action::ShowDependencyDetail => handle_show_dependency_detail(state),
+        Action::ToggleProjectDetails => handle_toggle_project_details(state),
+        Action::ShowFeatureExplorer => handle_show_feature_explorer(state),
+        Action::ToggleFeature => handle_toggle_feature(state),
+        Action::ToggleNoDefaultFeatures => handle_toggle_no_default_features(state),
+        Action::ToggleAllFeatures => handle_toggle_all_features(state),
+        Action::StartFeatureMatrix => handle_start_feature_matrix(state),
+        Action::FeatureMatrixStatus(tab_index, run, status) => {
+            handle_feature_matrix_status(state, tab_index, run, status)
+        }
+        Action::StartToolchainMatrix(command, installed) => {
+            handle_start_toolchain_matrix(state, command, installed)
+        }
+        Action::ToolchainMatrixStatus(project_id, column, status) => {
+            handle_toolchain_matrix_status(state, project_id, column, status)
+        }
+        Action::ShowToolchainMatrix => handle_show_toolchain_matrix(state),
         Action::ToggleUpdateSelection => handle_toggle_update_selection(state),
         Action::CheckForUpdates => handle_check_for_updates(state),
         Action::SettingsUpdateCacheInput(input) => handle_settings_update_cache_input(state, input),
         Action::SettingsToggleBackground => handle_settings_toggle_background(state),
+        Action::SettingsToggleOffline => handle_settings_toggle_offline(state),
+        Action::SettingsToggleSessionRestore => handle_settings_toggle_session_restore(state),
+        Action::OfflineDetected => handle_offline_detected(state),
+        Action::RegistryReachable => handle_registry_reachable(state),
         Action::SaveSettings => handle_save_settings(state),
-        Action::UpdateDependencies(project_name, deps) => {
-            handle_update_dependencies(state, project_name, deps)
+        Action::UpdateDependencies(project_id, deps) => {
+            handle_update_dependencies(state, project_id, deps)
         }
-        Action::UpdateDependenciesStreamStart(project_name) => {
-            handle_update_dependencies_stream_start(state, project_name)
+        Action::UpdateDependenciesStreamStart(project_id) => {
+            handle_update_dependencies_stream_start(state, project_id)
         }
-        Action::UpdateSingleDependency(project_name, dep) => {
-            handle_update_single_dependency(state, project_name, dep)
+        Action::UpdateSingleDependency(project_id, dep) => {
+            handle_update_single_dependency(state, project_id, dep)
         }
-        Action::UpdateDependencyCheckStatus(project_name, dep_name, status) => {
-            handle_update_dependency_status(state, Some(project_name), dep_name, status)
+        Action::UpdateDependencyCheckStatus(project_id, dep_name, status) => {
+            handle_update_dependency_status(state, Some(project_id), dep_name, status)
         }
         Action::CreateTab(title) => handle_create_tab(state, title),
         Action::AddOutput(tab_index, line) => handle_add_output(state, tab_index, line),
@@ -312,15 +442,16 @@ pub fn reducer(state: &mut AppState, action: Action) {
         Action::ProcessBackgroundUpdateQueue => {
             // Background update queue processing is handled in main event loop
         }
-        Action::QueueBackgroundUpdate(project_name, is_priority) => {
-            handle_queue_background_update(state, project_name, is_priority)
+        Action::QueueBackgroundUpdate(project_id, is_priority) => {
+            handle_queue_background_update(state, project_id, is_priority)
         }
-        Action::UpdateProjectCheckStatus(project_name, check_status) => {
-            handle_update_project_check_status(state, project_name, check_status)
+        Action::UpdateProjectCheckStatus(project_id, check_status) => {
+            handle_update_project_check_status(state, project_id, check_status)
         }
         Action::EnterFilterMode => handle_enter_filter_mode(state),
         Action::ExitFilterMode => handle_exit_filter_mode(state),
         Action::UpdateFilterInput(input) => handle_update_filter_input(state, input),
+        Action::SelectFilterMatches => handle_select_filter_matches(state),
         Action::ClearFilter => handle_clear_filter(state),
         Action::CycleTheme => handle_cycle_theme(state),
         Action::SetTheme(theme_name) => handle_set_theme(state, theme_name),
@@ -328,18 +459,70 @@ pub fn reducer(state: &mut AppState, action: Action) {
         Action::DecreaseLeftPane => handle_decrease_left_pane(state),
         Action::IncreaseTopRight => handle_increase_top_right(state),
         Action::DecreaseTopRight => handle_decrease_top_right(state),
+        Action::ResizeLeftPane(percent) => handle_resize_left_pane(state, percent),
+        Action::ResizeTopRight(percent) => handle_resize_top_right(state, percent),
         Action::ResetLayout => handle_reset_layout(state),
         Action::SaveConfig => handle_save_config(state),
         Action::ToggleShowAllFolders => handle_toggle_show_all_folders(state),
+        Action::ToggleGroupByTag => handle_toggle_group_by_tag(state),
+        Action::ToggleProjectTable => handle_toggle_project_table(state),
+        Action::CycleTableSort => handle_change_table_view(state, |view| view.cycle_sort()),
+        Action::ReverseTableSort => {
+            handle_change_table_view(state, |view| view.descending = !view.descending)
+        }
+        Action::CycleTableGrouping => {
+            handle_change_table_view(state, |view| view.grouping = view.grouping.next())
+        }
+        Action::CheckProjectActivity => {
+            // Git and modification checks are handled in main event loop (async)
+        }
+        Action::UpdateProjectActivity(project_id, git_status, modified) => {
+            handle_update_project_activity(state, project_id, git_status, modified)
+        }
+        Action::UpdateProjectStatus(project_id, status) => {
+            handle_update_project_status(state, project_id, status)
+        }
+        Action::EditProjectTags => handle_edit_project_tags(state),
+        Action::SelectTag(tag) => handle_select_tag(state, tag),
+        Action::BulkSelect(selection) => handle_bulk_select(state, selection),
+        Action::SaveSelection(name) => handle_save_selection(state, name),
+        Action::DeleteSelection(name) => handle_delete_selection(state, name),
         Action::CalculateProjectSizes => {
             // Size calculation is handled in main event loop (async)
         }
-        Action::UpdateProjectSize(project_name, total_size, target_size) => {
-            handle_update_project_size(state, project_name, total_size, target_size)
+        Action::UpdateProjectSize(project_id, total_size, target_size) => {
+            handle_update_project_size(state, project_id, total_size, target_size)
+        }
+        Action::TargetDirsResolved(dirs) => state.target_dirs = TargetDirs::new(dirs),
+        Action::UpdateTargetSize(dir, target_size) => {
+            handle_update_target_size(state, dir, target_size)
+        }
+        Action::ShowReclaim => handle_show_reclaim(state),
+        Action::ReclaimSelectNext => handle_reclaim_select(state, true),
+        Action::ReclaimSelectPrevious => handle_reclaim_select(state, false),
+        Action::TargetBreakdownLoaded(dir, breakdown) => {
+            handle_target_breakdown_loaded(state, dir, breakdown)
+        }
+        Action::SweepCriteriaChanged => handle_sweep_criteria_changed(state),
+        Action::PreviewSweep(_) | Action::ExecuteSweep => {
+            // Dry runs and sweeps are handled in main event loop (async)
         }
+        Action::SweepPlanned(plan) => handle_sweep_planned(state, plan),
+        Action::SweepFinished(dirs, freed, errors) => {
+            handle_sweep_finished(state, dirs, freed, errors)
+        }
+        Action::ShowDiskUsage => handle_show_disk_usage(state),
+        Action::DiskUsageSelectNext => handle_disk_usage_select(state, true),
+        Action::DiskUsageSelectPrevious => handle_disk_usage_select(state, false),
+        Action::DiskUsageOpen => handle_disk_usage_open(state),
+        Action::DiskUsageUp => handle_disk_usage_up(state),
+        Action::DiskUsageLoaded(dir, entries) => handle_disk_usage_loaded(state, dir, entries),
         Action::FocusNext => handle_focus_next(state),
-        Action::InitializeTree(target_dir) => handle_initialize_tree(state, target_dir),
-        Action::ExpandDirectory(..) => {
+        Action::InitializeTree(roots) => handle_initialize_tree(state, roots),
+        Action::SwitchRoots(roots) => handle_switch_roots(state, roots),
+        Action::ExpandDirectory(..)
+        | Action::ProjectFilesChanged(_)
+        | Action::DependenciesReloaded(..) => {
             // Handled in main loop asynchronously
         }
         Action::DirectoryLoaded(path, children) => handle_directory_loaded(state, path, children),
@@ -349,17 +532,28 @@ pub fn reducer(state: &mut AppState, action: Action) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::events::{Action, Mode};
-    use crate::project::{Project, ProjectStatus};
+    use crate::events::{Action, Command, Mode};
+    use crate::features::MatrixStatus;
+    use crate::project::{Project, ProjectCheckStatus, ProjectStatus};
+    use crate::toolchains::ToolchainStatus;
     use std::path::PathBuf;
 
     fn create_test_project(name: &str) -> Project {
+        let path = PathBuf::from(name);
         Project {
+            id: crate::project::ProjectId::from_dir(&path),
             name: name.to_string(),
-            path: PathBuf::from("test"),
+            display_name: name.to_string(),
+            path,
             status: ProjectStatus::Pending,
             version: "0.1.0".to_string(),
             authors: vec![],
+            edition: None,
+            rust_version: None,
+            license: None,
+            keywords: Vec::new(),
+            categories: Vec::new(),
+            tags: Vec::new(),
             dependencies: vec![],
             workspace_root: None,
             workspace_name: None,
@@ -368,6 +562,8 @@ mod tests {
             git_status: crate::project::GitStatus::Clean,
             total_size: None,
             target_size: None,
+            modified: None,
+            update_policy: Default::default(),
         }
     }
 
@@ -408,6 +604,382 @@ mod tests {
         assert_eq!(state.mode, Mode::Help);
     }
 
+    #[test]
+    fn test_reducer_dependency_detail_needs_selection() {
+        let mut state = AppState::new();
+        state.mode = Mode::Normal;
+
+        // No project selected: nothing to select or show
+        reducer(&mut state, Action::DependencySelectNext);
+        assert_eq!(state.dependency_list.selected(), None);
+        reducer(&mut state, Action::ShowDependencyDetail);
+        assert_eq!(state.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn test_toggle_project_details() {
+        let mut state = AppState::new();
+        state.details.scroll = 3;
+
+        reducer(&mut state, Action::ToggleProjectDetails);
+        assert!(state.details.visible);
+        assert_eq!(state.details.scroll, 0);
+
+        let fixture =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/details");
+        let project = crate::project::find_rust_projects(fixture.to_str().unwrap()).remove(0);
+        assert!(!state.details.details_for(&project).targets.is_empty());
+
+        // Scrolling is kept while the same project stays selected
+        state.details.scroll = 2;
+        state.details.details_for(&project);
+        assert_eq!(state.details.scroll, 2);
+        state.details.details_for(&create_test_project("other"));
+        assert_eq!(state.details.scroll, 0);
+
+        reducer(&mut state, Action::ToggleProjectDetails);
+        assert!(!state.details.visible);
+    }
+
+    #[test]
+    fn test_feature_explorer_flags_and_matrix() {
+        let mut state = AppState::new();
+        state.settings.group_by_tag = true;
+        let fixture =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/details");
+        let projects = crate::project::find_rust_projects(fixture.to_str().unwrap());
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(projects, ScanProgress::default()),
+        );
+        state.tree_state.select(Some(1));
+
+        reducer(&mut state, Action::ShowFeatureExplorer);
+        assert_eq!(state.mode, Mode::FeatureExplorer);
+        assert_eq!(state.features.available.names(), ["fast", "json"]);
+
+        // "json" is second; picking it and dropping defaults sets the project's flags
+        state.features.list_state.select(Some(1));
+        reducer(&mut state, Action::ToggleFeature);
+        reducer(&mut state, Action::ToggleNoDefaultFeatures);
+        let id = state.all_projects[0].id.clone();
+        assert_eq!(
+            state.features.flags[&id].apply("test --release"),
+            "test --release --no-default-features --features json"
+        );
+        reducer(&mut state, Action::ToggleFeature);
+        reducer(&mut state, Action::ToggleNoDefaultFeatures);
+        assert!(!state.features.flags.contains_key(&id));
+
+        reducer(&mut state, Action::StartFeatureMatrix);
+        let matrix = state.features.matrix.clone().unwrap();
+        assert_eq!(matrix.runs.len(), 4);
+        assert_eq!(state.tabs.len(), 1);
+
+        // A second matrix waits for the first; results only reach their own tab
+        reducer(&mut state, Action::StartFeatureMatrix);
+        assert_eq!(state.tabs.len(), 1);
+        reducer(
+            &mut state,
+            Action::FeatureMatrixStatus(matrix.tab_index, 0, MatrixStatus::Passed),
+        );
+        reducer(
+            &mut state,
+            Action::FeatureMatrixStatus(matrix.tab_index + 1, 1, MatrixStatus::Failed),
+        );
+        assert_eq!(state.features.matrix.as_ref().unwrap().counts(), (1, 0));
+    }
+
+    #[test]
+    fn test_toolchain_matrix_flags_msrv_failures() {
+        let mut state = AppState::new();
+        let fixture =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/details");
+        let projects = crate::project::find_rust_projects(fixture.to_str().unwrap());
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(projects, ScanProgress::default()),
+        );
+        let installed = vec![
+            "stable-x86_64-unknown-linux-gnu".to_string(),
+            "1.80.1-x86_64-unknown-linux-gnu".to_string(),
+        ];
+
+        // Nothing selected, nothing to run
+        reducer(
+            &mut state,
+            Action::StartToolchainMatrix("check".into(), installed.clone()),
+        );
+        assert!(state.toolchains.matrix.is_none());
+
+        // Dependency-less projects stay out of the list commands run on
+        state.projects = state.all_projects.clone();
+        let id = state.projects[0].id.clone();
+        state.selected_projects.insert(id.clone());
+        reducer(
+            &mut state,
+            Action::StartToolchainMatrix("check".into(), installed),
+        );
+        assert_eq!(state.mode, Mode::ToolchainMatrix);
+        assert_eq!(state.tabs.len(), 1);
+        let matrix = state.toolchains.matrix.as_ref().unwrap();
+        let statuses: Vec<&ToolchainStatus> =
+            matrix.rows[0].cells.iter().map(|c| &c.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                &ToolchainStatus::Pending,
+                &ToolchainStatus::Pending,
+                &ToolchainStatus::NotInstalled
+            ]
+        );
+
+        reducer(
+            &mut state,
+            Action::ToolchainMatrixStatus(id.clone(), 1, ToolchainStatus::Failed),
+        );
+        assert!(state.toolchains.msrv_failures.contains(&id));
+        reducer(
+            &mut state,
+            Action::ToolchainMatrixStatus(id.clone(), 0, ToolchainStatus::Passed),
+        );
+        reducer(
+            &mut state,
+            Action::ToolchainMatrixStatus(id.clone(), 1, ToolchainStatus::Passed),
+        );
+        assert!(!state.toolchains.msrv_failures.contains(&id));
+        assert!(!state.toolchains.matrix.as_ref().unwrap().is_running());
+    }
+
+    #[test]
+    fn test_shared_target_size_reaches_every_project() {
+        let mut state = AppState::new();
+        let a = create_test_project("a");
+        let b = create_test_project("b");
+        let c = create_test_project("c");
+        state.all_projects = vec![a.clone(), b.clone(), c.clone()];
+        state.projects = state.all_projects.clone();
+        let shared = PathBuf::from("/shared/target");
+        reducer(
+            &mut state,
+            Action::TargetDirsResolved(vec![
+                (a.id.clone(), shared.clone()),
+                (b.id.clone(), shared.clone()),
+                (c.id.clone(), PathBuf::from("c/target")),
+            ]),
+        );
+        reducer(
+            &mut state,
+            Action::UpdateProjectSize(a.id.clone(), Some(10), Some(500)),
+        );
+
+        // Cleaning through `a` empties the directory `b` builds into as well
+        reducer(&mut state, Action::UpdateTargetSize(shared, Some(0)));
+        let sizes: Vec<(Option<u64>, Option<u64>)> = state
+            .all_projects
+            .iter()
+            .map(|p| (p.total_size, p.target_size))
+            .collect();
+        assert_eq!(sizes, [(Some(10), Some(0)), (None, Some(0)), (None, None)]);
+        assert_eq!(state.target_dirs.shared_by(&a.id), 2);
+    }
+
+    #[test]
+    fn test_reclaim_ranks_target_dirs_and_tracks_sweeps() {
+        let mut state = AppState::new();
+        let a = create_test_project("a");
+        let b = create_test_project("b");
+        state.all_projects = vec![a.clone(), b.clone()];
+        let (a_target, b_target) = (PathBuf::from("a/target"), PathBuf::from("b/target"));
+        reducer(
+            &mut state,
+            Action::TargetDirsResolved(vec![
+                (a.id.clone(), a_target.clone()),
+                (b.id.clone(), b_target.clone()),
+            ]),
+        );
+        reducer(
+            &mut state,
+            Action::UpdateProjectSize(a.id.clone(), None, Some(10)),
+        );
+        reducer(
+            &mut state,
+            Action::UpdateProjectSize(b.id.clone(), None, Some(900)),
+        );
+
+        reducer(&mut state, Action::ShowReclaim);
+        assert_eq!(state.mode, Mode::Reclaim);
+        assert_eq!(state.reclaim.criteria.older_than_days, Some(30));
+        let entries = crate::components::reclaim::reclaim_entries(&state);
+        assert_eq!(state.reclaim.selected_dir(&entries), Some(b_target.clone()));
+        reducer(&mut state, Action::ReclaimSelectNext);
+        reducer(&mut state, Action::ReclaimSelectNext);
+        assert_eq!(state.reclaim.table_state.selected(), Some(0));
+
+        // A preview goes stale when the criteria change
+        let plan = crate::sweep::SweepPlan {
+            target_dirs: vec![b_target.clone()],
+            bytes: 500,
+            criteria: state.reclaim.criteria.clone(),
+            ..Default::default()
+        };
+        reducer(&mut state, Action::SweepPlanned(plan.clone()));
+        assert!(state.reclaim.plan.is_some());
+        state.reclaim.step_age(true);
+        assert_eq!(state.reclaim.criteria.older_than_days, Some(60));
+        reducer(&mut state, Action::SweepCriteriaChanged);
+        assert!(state.reclaim.plan.is_none());
+
+        // So does one still running when they change
+        reducer(&mut state, Action::SweepPlanned(plan));
+        assert!(state.reclaim.plan.is_none());
+        assert!(state.reclaim.message.is_some());
+
+        state
+            .reclaim
+            .breakdowns
+            .insert(b_target.clone(), crate::sweep::TargetBreakdown::default());
+        reducer(
+            &mut state,
+            Action::SweepFinished(vec![b_target.clone()], 500, Vec::new()),
+        );
+        assert!(!state.reclaim.breakdowns.contains_key(&b_target));
+        assert_eq!(state.reclaim.message.as_deref(), Some("Swept 500B"));
+    }
+
+    #[test]
+    fn test_disk_usage_drills_down_and_back_up() {
+        let mut state = AppState::new();
+        state.settings.group_by_tag = true;
+        let project = create_test_project("app");
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(vec![project.clone()], ScanProgress::default()),
+        );
+        state.tree_state.select(Some(1));
+        reducer(
+            &mut state,
+            Action::TargetDirsResolved(vec![(project.id.clone(), PathBuf::from("shared"))]),
+        );
+
+        reducer(&mut state, Action::ShowDiskUsage);
+        assert_eq!(state.mode, Mode::DiskUsage);
+        assert_eq!(state.disk_usage.target_dir, PathBuf::from("shared"));
+        assert_eq!(state.disk_usage.current(), Some(project.path.as_path()));
+
+        let entry = |path: &str, bytes, is_dir| crate::disk_usage::UsageEntry {
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: PathBuf::from(path),
+            bytes,
+            is_dir,
+            category: None,
+        };
+        reducer(
+            &mut state,
+            Action::DiskUsageLoaded(
+                project.path.clone(),
+                vec![entry("app/.git", 700, true), entry("app/src", 50, true)],
+            ),
+        );
+        assert_eq!(state.disk_usage.table_state.selected(), Some(0));
+
+        reducer(&mut state, Action::DiskUsageSelectNext);
+        reducer(&mut state, Action::DiskUsageOpen);
+        assert_eq!(
+            state.disk_usage.current(),
+            Some(std::path::Path::new("app/src"))
+        );
+        assert!(!state.disk_usage.at_root());
+        reducer(
+            &mut state,
+            Action::DiskUsageLoaded(
+                PathBuf::from("app/src"),
+                vec![entry("app/src/main.rs", 50, false)],
+            ),
+        );
+        assert_eq!(state.disk_usage.table_state.selected(), Some(0));
+        // Files can't be opened
+        reducer(&mut state, Action::DiskUsageOpen);
+        assert_eq!(
+            state.disk_usage.current(),
+            Some(std::path::Path::new("app/src"))
+        );
+
+        // Going up selects the directory just left
+        reducer(&mut state, Action::DiskUsageUp);
+        assert!(state.disk_usage.at_root());
+        assert_eq!(state.disk_usage.table_state.selected(), Some(1));
+        reducer(&mut state, Action::DiskUsageUp);
+        assert!(state.disk_usage.at_root());
+    }
+
+    #[test]
+    fn test_project_table_sorts_groups_and_follows_cursor() {
+        use crate::project_table::{SortColumn, TableGrouping};
+        let mut state = AppState::new();
+        state.settings.table_view.enabled = true;
+        state.settings.table_view.sort = SortColumn::Size;
+        state.settings.table_view.descending = true;
+        let projects: Vec<Project> = ["a", "b", "c"]
+            .into_iter()
+            .map(create_test_project)
+            .collect();
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(projects.clone(), ScanProgress::default()),
+        );
+        for (project, size) in projects.iter().zip([10, 500, 90]) {
+            reducer(
+                &mut state,
+                Action::UpdateProjectSize(project.id.clone(), None, Some(size)),
+            );
+        }
+        let names = |state: &AppState| -> Vec<String> {
+            state
+                .flattened_tree
+                .items
+                .iter()
+                .map(|(node, _)| node.node_type.name().to_string())
+                .collect()
+        };
+        assert_eq!(names(&state), ["b", "c", "a"]);
+
+        // The cursor stays on its project when the rows are re-sorted
+        state.tree_state.select(Some(2));
+        reducer(
+            &mut state,
+            Action::UpdateProjectSize(projects[0].id.clone(), None, Some(9000)),
+        );
+        assert_eq!(names(&state), ["a", "b", "c"]);
+        assert_eq!(
+            state.get_selected_project().map(|p| p.name.as_str()),
+            Some("a")
+        );
+
+        reducer(
+            &mut state,
+            Action::UpdateProjectStatus(projects[1].id.clone(), ProjectStatus::Failed),
+        );
+        assert_eq!(state.all_projects[1].status, ProjectStatus::Failed);
+
+        // Grouped by update status, groups collapse and expand with h/l
+        state.settings.table_view.grouping = TableGrouping::Status;
+        reducer(
+            &mut state,
+            Action::UpdateProjectCheckStatus(
+                projects[2].id.clone(),
+                ProjectCheckStatus::HasUpdates,
+            ),
+        );
+        assert_eq!(names(&state), ["outdated", "c", "unchecked", "a", "b"]);
+        state.tree_state.select(Some(0));
+        reducer(&mut state, Action::SelectParent);
+        assert!(state.collapsed_groups.contains("!outdated"));
+        assert_eq!(names(&state), ["outdated", "unchecked", "a", "b"]);
+        reducer(&mut state, Action::SelectChild);
+        assert_eq!(names(&state).len(), 5);
+    }
+
     #[test]
     fn test_reducer_finish_project_scan() {
         let mut state = AppState::new();
@@ -417,7 +989,7 @@ mod tests {
 
         reducer(
             &mut state,
-            Action::FinishProjectScan(projects, ".".to_string()),
+            Action::FinishProjectScan(projects, ScanRoots::from_dirs(&["."])),
         );
         assert!(!state.is_scanning);
         assert_eq!(state.mode, Mode::Normal);
@@ -429,6 +1001,7 @@ mod tests {
     fn test_reducer_toggle_selection() {
         let mut state = AppState::new();
         let project = create_test_project("test1");
+        let id = project.id.clone();
         state.projects = vec![project.clone()];
 
         // Create a simple tree with one project node
@@ -440,11 +1013,372 @@ mod tests {
 
         // Select the project
         reducer(&mut state, Action::ToggleSelection);
-        assert!(state.selected_projects.contains("test1"));
+        assert!(state.selected_projects.contains(&id));
 
         // Deselect the project
         reducer(&mut state, Action::ToggleSelection);
-        assert!(!state.selected_projects.contains("test1"));
+        assert!(!state.selected_projects.contains(&id));
+    }
+
+    #[test]
+    fn test_projects_discovered_streams_into_state() {
+        let mut state = AppState::new();
+        let mut first = create_test_project("repo-a/core");
+        first.name = "core".to_string();
+        let mut second = create_test_project("repo-b/core");
+        second.name = "core".to_string();
+        let progress = ScanProgress {
+            scanned: 10,
+            found: 1,
+        };
+
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(vec![first.clone()], progress),
+        );
+        assert_eq!(state.all_projects.len(), 1);
+        assert_eq!(state.all_projects[0].display_name, "core");
+        assert_eq!(state.scan_progress, Some(progress));
+
+        // A repeated project is ignored; a clashing name gets disambiguated
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(vec![first, second], ScanProgress::default()),
+        );
+        assert_eq!(state.all_projects.len(), 2);
+        assert_eq!(state.all_projects[0].display_name, "core (repo-a/core)");
+
+        reducer(
+            &mut state,
+            Action::FinishProjectScan(Vec::new(), ScanRoots::from_dirs(&["."])),
+        );
+        assert!(state.scan_progress.is_none());
+    }
+
+    #[test]
+    fn test_index_revalidation_applies_only_differences() {
+        let mut state = AppState::new();
+        let mut kept = create_test_project("kept");
+        kept.check_status = ProjectCheckStatus::UpToDate;
+        let edited = create_test_project("edited");
+        let gone = create_test_project("gone");
+        state.all_projects = vec![kept.clone(), edited.clone(), gone.clone()];
+        state.projects = state.all_projects.clone();
+        state.selected_projects.insert(gone.id.clone());
+
+        let mut changed = edited.clone();
+        changed.version = "0.2.0".to_string();
+        let added = create_test_project("added");
+        reducer(
+            &mut state,
+            Action::ProjectsChanged(crate::index::IndexDiff {
+                changed: vec![changed, added],
+                removed: vec![gone.id.clone()],
+            }),
+        );
+
+        let names: Vec<&str> = state.all_projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["added", "edited", "kept"]);
+        assert_eq!(state.all_projects[1].version, "0.2.0");
+        // Untouched projects keep their state
+        assert_eq!(
+            state.all_projects[2].check_status,
+            ProjectCheckStatus::UpToDate
+        );
+        assert!(state.selected_projects.is_empty());
+    }
+
+    #[test]
+    fn test_switch_roots_resets_projects_and_lists_collections() {
+        let mut state = AppState::new();
+        state
+            .config
+            .collections
+            .insert("work".to_string(), vec!["~/src/*".to_string()]);
+        state.command_history = vec!["build".to_string()];
+        let project = create_test_project("test1");
+        state.selected_projects.insert(project.id.clone());
+        state.all_projects = vec![project.clone()];
+        state.projects = vec![project];
+
+        reducer(&mut state, Action::ShowCommandPalette);
+        assert_eq!(
+            state.palette.filtered_commands,
+            [
+                Command::Cargo {
+                    command: "build".to_string()
+                },
+                Command::SwitchCollection {
+                    name: "work".to_string()
+                },
+            ]
+        );
+        reducer(&mut state, Action::UpdatePaletteInput("coll".to_string()));
+        assert_eq!(state.palette.filtered_commands.len(), 1);
+
+        let roots = ScanRoots {
+            collection: Some("work".to_string()),
+            paths: vec![PathBuf::from("/src/a"), PathBuf::from("/src/b")],
+        };
+        reducer(&mut state, Action::SwitchRoots(roots.clone()));
+        assert!(state.all_projects.is_empty());
+        assert!(state.projects.is_empty());
+        assert!(state.selected_projects.is_empty());
+        assert!(state.tree_root.is_none());
+        assert_eq!(state.mode, Mode::Loading);
+        assert_eq!(state.scan_roots, roots);
+    }
+
+    #[test]
+    fn test_tags_group_select_and_filter() {
+        let mut state = AppState::new();
+        state
+            .config
+            .tags
+            .insert("services".to_string(), vec!["api-*".to_string()]);
+        state.settings.group_by_tag = true;
+        let mut tool = create_test_project("tool");
+        tool.keywords = vec!["cli".to_string()];
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(
+                vec![
+                    create_test_project("api-users"),
+                    create_test_project("api-billing"),
+                    tool,
+                ],
+                ScanProgress::default(),
+            ),
+        );
+        assert_eq!(state.all_projects[0].tags, ["services"]);
+
+        // Grouped by tag: "#cli", its project, "#services", its two projects
+        let names: Vec<&str> = state
+            .flattened_tree
+            .items
+            .iter()
+            .map(|(node, _)| node.node_type.name())
+            .collect();
+        assert_eq!(
+            names,
+            ["#cli", "tool", "#services", "api-users", "api-billing"]
+        );
+
+        reducer(&mut state, Action::UpdateFilterInput("#serv".to_string()));
+        assert_eq!(state.filter.filtered_indices, [2, 3, 4]);
+
+        // Collapsing a tag group hides its projects
+        state.tree_state.select(Some(2));
+        reducer(&mut state, Action::SelectParent);
+        assert_eq!(state.flattened_tree.items.len(), 3);
+
+        reducer(&mut state, Action::SelectTag("services".to_string()));
+        assert_eq!(state.selected_projects.len(), 2);
+        assert!(!state.selected_projects.contains(&state.all_projects[2].id));
+    }
+
+    #[test]
+    fn test_filter_query_selects_matches_in_collapsed_groups() {
+        let mut state = AppState::new();
+        state.settings.group_by_tag = true;
+        let mut api = create_test_project("api");
+        api.keywords = vec!["services".to_string()];
+        api.git_status = crate::project::GitStatus::Dirty;
+        let mut tool = create_test_project("tool");
+        tool.keywords = vec!["cli".to_string()];
+        tool.git_status = crate::project::GitStatus::Dirty;
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(
+                vec![api, create_test_project("web"), tool],
+                ScanProgress::default(),
+            ),
+        );
+
+        // Collapse "#services", hiding "api"
+        state.tree_state.select(Some(2));
+        reducer(&mut state, Action::SelectParent);
+
+        reducer(&mut state, Action::EnterFilterMode);
+        assert_eq!(state.filter.matched_projects.len(), 3);
+        reducer(&mut state, Action::UpdateFilterInput("git:".to_string()));
+        assert!(state.filter.error.is_some());
+        reducer(&mut state, Action::SelectFilterMatches);
+        assert_eq!(state.mode, Mode::Filter);
+
+        reducer(
+            &mut state,
+            Action::UpdateFilterInput("git:dirty -#cli OR tool".to_string()),
+        );
+        assert!(state.filter.error.is_none());
+        assert_eq!(state.filter.filtered_indices, [1]);
+        assert_eq!(state.filter.matched_projects.len(), 2);
+
+        reducer(&mut state, Action::SelectFilterMatches);
+        assert_eq!(state.mode, Mode::Normal);
+        assert_eq!(state.tree_state.selected(), Some(1));
+        let mut selected: Vec<&str> = state
+            .all_projects
+            .iter()
+            .filter(|p| state.selected_projects.contains(&p.id))
+            .map(|p| p.name.as_str())
+            .collect();
+        selected.sort();
+        assert_eq!(selected, ["api", "tool"]);
+    }
+
+    #[test]
+    fn test_bulk_and_saved_selections() {
+        use crate::events::BulkSelection;
+        use std::collections::{BTreeSet, HashSet};
+
+        let mut state = AppState::new();
+        state.settings.group_by_tag = true;
+        let mut failed = create_test_project("failed");
+        failed.keywords = vec!["release".to_string()];
+        failed.status = ProjectStatus::Failed;
+        let mut outdated = create_test_project("outdated");
+        outdated.check_status = ProjectCheckStatus::HasUpdates;
+        outdated.dependencies = vec![crate::project::Dependency {
+            name: "serde".to_string(),
+            current_version: "1.0.0".to_string(),
+            latest_version: Some("1.0.200".to_string()),
+            check_status: crate::project::DependencyCheckStatus::Checked,
+            last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
+        }];
+        reducer(
+            &mut state,
+            Action::ProjectsDiscovered(
+                vec![failed, outdated, create_test_project("plain")],
+                ScanProgress::default(),
+            ),
+        );
+        let id = |name: &str| {
+            state
+                .all_projects
+                .iter()
+                .find(|p| p.name == name)
+                .unwrap()
+                .id
+                .clone()
+        };
+        let (failed, outdated, plain) = (id("failed"), id("outdated"), id("plain"));
+        let set = |ids: &[&ProjectId]| ids.iter().map(|&id| id.clone()).collect::<HashSet<_>>();
+
+        // Collapse "#release": only the untagged projects are visible
+        state.tree_state.select(Some(0));
+        reducer(&mut state, Action::SelectParent);
+        reducer(&mut state, Action::BulkSelect(BulkSelection::Visible));
+        assert_eq!(state.selected_projects, set(&[&outdated, &plain]));
+        reducer(&mut state, Action::BulkSelect(BulkSelection::Invert));
+        assert_eq!(state.selected_projects, set(&[&failed]));
+
+        reducer(&mut state, Action::BulkSelect(BulkSelection::Outdated));
+        assert_eq!(state.selected_projects, set(&[&outdated]));
+        reducer(&mut state, Action::BulkSelect(BulkSelection::Failed));
+        assert_eq!(state.selected_projects, set(&[&failed]));
+        reducer(
+            &mut state,
+            Action::BulkSelect(BulkSelection::Matching("dep:serde OR plain".to_string())),
+        );
+        assert_eq!(state.selected_projects, set(&[&outdated, &plain]));
+        reducer(
+            &mut state,
+            Action::BulkSelect(BulkSelection::Matching("git:".to_string())),
+        );
+        assert_eq!(state.selected_projects.len(), 2);
+        reducer(&mut state, Action::BulkSelect(BulkSelection::None));
+        assert!(state.selected_projects.is_empty());
+
+        // Saved selections skip projects that are gone
+        state.settings.saved_selections.insert(
+            "release train".to_string(),
+            BTreeSet::from([
+                failed.clone(),
+                plain.clone(),
+                ProjectId::from_dir(std::path::Path::new("/gone")),
+            ]),
+        );
+        reducer(
+            &mut state,
+            Action::UpdatePaletteInput("selection release".to_string()),
+        );
+        let recall = Command::Select {
+            selection: BulkSelection::Saved("release train".to_string()),
+        };
+        assert_eq!(state.palette.filtered_commands, [recall]);
+        reducer(
+            &mut state,
+            Action::BulkSelect(BulkSelection::Saved("release train".to_string())),
+        );
+        assert_eq!(state.selected_projects, set(&[&failed, &plain]));
+        reducer(
+            &mut state,
+            Action::BulkSelect(BulkSelection::Saved("unknown".to_string())),
+        );
+        assert_eq!(state.selected_projects.len(), 2);
+
+        reducer(
+            &mut state,
+            Action::UpdatePaletteInput("select outdated:>0".to_string()),
+        );
+        assert!(state.palette.filtered_commands.contains(&Command::Select {
+            selection: BulkSelection::Matching("outdated:>0".to_string()),
+        }));
+        reducer(
+            &mut state,
+            Action::UpdatePaletteInput("save selection nightly".to_string()),
+        );
+        assert_eq!(
+            state.palette.filtered_commands[0],
+            Command::SaveSelection {
+                name: "nightly".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_same_name_projects_keyed_by_id() {
+        let mut state = AppState::new();
+        let mut first = create_test_project("repo-a/core");
+        first.name = "core".to_string();
+        let mut second = create_test_project("repo-b/core");
+        second.name = "core".to_string();
+        state.all_projects = vec![first.clone(), second.clone()];
+        state.projects = state.all_projects.clone();
+
+        let mut flattened = crate::tree::FlattenedTree::new();
+        flattened
+            .items
+            .push((crate::tree::TreeNode::project(second.clone(), 0), 0));
+        state.flattened_tree = flattened;
+        state.tree_state.select(Some(0));
+
+        // Selection and lookups resolve to the second project, not the first match by name
+        reducer(&mut state, Action::ToggleSelection);
+        assert!(state.selected_projects.contains(&second.id));
+        assert!(!state.selected_projects.contains(&first.id));
+        assert_eq!(state.get_selected_project().unwrap().id, second.id);
+
+        let dep = crate::project::Dependency {
+            name: "serde".to_string(),
+            current_version: "1.0.0".to_string(),
+            latest_version: None,
+            check_status: crate::project::DependencyCheckStatus::NotChecked,
+            last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
+        };
+        reducer(
+            &mut state,
+            Action::UpdateDependencies(second.id.clone(), vec![dep]),
+        );
+        assert!(state.all_projects[0].dependencies.is_empty());
+        assert_eq!(state.all_projects[1].dependencies.len(), 1);
     }
 
     #[test]
@@ -536,6 +1470,39 @@ mod tests {
         assert_eq!(visible.len(), 2);
     }
 
+    #[test]
+    fn test_reducer_offline_detected() {
+        let mut state = AppState::new();
+        state.offline = None;
+        for name in ["test1", "test2"] {
+            state.update_queue.add_task(crate::runner::UpdateCheckTask {
+                project_id: create_test_project(name).id,
+                is_priority: false,
+            });
+        }
+        assert!(state.update_queue.get_next_task().is_some());
+
+        reducer(&mut state, Action::OfflineDetected);
+        assert_eq!(state.offline, Some(OfflineReason::Detected));
+        assert!(state.update_queue.queue.is_empty());
+        // The running check still reports back before the queue is idle
+        assert_eq!(state.update_queue.in_progress, 1);
+        state.update_queue.task_completed();
+        assert!(!state.update_queue.has_pending_tasks());
+
+        // An explicit reason is kept
+        state.offline = Some(OfflineReason::Flag);
+        reducer(&mut state, Action::OfflineDetected);
+        assert_eq!(state.offline, Some(OfflineReason::Flag));
+        reducer(&mut state, Action::RegistryReachable);
+        assert_eq!(state.offline, Some(OfflineReason::Flag));
+
+        // A detected one clears once the registry answers again
+        state.offline = Some(OfflineReason::Detected);
+        reducer(&mut state, Action::RegistryReachable);
+        assert_eq!(state.offline, None);
+    }
+
     #[test]
     fn test_tab_clone() {
         let tab = Tab {
@@ -1,32 +1,171 @@
 //! Cross-platform caching for dependency update checks
 //!
-//! This module provides persistent caching of dependency check results,
-//! keyed by Cargo.lock file hash to automatically invalidate when dependencies change.
+//! Version information is stored once per crate in a shared cache (keyed by
+//! registry and crate name, expired by TTL), so a crate used by many projects is
+//! only queried once. Each project additionally records the fingerprint of the
+//! Cargo.lock it was last checked against, which decides whether cached results
+//! can be applied to it on startup.
+//!
+//! All files carry a schema version, use a stable content hash and are written
+//! atomically (temp file + rename).
 
+use crate::index::{INDEX_SCHEMA_VERSION, ProjectIndex};
+use crate::project::{DependencyCheckStatus, Project, ProjectId, PublishedVersion};
+use crate::session::{SESSION_SCHEMA_VERSION, Session};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::collections::hash_map::DefaultHasher;
 use std::fs;
-use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// Version of the on-disk cache format; files with another version are ignored
+pub const CACHE_SCHEMA_VERSION: u32 = 2;
+
+/// Registry key used for crates.io
+pub const CRATES_IO_REGISTRY: &str = "crates-io";
+
+/// Key under which a registry's crates are cached
+///
+/// crates.io keeps its short key; other registries are keyed by their URL.
+pub fn registry_key(registry_url: &str) -> String {
+    let url = registry_url.trim_end_matches('/');
+    if url == "https://crates.io" {
+        CRATES_IO_REGISTRY.to_string()
+    } else {
+        url.to_string()
+    }
+}
+
+/// Serializes read-merge-write cycles of the crate cache within the process
+static CRATES_LOCK: Mutex<()> = Mutex::new(());
+
+/// Makes temp file names unique within the process
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Stable 64-bit FNV-1a hash
+///
+/// Unlike `DefaultHasher`, the output is guaranteed not to change between Rust
+/// releases, so it is safe to persist.
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
 
-/// Cached dependency information
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Describe how long ago a timestamp was, e.g. `"3h ago"`
+pub fn format_age(since: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(since)
+        .unwrap_or_default()
+        .as_secs();
+
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// Cached version information for a single crate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedDependency {
     /// The latest version available
     pub latest_version: Option<String>,
+    /// Published versions, used by the update policy
+    #[serde(default)]
+    pub versions: Vec<PublishedVersion>,
     /// When this was cached
-    pub cached_at: std::time::SystemTime,
+    pub cached_at: SystemTime,
+}
+
+impl CachedDependency {
+    /// Whether this entry is younger than `ttl`
+    pub fn is_fresh(&self, ttl: Duration, now: SystemTime) -> bool {
+        now.duration_since(self.cached_at)
+            .map(|age| age <= ttl)
+            .unwrap_or(false)
+    }
+}
+
+/// Shared per-crate version cache, grouped by registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateCache {
+    /// Format version of this file
+    pub schema_version: u32,
+    /// Registry key -> crate name -> cached versions
+    pub registries: HashMap<String, HashMap<String, CachedDependency>>,
+}
+
+impl Default for CrateCache {
+    fn default() -> Self {
+        Self {
+            schema_version: CACHE_SCHEMA_VERSION,
+            registries: HashMap::new(),
+        }
+    }
+}
+
+impl CrateCache {
+    /// Look up a crate's cached versions
+    pub fn get(&self, registry: &str, name: &str) -> Option<&CachedDependency> {
+        self.registries.get(registry)?.get(name)
+    }
+
+    /// Look up a crate's cached versions, only if younger than `ttl`
+    pub fn get_fresh(
+        &self,
+        registry: &str,
+        name: &str,
+        ttl: Duration,
+        now: SystemTime,
+    ) -> Option<&CachedDependency> {
+        self.get(registry, name)
+            .filter(|entry| entry.is_fresh(ttl, now))
+    }
+
+    /// Insert or replace a crate's cached versions
+    pub fn insert(&mut self, registry: &str, name: String, entry: CachedDependency) {
+        self.registries
+            .entry(registry.to_string())
+            .or_default()
+            .insert(name, entry);
+    }
+
+    /// Merge another cache into this one, keeping the newest entry per crate
+    pub fn merge(&mut self, other: CrateCache) {
+        for (registry, crates) in other.registries {
+            let target = self.registries.entry(registry).or_default();
+            for (name, entry) in crates {
+                let keep_existing = target
+                    .get(&name)
+                    .is_some_and(|existing| existing.cached_at >= entry.cached_at);
+                if !keep_existing {
+                    target.insert(name, entry);
+                }
+            }
+        }
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.registries.values().all(|crates| crates.is_empty())
+    }
 }
 
-/// Cache entry for a project
+/// Per-project record of the Cargo.lock the project was last checked against
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectCache {
-    /// Hash of the Cargo.lock file this cache is based on
+    /// Format version of this file
+    pub schema_version: u32,
+    /// Stable hash of the Cargo.lock file contents
     pub lock_file_hash: u64,
-    /// Cached dependency information
-    pub dependencies: HashMap<String, CachedDependency>,
 }
 
 /// Manages cross-platform caching of update check results
@@ -50,65 +189,154 @@ impl UpdateCache {
         Self { cache_dir }
     }
 
-    /// Compute hash of a Cargo.lock file
+    /// Create a cache manager rooted at a specific directory
+    pub fn with_dir(cache_dir: PathBuf) -> Self {
+        Self { cache_dir }
+    }
+
+    /// Compute a stable fingerprint of a Cargo.lock file
     pub fn hash_cargo_lock(lock_path: &Path) -> Option<u64> {
         let contents = fs::read(lock_path).ok()?;
-        let mut hasher = DefaultHasher::new();
-        contents.hash(&mut hasher);
-        Some(hasher.finish())
+        Some(stable_hash(&contents))
     }
 
-    /// Get the cache file path for a project
-    fn get_cache_path(&self, project_path: &Path) -> PathBuf {
-        // Create a unique cache filename based on project path
-        let path_str = project_path.to_string_lossy();
-        let mut hasher = DefaultHasher::new();
-        path_str.hash(&mut hasher);
-        let path_hash = hasher.finish();
+    /// Path of the shared crate cache
+    fn crates_path(&self) -> PathBuf {
+        self.cache_dir.join("crates.json")
+    }
 
-        self.cache_dir.join(format!("project_{:x}.json", path_hash))
+    /// Get the fingerprint file path for a project
+    fn get_cache_path(&self, project: &ProjectId) -> PathBuf {
+        let path_hash = stable_hash(project.manifest_path().to_string_lossy().as_bytes());
+        self.cache_dir
+            .join("projects")
+            .join(format!("{:016x}.json", path_hash))
     }
 
-    /// Load cached dependencies if Cargo.lock hasn't changed
-    pub fn load(
-        &self,
-        project_path: &Path,
-        current_lock_hash: u64,
-    ) -> Option<HashMap<String, CachedDependency>> {
-        let cache_path = self.get_cache_path(project_path);
+    /// Load the shared crate cache (empty if missing, unreadable or outdated)
+    pub fn load_crates(&self) -> CrateCache {
+        fs::read_to_string(self.crates_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str::<CrateCache>(&contents).ok())
+            .filter(|cache| cache.schema_version == CACHE_SCHEMA_VERSION)
+            .unwrap_or_default()
+    }
 
-        let contents = fs::read_to_string(&cache_path).ok()?;
-        let cache: ProjectCache = serde_json::from_str(&contents).ok()?;
+    /// Merge entries into the shared crate cache on disk
+    ///
+    /// The file is re-read before writing, and writers in this process take
+    /// turns, so concurrent checks don't drop each other's results.
+    pub fn save_crates(&self, crates: CrateCache) -> Result<()> {
+        let _guard = CRATES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut merged = self.load_crates();
+        merged.merge(crates);
 
-        // Only return cache if lock file hash matches (not invalidated)
-        if cache.lock_file_hash == current_lock_hash {
-            Some(cache.dependencies)
-        } else {
-            None
-        }
+        let json = serde_json::to_string(&merged).context("Failed to serialize crate cache")?;
+        write_atomic(&self.crates_path(), json.as_bytes())
     }
 
-    /// Save dependency information to cache
-    pub fn save(
-        &self,
-        project_path: &Path,
-        lock_hash: u64,
-        dependencies: HashMap<String, CachedDependency>,
-    ) -> Result<()> {
-        // Create cache directory if it doesn't exist
-        fs::create_dir_all(&self.cache_dir).context("Failed to create cache directory")?;
+    /// Whether a project was last checked against this Cargo.lock fingerprint
+    pub fn lock_matches(&self, project: &ProjectId, current_lock_hash: u64) -> bool {
+        fs::read_to_string(self.get_cache_path(project))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<ProjectCache>(&contents).ok())
+            .is_some_and(|cache| {
+                cache.schema_version == CACHE_SCHEMA_VERSION
+                    && cache.lock_file_hash == current_lock_hash
+            })
+    }
 
+    /// Record the Cargo.lock fingerprint a project was checked against
+    pub fn record_lock(&self, project: &ProjectId, lock_hash: u64) -> Result<()> {
         let cache = ProjectCache {
+            schema_version: CACHE_SCHEMA_VERSION,
             lock_file_hash: lock_hash,
-            dependencies,
         };
 
-        let cache_path = self.get_cache_path(project_path);
         let json = serde_json::to_string(&cache).context("Failed to serialize cache data")?;
-        fs::write(&cache_path, json)
-            .with_context(|| format!("Failed to write cache file: {}", cache_path.display()))?;
+        write_atomic(&self.get_cache_path(project), json.as_bytes())
+    }
 
-        Ok(())
+    /// Apply cached results for the registry under `registry_key` to `projects`
+    ///
+    /// Only projects whose Cargo.lock (the workspace's, for members) is unchanged
+    /// since they were last checked are updated.
+    pub fn apply_to_projects(&self, projects: &mut [Project], registry_key: &str) {
+        let crates = self.load_crates();
+        if crates.is_empty() {
+            return;
+        }
+
+        for project in projects.iter_mut() {
+            let Some(lock_hash) = Self::hash_cargo_lock(&project.lockfile_path()) else {
+                continue;
+            };
+            if !self.lock_matches(&project.id, lock_hash) {
+                continue;
+            }
+
+            for dep in &mut project.dependencies {
+                if let Some(cached_dep) = crates.get(registry_key, &dep.name) {
+                    dep.latest_version = cached_dep.latest_version.clone();
+                    dep.published_versions = cached_dep.versions.clone();
+                    dep.check_status = DependencyCheckStatus::Checked;
+                    // The cache timestamp, so checks respect the cache duration
+                    dep.last_checked = Some(cached_dep.cached_at);
+                }
+            }
+
+            project.check_status = Project::compute_check_status_from_deps(
+                &project.dependencies,
+                &project.update_policy,
+            );
+        }
+    }
+
+    /// Get the path of a file kept per set of scanned directories
+    fn get_roots_path(&self, kind: &str, roots: &[PathBuf]) -> PathBuf {
+        let key: Vec<_> = roots.iter().map(|root| root.to_string_lossy()).collect();
+        let root_hash = stable_hash(key.join("\n").as_bytes());
+        self.cache_dir
+            .join(kind)
+            .join(format!("{:016x}.json", root_hash))
+    }
+
+    /// Get the project index path for a set of scanned directories
+    fn get_index_path(&self, roots: &[PathBuf]) -> PathBuf {
+        self.get_roots_path("index", roots)
+    }
+
+    /// Load the project index for a set of scanned directories (if present and current)
+    pub fn load_index(&self, roots: &[PathBuf]) -> Option<ProjectIndex> {
+        fs::read_to_string(self.get_index_path(roots))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<ProjectIndex>(&contents).ok())
+            .filter(|index| index.schema_version == INDEX_SCHEMA_VERSION && index.roots == roots)
+    }
+
+    /// Replace the project index for its scanned directories
+    pub fn save_index(&self, index: &ProjectIndex) -> Result<()> {
+        let json = serde_json::to_string(index).context("Failed to serialize project index")?;
+        write_atomic(&self.get_index_path(&index.roots), json.as_bytes())
+    }
+
+    /// Load the session saved for a set of scanned directories (if present and current)
+    pub fn load_session(&self, roots: &[PathBuf]) -> Option<Session> {
+        fs::read_to_string(self.get_roots_path("sessions", roots))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Session>(&contents).ok())
+            .filter(|session| {
+                session.schema_version == SESSION_SCHEMA_VERSION && session.roots == roots
+            })
+    }
+
+    /// Replace the session saved for its scanned directories
+    pub fn save_session(&self, session: &Session) -> Result<()> {
+        let json = serde_json::to_string(session).context("Failed to serialize session")?;
+        write_atomic(
+            &self.get_roots_path("sessions", &session.roots),
+            json.as_bytes(),
+        )
     }
 
     /// Clear all cached data
@@ -125,30 +353,290 @@ impl UpdateCache {
     }
 }
 
+/// Write a file by writing a sibling temp file and renaming it into place
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create cache directory")?;
+    }
+
+    let tmp_path = path.with_extension(format!(
+        "tmp.{}.{}",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed to write cache file: {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, path).with_context(|| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to replace cache file: {}", path.display())
+    })?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::temp_dir;
+
+    fn temp_cache(name: &str) -> UpdateCache {
+        UpdateCache::with_dir(temp_dir("cache", name))
+    }
+
+    fn entry(latest: &str, cached_at: SystemTime) -> CachedDependency {
+        CachedDependency {
+            latest_version: Some(latest.to_string()),
+            versions: vec![],
+            cached_at,
+        }
+    }
 
     #[test]
     fn test_cache_path_consistency() {
         let cache = UpdateCache::new();
-        let path = Path::new("/home/user/project1");
+        let project = ProjectId::from_dir(Path::new("/home/user/project1"));
 
-        // Same path should generate same cache filename
-        let path1 = cache.get_cache_path(path);
-        let path2 = cache.get_cache_path(path);
+        // Same project should generate same cache filename
+        let path1 = cache.get_cache_path(&project);
+        let path2 = cache.get_cache_path(&project);
         assert_eq!(path1, path2);
     }
 
     #[test]
     fn test_different_projects_different_cache() {
         let cache = UpdateCache::new();
-        let path1 = Path::new("/home/user/project1");
-        let path2 = Path::new("/home/user/project2");
+        let project1 = ProjectId::from_dir(Path::new("/home/user/project1"));
+        let project2 = ProjectId::from_dir(Path::new("/home/user/project2"));
 
-        // Different paths should generate different cache filenames
-        let cache1 = cache.get_cache_path(path1);
-        let cache2 = cache.get_cache_path(path2);
+        // Different projects should generate different cache filenames
+        let cache1 = cache.get_cache_path(&project1);
+        let cache2 = cache.get_cache_path(&project2);
         assert_ne!(cache1, cache2);
     }
+
+    #[test]
+    fn test_stable_hash_known_values() {
+        // FNV-1a reference values; these must never change
+        assert_eq!(stable_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now = SystemTime::now();
+        assert_eq!(format_age(now), "just now");
+        assert_eq!(format_age(now - Duration::from_secs(5 * 60)), "5m ago");
+        assert_eq!(format_age(now - Duration::from_secs(3 * 3600)), "3h ago");
+        assert_eq!(format_age(now - Duration::from_secs(2 * 86400)), "2d ago");
+    }
+
+    #[test]
+    fn test_crate_cache_roundtrip_and_merge() {
+        let cache = temp_cache("roundtrip");
+        let now = SystemTime::now();
+
+        let mut first = CrateCache::default();
+        first.insert(CRATES_IO_REGISTRY, "tokio".into(), entry("1.40.0", now));
+        cache.save_crates(first).unwrap();
+
+        // A second save merges instead of overwriting
+        let mut second = CrateCache::default();
+        second.insert(CRATES_IO_REGISTRY, "serde".into(), entry("1.0.200", now));
+        cache.save_crates(second).unwrap();
+
+        let loaded = cache.load_crates();
+        assert!(loaded.get(CRATES_IO_REGISTRY, "tokio").is_some());
+        assert!(loaded.get(CRATES_IO_REGISTRY, "serde").is_some());
+        assert!(loaded.get("other-registry", "tokio").is_none());
+
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_concurrent_saves_keep_every_crate() {
+        let cache = temp_cache("concurrent");
+        let now = SystemTime::now();
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let cache = cache.clone();
+                std::thread::spawn(move || {
+                    let mut crates = CrateCache::default();
+                    crates.insert(
+                        CRATES_IO_REGISTRY,
+                        format!("crate-{i}"),
+                        entry("1.0.0", now),
+                    );
+                    cache.save_crates(crates).unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let loaded = cache.load_crates();
+        for i in 0..8 {
+            assert!(
+                loaded
+                    .get(CRATES_IO_REGISTRY, &format!("crate-{i}"))
+                    .is_some()
+            );
+        }
+
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_registry_key() {
+        assert_eq!(registry_key("https://crates.io/"), CRATES_IO_REGISTRY);
+        assert_eq!(
+            registry_key("https://registry.example.com/"),
+            "https://registry.example.com"
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_newest_entry() {
+        let now = SystemTime::now();
+        let mut cache = CrateCache::default();
+        cache.insert(CRATES_IO_REGISTRY, "tokio".into(), entry("1.40.0", now));
+
+        let mut older = CrateCache::default();
+        older.insert(
+            CRATES_IO_REGISTRY,
+            "tokio".into(),
+            entry("1.30.0", now - Duration::from_secs(60)),
+        );
+        cache.merge(older);
+
+        let latest = &cache
+            .get(CRATES_IO_REGISTRY, "tokio")
+            .unwrap()
+            .latest_version;
+        assert_eq!(latest.as_deref(), Some("1.40.0"));
+    }
+
+    #[test]
+    fn test_get_fresh_respects_ttl() {
+        let now = SystemTime::now();
+        let mut cache = CrateCache::default();
+        cache.insert(
+            CRATES_IO_REGISTRY,
+            "tokio".into(),
+            entry("1.40.0", now - Duration::from_secs(120)),
+        );
+
+        let ttl = Duration::from_secs(60);
+        assert!(
+            cache
+                .get_fresh(CRATES_IO_REGISTRY, "tokio", ttl, now)
+                .is_none()
+        );
+        let ttl = Duration::from_secs(600);
+        assert!(
+            cache
+                .get_fresh(CRATES_IO_REGISTRY, "tokio", ttl, now)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_outdated_schema_is_ignored() {
+        let cache = temp_cache("schema");
+        fs::create_dir_all(&cache.cache_dir).unwrap();
+        fs::write(
+            cache.crates_path(),
+            r#"{"schema_version":1,"registries":{"crates-io":{}}}"#,
+        )
+        .unwrap();
+
+        assert!(cache.load_crates().is_empty());
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_lock_fingerprint() {
+        let cache = temp_cache("lock");
+        let project = ProjectId::from_dir(Path::new("/home/user/project1"));
+
+        assert!(!cache.lock_matches(&project, 42));
+        cache.record_lock(&project, 42).unwrap();
+        assert!(cache.lock_matches(&project, 42));
+        assert!(!cache.lock_matches(&project, 43));
+
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_workspace_members_pick_up_cached_results() {
+        let cache = temp_cache("member");
+        let root =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspaces/inherited");
+        let mut projects = crate::project::find_rust_projects(root.to_str().unwrap());
+        let member = &projects[0];
+        assert!(member.workspace_root.is_some());
+        assert!(!member.path.join("Cargo.lock").exists());
+
+        // Checked against the workspace's Cargo.lock
+        let lock_hash = UpdateCache::hash_cargo_lock(&member.lockfile_path()).unwrap();
+        cache.record_lock(&member.id, lock_hash).unwrap();
+        let mut crates = CrateCache::default();
+        crates.insert(
+            CRATES_IO_REGISTRY,
+            "serde".to_string(),
+            entry("1.0.215", SystemTime::now()),
+        );
+        cache.save_crates(crates).unwrap();
+
+        cache.apply_to_projects(&mut projects, CRATES_IO_REGISTRY);
+        let serde = projects[0]
+            .dependencies
+            .iter()
+            .find(|d| d.name == "serde")
+            .unwrap();
+        assert_eq!(serde.check_status, DependencyCheckStatus::Checked);
+        assert_eq!(serde.latest_version.as_deref(), Some("1.0.215"));
+
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_index_is_keyed_by_roots() {
+        use crate::settings::DiscoveryBackend;
+
+        let cache = temp_cache("index");
+        let roots = [
+            PathBuf::from("/home/user/src"),
+            PathBuf::from("/home/user/oss"),
+        ];
+
+        assert!(cache.load_index(&roots).is_none());
+        let index = ProjectIndex::build(&roots, DiscoveryBackend::Manifest, &[]);
+        cache.save_index(&index).unwrap();
+        assert_eq!(cache.load_index(&roots).unwrap().roots, roots);
+        assert!(cache.load_index(&roots[..1]).is_none());
+
+        let _ = cache.clear();
+    }
+
+    #[test]
+    fn test_session_is_keyed_by_roots() {
+        let cache = temp_cache("session");
+        let roots = [PathBuf::from("/home/user/src")];
+
+        assert!(cache.load_session(&roots).is_none());
+        let mut session = Session::capture(&crate::app::AppState::new());
+        session.roots = roots.to_vec();
+        session.filter = Some("dep:tokio".to_string());
+        cache.save_session(&session).unwrap();
+        assert_eq!(cache.load_session(&roots), Some(session));
+        assert!(
+            cache
+                .load_session(&[PathBuf::from("/home/user/oss")])
+                .is_none()
+        );
+
+        let _ = cache.clear();
+    }
 }
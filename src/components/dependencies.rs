@@ -1,12 +1,15 @@
 use crate::app::AppState;
+use crate::cache::format_age;
 use crate::components::Component;
 use crate::events::{Action, Focus};
+use crate::policy::{UpdatePolicy, UpdateVerdict};
 use crate::project::DependencyCheckStatus;
 use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
+    text::Line,
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
 
@@ -21,64 +24,82 @@ impl DependenciesPane {
 impl DependenciesPane {
     // ... (new function remains the same)
 
-    fn create_dependency_list_item<'a>(dep: &'a crate::project::Dependency) -> ListItem<'a> {
-        let is_outdated = dep.has_stable_update();
+    fn create_dependency_list_item<'a>(
+        dep: &'a crate::project::Dependency,
+        policy: &UpdatePolicy,
+        offline: bool,
+    ) -> ListItem<'a> {
+        let verdict = dep.update_verdict(policy);
 
         let (icon, style) = match dep.check_status {
             DependencyCheckStatus::NotChecked => ("⋯", Style::default().fg(Color::DarkGray)),
             DependencyCheckStatus::Checking => ("⟳", Style::default().fg(Color::Cyan)),
-            DependencyCheckStatus::Checked => {
-                if is_outdated {
-                    (
-                        "⚠",
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(ratatui::style::Modifier::BOLD),
-                    )
-                } else {
-                    ("✓", Style::default().fg(Color::Green))
-                }
-            }
-        };
-
-        let line = if is_outdated && dep.check_status == DependencyCheckStatus::Checked {
-            let mut spans = vec![
-                ratatui::text::Span::styled(icon, style),
-                ratatui::text::Span::raw(" "),
-                ratatui::text::Span::styled(&dep.name, Style::default().fg(Color::White)),
-                ratatui::text::Span::raw(" "),
-                ratatui::text::Span::styled(
-                    &dep.current_version,
-                    Style::default().fg(Color::DarkGray),
-                ),
-                ratatui::text::Span::styled(" → ", Style::default().fg(Color::Yellow)),
-                ratatui::text::Span::styled(
-                    dep.latest_version.as_ref().unwrap(),
+            DependencyCheckStatus::Failed(_) => ("✗", Style::default().fg(Color::Red)),
+            DependencyCheckStatus::Checked => match verdict {
+                UpdateVerdict::Available(_) => (
+                    "⚠",
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(ratatui::style::Modifier::BOLD),
                 ),
-            ];
+                UpdateVerdict::Deferred { .. } => ("⏸", Style::default().fg(Color::DarkGray)),
+                UpdateVerdict::UpToDate => ("✓", Style::default().fg(Color::Green)),
+            },
+        };
 
-            // Add note for major version updates that require Cargo.toml change
-            if let Some(note) = dep.update_note() {
-                spans.push(ratatui::text::Span::styled(
-                    format!(" ({})", note),
-                    Style::default()
-                        .fg(Color::DarkGray)
-                        .add_modifier(ratatui::style::Modifier::ITALIC),
-                ));
-            }
+        let mut line = match (&dep.check_status, verdict) {
+            (DependencyCheckStatus::Checked, UpdateVerdict::Available(target)) => {
+                let is_major = dep.is_major_bump(&target);
+                let mut spans = vec![
+                    ratatui::text::Span::styled(icon, style),
+                    ratatui::text::Span::raw(" "),
+                    ratatui::text::Span::styled(&dep.name, Style::default().fg(Color::White)),
+                    ratatui::text::Span::raw(" "),
+                    ratatui::text::Span::styled(
+                        &dep.current_version,
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    ratatui::text::Span::styled(" → ", Style::default().fg(Color::Yellow)),
+                    ratatui::text::Span::styled(
+                        target,
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(ratatui::style::Modifier::BOLD),
+                    ),
+                ];
 
-            ratatui::text::Line::from(spans)
-        } else {
-            let status_text = match dep.check_status {
-                DependencyCheckStatus::NotChecked => " (not checked)",
-                DependencyCheckStatus::Checking => " (checking...)",
-                DependencyCheckStatus::Checked => "",
-            };
+                // Add note for major version updates that require Cargo.toml change
+                if is_major {
+                    spans.push(ratatui::text::Span::styled(
+                        " (requires Cargo.toml change)",
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(ratatui::style::Modifier::ITALIC),
+                    ));
+                }
 
-            ratatui::text::Line::from(vec![
+                ratatui::text::Line::from(spans)
+            }
+            (DependencyCheckStatus::Checked, UpdateVerdict::Deferred { latest, reason }) => {
+                // Known-deferred update: show it, but muted so it doesn't demand attention
+                ratatui::text::Line::from(vec![
+                    ratatui::text::Span::styled(icon, style),
+                    ratatui::text::Span::raw(" "),
+                    ratatui::text::Span::styled(&dep.name, Style::default().fg(Color::White)),
+                    ratatui::text::Span::raw(" "),
+                    ratatui::text::Span::styled(
+                        format!("{} → {}", dep.current_version, latest),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    ratatui::text::Span::styled(
+                        format!(" ({})", reason.label()),
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(ratatui::style::Modifier::ITALIC),
+                    ),
+                ])
+            }
+            (DependencyCheckStatus::Failed(reason), _) => ratatui::text::Line::from(vec![
                 ratatui::text::Span::styled(icon, style),
                 ratatui::text::Span::raw(" "),
                 ratatui::text::Span::styled(&dep.name, Style::default().fg(Color::White)),
@@ -88,20 +109,58 @@ impl DependenciesPane {
                     Style::default().fg(Color::DarkGray),
                 ),
                 ratatui::text::Span::styled(
-                    status_text,
+                    format!(" (lookup failed: {})", reason),
                     Style::default()
-                        .fg(Color::DarkGray)
+                        .fg(Color::Red)
                         .add_modifier(ratatui::style::Modifier::ITALIC),
                 ),
-            ])
+            ]),
+            _ => {
+                let status_text = match dep.check_status {
+                    DependencyCheckStatus::NotChecked => " (not checked)",
+                    DependencyCheckStatus::Checking => " (checking...)",
+                    DependencyCheckStatus::Checked | DependencyCheckStatus::Failed(_) => "",
+                };
+
+                ratatui::text::Line::from(vec![
+                    ratatui::text::Span::styled(icon, style),
+                    ratatui::text::Span::raw(" "),
+                    ratatui::text::Span::styled(&dep.name, Style::default().fg(Color::White)),
+                    ratatui::text::Span::raw(" "),
+                    ratatui::text::Span::styled(
+                        format!("v{}", dep.current_version),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    ratatui::text::Span::styled(
+                        status_text,
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(ratatui::style::Modifier::ITALIC),
+                    ),
+                ])
+            }
         };
 
+        // Offline results may be stale: say how old they are
+        if offline && dep.check_status == DependencyCheckStatus::Checked {
+            let age = match dep.last_checked {
+                Some(checked_at) => format!(" · cached {}", format_age(checked_at)),
+                None => " · local index".to_string(),
+            };
+            line.spans.push(ratatui::text::Span::styled(
+                age,
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
         ListItem::new(line)
     }
 
     fn get_title(
         dependencies: &[crate::project::Dependency],
         outdated_count: usize,
+        deferred_count: usize,
+        failed_count: usize,
         not_checked_count: usize,
         checking_count: usize,
     ) -> (String, Style) {
@@ -120,11 +179,36 @@ impl DependenciesPane {
                 format!(" Dependencies ({} not checked) ", dependencies.len()),
                 Style::default().fg(Color::DarkGray),
             )
+        } else if failed_count > 0 {
+            (
+                format!(
+                    " Dependencies ({} lookups failed, {} outdated) ",
+                    failed_count, outdated_count
+                ),
+                Style::default().fg(Color::Red),
+            )
+        } else if outdated_count > 0 && deferred_count > 0 {
+            (
+                format!(
+                    " Dependencies ({} outdated, {} deferred) ",
+                    outdated_count, deferred_count
+                ),
+                Style::default().fg(Color::Yellow),
+            )
         } else if outdated_count > 0 {
             (
                 format!(" Dependencies ({} outdated) ", outdated_count),
                 Style::default().fg(Color::Yellow),
             )
+        } else if deferred_count > 0 {
+            (
+                format!(
+                    " Dependencies ({} up-to-date, {} deferred) ",
+                    dependencies.len() - deferred_count,
+                    deferred_count
+                ),
+                Style::default().fg(Color::Green),
+            )
         } else {
             (
                 format!(" Dependencies ({} up-to-date) ", dependencies.len()),
@@ -132,12 +216,30 @@ impl DependenciesPane {
             )
         }
     }
+
+    /// Package metadata shown under the list, e.g. ` v1.2.0 · 2021 · MSRV 1.80 · MIT `
+    fn project_summary(project: &crate::project::Project) -> String {
+        let mut parts = vec![format!("v{}", project.version)];
+        if let Some(ref edition) = project.edition {
+            parts.push(edition.clone());
+        }
+        if let Some(ref rust_version) = project.rust_version {
+            parts.push(format!("MSRV {}", rust_version));
+        }
+        if let Some(ref license) = project.license {
+            parts.push(license.clone());
+        }
+        format!(" {} ", parts.join(" · "))
+    }
 }
 
 impl Component for DependenciesPane {
     fn handle_key_events(&mut self, key: KeyCode, _app: &mut AppState) -> Option<Action> {
         match key {
             KeyCode::Char('u') => Some(Action::StartUpdateWizard),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::DependencySelectNext),
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::DependencySelectPrevious),
+            KeyCode::Enter => Some(Action::ShowDependencyDetail),
             _ => None,
         }
     }
@@ -145,6 +247,8 @@ impl Component for DependenciesPane {
     fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
         if let Some(p) = app.get_selected_project() {
             let mut outdated_count = 0;
+            let mut deferred_count = 0;
+            let mut failed_count = 0;
             let mut not_checked_count = 0;
             let mut checking_count = 0;
 
@@ -152,25 +256,28 @@ impl Component for DependenciesPane {
                 .dependencies
                 .iter()
                 .map(|dep| {
-                    let is_outdated = dep.has_stable_update();
-
-                    if is_outdated {
-                        outdated_count += 1;
-                    }
-
                     match dep.check_status {
                         DependencyCheckStatus::NotChecked => not_checked_count += 1,
                         DependencyCheckStatus::Checking => checking_count += 1,
-                        DependencyCheckStatus::Checked => {}
+                        DependencyCheckStatus::Failed(_) => failed_count += 1,
+                        DependencyCheckStatus::Checked => {
+                            match dep.update_verdict(&p.update_policy) {
+                                UpdateVerdict::Available(_) => outdated_count += 1,
+                                UpdateVerdict::Deferred { .. } => deferred_count += 1,
+                                UpdateVerdict::UpToDate => {}
+                            }
+                        }
                     }
 
-                    Self::create_dependency_list_item(dep)
+                    Self::create_dependency_list_item(dep, &p.update_policy, app.is_offline())
                 })
                 .collect();
 
             let (title, title_style) = Self::get_title(
                 &p.dependencies,
                 outdated_count,
+                deferred_count,
+                failed_count,
                 not_checked_count,
                 checking_count,
             );
@@ -182,14 +289,34 @@ impl Component for DependenciesPane {
                 title_style
             };
 
-            let dependency_list = List::new(dependency_items).block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(title)
-                    .border_style(border_style),
-            );
+            // Dependencies may have been re-read since the selection was made
+            let mut list_state = app.dependency_list.clone();
+            if list_state
+                .selected()
+                .is_some_and(|i| i >= dependency_items.len())
+            {
+                list_state.select(None);
+            }
+
+            let dependency_list = List::new(dependency_items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(title)
+                        .title_bottom(
+                            Line::from(Self::project_summary(p))
+                                .style(Style::default().fg(Color::DarkGray)),
+                        )
+                        .border_style(border_style),
+                )
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::Rgb(40, 40, 60))
+                        .add_modifier(Modifier::BOLD),
+                );
 
-            f.render_widget(dependency_list, area);
+            f.render_stateful_widget(dependency_list, area, &mut list_state);
+            app.dependency_list = list_state;
         } else {
             let empty =
                 Paragraph::new("No project selected.\n\nSelect a project to view dependencies.")
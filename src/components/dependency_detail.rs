@@ -0,0 +1,279 @@
+use crate::app::AppState;
+use crate::components::Component;
+use crate::events::Action;
+use crate::project::{Dependency, DependencyCheckStatus, VersionStep};
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+use std::time::SystemTime;
+
+/// Modal listing every published version between the locked and the latest one
+pub struct DependencyDetail {}
+
+impl DependencyDetail {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn format_date(released_at: Option<SystemTime>) -> String {
+        match released_at {
+            Some(time) => chrono::DateTime::<chrono::Utc>::from(time)
+                .format("%Y-%m-%d")
+                .to_string(),
+            None => "—".to_string(),
+        }
+    }
+
+    fn header_lines(dep: &Dependency, steps: &[VersionStep]) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                dep.name.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                dep.current_version.clone(),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(" → ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                dep.latest_version
+                    .clone()
+                    .unwrap_or_else(|| "?".to_string()),
+                Style::default().fg(Color::Yellow),
+            ),
+        ])];
+
+        let mut declared = Vec::new();
+        if let Some(ref requirement) = dep.requirement {
+            declared.push(format!("requires {}", requirement));
+        }
+        if !dep.features.is_empty() {
+            declared.push(format!("features: {}", dep.features.join(", ")));
+        }
+        if !declared.is_empty() {
+            lines.push(Line::from(Span::styled(
+                declared.join(" · "),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        if dep.is_current_yanked() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "⚠ The locked version {} has been yanked",
+                    dep.current_version
+                ),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        let newer = steps.iter().filter(|s| !s.is_current).count();
+        let compatible = steps
+            .iter()
+            .filter(|s| !s.is_current && s.compatible)
+            .count();
+        let yanked = steps.iter().filter(|s| !s.is_current && s.yanked).count();
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} newer versions: {} semver-compatible, {} yanked",
+                newer, compatible, yanked
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Line::from(""));
+        lines
+    }
+
+    fn version_line(step: &VersionStep) -> Line<'static> {
+        let version_style = if step.yanked {
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::CROSSED_OUT)
+        } else if step.is_current {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let mut spans = vec![
+            Span::styled(
+                if step.is_current { "▶ " } else { "  " },
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(format!("{:<20}", step.version), version_style),
+            Span::styled(
+                format!("{:<12}", Self::format_date(step.released_at)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        if step.is_current {
+            spans.push(Span::styled("locked", Style::default().fg(Color::Cyan)));
+        } else if step.compatible {
+            spans.push(Span::styled(
+                "compatible",
+                Style::default().fg(Color::Green),
+            ));
+        } else {
+            spans.push(Span::styled("breaking", Style::default().fg(Color::Yellow)));
+        }
+        if step.yanked {
+            spans.push(Span::styled(
+                " · yanked",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        Line::from(spans)
+    }
+
+    fn content_lines(dep: &Dependency) -> Vec<Line<'static>> {
+        if dep.published_versions.is_empty() {
+            let message = match dep.check_status {
+                DependencyCheckStatus::Failed(ref reason) => {
+                    format!("Version history unavailable: {}", reason)
+                }
+                _ => "No version history yet. Press 'u' to check for updates.".to_string(),
+            };
+            return vec![Line::from(Span::styled(
+                message,
+                Style::default().fg(Color::DarkGray),
+            ))];
+        }
+
+        let steps = dep.version_steps();
+        let mut lines = Self::header_lines(dep, &steps);
+        lines.push(Line::from(Span::styled(
+            format!("  {:<20}{:<12}{}", "Version", "Released", "Notes"),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(steps.iter().map(Self::version_line));
+        lines
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+        let popup_layout = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(r);
+
+        Layout::default()
+            .direction(ratatui::layout::Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(popup_layout[1])[1]
+    }
+}
+
+impl Component for DependencyDetail {
+    fn handle_key_events(&mut self, key: KeyCode, app: &mut AppState) -> Option<Action> {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => Some(Action::EnterNormalMode),
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.dependency_detail_scroll = app.dependency_detail_scroll.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.dependency_detail_scroll = app.dependency_detail_scroll.saturating_add(1);
+                None
+            }
+            KeyCode::PageUp => {
+                app.dependency_detail_scroll = app.dependency_detail_scroll.saturating_sub(10);
+                None
+            }
+            KeyCode::PageDown => {
+                app.dependency_detail_scroll = app.dependency_detail_scroll.saturating_add(10);
+                None
+            }
+            KeyCode::Home => {
+                app.dependency_detail_scroll = 0;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        let Some(dep) = app.get_selected_dependency() else {
+            return;
+        };
+        let title = format!(" {} — version history ", dep.name);
+        let lines = Self::content_lines(dep);
+
+        let popup_area = Self::centered_rect(70, 70, area);
+        f.render_widget(Clear, popup_area);
+
+        let chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(popup_area);
+
+        // Clamp scroll to the content so PageDown can't run off the end
+        let content_height = lines.len();
+        let viewport_height = chunks[0].height.saturating_sub(2) as usize;
+        let max_scroll = content_height.saturating_sub(viewport_height);
+        app.dependency_detail_scroll = app.dependency_detail_scroll.min(max_scroll);
+        let scroll = app.dependency_detail_scroll;
+
+        let content = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                    .border_style(
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .scroll((scroll as u16, 0));
+        f.render_widget(content, chunks[0]);
+
+        if content_height > viewport_height {
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓"));
+            let mut scrollbar_state = ScrollbarState::default()
+                .content_length(content_height)
+                .viewport_content_length(viewport_height)
+                .position(scroll);
+            let scrollbar_area = Rect {
+                x: chunks[0].x + chunks[0].width - 1,
+                y: chunks[0].y + 1,
+                width: 1,
+                height: chunks[0].height.saturating_sub(1),
+            };
+            f.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+        }
+
+        let footer = Paragraph::new(" ↑↓/j k: scroll | PgUp/PgDn: page | Esc: close ")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            );
+        f.render_widget(footer, chunks[1]);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::app::AppState;
+use crate::components::Component;
+use crate::disk_usage::{UsageEntry, category_totals};
+use crate::events::Action;
+use crate::project::Project;
+use crate::ui::layout::centered_rect;
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Width of the size bars
+const BAR_WIDTH: usize = 20;
+
+/// State of the disk usage view of a project
+#[derive(Debug, Clone, Default)]
+pub struct DiskUsageState {
+    pub project_name: String,
+    /// Target directory of the project, categorized even outside of it
+    pub target_dir: PathBuf,
+    /// Directories descended into, the project root first
+    pub stack: Vec<PathBuf>,
+    /// Listings measured so far, per directory
+    pub listings: HashMap<PathBuf, Vec<UsageEntry>>,
+    /// Listings being measured
+    pub loading: HashSet<PathBuf>,
+    pub table_state: TableState,
+}
+
+impl DiskUsageState {
+    pub fn new(project_name: String, root: PathBuf, target_dir: PathBuf) -> Self {
+        Self {
+            project_name,
+            target_dir,
+            stack: vec![root],
+            ..Default::default()
+        }
+    }
+
+    /// Directory being shown
+    pub fn current(&self) -> Option<&Path> {
+        self.stack.last().map(PathBuf::as_path)
+    }
+
+    /// Whether the project root is shown, where entries have categories
+    pub fn at_root(&self) -> bool {
+        self.stack.len() == 1
+    }
+
+    /// Entries of the directory being shown, largest first
+    pub fn entries(&self) -> &[UsageEntry] {
+        self.current()
+            .and_then(|dir| self.listings.get(dir))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn selected_entry(&self) -> Option<&UsageEntry> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.entries().get(i))
+    }
+}
+
+/// Modal showing where a project's bytes are, one directory at a time
+pub struct DiskUsageView {}
+
+impl DiskUsageView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn bar(bytes: u64, total: u64) -> Vec<Span<'static>> {
+        let share = bytes as f64 / total.max(1) as f64;
+        let filled = ((share * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+        vec![
+            Span::styled("█".repeat(filled), Style::default().fg(Color::Cyan)),
+            Span::styled(
+                "░".repeat(BAR_WIDTH - filled),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]
+    }
+
+    fn category_lines(entries: &[UsageEntry]) -> Vec<Line<'static>> {
+        let totals = category_totals(entries);
+        let total: u64 = totals.iter().map(|(_, bytes)| bytes).sum();
+        totals
+            .into_iter()
+            .map(|(category, bytes)| {
+                let mut spans = vec![Span::styled(
+                    format!("{:>9} ", Project::format_size(bytes)),
+                    Style::default().fg(Color::Yellow),
+                )];
+                spans.extend(Self::bar(bytes, total));
+                spans.push(Span::raw(format!(" {}", category.label())));
+                Line::from(spans)
+            })
+            .collect()
+    }
+}
+
+impl Component for DiskUsageView {
+    fn handle_key_events(&mut self, key: KeyCode, _app: &mut AppState) -> Option<Action> {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::EnterNormalMode),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::DiskUsageSelectNext),
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::DiskUsageSelectPrevious),
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => Some(Action::DiskUsageOpen),
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => Some(Action::DiskUsageUp),
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        let popup_area = centered_rect(85, 85, area);
+        f.render_widget(Clear, popup_area);
+
+        let usage = &app.disk_usage;
+        let entries = usage.entries();
+        let loading = usage
+            .current()
+            .is_some_and(|dir| usage.loading.contains(dir));
+        let category_lines = if usage.at_root() {
+            Self::category_lines(entries)
+        } else {
+            Vec::new()
+        };
+        let chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Length(if category_lines.is_empty() {
+                    0
+                } else {
+                    category_lines.len() as u16 + 2
+                }),
+                Constraint::Min(6),
+                Constraint::Length(3),
+            ])
+            .split(popup_area);
+
+        let total: u64 = entries.iter().map(|e| e.bytes).sum();
+        let location = match (usage.stack.first(), usage.current()) {
+            (Some(root), Some(dir)) if dir != root => match dir.strip_prefix(root) {
+                Ok(relative) => format!(" · {}", relative.display()),
+                Err(_) => format!(" · {}", dir.display()),
+            },
+            _ => String::new(),
+        };
+        let title = format!(
+            " Disk usage · {}{} · {} ",
+            usage.project_name,
+            location,
+            if loading && entries.is_empty() {
+                "measuring…".to_string()
+            } else {
+                Project::format_size(total)
+            }
+        );
+
+        if !category_lines.is_empty() {
+            let summary = Paragraph::new(category_lines).block(
+                Block::default()
+                    .title(" By kind ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            );
+            f.render_widget(summary, chunks[0]);
+        }
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let rows: Vec<Row> = entries
+            .iter()
+            .map(|entry| {
+                let name = if entry.is_dir {
+                    format!("{}/", entry.name)
+                } else {
+                    entry.name.clone()
+                };
+                Row::new(vec![
+                    Cell::from(Project::format_size(entry.bytes))
+                        .style(Style::default().fg(Color::Yellow)),
+                    Cell::from(Line::from(Self::bar(entry.bytes, total))),
+                    Cell::from(name),
+                    Cell::from(entry.category.map_or("", |c| c.label())).style(dim),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(9),
+                Constraint::Length(BAR_WIDTH as u16),
+                Constraint::Fill(1),
+                Constraint::Length(14),
+            ],
+        )
+        .column_spacing(2)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::Rgb(40, 40, 60))
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+        f.render_stateful_widget(table, chunks[1], &mut app.disk_usage.table_state);
+
+        let footer = Paragraph::new(" Enter: open | Backspace: up | ↑↓: select | Esc: close ")
+            .style(dim)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            );
+        f.render_widget(footer, chunks[2]);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::app::AppState;
+use crate::components::Component;
+use crate::events::Action;
+use crate::features::{FeatureFlags, FeatureMatrix, MatrixStatus, ProjectFeatures};
+use crate::project::ProjectId;
+use crate::ui::layout::centered_rect;
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table},
+};
+use std::collections::HashMap;
+
+/// State of the feature explorer and the feature flags picked in it
+#[derive(Debug, Clone, Default)]
+pub struct FeatureExplorerState {
+    /// Project the explorer was opened for
+    pub project: Option<ProjectId>,
+    /// Features of that project
+    pub available: ProjectFeatures,
+    pub list_state: ListState,
+    /// Feature flags added to cargo commands, per project
+    pub flags: HashMap<ProjectId, FeatureFlags>,
+    /// The latest feature matrix run
+    pub matrix: Option<FeatureMatrix>,
+}
+
+impl FeatureExplorerState {
+    /// Flags of the project the explorer is open for
+    pub fn current_flags(&self) -> Option<&FeatureFlags> {
+        self.project.as_ref().and_then(|id| self.flags.get(id))
+    }
+
+    /// The matrix run, if it ran over the project the explorer is open for
+    pub fn current_matrix(&self) -> Option<&FeatureMatrix> {
+        self.matrix
+            .as_ref()
+            .filter(|matrix| Some(&matrix.project) == self.project.as_ref())
+    }
+
+    /// Change the flags of the project the explorer is open for
+    ///
+    /// Flags that end up empty are dropped, so the project runs commands as is.
+    pub fn update_flags(&mut self, update: impl FnOnce(&mut FeatureFlags)) {
+        let Some(id) = self.project.clone() else {
+            return;
+        };
+        let flags = self.flags.entry(id.clone()).or_default();
+        update(flags);
+        if flags.is_empty() {
+            self.flags.remove(&id);
+        }
+    }
+}
+
+/// Modal listing the selected project's features, for picking flags and running a matrix
+pub struct FeatureExplorer {}
+
+impl FeatureExplorer {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn summary_lines(
+        available: &ProjectFeatures,
+        flags: Option<&FeatureFlags>,
+    ) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(Color::DarkGray);
+        let list = |items: &[String]| {
+            if items.is_empty() {
+                "none".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let args = flags
+            .map(|flags| flags.args().join(" "))
+            .filter(|args| !args.is_empty());
+
+        vec![
+            Line::from(vec![
+                Span::styled("Default features   ", dim),
+                Span::raw(list(&available.defaults)),
+            ]),
+            Line::from(vec![
+                Span::styled("Optional deps      ", dim),
+                Span::raw(list(&available.optional_deps)),
+            ]),
+            Line::from(vec![
+                Span::styled("Command flags      ", dim),
+                match args {
+                    Some(args) => Span::styled(
+                        args,
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    None => Span::styled("none (default features)", dim),
+                },
+            ]),
+        ]
+    }
+
+    fn feature_items<'a>(
+        available: &'a ProjectFeatures,
+        flags: Option<&FeatureFlags>,
+    ) -> Vec<ListItem<'a>> {
+        let all = flags.is_some_and(|f| f.all_features);
+        let no_default = flags.is_some_and(|f| f.no_default_features);
+
+        available
+            .features
+            .iter()
+            .map(|feature| {
+                let picked = flags.is_some_and(|f| f.features.contains(&feature.name));
+                let (checkbox, style) = if picked || all {
+                    ("☑", Style::default().fg(Color::Green))
+                } else if feature.default && !no_default {
+                    ("◪", Style::default().fg(Color::Cyan))
+                } else {
+                    ("☐", Style::default().fg(Color::DarkGray))
+                };
+
+                let mut spans = vec![
+                    Span::styled(checkbox, style),
+                    Span::raw(" "),
+                    Span::styled(feature.name.as_str(), Style::default().fg(Color::White)),
+                ];
+                if feature.default {
+                    spans.push(Span::styled(" (default)", Style::default().fg(Color::Cyan)));
+                }
+                if feature.implicit {
+                    spans.push(Span::styled(
+                        " (optional dependency)",
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                } else if !feature.enables.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" → {}", feature.enables.join(", ")),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    }
+
+    /// Grid with a row per combination: its result, then a mark per feature
+    fn matrix_table(matrix: &FeatureMatrix) -> Table<'static> {
+        let (passed, failed) = matrix.counts();
+        let combinations = if matrix.total > matrix.runs.len() {
+            format!("{} of {} combinations", matrix.runs.len(), matrix.total)
+        } else {
+            format!("{} combinations", matrix.runs.len())
+        };
+        let title = format!(
+            " Feature matrix · {} passed, {} failed · {} ",
+            passed, failed, combinations
+        );
+
+        let header = Row::new(
+            std::iter::once(Cell::from(""))
+                .chain(matrix.features.iter().map(|f| Cell::from(f.clone())))
+                .collect::<Vec<_>>(),
+        )
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let rows: Vec<Row> = matrix
+            .runs
+            .iter()
+            .map(|run| {
+                let (icon, style) = match run.status {
+                    MatrixStatus::Pending => ("⋯", Style::default().fg(Color::DarkGray)),
+                    MatrixStatus::Running => ("⟳", Style::default().fg(Color::Cyan)),
+                    MatrixStatus::Passed => ("✓", Style::default().fg(Color::Green)),
+                    MatrixStatus::Failed => ("✗", Style::default().fg(Color::Red)),
+                };
+                let marks = matrix.features.iter().map(|feature| {
+                    if run.features.contains(feature) {
+                        Cell::from("●").style(style)
+                    } else {
+                        Cell::from("·").style(Style::default().fg(Color::DarkGray))
+                    }
+                });
+                Row::new(
+                    std::iter::once(Cell::from(icon).style(style))
+                        .chain(marks)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        let widths: Vec<Constraint> = std::iter::once(Constraint::Length(2))
+            .chain(
+                matrix
+                    .features
+                    .iter()
+                    .map(|f| Constraint::Length(f.chars().count().max(1) as u16)),
+            )
+            .collect();
+
+        Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+    }
+}
+
+impl Component for FeatureExplorer {
+    fn handle_key_events(&mut self, key: KeyCode, app: &mut AppState) -> Option<Action> {
+        let count = app.features.available.features.len();
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => Some(Action::EnterNormalMode),
+            KeyCode::Char(' ') => Some(Action::ToggleFeature),
+            KeyCode::Char('d') => Some(Action::ToggleNoDefaultFeatures),
+            KeyCode::Char('a') => Some(Action::ToggleAllFeatures),
+            KeyCode::Char('m') => Some(Action::StartFeatureMatrix),
+            KeyCode::Char('c') => {
+                app.features
+                    .update_flags(|flags| *flags = FeatureFlags::default());
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                let i = match app.features.list_state.selected() {
+                    Some(i) if i + 1 < count => i + 1,
+                    _ => 0,
+                };
+                app.features.list_state.select(Some(i));
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+                let i = match app.features.list_state.selected() {
+                    Some(0) | None => count - 1,
+                    Some(i) => i - 1,
+                };
+                app.features.list_state.select(Some(i));
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        let Some(name) = app
+            .all_projects
+            .iter()
+            .find(|p| Some(&p.id) == app.features.project.as_ref())
+            .map(|p| p.display_name.clone())
+        else {
+            return;
+        };
+        let popup_area = centered_rect(70, 80, area);
+        f.render_widget(Clear, popup_area);
+
+        let matrix_height = app
+            .features
+            .current_matrix()
+            .map_or(0, |m| (m.runs.len() as u16 + 3).min(14));
+
+        let chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(3),
+                Constraint::Length(matrix_height),
+                Constraint::Length(3),
+            ])
+            .split(popup_area);
+
+        let flags = app.features.current_flags();
+        let summary = Paragraph::new(Self::summary_lines(&app.features.available, flags)).block(
+            Block::default()
+                .title(format!(" Features · {} ", name))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .border_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+        f.render_widget(summary, chunks[0]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        if app.features.available.features.is_empty() {
+            let empty = Paragraph::new("This package declares no features.")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items = Self::feature_items(&app.features.available, flags);
+            let list = List::new(items).block(block).highlight_style(
+                Style::default()
+                    .bg(Color::Rgb(40, 40, 60))
+                    .add_modifier(Modifier::BOLD),
+            );
+            f.render_stateful_widget(list, chunks[1], &mut app.features.list_state);
+        }
+
+        if let Some(matrix) = app.features.current_matrix() {
+            // Keep the combination being checked in view
+            let current = matrix
+                .runs
+                .iter()
+                .position(|run| run.status == MatrixStatus::Running);
+            let mut table_state = ratatui::widgets::TableState::default().with_selected(current);
+            f.render_stateful_widget(Self::matrix_table(matrix), chunks[2], &mut table_state);
+        }
+
+        let footer = Paragraph::new(
+            " Space: toggle | d: no defaults | a: all | c: clear | m: matrix | Esc: close ",
+        )
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        f.render_widget(footer, chunks[3]);
+    }
+}
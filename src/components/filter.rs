@@ -1,10 +1,15 @@
 //! Filter/search component for project discovery
 //!
 //! Provides live search functionality similar to vim's `/` search mode.
-//! Allows users to quickly find projects by name or path.
+//! The input is a [`Query`], so projects can be found by name or path as well
+//! as by fields such as `outdated:>3` or `dep:tokio`, and the matching projects
+//! can be selected in one go.
 
 use crate::app::AppState;
 use crate::events::Action;
+use crate::project::{Project, ProjectId};
+use crate::query::Query;
+use crate::tree::{FlattenedTree, TreeNode, TreeNodeType};
 use crossterm::event::KeyCode;
 use ratatui::Frame;
 use ratatui::layout::Rect;
@@ -18,54 +23,58 @@ pub struct FilterState {
     pub filtered_indices: Vec<usize>,
     /// Current selection within filtered results
     pub selected: usize,
+    /// Every matching project, including those in collapsed directories
+    pub matched_projects: Vec<ProjectId>,
+    /// Why the input is not a valid query
+    pub error: Option<String>,
 }
 
 impl FilterState {
     pub fn new() -> Self {
-        Self {
-            input: String::new(),
-            filtered_indices: Vec::new(),
-            selected: 0,
-        }
+        Self::default()
     }
 
     /// Update the filter text and recompute filtered indices
-    pub fn update_input(&mut self, input: String, app: &AppState) {
+    pub fn update_input(&mut self, input: String, tree: &FlattenedTree, projects: &[Project]) {
         self.input = input;
         self.selected = 0;
-        self.recompute_matches(app);
+        self.recompute_matches(tree, projects);
     }
 
-    /// Recompute which indices match the current filter
-    pub fn recompute_matches(&mut self, app: &AppState) {
+    /// Recompute which tree indices and projects match the current filter
+    ///
+    /// An invalid query matches nothing and leaves its error in `error`.
+    pub fn recompute_matches(&mut self, tree: &FlattenedTree, projects: &[Project]) {
         self.filtered_indices.clear();
+        self.matched_projects.clear();
+
+        let query = match Query::parse(&self.input) {
+            Ok(query) => {
+                self.error = None;
+                query
+            }
+            Err(error) => {
+                self.error = Some(error);
+                self.selected = 0;
+                return;
+            }
+        };
 
-        if self.input.is_empty() {
+        if self.input.trim().is_empty() {
             // If filter is empty, show all items
-            self.filtered_indices = (0..app.flattened_tree.items.len()).collect();
+            self.filtered_indices = (0..tree.items.len()).collect();
         } else {
-            let input_lower = self.input.to_lowercase();
-
-            for (idx, (node, _)) in app.flattened_tree.items.iter().enumerate() {
-                let matches = match &node.node_type {
-                    crate::tree::TreeNodeType::Directory { name, .. } => {
-                        name.to_lowercase().contains(&input_lower)
-                    }
-                    crate::tree::TreeNodeType::Project(project) => {
-                        project.name.to_lowercase().contains(&input_lower)
-                            || project
-                                .path
-                                .to_string_lossy()
-                                .to_lowercase()
-                                .contains(&input_lower)
-                    }
-                };
-
-                if matches {
+            for (idx, (node, _)) in tree.items.iter().enumerate() {
+                if node_matches(node, &query, projects) {
                     self.filtered_indices.push(idx);
                 }
             }
         }
+        self.matched_projects = projects
+            .iter()
+            .filter(|p| query.matches(p))
+            .map(|p| p.id.clone())
+            .collect();
 
         // Clamp selection
         if self.selected >= self.filtered_indices.len() {
@@ -101,6 +110,25 @@ impl FilterState {
         self.input.clear();
         self.filtered_indices.clear();
         self.selected = 0;
+        self.matched_projects.clear();
+        self.error = None;
+    }
+}
+
+/// Whether a tree node matches a filter query
+///
+/// Projects are looked up in `projects`, since tree nodes hold copies made
+/// before tags, git state and check results came in. Directories and tag
+/// groups match by name and tag alone (see [`Query::matches_group`]).
+pub fn node_matches(node: &TreeNode, query: &Query, projects: &[Project]) -> bool {
+    match &node.node_type {
+        TreeNodeType::Directory { name, .. } => query.matches_group(name, node.tag()),
+        TreeNodeType::Project(project) => query.matches(
+            projects
+                .iter()
+                .find(|p| p.id == project.id)
+                .unwrap_or(project),
+        ),
     }
 }
 
@@ -115,6 +143,7 @@ impl FilterComponent {
         match key {
             KeyCode::Esc => Some(Action::ExitFilterMode),
             KeyCode::Enter => Some(Action::ExitFilterMode),
+            KeyCode::Tab => Some(Action::SelectFilterMatches),
             _ => None,
         }
     }
@@ -143,12 +172,14 @@ mod tests {
         filter.input = "test".to_string();
         filter.filtered_indices = vec![1, 2, 3];
         filter.selected = 2;
+        filter.error = Some("unknown field `test`".to_string());
 
         filter.clear();
 
         assert!(filter.input.is_empty());
         assert!(filter.filtered_indices.is_empty());
         assert_eq!(filter.selected, 0);
+        assert!(filter.error.is_none());
     }
 
     #[test]
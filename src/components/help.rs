@@ -101,6 +101,58 @@ impl Component for Help {
                 Span::styled("  ←→ / h l     ", Style::default().fg(Color::Cyan)),
                 Span::raw("Collapse/Expand workspaces"),
             ]),
+            Line::from(vec![
+                Span::styled("  * / ~         ", Style::default().fg(Color::Cyan)),
+                Span::raw("Select all visible projects / invert the selection"),
+            ]),
+            Line::from(vec![
+                Span::styled("  g             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Group projects by tag / by directory"),
+            ]),
+            Line::from(vec![
+                Span::styled("  v             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Show projects as a table / as a tree"),
+            ]),
+            Line::from(vec![
+                Span::styled("  o / O         ", Style::default().fg(Color::Cyan)),
+                Span::raw("Table: sort by next column / reverse"),
+            ]),
+            Line::from(vec![
+                Span::styled("  g (table)     ", Style::default().fg(Color::Cyan)),
+                Span::raw("Group by workspace, tag, status or none"),
+            ]),
+            Line::from(vec![
+                Span::styled("  #             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Edit tags of the project"),
+            ]),
+            Line::from(vec![
+                Span::styled("  /             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Filter (dep:tokio outdated:>3 #tag ...)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  / then Tab    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Select every project the filter matches"),
+            ]),
+            Line::from(vec![
+                Span::styled("  i             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Show project details / dependencies"),
+            ]),
+            Line::from(vec![
+                Span::styled("  f             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Feature explorer and feature matrix"),
+            ]),
+            Line::from(vec![
+                Span::styled("  m             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Latest toolchain matrix (`:toolchains <cmd>`)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  d             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Disk usage of target dirs and sweeping"),
+            ]),
+            Line::from(vec![
+                Span::styled("  D             ", Style::default().fg(Color::Cyan)),
+                Span::raw("Where the project's disk space goes"),
+            ]),
             Line::from(vec![
                 Span::styled("  Tab / S-Tab  ", Style::default().fg(Color::Cyan)),
                 Span::raw("Cycle focus between panes"),
@@ -121,6 +173,10 @@ impl Component for Help {
                 Span::styled("  Shift+R      ", Style::default().fg(Color::Cyan)),
                 Span::raw("Reset layout to defaults"),
             ]),
+            Line::from(vec![
+                Span::styled("  Mouse        ", Style::default().fg(Color::Cyan)),
+                Span::raw("Click rows, checkboxes and tabs; wheel scrolls; drag borders"),
+            ]),
             Line::from(""),
             Line::from(vec![Span::styled(
                 "Commands",
@@ -137,6 +193,10 @@ impl Component for Help {
                 Span::styled("  u             ", Style::default().fg(Color::Cyan)),
                 Span::raw("Check for dependency updates"),
             ]),
+            Line::from(vec![
+                Span::styled("  Enter         ", Style::default().fg(Color::Cyan)),
+                Span::raw("Show version history (Dependencies pane)"),
+            ]),
             Line::from(vec![
                 Span::styled("  ?             ", Style::default().fg(Color::Cyan)),
                 Span::raw("Toggle this help screen"),
@@ -165,6 +225,14 @@ impl Component for Help {
                 Span::styled("  Tab           ", Style::default().fg(Color::Cyan)),
                 Span::raw("Toggle scope (Selected/All projects)"),
             ]),
+            Line::from(vec![
+                Span::styled("  select ...    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Select visible, outdated, failed, invert, none or a query"),
+            ]),
+            Line::from(vec![
+                Span::styled("  save selection", Style::default().fg(Color::Cyan)),
+                Span::raw(" Name the selection; recall it with selection <name>"),
+            ]),
             Line::from(vec![
                 Span::styled("  Enter         ", Style::default().fg(Color::Cyan)),
                 Span::raw("Execute selected command"),
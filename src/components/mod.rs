@@ -9,16 +9,22 @@ use crossterm::event::KeyCode;
 use ratatui::{Frame, layout::Rect};
 
 pub mod dependencies;
+pub mod dependency_detail;
+pub mod disk_usage;
+pub mod features;
 pub mod filter;
 pub mod help;
 pub mod output;
 pub mod palette;
 pub mod progress;
+pub mod project_details;
 pub mod projects;
+pub mod reclaim;
 pub mod settings;
 pub mod spinner;
 pub mod status;
 pub mod text_input;
+pub mod toolchains;
 pub mod tree_view;
 pub mod updater;
 
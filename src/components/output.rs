@@ -13,14 +13,12 @@ use ratatui::{
 };
 
 pub struct TabbedOutputPane {
-    scroll: usize,
     scroll_state: ScrollbarState,
 }
 
 impl TabbedOutputPane {
     pub fn new() -> Self {
         Self {
-            scroll: 0,
             scroll_state: ScrollbarState::default(),
         }
     }
@@ -60,12 +58,13 @@ impl Component for TabbedOutputPane {
                 }
             }
             KeyCode::PageUp | KeyCode::Char('k') | KeyCode::Up => {
-                self.scroll = self.scroll.saturating_sub(10);
+                app.output_scroll = app.output_scroll.saturating_sub(10);
                 None
             }
             KeyCode::PageDown | KeyCode::Char('j') | KeyCode::Down => {
                 if let Some(tab) = app.tabs.get(app.active_tab) {
-                    self.scroll = (self.scroll + 10).min(tab.buffer.len().saturating_sub(1));
+                    app.output_scroll =
+                        (app.output_scroll + 10).min(tab.buffer.len().saturating_sub(1));
                 }
                 None
             }
@@ -85,6 +84,9 @@ impl Component for TabbedOutputPane {
                 ratatui::layout::Constraint::Min(0),
             ])
             .split(area);
+        app.mouse.output_tabs = chunks[0];
+        app.mouse.output = chunks[1];
+        app.mouse.tab_titles.clear();
 
         // Render tabs
         if !app.tabs.is_empty() {
@@ -108,6 +110,17 @@ impl Component for TabbedOutputPane {
                 })
                 .collect();
 
+            // Where each title lands, padded by a space either side and divided by a line
+            let mut x = chunks[0].x + 1;
+            let right = chunks[0].right().saturating_sub(1);
+            for title in &titles {
+                let width = (title.width() as u16 + 2).min(right.saturating_sub(x));
+                app.mouse
+                    .tab_titles
+                    .push(Rect::new(x, chunks[0].y + 1, width, 1));
+                x = (x + width + 1).min(right);
+            }
+
             // Highlight border when focused
             let border_style = if app.focus == Focus::Output {
                 Style::default().fg(Color::Cyan)
@@ -148,12 +161,13 @@ impl Component for TabbedOutputPane {
 
             // Update scroll bounds
             let max_scroll = active_tab.buffer.len().saturating_sub(content_height);
-            self.scroll = self.scroll.min(max_scroll);
+            app.output_scroll = app.output_scroll.min(max_scroll);
+            let scroll = app.output_scroll;
 
             let visible_content: Vec<Line> = active_tab
                 .buffer
                 .iter()
-                .skip(self.scroll)
+                .skip(scroll)
                 .take(content_height)
                 .map(|line| {
                     // Colorize output based on content
@@ -181,13 +195,13 @@ impl Component for TabbedOutputPane {
             let status_info = if active_tab.is_finished {
                 format!(
                     " [Finished] Line {}/{} ",
-                    self.scroll + 1,
+                    scroll + 1,
                     active_tab.buffer.len().max(1)
                 )
             } else {
                 format!(
                     " [Running...] Line {}/{} ",
-                    self.scroll + 1,
+                    scroll + 1,
                     active_tab.buffer.len().max(1)
                 )
             };
@@ -217,7 +231,7 @@ impl Component for TabbedOutputPane {
                     .scroll_state
                     .content_length(active_tab.buffer.len())
                     .viewport_content_length(content_height)
-                    .position(self.scroll);
+                    .position(scroll);
 
                 let scrollbar_area = Rect {
                     x: chunks[1].x + chunks[1].width - 1,
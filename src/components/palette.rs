@@ -1,7 +1,7 @@
 use crate::app::AppState;
 use crate::components::Component;
 use crate::events::Action;
-use crate::events::Command;
+use crate::events::{BulkSelection, Command};
 use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
@@ -41,15 +41,27 @@ impl Component for CommandPalette {
     fn handle_key_events(&mut self, key: KeyCode, app: &mut AppState) -> Option<Action> {
         match key {
             KeyCode::Enter => {
-                let command = app
+                let input = app.palette.input.value();
+                if let Some(command) = app
                     .palette
                     .list_state
                     .selected()
                     .and_then(|i| app.palette.filtered_commands.get(i))
-                    .cloned()
-                    .unwrap_or_else(|| Command::Cargo {
-                        command: app.palette.input.value().to_string(),
-                    });
+                {
+                    return Some(Action::ExecuteCommand(command.clone()));
+                }
+                // An invalid query or an empty selection is not a cargo command
+                if input.starts_with("select ") || input.starts_with("save selection ") {
+                    return None;
+                }
+                let command = match input.strip_prefix("toolchains ") {
+                    Some(command) => Command::ToolchainMatrix {
+                        command: command.trim().to_string(),
+                    },
+                    None => Command::Cargo {
+                        command: input.to_string(),
+                    },
+                };
                 Some(Action::ExecuteCommand(command))
             }
             KeyCode::Esc => Some(Action::EnterNormalMode),
@@ -111,6 +123,74 @@ impl Component for CommandPalette {
                         ),
                         Span::styled(command, Style::default().fg(Color::White)),
                     ]),
+                    Command::SwitchCollection { name } => Line::from(vec![
+                        Span::styled(
+                            "collection ",
+                            Style::default()
+                                .fg(Color::Magenta)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(name, Style::default().fg(Color::White)),
+                    ]),
+                    Command::SelectTag { tag } => Line::from(vec![
+                        Span::styled(
+                            "select ",
+                            Style::default()
+                                .fg(Color::Green)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(format!("#{}", tag), Style::default().fg(Color::White)),
+                    ]),
+                    Command::Select { selection } => {
+                        let (verb, target) = match selection {
+                            BulkSelection::Saved(name) => ("selection ", name.clone()),
+                            BulkSelection::Matching(query) => ("select ", query.clone()),
+                            fixed => (
+                                "select ",
+                                BulkSelection::FIXED
+                                    .iter()
+                                    .find(|(selection, _)| selection == fixed)
+                                    .map_or(String::new(), |(_, name)| name.to_string()),
+                            ),
+                        };
+                        Line::from(vec![
+                            Span::styled(
+                                verb,
+                                Style::default()
+                                    .fg(Color::Green)
+                                    .add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(target, Style::default().fg(Color::White)),
+                        ])
+                    }
+                    Command::SaveSelection { name } => Line::from(vec![
+                        Span::styled(
+                            "save selection ",
+                            Style::default()
+                                .fg(Color::Green)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!("{} ({} projects)", name, app.selected_projects.len()),
+                            Style::default().fg(Color::White),
+                        ),
+                    ]),
+                    Command::DeleteSelection { name } => Line::from(vec![
+                        Span::styled(
+                            "forget selection ",
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(name, Style::default().fg(Color::White)),
+                    ]),
+                    Command::ToolchainMatrix { command } => Line::from(vec![
+                        Span::styled(
+                            "toolchains ",
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(command, Style::default().fg(Color::White)),
+                    ]),
                     _ => Line::from(format!("{:?}", cmd)),
                 };
                 ListItem::new(text)
@@ -138,6 +218,7 @@ impl Component for CommandPalette {
             .highlight_symbol("▶ ");
 
         f.render_stateful_widget(list, chunks[1], &mut app.palette.list_state);
+        app.mouse.palette_list = chunks[1];
 
         // Help text
         let selected_info = if app.selected_projects.is_empty() {
@@ -0,0 +1,242 @@
+use crate::app::AppState;
+use crate::components::Component;
+use crate::details::ProjectDetails;
+use crate::events::{Action, Focus};
+use crate::index::FileStamp;
+use crate::project::{Project, ProjectId};
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+
+/// Whether the details pane replaces the dependencies pane, and what it shows
+#[derive(Debug, Clone, Default)]
+pub struct ProjectDetailsState {
+    /// Shown in place of the dependencies pane
+    pub visible: bool,
+    /// Scroll offset of the details
+    pub scroll: usize,
+    /// Details of the last project shown, with the manifest stamp they were read at
+    cached: Option<(ProjectId, Option<FileStamp>, ProjectDetails)>,
+}
+
+impl ProjectDetailsState {
+    /// Details of `project`, read again when the project or its manifest changes
+    pub fn details_for(&mut self, project: &Project) -> &ProjectDetails {
+        let stamp = FileStamp::of(&project.path.join("Cargo.toml"));
+        let (same_project, fresh) = match &self.cached {
+            Some((id, cached, _)) => (*id == project.id, *id == project.id && *cached == stamp),
+            None => (false, false),
+        };
+        if !same_project {
+            self.scroll = 0;
+        }
+        if !fresh {
+            self.cached = None;
+        }
+        let (_, _, details) = self
+            .cached
+            .get_or_insert_with(|| (project.id.clone(), stamp, ProjectDetails::load(project)));
+        details
+    }
+}
+
+/// Edition, MSRV, targets, features, toolchain and README of the selected project
+pub struct ProjectDetailsPane {}
+
+impl ProjectDetailsPane {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn section(title: &str) -> Line<'static> {
+        Line::from(Span::styled(
+            title.to_string(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    fn field(label: &str, value: String, style: Style) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<11}", label),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(value, style),
+        ])
+    }
+
+    fn content_lines(project: &Project, details: &ProjectDetails) -> Vec<Line<'static>> {
+        let white = Style::default().fg(Color::White);
+        let dim = Style::default().fg(Color::DarkGray);
+        let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "—".to_string());
+
+        let mut lines = vec![
+            Self::section("Package"),
+            Self::field("Edition", or_dash(&project.edition), white),
+            Self::field("MSRV", or_dash(&project.rust_version), white),
+            Self::field("License", or_dash(&project.license), white),
+        ];
+
+        let toolchain = match &details.toolchain {
+            Some(toolchain) => {
+                let path = toolchain
+                    .path
+                    .strip_prefix(&project.path)
+                    .unwrap_or(&toolchain.path)
+                    .display()
+                    .to_string();
+                match &toolchain.channel {
+                    Some(channel) => format!("{} ({})", channel, path),
+                    None => path,
+                }
+            }
+            None => "—".to_string(),
+        };
+        lines.push(Self::field("Toolchain", toolchain, white));
+
+        let build = match &details.build_script {
+            Some(path) => (format!("✓ {}", path.display()), white),
+            None => ("none".to_string(), dim),
+        };
+        lines.push(Self::field("Build", build.0, build.1));
+
+        lines.push(Line::from(""));
+        lines.push(Self::section("Targets"));
+        if details.targets.is_empty() {
+            lines.push(Line::from(Span::styled("  none", dim)));
+        }
+        for target in &details.targets {
+            lines.push(Self::field(
+                target.kind.label(),
+                target.name.clone(),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Self::section("Features"));
+        if details.features.is_empty() {
+            lines.push(Line::from(Span::styled("  none", dim)));
+        }
+        for (feature, members) in &details.features {
+            let mut spans = vec![Span::styled(format!("  {}", feature), white)];
+            if !members.is_empty() {
+                spans.push(Span::styled(format!(" = {}", members.join(", ")), dim));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        if let Some(readme) = &details.readme {
+            let name = readme
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            lines.push(Line::from(""));
+            lines.push(Self::section(&name));
+            lines.extend(
+                readme
+                    .excerpt
+                    .iter()
+                    .map(|line| Line::from(Span::raw(format!("  {}", line)))),
+            );
+        }
+
+        lines
+    }
+}
+
+impl Component for ProjectDetailsPane {
+    fn handle_key_events(&mut self, key: KeyCode, app: &mut AppState) -> Option<Action> {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.details.scroll = app.details.scroll.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.details.scroll = app.details.scroll.saturating_add(1);
+                None
+            }
+            KeyCode::PageUp => {
+                app.details.scroll = app.details.scroll.saturating_sub(10);
+                None
+            }
+            KeyCode::PageDown => {
+                app.details.scroll = app.details.scroll.saturating_add(10);
+                None
+            }
+            KeyCode::Home => {
+                app.details.scroll = 0;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        let border_style = if app.focus == Focus::Dependencies {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        let Some(project) = app.get_selected_project().cloned() else {
+            let empty = Paragraph::new("No project selected.\n\nSelect a project to view details.")
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Details ")
+                        .border_style(border_style),
+                )
+                .style(Style::default().fg(Color::DarkGray));
+            f.render_widget(empty, area);
+            return;
+        };
+
+        let lines = Self::content_lines(&project, app.details.details_for(&project));
+
+        // Clamp scroll to the content so PageDown can't run off the end
+        let content_height = lines.len();
+        let viewport_height = area.height.saturating_sub(2) as usize;
+        let max_scroll = content_height.saturating_sub(viewport_height);
+        app.details.scroll = app.details.scroll.min(max_scroll);
+        let scroll = app.details.scroll;
+
+        let content = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Details · {} v{} ", project.name, project.version))
+                    .title_bottom(
+                        Line::from(" i: dependencies ").style(Style::default().fg(Color::DarkGray)),
+                    )
+                    .border_style(border_style),
+            )
+            .scroll((scroll as u16, 0));
+        f.render_widget(content, area);
+
+        if content_height > viewport_height {
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓"));
+            let mut scrollbar_state = ScrollbarState::default()
+                .content_length(content_height)
+                .viewport_content_length(viewport_height)
+                .position(scroll);
+            let scrollbar_area = Rect {
+                x: area.x + area.width.saturating_sub(1),
+                y: area.y + 1,
+                width: 1,
+                height: area.height.saturating_sub(2),
+            };
+            f.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+        }
+    }
+}
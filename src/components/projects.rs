@@ -1,16 +1,30 @@
 use crate::app::AppState;
 use crate::components::Component;
-use crate::events::Action;
+use crate::events::{Action, BulkSelection};
+use crate::project::{GitStatus, Project, ProjectId, ProjectStatus};
+use crate::project_table::{SortColumn, TableGrouping, outdated};
+use crate::tree::TreeNodeType;
 use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
-    widgets::ListItem,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, ListItem, Row, Table, TableState},
 };
+use std::collections::HashMap;
 
 pub struct ProjectList {}
 
+/// Per-project annotations kept in the app state rather than on the tree's copy
+struct ProjectBadges<'a> {
+    tags: &'a [String],
+    /// The latest MSRV check of the toolchain matrix failed
+    msrv_failed: bool,
+    /// Projects building into the same target directory, itself included
+    target_shared_by: usize,
+}
+
 impl ProjectList {
     pub fn new() -> Self {
         Self {}
@@ -56,22 +70,46 @@ impl ProjectList {
                 // Green - all up to date
                 ("✓", Style::default().fg(colors.success))
             }
+            ProjectCheckStatus::CheckFailed => {
+                // Red - some lookups failed, results are incomplete
+                ("✗", Style::default().fg(colors.error))
+            }
         }
     }
 }
 
 impl ProjectList {
+    /// Style of a target directory size, warning about large ones
+    fn size_style(target_size: u64, colors: crate::ui::styles::Colors) -> Style {
+        if target_size > 1_000_000_000 {
+            // > 1GB: red warning
+            Style::default()
+                .fg(colors.error)
+                .add_modifier(ratatui::style::Modifier::BOLD)
+        } else if target_size > 100_000_000 {
+            // > 100MB: yellow warning
+            Style::default().fg(colors.warning)
+        } else if target_size > 0 {
+            // > 0: dim gray
+            Style::default().fg(colors.muted)
+        } else {
+            // 0 bytes: very dim
+            Style::default().fg(Color::Rgb(80, 80, 80))
+        }
+    }
+
     /// Create a list item for a project from the tree (with indentation)
     fn create_tree_project_item<'a>(
-        selected_projects: &'a std::collections::HashSet<String>,
+        selected_projects: &'a std::collections::HashSet<crate::project::ProjectId>,
         project: &'a crate::project::Project,
+        badges: ProjectBadges<'a>,
         depth: usize,
         is_selected: bool,
         colors: crate::ui::styles::Colors,
     ) -> ListItem<'a> {
         let (status_icon, status_style) = Self::get_project_status(project, colors);
 
-        let is_checked = selected_projects.contains(&project.name);
+        let is_checked = selected_projects.contains(&project.id);
         let checkbox_symbol = if is_checked { "☑" } else { "☐" };
         let checkbox_style = if is_checked {
             Style::default().fg(colors.success)
@@ -112,29 +150,37 @@ impl ProjectList {
             ));
         }
 
+        if badges.msrv_failed {
+            spans.push(ratatui::text::Span::styled(
+                " ⚠ MSRV",
+                Style::default()
+                    .fg(colors.error)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        for tag in badges.tags {
+            spans.push(ratatui::text::Span::styled(
+                format!(" #{}", tag),
+                Style::default().fg(colors.dim),
+            ));
+        }
+
         // Add size information if available
         if let Some(target_size) = project.target_size {
             let size_str = crate::project::Project::format_size(target_size);
-            let size_style = if target_size > 1_000_000_000 {
-                // > 1GB: red warning
-                Style::default()
-                    .fg(colors.error)
-                    .add_modifier(ratatui::style::Modifier::BOLD)
-            } else if target_size > 100_000_000 {
-                // > 100MB: yellow warning
-                Style::default().fg(colors.warning)
-            } else if target_size > 0 {
-                // > 0: dim gray
-                Style::default().fg(colors.muted)
-            } else {
-                // 0 bytes: very dim
-                Style::default().fg(Color::Rgb(80, 80, 80))
-            };
+            let size_style = Self::size_style(target_size, colors);
             spans.push(ratatui::text::Span::raw(" "));
             spans.push(ratatui::text::Span::styled(
                 format!("📦{}", size_str),
                 size_style,
             ));
+            if badges.target_shared_by > 1 {
+                spans.push(ratatui::text::Span::styled(
+                    format!(" shared×{}", badges.target_shared_by),
+                    Style::default().fg(colors.dim),
+                ));
+            }
         }
 
         ListItem::new(ratatui::text::Line::from(spans))
@@ -149,11 +195,17 @@ impl Component for ProjectList {
             KeyCode::Left | KeyCode::Char('h') => Some(Action::SelectParent),
             KeyCode::Right | KeyCode::Char('l') => Some(Action::SelectChild),
             KeyCode::Char(' ') => Some(Action::ToggleSelection),
+            KeyCode::Char('*') => Some(Action::BulkSelect(BulkSelection::Visible)),
+            KeyCode::Char('~') => Some(Action::BulkSelect(BulkSelection::Invert)),
             _ => None,
         }
     }
 
     fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        if app.settings.table_view.enabled {
+            self.draw_table(f, app, area);
+            return;
+        }
         let selected_index = app.tree_state.selected();
         let mut items: Vec<ListItem> = Vec::new();
         let colors = app.current_colors();
@@ -178,12 +230,12 @@ impl Component for ProjectList {
                         .take_while(|(child_node, _)| child_node.depth > node.depth)
                         .filter_map(|(child_node, _)| {
                             if let crate::tree::TreeNodeType::Project(p) = &child_node.node_type {
-                                Some(&p.name)
+                                Some(&p.id)
                             } else {
                                 None
                             }
                         })
-                        .any(|name| app.selected_projects.contains(name));
+                        .any(|id| app.selected_projects.contains(id));
 
                     let checkbox_symbol = if has_selected_children { "☑" } else { "☐" };
                     let checkbox_style = if has_selected_children {
@@ -212,10 +264,23 @@ impl Component for ProjectList {
                     ])));
                 }
                 crate::tree::TreeNodeType::Project(project) => {
+                    // Tags are resolved on the app's copy, not the tree's
+                    let tags = app
+                        .all_projects
+                        .iter()
+                        .find(|p| p.id == project.id)
+                        .map(|p| p.tags.as_slice())
+                        .unwrap_or_default();
                     // Render project node
+                    let badges = ProjectBadges {
+                        tags,
+                        msrv_failed: app.toolchains.msrv_failures.contains(&project.id),
+                        target_shared_by: app.target_dirs.shared_by(&project.id),
+                    };
                     items.push(Self::create_tree_project_item(
                         &app.selected_projects,
                         project,
+                        badges,
                         node.depth,
                         is_selected,
                         colors,
@@ -232,24 +297,33 @@ impl Component for ProjectList {
             .filter(|(node, _)| node.node_type.is_project())
             .count();
         let all_count = app.all_projects.len();
+        // Name the collection (or root count) when there's more than one directory
+        let scope = if app.scan_roots.collection.is_some() || app.scan_roots.paths.len() > 1 {
+            format!(" · {}", app.scan_roots.label())
+        } else {
+            String::new()
+        };
 
         let title = if selected_count > 0 {
             if all_count > total_count {
                 format!(
-                    " Projects ({}/{} selected, {}/{} shown) ",
-                    selected_count, total_count, total_count, all_count
+                    " Projects{} ({}/{} selected, {}/{} shown) ",
+                    scope, selected_count, total_count, total_count, all_count
                 )
             } else {
-                format!(" Projects ({}/{} selected) ", selected_count, total_count)
+                format!(
+                    " Projects{} ({}/{} selected) ",
+                    scope, selected_count, total_count
+                )
             }
         } else if all_count > total_count {
-            format!(" Projects ({}/{} shown) ", total_count, all_count)
+            format!(" Projects{} ({}/{} shown) ", scope, total_count, all_count)
         } else {
-            format!(" Projects ({}) ", total_count)
+            format!(" Projects{} ({}) ", scope, total_count)
         };
 
         let help_text = if area.height > items.len() as u16 + 4 {
-            "\n\n ↑↓/jk: Navigate\n ←→/hl: Collapse/Expand\n Space: Select\n :: Command"
+            "\n\n ↑↓/jk: Navigate\n ←→/hl: Collapse/Expand\n Space: Select\n g: Group by tag\n v: Table view\n :: Command"
         } else {
             ""
         };
@@ -288,3 +362,231 @@ impl Component for ProjectList {
         f.render_stateful_widget(project_list, area, &mut app.tree_state);
     }
 }
+
+impl ProjectList {
+    /// Cells of a project's row in the table, after the checkbox and name
+    fn table_cells(
+        project: &Project,
+        target_shared_by: usize,
+        colors: crate::ui::styles::Colors,
+    ) -> Vec<Cell<'static>> {
+        let muted = Style::default().fg(colors.muted);
+        let outdated = match outdated(project) {
+            Some(0) => Cell::from("0").style(Style::default().fg(colors.success)),
+            Some(count) => Cell::from(count.to_string()).style(
+                Style::default()
+                    .fg(colors.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            None => Cell::from("·").style(muted),
+        };
+        let git = match project.git_status {
+            GitStatus::Clean => Cell::from("clean").style(muted),
+            GitStatus::Dirty => Cell::from("dirty").style(Style::default().fg(colors.warning)),
+            GitStatus::Unknown => Cell::from("·").style(muted),
+        };
+        let size = match project.target_size {
+            Some(bytes) if target_shared_by > 1 => Cell::from(format!(
+                "{} ×{}",
+                Project::format_size(bytes),
+                target_shared_by
+            ))
+            .style(Self::size_style(bytes, colors)),
+            Some(bytes) => {
+                Cell::from(Project::format_size(bytes)).style(Self::size_style(bytes, colors))
+            }
+            None => Cell::from("…").style(muted),
+        };
+        let last_run = match project.status {
+            ProjectStatus::Pending => Cell::from("·").style(muted),
+            ProjectStatus::Running => {
+                Cell::from("⟳ running").style(Style::default().fg(Color::Blue))
+            }
+            ProjectStatus::Success => Cell::from("✓ ok").style(Style::default().fg(colors.success)),
+            ProjectStatus::Failed => Cell::from("✗ failed").style(
+                Style::default()
+                    .fg(colors.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        };
+        let modified = match project.modified {
+            Some(modified) => Cell::from(crate::cache::format_age(modified)).style(muted),
+            None => Cell::from("·").style(muted),
+        };
+        vec![
+            Cell::from(project.version.clone()).style(muted),
+            outdated,
+            git,
+            size,
+            last_run,
+            modified,
+        ]
+    }
+
+    /// Draw projects as a sortable table, optionally grouped
+    fn draw_table(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        let colors = app.current_colors();
+        let view = app.settings.table_view;
+        // Rows show the app's projects, which are more current than the tree's copies
+        let projects: HashMap<&ProjectId, &Project> =
+            app.all_projects.iter().map(|p| (&p.id, p)).collect();
+
+        let mut rows: Vec<Row> = Vec::new();
+        for (node, _) in &app.flattened_tree.items {
+            match &node.node_type {
+                TreeNodeType::Directory { name, .. } => {
+                    let members: Vec<&Project> = node
+                        .children
+                        .iter()
+                        .filter_map(|child| match &child.node_type {
+                            TreeNodeType::Project(p) => projects.get(&p.id).copied(),
+                            TreeNodeType::Directory { .. } => None,
+                        })
+                        .collect();
+                    let has_selected = members
+                        .iter()
+                        .any(|p| app.selected_projects.contains(&p.id));
+                    let outdated: usize = members.iter().filter_map(|p| outdated(p)).sum();
+                    let target: u64 = members.iter().filter_map(|p| p.target_size).sum();
+                    let group_style = Style::default().fg(colors.dim).add_modifier(Modifier::BOLD);
+                    rows.push(Row::new(vec![
+                        Cell::from(if has_selected { "☑" } else { "☐" }),
+                        Cell::from(format!(
+                            "{} {} ({})",
+                            if node.expanded { "▾" } else { "▸" },
+                            name,
+                            members.len()
+                        ))
+                        .style(group_style),
+                        Cell::from(""),
+                        Cell::from(if outdated > 0 {
+                            outdated.to_string()
+                        } else {
+                            String::new()
+                        })
+                        .style(group_style),
+                        Cell::from(""),
+                        Cell::from(if target > 0 {
+                            Project::format_size(target)
+                        } else {
+                            String::new()
+                        })
+                        .style(group_style),
+                    ]));
+                }
+                TreeNodeType::Project(tree_project) => {
+                    let project = projects
+                        .get(&tree_project.id)
+                        .copied()
+                        .unwrap_or(tree_project);
+                    let is_checked = app.selected_projects.contains(&project.id);
+                    let (status_icon, status_style) = Self::get_project_status(project, colors);
+                    let mut name = vec![
+                        Span::raw("  ".repeat(node.depth)),
+                        Span::styled(status_icon, status_style),
+                        Span::raw(" "),
+                        Span::styled(
+                            project.display_name.clone(),
+                            Style::default().fg(colors.text),
+                        ),
+                    ];
+                    if app.toolchains.msrv_failures.contains(&project.id) {
+                        name.push(Span::styled(
+                            " ⚠ MSRV",
+                            Style::default()
+                                .fg(colors.error)
+                                .add_modifier(Modifier::BOLD),
+                        ));
+                    }
+                    let mut cells = vec![
+                        Cell::from(if is_checked { "☑" } else { "☐" }).style(if is_checked {
+                            Style::default().fg(colors.success)
+                        } else {
+                            Style::default().fg(colors.muted)
+                        }),
+                        Cell::from(Line::from(name)),
+                    ];
+                    cells.extend(Self::table_cells(
+                        project,
+                        app.target_dirs.shared_by(&project.id),
+                        colors,
+                    ));
+                    rows.push(Row::new(cells));
+                }
+            }
+        }
+
+        let header: Vec<Cell> = std::iter::once(Cell::from(""))
+            .chain(SortColumn::ALL.iter().map(|&column| {
+                if column == view.sort {
+                    let arrow = if view.descending { "▼" } else { "▲" };
+                    Cell::from(format!("{} {}", column.label(), arrow)).style(
+                        Style::default()
+                            .fg(colors.primary)
+                            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    )
+                } else {
+                    Cell::from(column.label())
+                }
+            }))
+            .collect();
+
+        let project_count = app
+            .flattened_tree
+            .items
+            .iter()
+            .filter(|(node, _)| node.node_type.is_project())
+            .count();
+        let selected_count = app.selected_projects.len();
+        let mut title = format!(" Projects ({}", project_count);
+        if selected_count > 0 {
+            title.push_str(&format!(", {} selected", selected_count));
+        }
+        if view.grouping != TableGrouping::None {
+            title.push_str(&format!(") · {} ", view.grouping.label()));
+        } else {
+            title.push_str(") ");
+        }
+
+        let border_color = if app.focus == crate::events::Focus::Projects {
+            Color::Cyan
+        } else {
+            colors.primary
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(9),
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(12),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(Row::new(header).style(Style::default().fg(colors.muted)))
+        .column_spacing(1)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::Rgb(40, 40, 60))
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(title)
+                .title_bottom(" v: tree | o/O: sort | g: group ")
+                .border_style(Style::default().fg(border_color)),
+        );
+
+        // The table scrolls like the list it replaces
+        let mut table_state = TableState::default()
+            .with_selected(app.tree_state.selected())
+            .with_offset(app.tree_state.offset());
+        f.render_stateful_widget(table, area, &mut table_state);
+        *app.tree_state.offset_mut() = table_state.offset();
+    }
+}
@@ -0,0 +1,344 @@
+use crate::app::AppState;
+use crate::components::Component;
+use crate::events::Action;
+use crate::project::Project;
+use crate::sweep::{SweepCriteria, SweepPlan, TargetBreakdown};
+use crate::ui::layout::centered_rect;
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Age presets `+` and `-` step through
+const AGE_STEPS: &[u32] = &[1, 3, 7, 14, 30, 60, 90, 180, 365];
+
+/// A target directory and the projects building into it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReclaimEntry {
+    pub target_dir: PathBuf,
+    pub projects: Vec<String>,
+    pub bytes: Option<u64>,
+}
+
+/// Target directories ranked by reclaimable space, largest first
+pub fn reclaim_entries(app: &AppState) -> Vec<ReclaimEntry> {
+    let projects: HashMap<_, &Project> = app.all_projects.iter().map(|p| (&p.id, p)).collect();
+    let mut entries: Vec<ReclaimEntry> = app
+        .target_dirs
+        .groups()
+        .into_iter()
+        .map(|(dir, ids)| {
+            let members: Vec<&Project> = ids
+                .iter()
+                .filter_map(|id| projects.get(id).copied())
+                .collect();
+            let mut names: Vec<String> = members.iter().map(|p| p.display_name.clone()).collect();
+            names.sort();
+            ReclaimEntry {
+                target_dir: dir.to_path_buf(),
+                projects: names,
+                bytes: members.iter().filter_map(|p| p.target_size).max(),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.bytes
+            .cmp(&a.bytes)
+            .then_with(|| a.target_dir.cmp(&b.target_dir))
+    });
+    entries
+}
+
+/// State of the disk reclamation view
+#[derive(Debug, Clone, Default)]
+pub struct ReclaimState {
+    pub table_state: TableState,
+    /// Breakdowns loaded so far, per target directory
+    pub breakdowns: HashMap<PathBuf, TargetBreakdown>,
+    /// Breakdowns being measured
+    pub loading: HashSet<PathBuf>,
+    pub criteria: SweepCriteria,
+    /// Dry-run preview of the next sweep
+    pub plan: Option<SweepPlan>,
+    /// A preview or sweep is running
+    pub busy: bool,
+    /// Outcome of the last sweep
+    pub message: Option<String>,
+}
+
+impl ReclaimState {
+    /// Target directory of the selected row
+    pub fn selected_dir(&self, entries: &[ReclaimEntry]) -> Option<PathBuf> {
+        self.table_state
+            .selected()
+            .and_then(|i| entries.get(i))
+            .map(|entry| entry.target_dir.clone())
+    }
+
+    /// Step the age criterion through the presets, turning it on if it was off
+    pub fn step_age(&mut self, up: bool) {
+        let days = self.criteria.older_than_days.map(|days| {
+            let next = if up {
+                AGE_STEPS.iter().copied().find(|&step| step > days)
+            } else {
+                AGE_STEPS.iter().rev().copied().find(|&step| step < days)
+            };
+            next.unwrap_or(days)
+        });
+        self.criteria.older_than_days = days.or(Some(AGE_STEPS[4]));
+    }
+}
+
+/// Modal ranking target directories by size, with breakdowns and sweeping
+pub struct ReclaimView {}
+
+impl ReclaimView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn size(bytes: Option<u64>) -> String {
+        bytes.map_or_else(|| "…".to_string(), Project::format_size)
+    }
+
+    fn breakdown_lines(breakdown: Option<&TargetBreakdown>) -> Vec<Line<'static>> {
+        let Some(breakdown) = breakdown else {
+            return vec![Line::styled(
+                "Measuring…",
+                Style::default().fg(Color::DarkGray),
+            )];
+        };
+        if breakdown.usages.is_empty() {
+            return vec![Line::styled(
+                "Nothing built yet",
+                Style::default().fg(Color::DarkGray),
+            )];
+        }
+        breakdown
+            .usages
+            .iter()
+            .map(|usage| {
+                let share = usage.bytes as f64 / breakdown.total.max(1) as f64;
+                let filled = (share * 20.0).round() as usize;
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:>9} ", Project::format_size(usage.bytes)),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled("█".repeat(filled), Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        "░".repeat(20 - filled.min(20)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(format!(" {}", usage.name)),
+                ])
+            })
+            .collect()
+    }
+
+    fn sweep_lines(app: &AppState) -> Vec<Line<'static>> {
+        let reclaim = &app.reclaim;
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Sweep artifacts ", dim),
+            Span::styled(
+                reclaim.criteria.describe(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])];
+
+        if reclaim.busy {
+            lines.push(Line::styled("Working…", Style::default().fg(Color::Cyan)));
+        } else if let Some(plan) = &reclaim.plan {
+            let scope = match plan.target_dirs.as_slice() {
+                [dir] => dir.display().to_string(),
+                dirs => format!("{} target directories", dirs.len()),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("Dry run: ", dim),
+                Span::styled(
+                    format!("{} would be freed", Project::format_size(plan.bytes)),
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    " · {} units, {} entries in {}",
+                    plan.units,
+                    plan.entries.len(),
+                    scope
+                )),
+            ]));
+            for entry in plan.entries.iter().take(5) {
+                lines.push(Line::styled(
+                    format!(
+                        "  {:>9}  {}",
+                        Project::format_size(entry.bytes),
+                        entry.path.display()
+                    ),
+                    dim,
+                ));
+            }
+            if !plan.entries.is_empty() {
+                lines.push(Line::styled(
+                    "Press x to delete these",
+                    Style::default().fg(Color::Red),
+                ));
+            }
+        } else if let Some(message) = &reclaim.message {
+            lines.push(Line::raw(message.clone()));
+        }
+        lines
+    }
+}
+
+impl Component for ReclaimView {
+    fn handle_key_events(&mut self, key: KeyCode, app: &mut AppState) -> Option<Action> {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::EnterNormalMode),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::ReclaimSelectNext),
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::ReclaimSelectPrevious),
+            KeyCode::Char('p') => Some(Action::PreviewSweep(false)),
+            KeyCode::Char('P') => Some(Action::PreviewSweep(true)),
+            KeyCode::Char('x') => Some(Action::ExecuteSweep),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                app.reclaim.step_age(true);
+                Some(Action::SweepCriteriaChanged)
+            }
+            KeyCode::Char('-') => {
+                app.reclaim.step_age(false);
+                Some(Action::SweepCriteriaChanged)
+            }
+            KeyCode::Char('a') => {
+                let criteria = &mut app.reclaim.criteria;
+                criteria.older_than_days = match criteria.older_than_days {
+                    Some(_) => None,
+                    None => Some(app.config.sweep.older_than_days),
+                };
+                Some(Action::SweepCriteriaChanged)
+            }
+            KeyCode::Char('t') => {
+                app.reclaim.criteria.uninstalled_toolchains ^= true;
+                Some(Action::SweepCriteriaChanged)
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        let popup_area = centered_rect(85, 85, area);
+        f.render_widget(Clear, popup_area);
+
+        let entries = reclaim_entries(app);
+        let sweep_lines = Self::sweep_lines(app);
+        let chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Min(6),
+                Constraint::Length(10),
+                Constraint::Length(sweep_lines.len() as u16 + 2),
+                Constraint::Length(3),
+            ])
+            .split(popup_area);
+
+        let total: u64 = entries.iter().filter_map(|e| e.bytes).sum();
+        let title = format!(
+            " Disk · {} in {} target directories ",
+            Project::format_size(total),
+            entries.len()
+        );
+        let rows: Vec<Row> = entries
+            .iter()
+            .map(|entry| {
+                let shared = if entry.projects.len() > 1 {
+                    format!(" (shared×{})", entry.projects.len())
+                } else {
+                    String::new()
+                };
+                Row::new(vec![
+                    Cell::from(Self::size(entry.bytes)).style(Style::default().fg(Color::Yellow)),
+                    Cell::from(format!("{}{}", entry.projects.join(", "), shared)),
+                    Cell::from(entry.target_dir.display().to_string())
+                        .style(Style::default().fg(Color::DarkGray)),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(9),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(vec!["Size", "Projects", "Target directory"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .column_spacing(2)
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::Rgb(40, 40, 60))
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+        f.render_stateful_widget(table, chunks[0], &mut app.reclaim.table_state);
+
+        let selected = app
+            .reclaim
+            .table_state
+            .selected()
+            .and_then(|i| entries.get(i));
+        let breakdown = Paragraph::new(Self::breakdown_lines(
+            selected.and_then(|entry| app.reclaim.breakdowns.get(&entry.target_dir)),
+        ))
+        .block(
+            Block::default()
+                .title(" Breakdown ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        f.render_widget(breakdown, chunks[1]);
+
+        let sweep = Paragraph::new(sweep_lines).block(
+            Block::default()
+                .title(" Sweep ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        f.render_widget(sweep, chunks[2]);
+
+        let footer = Paragraph::new(
+            " p: preview (P: all) | x: sweep | +/-: days | a: age | t: toolchains | Esc: close ",
+        )
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        f.render_widget(footer, chunks[3]);
+    }
+}
@@ -15,6 +15,8 @@ use tui_input::{Input, backend::crossterm::EventHandler};
 pub struct SettingsModalState {
     pub cache_minutes_input: Input,
     pub background_updates_enabled: bool,
+    pub offline: bool,
+    pub restore_session: bool,
     pub error_message: Option<String>,
 }
 
@@ -23,6 +25,8 @@ impl SettingsModalState {
         Self {
             cache_minutes_input: Input::default(),
             background_updates_enabled: false,
+            offline: false,
+            restore_session: false,
             error_message: None,
         }
     }
@@ -64,6 +68,8 @@ impl Component for SettingsModal {
             KeyCode::Char(' ') | KeyCode::Char('b') | KeyCode::Char('B') => {
                 Some(Action::SettingsToggleBackground)
             }
+            KeyCode::Char('o') | KeyCode::Char('O') => Some(Action::SettingsToggleOffline),
+            KeyCode::Char('r') | KeyCode::Char('R') => Some(Action::SettingsToggleSessionRestore),
             _ => {
                 let mut input = app.settings_modal.cache_minutes_input.clone();
                 if input
@@ -86,6 +92,8 @@ impl Component for SettingsModal {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
@@ -143,6 +151,52 @@ impl Component for SettingsModal {
             .block(Block::default().borders(Borders::LEFT | Borders::RIGHT));
         f.render_widget(cache_para, chunks[2]);
 
+        let offline_status = if app.settings_modal.offline {
+            Span::styled(
+                "On",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled("Off", Style::default().fg(Color::DarkGray))
+        };
+
+        let offline_line = Line::from(vec![
+            Span::styled(" Offline mode ", Style::default().fg(Color::White)),
+            Span::raw(" "),
+            offline_status,
+            Span::raw("    (o) toggle"),
+        ]);
+
+        let offline_para = Paragraph::new(offline_line)
+            .alignment(Alignment::Left)
+            .block(Block::default().borders(Borders::LEFT | Borders::RIGHT));
+        f.render_widget(offline_para, chunks[3]);
+
+        let session_status = if app.settings_modal.restore_session {
+            Span::styled(
+                "On",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled("Off", Style::default().fg(Color::DarkGray))
+        };
+
+        let session_line = Line::from(vec![
+            Span::styled(" Restore session ", Style::default().fg(Color::White)),
+            Span::raw(" "),
+            session_status,
+            Span::raw("    (r) toggle"),
+        ]);
+
+        let session_para = Paragraph::new(session_line)
+            .alignment(Alignment::Left)
+            .block(Block::default().borders(Borders::LEFT | Borders::RIGHT));
+        f.render_widget(session_para, chunks[4]);
+
         let mut lines = vec![Line::from(vec![
             Span::styled(" Enter", Style::default().fg(Color::Green)),
             Span::raw(": Save"),
@@ -165,6 +219,6 @@ impl Component for SettingsModal {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Magenta)),
         );
-        f.render_widget(help_para, chunks[4]);
+        f.render_widget(help_para, chunks[6]);
     }
 }
@@ -23,7 +23,7 @@ impl Component for Spinner {
         None
     }
 
-    fn draw(&mut self, f: &mut Frame, _app: &mut AppState, area: Rect) {
+    fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
         let frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
         let timestamp = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
@@ -32,7 +32,13 @@ impl Component for Spinner {
         let idx = (timestamp / 80) as usize % frames.len(); // 80ms per frame
         let symbol = frames[idx];
 
-        let text = format!(" {} Scanning for projects... ", symbol);
+        let text = match app.scan_progress {
+            Some(progress) => format!(
+                " {} Scanning for projects... {} found in {} directories ",
+                symbol, progress.found, progress.scanned
+            ),
+            None => format!(" {} Scanning for projects... ", symbol),
+        };
 
         // Calculate center for a small box
         let width = (text.len() as u16) + 2;
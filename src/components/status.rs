@@ -1,6 +1,7 @@
 use crate::app::AppState;
 use crate::components::Component;
 use crate::events::{Action, Focus, Mode};
+use crate::toolchains::ToolchainStatus;
 use ratatui::{
     Frame,
     layout::Rect,
@@ -54,10 +55,27 @@ impl Component for StatusBar {
             ),
             Mode::TextInput => ("INPUT", Color::Blue, "Enter confirm | Esc cancel"),
             Mode::Help => ("HELP", Color::Yellow, "Esc or 'q' to close"),
+            Mode::DependencyDetail => ("VERSIONS", Color::Cyan, "↑↓ scroll | Esc or 'q' to close"),
+            Mode::FeatureExplorer => (
+                "FEATURES",
+                Color::Magenta,
+                "Space toggle | d no defaults | a all | m matrix | Esc close",
+            ),
+            Mode::ToolchainMatrix => ("TOOLCHAINS", Color::Magenta, "Esc or 'q' to close"),
+            Mode::Reclaim => (
+                "DISK",
+                Color::Magenta,
+                "↑↓ select | p preview | x sweep | +/- days | Esc close",
+            ),
+            Mode::DiskUsage => (
+                "USAGE",
+                Color::Magenta,
+                "↑↓ select | Enter open | Backspace up | Esc close",
+            ),
             Mode::Filter => (
                 "FILTER",
                 Color::Cyan,
-                "Type to search | ↑↓ navigate | Enter select | Esc cancel",
+                "Type a query | ↑↓ navigate | Enter jump | Tab select matches | Esc close",
             ),
         };
 
@@ -73,15 +91,32 @@ impl Component for StatusBar {
         let mut bg_spans: Vec<Span> = Vec::new();
 
         // Scanning indicator
-        if app.is_scanning {
+        if app.is_scanning || app.scan_progress.is_some() {
+            let label = match app.scan_progress {
+                Some(progress) => format!(
+                    " ⟳ Scanning ({} found, {} dirs) ",
+                    progress.found, progress.scanned
+                ),
+                None => " ⟳ Scanning ".to_string(),
+            };
             bg_spans.push(Span::styled(
-                " ⟳ Scanning ",
+                label,
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
             ));
         }
 
+        // Offline indicator
+        if let Some(reason) = app.offline {
+            bg_spans.push(Span::styled(
+                format!(" ✈ Offline ({}) ", reason.label()),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
         // Update checking indicator
         if app.is_checking_updates {
             bg_spans.push(Span::styled(
@@ -92,6 +127,40 @@ impl Component for StatusBar {
             ));
         }
 
+        // Feature matrix indicator
+        if let Some(matrix) = app.features.matrix.as_ref().filter(|m| m.is_running()) {
+            let (passed, failed) = matrix.counts();
+            bg_spans.push(Span::styled(
+                format!(" ⚙ Matrix {}/{} ", passed + failed, matrix.runs.len()),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if let Some(matrix) = app.toolchains.matrix.as_ref().filter(|m| m.is_running()) {
+            let statuses = || {
+                matrix
+                    .rows
+                    .iter()
+                    .flat_map(|row| &row.cells)
+                    .map(|c| &c.status)
+            };
+            let done = statuses()
+                .filter(|s| matches!(s, ToolchainStatus::Passed | ToolchainStatus::Failed))
+                .count();
+            let runs = done
+                + statuses()
+                    .filter(|s| matches!(s, ToolchainStatus::Pending | ToolchainStatus::Running))
+                    .count();
+            bg_spans.push(Span::styled(
+                format!(" ⚙ Toolchains {}/{} ", done, runs),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
         // Pending background tasks indicator
         let pending_count = app.update_queue.queue.len();
         if pending_count > 0 {
@@ -101,6 +170,37 @@ impl Component for StatusBar {
             ));
         }
 
+        // Filter query and what it matches
+        if app.mode == Mode::Filter {
+            bg_spans.push(Span::styled(
+                format!(" /{}▏", app.filter.input),
+                Style::default().fg(Color::White),
+            ));
+            bg_spans.push(match &app.filter.error {
+                Some(error) => {
+                    Span::styled(format!(" {} ", error), Style::default().fg(Color::Red))
+                }
+                None => Span::styled(
+                    format!(
+                        " {} project{} · match {}/{} ",
+                        app.filter.matched_projects.len(),
+                        if app.filter.matched_projects.len() == 1 {
+                            ""
+                        } else {
+                            "s"
+                        },
+                        if app.filter.filtered_indices.is_empty() {
+                            0
+                        } else {
+                            app.filter.selected + 1
+                        },
+                        app.filter.filtered_indices.len()
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            });
+        }
+
         // Focus indicator - only show in Normal mode
         let focus_info = if app.mode == Mode::Normal {
             let (icon, label) = match app.focus {
@@ -1,6 +1,7 @@
 use crate::app::AppState;
 use crate::components::Component;
 use crate::events::Action;
+use crate::project::ProjectId;
 use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
@@ -9,10 +10,18 @@ use ratatui::{
 };
 use tui_input::{Input, backend::crossterm::EventHandler};
 
+/// What a text input's value is used for once submitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputTarget {
+    /// Tags assigned to a project
+    ProjectTags(ProjectId),
+}
+
 #[derive(Debug, Clone)]
 pub struct TextInputState {
     pub title: String,
     pub input: Input,
+    pub target: Option<TextInputTarget>,
 }
 
 impl TextInputState {
@@ -20,6 +29,7 @@ impl TextInputState {
         Self {
             title: String::new(),
             input: Input::default(),
+            target: None,
         }
     }
 }
@@ -36,10 +46,7 @@ impl Component for TextInput {
     fn handle_key_events(&mut self, key: KeyCode, app: &mut AppState) -> Option<Action> {
         match key {
             KeyCode::Esc => Some(Action::EnterNormalMode),
-            KeyCode::Enter => {
-                // another hack
-                None
-            }
+            KeyCode::Enter => Some(Action::SubmitTextInput),
             _ => {
                 let mut input = app.text_input.input.clone();
                 if input
@@ -0,0 +1,152 @@
+use crate::app::AppState;
+use crate::components::Component;
+use crate::events::Action;
+use crate::project::ProjectId;
+use crate::toolchains::{ToolchainMatrix, ToolchainStatus};
+use crate::ui::layout::centered_rect;
+use crossterm::event::KeyCode;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+};
+use std::collections::HashSet;
+
+/// The latest toolchain matrix and the MSRV checks that failed
+#[derive(Debug, Clone, Default)]
+pub struct ToolchainMatrixState {
+    pub matrix: Option<ToolchainMatrix>,
+    /// Projects whose latest MSRV check failed (flagged in the tree)
+    pub msrv_failures: HashSet<ProjectId>,
+}
+
+/// Modal with a projects × toolchains grid of results
+pub struct ToolchainMatrixView {}
+
+impl ToolchainMatrixView {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn cell(cell: &crate::toolchains::ToolchainCell) -> Cell<'static> {
+        let toolchain = cell.toolchain.clone().unwrap_or_default();
+        let (text, style) = match cell.status {
+            ToolchainStatus::NotApplicable => {
+                ("—".to_string(), Style::default().fg(Color::DarkGray))
+            }
+            ToolchainStatus::NotInstalled => (
+                format!("⊘ {} (not installed)", toolchain),
+                Style::default().fg(Color::DarkGray),
+            ),
+            ToolchainStatus::Pending => (
+                format!("⋯ {}", toolchain),
+                Style::default().fg(Color::DarkGray),
+            ),
+            ToolchainStatus::Running => {
+                (format!("⟳ {}", toolchain), Style::default().fg(Color::Cyan))
+            }
+            ToolchainStatus::Passed => (
+                format!("✓ {}", toolchain),
+                Style::default().fg(Color::Green),
+            ),
+            ToolchainStatus::Failed => (
+                format!("✗ {}", toolchain),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        };
+        Cell::from(text).style(style)
+    }
+}
+
+impl Component for ToolchainMatrixView {
+    fn handle_key_events(&mut self, key: KeyCode, _app: &mut AppState) -> Option<Action> {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => Some(Action::EnterNormalMode),
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame, app: &mut AppState, area: Rect) {
+        let Some(matrix) = &app.toolchains.matrix else {
+            return;
+        };
+        let popup_area = centered_rect(80, 70, area);
+        f.render_widget(Clear, popup_area);
+
+        let chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(popup_area);
+
+        let header = Row::new(
+            std::iter::once("Project".to_string())
+                .chain(matrix.columns.iter().cloned())
+                .map(Cell::from)
+                .collect::<Vec<_>>(),
+        )
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows: Vec<Row> = matrix
+            .rows
+            .iter()
+            .map(|row| {
+                Row::new(
+                    std::iter::once(Cell::from(row.name.clone()))
+                        .chain(row.cells.iter().map(Self::cell))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        let name_width = matrix
+            .rows
+            .iter()
+            .map(|row| row.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(7) as u16;
+        let widths: Vec<Constraint> = std::iter::once(Constraint::Length(name_width))
+            .chain(matrix.columns.iter().map(|_| Constraint::Fill(1)))
+            .collect();
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .block(
+                Block::default()
+                    .title(format!(" Toolchain matrix · cargo +… {} ", matrix.command))
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                    .border_style(
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            );
+        f.render_widget(table, chunks[0]);
+
+        let installed = if matrix.installed.is_empty() {
+            "none found".to_string()
+        } else {
+            matrix
+                .installed
+                .iter()
+                .map(|name| name.split('-').next().unwrap_or(name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let footer = Paragraph::new(format!(" Installed: {} | Esc: close ", installed))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            );
+        f.render_widget(footer, chunks[1]);
+    }
+}
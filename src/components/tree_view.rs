@@ -91,6 +91,7 @@ impl TreeView {
                         base_style.fg(Color::Yellow).add_modifier(Modifier::BOLD)
                     }
                     ProjectCheckStatus::UpToDate => base_style.fg(Color::Green),
+                    ProjectCheckStatus::CheckFailed => base_style.fg(Color::Red),
                 }
             }
         }
@@ -105,6 +106,7 @@ impl TreeView {
             ProjectCheckStatus::Checking => "⟳",
             ProjectCheckStatus::HasUpdates => "⚠",
             ProjectCheckStatus::UpToDate => "✓",
+            ProjectCheckStatus::CheckFailed => "✗",
         }
     }
 }
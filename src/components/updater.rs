@@ -1,7 +1,8 @@
 use crate::app::AppState;
 use crate::components::Component;
 use crate::events::Action;
-use crate::project::Dependency;
+use crate::policy::{UpdatePolicy, UpdateVerdict};
+use crate::project::{Dependency, ProjectId};
 use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
@@ -11,12 +12,13 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
 use std::collections::HashSet;
+use std::sync::Arc;
 
 /// Pending directory check for multiple projects
 #[derive(Debug, Clone)]
 pub struct PendingDirectoryCheck {
     pub directory_name: String,
-    pub project_names: Vec<String>,
+    pub projects: Vec<ProjectId>,
 }
 
 /// State for the update wizard UI component
@@ -29,16 +31,18 @@ pub struct UpdateWizardState {
     pub selected_dependencies: HashSet<String>,
     pub list_state: ratatui::widgets::ListState,
     /// The project this wizard is locked to (prevents background updates from changing display)
-    pub locked_project_name: Option<String>,
+    pub locked_project: Option<ProjectId>,
     /// Whether a user-initiated check is in progress for the locked project
     /// This prevents background checks from clearing the is_checking_updates flag prematurely
     pub user_check_in_progress: bool,
     /// Pending directory check (set when user presses 'u' on a directory)
     /// The async handler will process this and queue all projects for checking
     pub pending_directory_check: Option<PendingDirectoryCheck>,
-    /// Project name pending dependency reload after update command completes
+    /// Project pending dependency reload after update command completes
     /// Set when RunUpdate starts, cleared and processed when command finishes
-    pub pending_reload_project: Option<String>,
+    pub pending_reload_project: Option<ProjectId>,
+    /// Update policy of the locked project, used to pick update targets
+    pub policy: Arc<UpdatePolicy>,
 }
 
 impl UpdateWizardState {
@@ -47,12 +51,42 @@ impl UpdateWizardState {
             outdated_dependencies: Vec::new(),
             selected_dependencies: HashSet::new(),
             list_state: ratatui::widgets::ListState::default(),
-            locked_project_name: None,
+            locked_project: None,
             user_check_in_progress: false,
             pending_directory_check: None,
             pending_reload_project: None,
+            policy: Arc::default(),
         }
     }
+
+    /// Replace the outdated list with the dependencies the policy offers updates for
+    pub fn set_outdated_from(&mut self, deps: &[Dependency]) {
+        self.outdated_dependencies = deps
+            .iter()
+            .filter(|d| d.has_update(&self.policy))
+            .cloned()
+            .collect();
+    }
+
+    /// `cargo update` arguments for each selected dependency, in list order
+    ///
+    /// Each one is pinned with `--precise` to the newest version the policy
+    /// allows within the Cargo.toml requirement, the one shown in the wizard.
+    /// Dependencies whose only updates are major bumps are left out, since
+    /// cargo can't reach them without a Cargo.toml change.
+    pub fn update_commands(&self) -> Vec<String> {
+        self.outdated_dependencies
+            .iter()
+            .filter(|dep| self.selected_dependencies.contains(&dep.name))
+            .filter_map(|dep| {
+                let target = dep.compatible_update(&self.policy)?;
+                Some(format!(
+                    "update -p {}@{} --precise {}",
+                    dep.name, dep.current_version, target
+                ))
+            })
+            .collect()
+    }
 }
 
 pub struct UpdateWizard {}
@@ -138,7 +172,12 @@ impl Component for UpdateWizard {
             .split(popup_area);
 
         // Title - show the locked project name and checking status
-        let title_text = if let Some(ref project_name) = app.updater.locked_project_name {
+        let locked = app
+            .updater
+            .locked_project
+            .as_ref()
+            .and_then(|id| app.projects.iter().find(|p| &p.id == id));
+        let title_text = if let Some(project_name) = locked.map(|p| &p.display_name) {
             if app.is_checking_updates {
                 format!(" Update Dependencies - {} ⟳ ", project_name)
             } else {
@@ -165,21 +204,15 @@ impl Component for UpdateWizard {
         if app.updater.outdated_dependencies.is_empty() {
             // Check if dependencies have been checked (have latest_version set)
             // Use the LOCKED project, not the currently selected one (user may have moved cursor)
-            let has_been_checked = if let Some(ref locked_name) = app.updater.locked_project_name {
-                app.projects
-                    .iter()
-                    .find(|p| &p.name == locked_name)
-                    .map(|project| {
-                        !project.dependencies.is_empty()
-                            && project
-                                .dependencies
-                                .iter()
-                                .any(|d| d.latest_version.is_some())
-                    })
-                    .unwrap_or(false)
-            } else {
-                false
-            };
+            let has_been_checked = locked
+                .map(|project| {
+                    !project.dependencies.is_empty()
+                        && project
+                            .dependencies
+                            .iter()
+                            .any(|d| d.latest_version.is_some())
+                })
+                .unwrap_or(false);
 
             let empty_msg = if app.is_checking_updates {
                 " ⟳ Checking for updates...\n\n Please wait or press Esc to cancel "
@@ -207,7 +240,20 @@ impl Component for UpdateWizard {
                 .iter()
                 .map(|dep| {
                     let is_selected = app.updater.selected_dependencies.contains(&dep.name);
-                    let is_major = dep.is_major_update();
+                    let available = match dep.update_verdict(&app.updater.policy) {
+                        UpdateVerdict::Available(target) => target,
+                        _ => dep.latest_version.clone().unwrap_or_default(),
+                    };
+                    // What `cargo update` will move to; major bumps need a Cargo.toml change
+                    let compatible = dep.compatible_update(&app.updater.policy);
+                    let note = match &compatible {
+                        Some(target) if *target != available => {
+                            Some(format!(" ({} needs a Cargo.toml change)", available))
+                        }
+                        Some(_) => None,
+                        None => Some(" (major, needs a Cargo.toml change: skipped)".to_string()),
+                    };
+                    let target = compatible.unwrap_or(available);
                     let checkbox = if is_selected { "☑" } else { "☐" };
 
                     let mut spans = vec![
@@ -230,7 +276,7 @@ impl Component for UpdateWizard {
                         Span::styled(&dep.current_version, Style::default().fg(Color::Red)),
                         Span::styled(" → ", Style::default().fg(Color::Yellow)),
                         Span::styled(
-                            dep.latest_version.as_ref().unwrap(),
+                            target,
                             Style::default()
                                 .fg(Color::Green)
                                 .add_modifier(Modifier::BOLD),
@@ -238,9 +284,9 @@ impl Component for UpdateWizard {
                     ];
 
                     // Add note for major version updates
-                    if is_major {
+                    if let Some(note) = note {
                         spans.push(Span::styled(
-                            " (major)",
+                            note,
                             Style::default()
                                 .fg(Color::Magenta)
                                 .add_modifier(Modifier::ITALIC),
@@ -356,4 +402,90 @@ mod tests {
         );
         assert!(cloned.selected_dependencies.contains("serde"));
     }
+
+    #[test]
+    fn test_update_commands_use_policy_target() {
+        use crate::config::UpdatePolicyConfig;
+        use crate::project::{DependencyCheckStatus, PublishedVersion};
+
+        let dep = |name: &str, versions: &[&str]| Dependency {
+            name: name.into(),
+            current_version: "1.0.0".into(),
+            latest_version: versions.first().map(|v| v.to_string()),
+            check_status: DependencyCheckStatus::Checked,
+            last_checked: None,
+            published_versions: versions
+                .iter()
+                .map(|v| PublishedVersion {
+                    version: v.to_string(),
+                    released_at: None,
+                    yanked: false,
+                })
+                .collect(),
+            requirement: None,
+            features: Vec::new(),
+        };
+
+        let mut state = UpdateWizardState::new();
+        state.policy = Arc::new(UpdatePolicy::from_config(&UpdatePolicyConfig {
+            pin: [("tokio".to_string(), "1".to_string())].into(),
+            ..UpdatePolicyConfig::default()
+        }));
+        state.outdated_dependencies = vec![
+            dep("tokio", &["2.0.0", "1.4.0", "1.0.0"]),
+            dep("serde", &["1.2.0", "1.0.0"]),
+            dep("anyhow", &["1.1.0", "1.0.0"]),
+        ];
+        state.selected_dependencies = ["tokio", "serde"].map(String::from).into();
+
+        assert_eq!(
+            state.update_commands(),
+            [
+                "update -p tokio@1.0.0 --precise 1.4.0",
+                "update -p serde@1.0.0 --precise 1.2.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_commands_stay_within_requirement() {
+        use crate::project::{DependencyCheckStatus, PublishedVersion};
+
+        let dep = |name: &str, requirement: &str, versions: &[&str]| Dependency {
+            name: name.into(),
+            current_version: "1.0.0".into(),
+            latest_version: versions.first().map(|v| v.to_string()),
+            check_status: DependencyCheckStatus::Checked,
+            last_checked: None,
+            published_versions: versions
+                .iter()
+                .map(|v| PublishedVersion {
+                    version: v.to_string(),
+                    released_at: None,
+                    yanked: false,
+                })
+                .collect(),
+            requirement: Some(requirement.into()),
+            features: Vec::new(),
+        };
+
+        let mut state = UpdateWizardState::new();
+        state.outdated_dependencies = vec![
+            // A new major release, with a compatible one below it
+            dep("clap", "1", &["2.0.0", "1.5.0", "1.0.0"]),
+            // Only a major release: cargo would reject `--precise 2.0.0`
+            dep("rand", "1", &["2.0.0", "1.0.0"]),
+            // A tilde requirement keeps the minor version
+            dep("regex", "~1.0", &["1.3.0", "1.0.4", "1.0.0"]),
+        ];
+        state.selected_dependencies = ["clap", "rand", "regex"].map(String::from).into();
+
+        assert_eq!(
+            state.update_commands(),
+            [
+                "update -p clap@1.0.0 --precise 1.5.0",
+                "update -p regex@1.0.0 --precise 1.0.4",
+            ]
+        );
+    }
 }
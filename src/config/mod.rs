@@ -5,14 +5,17 @@
 
 pub mod keybinding_config;
 pub mod theme_config;
+pub mod update_policy_config;
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 pub use keybinding_config::KeybindingConfig;
 pub use theme_config::ThemeConfig;
+pub use update_policy_config::UpdatePolicyConfig;
 
 /// Main configuration structure for CarWash
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -31,6 +34,38 @@ pub struct Config {
     /// Progress visualization settings
     #[serde(default)]
     pub progress: ProgressConfig,
+
+    /// Dependency update policy (ignores, pins, pre-releases, cooldown)
+    #[serde(default)]
+    pub updates: UpdatePolicyConfig,
+
+    /// Registry lookup settings for update checks
+    #[serde(default)]
+    pub checker: CheckerConfig,
+
+    /// Project discovery limits
+    #[serde(default)]
+    pub scan: ScanConfig,
+
+    /// Named sets of scan roots, e.g. `work = ["~/src/company/*", "~/oss/foo"]`
+    #[serde(default)]
+    pub collections: BTreeMap<String, Vec<String>>,
+
+    /// Tags assigned by project name or path, e.g. `services = ["*-service", "~/src/svc/*"]`
+    #[serde(default)]
+    pub tags: BTreeMap<String, Vec<String>>,
+
+    /// Bounds for feature matrix runs
+    #[serde(default)]
+    pub feature_matrix: FeatureMatrixConfig,
+
+    /// Toolchains a toolchain matrix runs on
+    #[serde(default)]
+    pub toolchains: ToolchainsConfig,
+
+    /// Defaults for sweeping stale build artifacts
+    #[serde(default)]
+    pub sweep: SweepConfig,
 }
 
 /// Layout preference configuration
@@ -93,6 +128,197 @@ impl Default for ProgressConfig {
     }
 }
 
+/// Update checker configuration (registry lookups)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckerConfig {
+    /// Base URL of the crates.io-compatible registry API
+    #[serde(default = "default_registry_url")]
+    pub registry_url: String,
+
+    /// Timeout for a single registry request, in seconds
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Maximum number of registry requests in flight across all checks
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+
+    /// Retries for timeouts, network errors, 429 and 5xx responses
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled for each further attempt
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+
+    /// Minimum spacing between registry requests, 0 to rely on `concurrency` alone
+    #[serde(default = "default_min_request_interval_ms")]
+    pub min_request_interval_ms: u64,
+
+    /// Local crates.io index checkout consulted when running offline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_index: Option<PathBuf>,
+}
+
+fn default_registry_url() -> String {
+    "https://crates.io".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_concurrency() -> usize {
+    5
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+
+fn default_min_request_interval_ms() -> u64 {
+    50
+}
+
+impl Default for CheckerConfig {
+    fn default() -> Self {
+        Self {
+            registry_url: default_registry_url(),
+            timeout_secs: default_timeout_secs(),
+            concurrency: default_concurrency(),
+            max_retries: default_max_retries(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            min_request_interval_ms: default_min_request_interval_ms(),
+            local_index: None,
+        }
+    }
+}
+
+/// Project discovery configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanConfig {
+    /// How many directory levels below the scanned directory to descend
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+
+    /// Glob patterns for paths to skip, matched against directory names and
+    /// paths relative to the scanned directory (e.g. `"archive"`, `"vendor/*"`)
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+fn default_max_depth() -> usize {
+    12
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: default_max_depth(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+/// Feature matrix configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureMatrixConfig {
+    /// Most combinations checked in one matrix run
+    #[serde(default = "default_max_combinations")]
+    pub max_combinations: usize,
+
+    /// Most features combined at once (all of them if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+}
+
+fn default_max_combinations() -> usize {
+    64
+}
+
+impl Default for FeatureMatrixConfig {
+    fn default() -> Self {
+        Self {
+            max_combinations: default_max_combinations(),
+            depth: None,
+        }
+    }
+}
+
+/// Toolchain matrix configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolchainsConfig {
+    /// Toolchains to run on; `msrv` and `pinned` stand for each project's
+    /// `rust-version` and `rust-toolchain` file
+    #[serde(default = "default_toolchain_matrix")]
+    pub matrix: Vec<String>,
+}
+
+fn default_toolchain_matrix() -> Vec<String> {
+    vec!["stable".into(), "msrv".into(), "nightly".into()]
+}
+
+impl Default for ToolchainsConfig {
+    fn default() -> Self {
+        Self {
+            matrix: default_toolchain_matrix(),
+        }
+    }
+}
+
+/// Sweep configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepConfig {
+    /// Artifacts unused for this many days are swept
+    #[serde(default = "default_sweep_days")]
+    pub older_than_days: u32,
+}
+
+fn default_sweep_days() -> u32 {
+    30
+}
+
+impl Default for SweepConfig {
+    fn default() -> Self {
+        Self {
+            older_than_days: default_sweep_days(),
+        }
+    }
+}
+
+/// Per-repository configuration read from `.carwash.toml`
+///
+/// Lives next to a project's or workspace's `Cargo.toml` and refines the global
+/// configuration for that repository only.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RepoConfig {
+    /// Update policy rules layered on top of the global `[updates]` section
+    #[serde(default)]
+    pub updates: UpdatePolicyConfig,
+}
+
+impl RepoConfig {
+    /// File name looked up in project and workspace roots
+    pub const FILE_NAME: &'static str = ".carwash.toml";
+
+    /// Load the repository config from a directory, if present and valid
+    pub fn load(dir: &std::path::Path) -> Option<Self> {
+        let path = dir.join(Self::FILE_NAME);
+        let content = fs::read_to_string(&path).ok()?;
+        match toml::from_str::<RepoConfig>(&content) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                tracing::warn!("Failed to parse {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+}
+
 impl Config {
     /// Get the path to the config file
     pub fn config_path() -> Result<PathBuf> {
@@ -186,6 +412,49 @@ mod tests {
         assert_eq!(progress.animation_speed, "normal");
     }
 
+    #[test]
+    fn test_checker_config_partial() {
+        let config: Config =
+            toml::from_str("[theme]\n[checker]\ntimeout_secs = 10\n").expect("Failed to parse");
+        assert_eq!(config.checker.timeout_secs, 10);
+        assert_eq!(config.checker.concurrency, 5);
+        assert_eq!(config.checker.registry_url, "https://crates.io");
+    }
+
+    #[test]
+    fn test_scan_config_partial() {
+        let config: Config =
+            toml::from_str("[theme]\n[scan]\nexclude = [\"archive\"]\n").expect("Failed to parse");
+        assert_eq!(config.scan.exclude, ["archive"]);
+        assert_eq!(config.scan.max_depth, 12);
+        assert!(config.collections.is_empty());
+    }
+
+    #[test]
+    fn test_collections() {
+        let config: Config =
+            toml::from_str("[theme]\n[collections]\nwork = [\"~/src/company/*\", \"~/oss/foo\"]\n")
+                .expect("Failed to parse");
+        assert_eq!(config.collections["work"], ["~/src/company/*", "~/oss/foo"]);
+    }
+
+    #[test]
+    fn test_tags() {
+        let config: Config = toml::from_str("[theme]\n[tags]\nservices = [\"*-service\"]\n")
+            .expect("Failed to parse");
+        assert_eq!(config.tags["services"], ["*-service"]);
+    }
+
+    #[test]
+    fn test_feature_matrix_config() {
+        let config: Config =
+            toml::from_str("[theme]\n[feature_matrix]\ndepth = 2\n").expect("Failed to parse");
+        assert_eq!(config.feature_matrix.depth, Some(2));
+        assert_eq!(config.feature_matrix.max_combinations, 64);
+        assert_eq!(config.toolchains.matrix, ["stable", "msrv", "nightly"]);
+        assert_eq!(config.sweep.older_than_days, 30);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::default();
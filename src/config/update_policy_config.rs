@@ -0,0 +1,134 @@
+//! Update policy configuration
+//!
+//! Declarative rules that decide which available dependency updates are worth
+//! surfacing. The same `[updates]` section is accepted in the global config file
+//! and in a per-repository `.carwash.toml`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// `[updates]` section of the config file or a repository's `.carwash.toml`
+///
+/// ```toml
+/// [updates]
+/// ignore = ["openssl", "windows-sys@>=0.60"]
+/// allow-prerelease = ["ratatui"]
+/// cooldown-days = 3
+///
+/// [updates.pin]
+/// tokio = "1"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdatePolicyConfig {
+    /// Crates whose updates are ignored, optionally restricted to a version range
+    /// of candidate versions (`"name"` or `"name@<req>"`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+
+    /// Version requirements that update candidates must satisfy, per crate
+    /// (e.g. `tokio = "1"` keeps tokio below 2.0)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pin: BTreeMap<String, String>,
+
+    /// Crates for which pre-release versions count as updates (`"*"` for all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_prerelease: Vec<String>,
+
+    /// Minimum age in days before a release is offered as an update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown_days: Option<u64>,
+}
+
+impl UpdatePolicyConfig {
+    /// Whether this section contains no rules at all
+    pub fn is_empty(&self) -> bool {
+        self.ignore.is_empty()
+            && self.pin.is_empty()
+            && self.allow_prerelease.is_empty()
+            && self.cooldown_days.is_none()
+    }
+
+    /// Layer a more specific section (e.g. a repository's) on top of this one
+    ///
+    /// Ignore and pre-release lists are combined, pins are overridden per crate and
+    /// the cooldown of the more specific section wins when set.
+    pub fn merged_with(&self, overrides: &UpdatePolicyConfig) -> UpdatePolicyConfig {
+        let mut merged = self.clone();
+
+        for entry in &overrides.ignore {
+            if !merged.ignore.contains(entry) {
+                merged.ignore.push(entry.clone());
+            }
+        }
+        for (name, req) in &overrides.pin {
+            merged.pin.insert(name.clone(), req.clone());
+        }
+        for name in &overrides.allow_prerelease {
+            if !merged.allow_prerelease.contains(name) {
+                merged.allow_prerelease.push(name.clone());
+            }
+        }
+        if overrides.cooldown_days.is_some() {
+            merged.cooldown_days = overrides.cooldown_days;
+        }
+
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_updates_section() {
+        let toml_str = r#"
+ignore = ["openssl", "hyper@>=1"]
+allow-prerelease = ["ratatui"]
+cooldown-days = 3
+
+[pin]
+tokio = "1"
+"#;
+        let config: UpdatePolicyConfig = toml::from_str(toml_str).expect("Failed to parse");
+        assert_eq!(config.ignore.len(), 2);
+        assert_eq!(config.pin.get("tokio"), Some(&"1".to_string()));
+        assert_eq!(config.allow_prerelease, vec!["ratatui".to_string()]);
+        assert_eq!(config.cooldown_days, Some(3));
+    }
+
+    #[test]
+    fn test_merge_overrides() {
+        let global = UpdatePolicyConfig {
+            ignore: vec!["openssl".into()],
+            pin: [("tokio".to_string(), "1".to_string())]
+                .into_iter()
+                .collect(),
+            allow_prerelease: vec![],
+            cooldown_days: Some(7),
+        };
+        let repo = UpdatePolicyConfig {
+            ignore: vec!["openssl".into(), "rand".into()],
+            pin: [("tokio".to_string(), "<1.40".to_string())]
+                .into_iter()
+                .collect(),
+            allow_prerelease: vec!["ratatui".into()],
+            cooldown_days: None,
+        };
+
+        let merged = global.merged_with(&repo);
+        assert_eq!(
+            merged.ignore,
+            vec!["openssl".to_string(), "rand".to_string()]
+        );
+        assert_eq!(merged.pin.get("tokio"), Some(&"<1.40".to_string()));
+        assert_eq!(merged.allow_prerelease, vec!["ratatui".to_string()]);
+        assert_eq!(merged.cooldown_days, Some(7));
+    }
+
+    #[test]
+    fn test_default_is_empty() {
+        assert!(UpdatePolicyConfig::default().is_empty());
+    }
+}
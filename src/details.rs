@@ -0,0 +1,296 @@
+//! Project details read on demand for the details pane
+//!
+//! Besides what the scan keeps on [`Project`], the details pane shows a
+//! package's build targets, features, build script, toolchain file and the
+//! start of its README. These are read from disk only for the project being
+//! looked at, following Cargo's target auto-discovery and rustup's toolchain
+//! file lookup.
+
+use crate::project::{CargoToml, Project, TargetSpec};
+use crate::workspace::read_manifest;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lines of the README shown in the details pane
+const README_EXCERPT_LINES: usize = 12;
+
+/// Kind of a build target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TargetKind {
+    Lib,
+    Bin,
+    Example,
+    Bench,
+}
+
+impl TargetKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TargetKind::Lib => "lib",
+            TargetKind::Bin => "bin",
+            TargetKind::Example => "example",
+            TargetKind::Bench => "bench",
+        }
+    }
+}
+
+/// A build target of the package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub kind: TargetKind,
+    pub name: String,
+}
+
+/// A `rust-toolchain` file that applies to the project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toolchain {
+    pub path: PathBuf,
+    /// Pinned channel, e.g. `stable` or `1.80.0`
+    pub channel: Option<String>,
+}
+
+/// The start of the project's README
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Readme {
+    pub path: PathBuf,
+    pub excerpt: Vec<String>,
+}
+
+/// What the details pane shows beyond the project's package metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectDetails {
+    pub targets: Vec<Target>,
+    /// Features in manifest order with `default` first, and what each enables
+    pub features: Vec<(String, Vec<String>)>,
+    /// Build script, relative to the project directory
+    pub build_script: Option<PathBuf>,
+    pub toolchain: Option<Toolchain>,
+    pub readme: Option<Readme>,
+}
+
+impl ProjectDetails {
+    /// Read the details of a project from its manifest and directory
+    pub fn load(project: &Project) -> Self {
+        let Some(toml) = read_manifest(&project.path.join("Cargo.toml")) else {
+            return Self::default();
+        };
+        let dir = project.path.as_path();
+
+        let mut features: Vec<(String, Vec<String>)> = toml.features.clone().into_iter().collect();
+        features.sort_by_key(|(name, _)| name != "default");
+
+        Self {
+            targets: targets(dir, &toml),
+            features,
+            build_script: build_script(dir, &toml),
+            toolchain: find_toolchain(dir),
+            readme: readme(dir, &toml),
+        }
+    }
+}
+
+/// Targets declared in the manifest plus those Cargo discovers by convention
+fn targets(dir: &Path, toml: &CargoToml) -> Vec<Target> {
+    let Some(package) = &toml.package else {
+        return Vec::new();
+    };
+    let crate_name = package.name.replace('-', "_");
+    let mut targets = Vec::new();
+
+    if toml.lib.is_some() || dir.join("src/lib.rs").is_file() {
+        let name = toml
+            .lib
+            .as_ref()
+            .and_then(|lib| lib.name.clone())
+            .unwrap_or(crate_name);
+        targets.push(Target {
+            kind: TargetKind::Lib,
+            name,
+        });
+    }
+
+    let mut bins = declared(&toml.bin);
+    if package.autobins != Some(false) {
+        if dir.join("src/main.rs").is_file() {
+            bins.push(package.name.clone());
+        }
+        bins.extend(discovered(&dir.join("src/bin")));
+    }
+    push_targets(&mut targets, TargetKind::Bin, bins);
+
+    let mut examples = declared(&toml.example);
+    if package.autoexamples != Some(false) {
+        examples.extend(discovered(&dir.join("examples")));
+    }
+    push_targets(&mut targets, TargetKind::Example, examples);
+
+    let mut benches = declared(&toml.bench);
+    if package.autobenches != Some(false) {
+        benches.extend(discovered(&dir.join("benches")));
+    }
+    push_targets(&mut targets, TargetKind::Bench, benches);
+
+    targets
+}
+
+fn declared(specs: &[TargetSpec]) -> Vec<String> {
+    specs.iter().filter_map(|spec| spec.name.clone()).collect()
+}
+
+fn push_targets(targets: &mut Vec<Target>, kind: TargetKind, mut names: Vec<String>) {
+    names.sort();
+    names.dedup();
+    targets.extend(names.into_iter().map(|name| Target { kind, name }));
+}
+
+/// Target names found by convention: `dir/*.rs` and `dir/*/main.rs`
+fn discovered(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                path.file_stem().map(|s| s.to_string_lossy().into_owned())
+            } else if path.join("main.rs").is_file() {
+                path.file_name().map(|s| s.to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The build script: `package.build`, or `build.rs` unless disabled
+fn build_script(dir: &Path, toml: &CargoToml) -> Option<PathBuf> {
+    let build = toml.package.as_ref().and_then(|p| p.build.as_ref());
+    match build {
+        Some(toml::Value::String(path)) => Some(PathBuf::from(path)),
+        Some(toml::Value::Boolean(false)) => None,
+        _ => dir
+            .join("build.rs")
+            .is_file()
+            .then(|| PathBuf::from("build.rs")),
+    }
+}
+
+/// The closest `rust-toolchain.toml` or `rust-toolchain` file, as rustup finds it
+pub fn find_toolchain(dir: &Path) -> Option<Toolchain> {
+    dir.ancestors().find_map(|dir| {
+        ["rust-toolchain.toml", "rust-toolchain"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+            .map(|path| {
+                let channel = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|content| toolchain_channel(&content));
+                Toolchain { path, channel }
+            })
+    })
+}
+
+/// Channel of a toolchain file, either TOML or the legacy single line
+fn toolchain_channel(content: &str) -> Option<String> {
+    if let Ok(value) = toml::from_str::<toml::Value>(content) {
+        return value
+            .get("toolchain")
+            .and_then(|t| t.get("channel"))
+            .and_then(|c| c.as_str())
+            .map(String::from);
+    }
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+}
+
+/// The README named by `package.readme`, or the first conventional one
+fn readme(dir: &Path, toml: &CargoToml) -> Option<Readme> {
+    let path = match toml.package.as_ref().and_then(|p| p.readme.as_ref()) {
+        Some(toml::Value::Boolean(false)) => return None,
+        Some(toml::Value::String(path)) => dir.join(path),
+        _ => ["README.md", "README.txt", "README"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())?,
+    };
+    let content = fs::read_to_string(&path).ok()?;
+    Some(Readme {
+        path,
+        excerpt: excerpt(&content),
+    })
+}
+
+/// The first lines of a README, skipping leading blank lines and badges
+fn excerpt(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| line.trim().is_empty() || is_badge_line(line))
+        .take(README_EXCERPT_LINES)
+        .map(String::from)
+        .collect()
+}
+
+fn is_badge_line(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("[![") || line.starts_with("![") || line.starts_with("<img")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::find_rust_projects;
+
+    fn fixture() -> Project {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/details");
+        find_rust_projects(dir.to_str().unwrap()).remove(0)
+    }
+
+    #[test]
+    fn test_details_of_fixture() {
+        let details = ProjectDetails::load(&fixture());
+
+        let targets: Vec<(&str, &str)> = details
+            .targets
+            .iter()
+            .map(|t| (t.kind.label(), t.name.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                ("lib", "details_app"),
+                ("bin", "details-app"),
+                ("bin", "helper"),
+                ("bin", "tool"),
+                ("example", "demo"),
+                ("bench", "speed"),
+            ]
+        );
+
+        let features: Vec<&str> = details.features.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(features, ["default", "fast", "json"]);
+        assert_eq!(details.features[0].1, ["fast"]);
+
+        assert_eq!(details.build_script, Some(PathBuf::from("build.rs")));
+        assert_eq!(
+            details.toolchain.and_then(|t| t.channel).as_deref(),
+            Some("1.85.0")
+        );
+        let readme = details.readme.unwrap();
+        assert_eq!(readme.excerpt[0], "# details-app");
+    }
+
+    #[test]
+    fn test_toolchain_channel_formats() {
+        assert_eq!(
+            toolchain_channel("[toolchain]\nchannel = \"nightly\"\n").as_deref(),
+            Some("nightly")
+        );
+        assert_eq!(toolchain_channel("\nstable\n").as_deref(), Some("stable"));
+    }
+}
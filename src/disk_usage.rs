@@ -0,0 +1,436 @@
+//! Directory sizes and per-project disk usage
+//!
+//! Sizes are summed by walking directories in parallel with rayon. Every
+//! directory's own files are cached under its modification time, which
+//! changes whenever an entry is added, removed or renamed in it, so measuring
+//! again only reads the directories that changed. The cache is bounded: the
+//! least recently measured directories are dropped first, and everything below
+//! a target directory is dropped once it is cleaned or swept. Files with
+//! several hard links are counted once per measurement, and symlinks are never
+//! followed.
+
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Directory contents by path, valid while the directory's mtime is unchanged
+static SIZE_CACHE: Lazy<Mutex<SizeCache>> = Lazy::new(|| Mutex::new(SizeCache::default()));
+
+/// Most files and directories the cache holds before evicting (a few tens of MB)
+const MAX_CACHED_ENTRIES: usize = 1_000_000;
+
+/// Cached directory contents, evicting the least recently used past [`MAX_CACHED_ENTRIES`]
+#[derive(Debug, Default)]
+struct SizeCache {
+    dirs: HashMap<PathBuf, CachedDir>,
+    /// Files and directories held across all entries
+    entries: usize,
+    /// Advances on every lookup, to order entries by last use
+    clock: u64,
+}
+
+impl SizeCache {
+    fn get(&mut self, dir: &Path, modified: SystemTime) -> Option<CachedDir> {
+        self.clock += 1;
+        let cached = self.dirs.get_mut(dir)?;
+        cached.used = self.clock;
+        (cached.modified == modified).then(|| cached.clone())
+    }
+
+    fn insert(&mut self, dir: PathBuf, mut contents: CachedDir) {
+        self.clock += 1;
+        contents.used = self.clock;
+        self.entries += contents.weight();
+        if let Some(old) = self.dirs.insert(dir, contents) {
+            self.entries -= old.weight();
+        }
+        if self.entries > MAX_CACHED_ENTRIES {
+            self.evict(MAX_CACHED_ENTRIES * 3 / 4);
+        }
+    }
+
+    /// Drop `dir` and every directory below it
+    fn remove_below(&mut self, dir: &Path) {
+        let mut removed = 0;
+        self.dirs.retain(|path, cached| {
+            let keep = !path.starts_with(dir);
+            if !keep {
+                removed += cached.weight();
+            }
+            keep
+        });
+        self.entries -= removed;
+    }
+
+    /// Drop the least recently used directories until at most `target` entries are held
+    fn evict(&mut self, target: usize) {
+        let mut by_use: Vec<(u64, PathBuf)> = self
+            .dirs
+            .iter()
+            .map(|(path, cached)| (cached.used, path.clone()))
+            .collect();
+        by_use.sort_unstable();
+        for (_, path) in by_use {
+            if self.entries <= target {
+                break;
+            }
+            if let Some(cached) = self.dirs.remove(&path) {
+                self.entries -= cached.weight();
+            }
+        }
+    }
+}
+
+/// Forget the cached contents of `dir` and everything below it
+///
+/// Called once a target directory is cleaned or swept, since directories that
+/// are gone would otherwise stay cached.
+pub fn forget_below(dir: &Path) {
+    if let Ok(mut cache) = SIZE_CACHE.lock() {
+        cache.remove_below(dir);
+    }
+}
+
+/// What a directory directly holds
+#[derive(Debug, Clone)]
+struct CachedDir {
+    modified: SystemTime,
+    /// [`SizeCache`] clock at the last lookup
+    used: u64,
+    /// Bytes of files that can't be told apart by inode
+    bytes: u64,
+    /// Files by (device, inode, bytes); a new hard link to a file doesn't
+    /// change the mtime of the directory it was already in, so every file is
+    /// kept rather than only those with several links
+    files: Vec<(u64, u64, u64)>,
+    subdirs: Vec<OsString>,
+}
+
+impl CachedDir {
+    fn read(dir: &Path, modified: SystemTime) -> Option<Self> {
+        let mut cached = Self {
+            modified,
+            used: 0,
+            bytes: 0,
+            files: Vec::new(),
+            subdirs: Vec::new(),
+        };
+        for entry in fs::read_dir(dir).ok()?.filter_map(|e| e.ok()) {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                cached.subdirs.push(entry.file_name());
+            } else if file_type.is_file() {
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                match file_id(&metadata) {
+                    Some((dev, ino)) => cached.files.push((dev, ino, metadata.len())),
+                    None => cached.bytes += metadata.len(),
+                }
+            }
+        }
+        Some(cached)
+    }
+
+    /// Size of the entry in [`MAX_CACHED_ENTRIES`] terms
+    fn weight(&self) -> usize {
+        1 + self.files.len() + self.subdirs.len()
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Total size of the files in a directory and everything below it
+///
+/// `None` if the directory can't be read.
+pub fn directory_size(path: &Path) -> Option<u64> {
+    let seen = Mutex::new(HashSet::new());
+    measure(path, &seen)
+}
+
+fn measure(dir: &Path, seen: &Mutex<HashSet<(u64, u64)>>) -> Option<u64> {
+    let Some(modified) = fs::symlink_metadata(dir).and_then(|m| m.modified()).ok() else {
+        // Gone, e.g. after a clean: forget it and what was below it
+        forget_below(dir);
+        return None;
+    };
+    let cached = SIZE_CACHE
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.get(dir, modified));
+    let contents = match cached {
+        Some(contents) => contents,
+        None => {
+            let contents = CachedDir::read(dir, modified)?;
+            if let Ok(mut cache) = SIZE_CACHE.lock() {
+                cache.insert(dir.to_path_buf(), contents.clone());
+            }
+            contents
+        }
+    };
+
+    let files: u64 = match seen.lock() {
+        Ok(mut seen) => contents
+            .files
+            .iter()
+            .filter(|(dev, ino, _)| seen.insert((*dev, *ino)))
+            .map(|(_, _, bytes)| bytes)
+            .sum(),
+        Err(_) => 0,
+    };
+    let subdirs: u64 = contents
+        .subdirs
+        .par_iter()
+        .filter_map(|name| measure(&dir.join(name), seen))
+        .sum();
+    Some(contents.bytes + files + subdirs)
+}
+
+/// What a part of a project is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UsageCategory {
+    BuildOutput,
+    Git,
+    Vendored,
+    Assets,
+    Source,
+    Other,
+}
+
+impl UsageCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::BuildOutput => "build output",
+            Self::Git => "git",
+            Self::Vendored => "vendored deps",
+            Self::Assets => "assets",
+            Self::Source => "source",
+            Self::Other => "other",
+        }
+    }
+
+    /// Category of a project's top-level entry
+    fn of(path: &Path, target_dir: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if path == target_dir || name == "target" {
+            Self::BuildOutput
+        } else if name == ".git" {
+            Self::Git
+        } else if matches!(name.as_str(), "vendor" | "third_party" | "third-party") {
+            Self::Vendored
+        } else if matches!(
+            name.as_str(),
+            "assets" | "static" | "public" | "resources" | "images" | "media" | "fonts"
+        ) || matches!(
+            extension.as_str(),
+            "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "ico" | "wasm" | "ttf" | "woff2"
+        ) {
+            Self::Assets
+        } else if matches!(
+            name.as_str(),
+            "src" | "tests" | "benches" | "examples" | "build.rs" | "cargo.toml" | "cargo.lock"
+        ) || extension == "rs"
+        {
+            Self::Source
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// One file or directory of a disk usage listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub is_dir: bool,
+    /// Only set on a project's top level
+    pub category: Option<UsageCategory>,
+}
+
+/// The entries of `dir`, largest first
+///
+/// At a project's top level (`target_dir` given) entries get a category, and
+/// a target directory outside the project is listed as well.
+pub fn list_usage(dir: &Path, target_dir: Option<&Path>) -> Vec<UsageEntry> {
+    let mut paths: Vec<(PathBuf, bool)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| Some((e.path(), e.file_type().ok()?)))
+                .filter(|(_, file_type)| !file_type.is_symlink())
+                .map(|(path, file_type)| (path, file_type.is_dir()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(target_dir) = target_dir.filter(|t| !t.starts_with(dir) && t.is_dir()) {
+        paths.push((target_dir.to_path_buf(), true));
+    }
+
+    let mut entries: Vec<UsageEntry> = paths
+        .into_par_iter()
+        .map(|(path, is_dir)| {
+            let bytes = if is_dir {
+                directory_size(&path).unwrap_or(0)
+            } else {
+                fs::symlink_metadata(&path).map_or(0, |m| m.len())
+            };
+            let name = if path.starts_with(dir) {
+                path.file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            } else {
+                path.display().to_string()
+            };
+            UsageEntry {
+                name,
+                category: target_dir.map(|target| UsageCategory::of(&path, target)),
+                path,
+                bytes,
+                is_dir,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    entries
+}
+
+/// Bytes per category of a project's top-level listing, largest first
+pub fn category_totals(entries: &[UsageEntry]) -> Vec<(UsageCategory, u64)> {
+    let mut totals: HashMap<UsageCategory, u64> = HashMap::new();
+    for entry in entries {
+        if let Some(category) = entry.category {
+            *totals.entry(category).or_default() += entry.bytes;
+        }
+    }
+    let mut totals: Vec<(UsageCategory, u64)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn write(path: &Path, bytes: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; bytes]).unwrap();
+    }
+
+    #[test]
+    fn test_sizes_are_cached_and_count_hard_links_once() {
+        let dir = temp_dir("disk-usage", "sizes");
+        write(&dir.join("a/one.bin"), 100);
+        write(&dir.join("a/b/two.bin"), 20);
+        write(&dir.join("three.bin"), 3);
+        assert_eq!(directory_size(&dir), Some(123));
+        assert_eq!(directory_size(&dir.join("empty")), None);
+
+        #[cfg(unix)]
+        {
+            fs::hard_link(dir.join("a/one.bin"), dir.join("a/b/link.bin")).unwrap();
+            std::os::unix::fs::symlink(dir.join("a"), dir.join("loop")).unwrap();
+            assert_eq!(directory_size(&dir), Some(123));
+        }
+
+        // Adding a file changes the directory's mtime, so only it is read again
+        write(&dir.join("a/b/four.bin"), 4000);
+        assert_eq!(directory_size(&dir), Some(4123));
+    }
+
+    #[test]
+    fn test_size_cache_evicts_least_recently_used_and_below_dirs() {
+        let now = SystemTime::now();
+        let contents = |files: usize| CachedDir {
+            modified: now,
+            used: 0,
+            bytes: 0,
+            files: vec![(0, 0, 1); files],
+            subdirs: Vec::new(),
+        };
+        let mut cache = SizeCache::default();
+        cache.insert(PathBuf::from("/t/a"), contents(MAX_CACHED_ENTRIES / 2));
+        cache.insert(PathBuf::from("/t/a/deps"), contents(10));
+        cache.insert(PathBuf::from("/t/b"), contents(MAX_CACHED_ENTRIES / 4));
+        assert!(cache.get(Path::new("/t/a"), now).is_some());
+
+        // Past the bound the least recently used go first
+        cache.insert(PathBuf::from("/t/c"), contents(MAX_CACHED_ENTRIES / 2));
+        assert!(cache.entries <= MAX_CACHED_ENTRIES);
+        assert!(!cache.dirs.contains_key(Path::new("/t/b")));
+        assert!(cache.dirs.contains_key(Path::new("/t/c")));
+
+        // Cleaning a directory forgets it and everything below it
+        cache.insert(PathBuf::from("/t/a"), contents(1));
+        cache.insert(PathBuf::from("/t/a/deps"), contents(10));
+        cache.insert(PathBuf::from("/t/ab"), contents(1));
+        cache.remove_below(Path::new("/t/a"));
+        assert!(cache.dirs.contains_key(Path::new("/t/ab")));
+        assert!(cache.dirs.keys().all(|dir| !dir.starts_with("/t/a")));
+        assert_eq!(
+            cache.entries,
+            cache.dirs.values().map(CachedDir::weight).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_project_listing_categories() {
+        let project = temp_dir("disk-usage", "listing");
+        write(&project.join("target/debug/app"), 5000);
+        write(&project.join(".git/objects/pack.pack"), 700);
+        write(&project.join("vendor/dep/lib.rs"), 300);
+        write(&project.join("assets/logo.png"), 200);
+        write(&project.join("src/main.rs"), 50);
+        write(&project.join("Cargo.toml"), 10);
+
+        let entries = list_usage(&project, Some(&project.join("target")));
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["target", ".git", "vendor", "assets", "src", "Cargo.toml"]
+        );
+        assert_eq!(
+            category_totals(&entries),
+            [
+                (UsageCategory::BuildOutput, 5000),
+                (UsageCategory::Git, 700),
+                (UsageCategory::Vendored, 300),
+                (UsageCategory::Assets, 200),
+                (UsageCategory::Source, 60),
+            ]
+        );
+
+        // A target directory elsewhere, e.g. at the workspace root, is listed too
+        let shared = temp_dir("disk-usage", "listing-shared-target");
+        write(&shared.join("debug/app"), 9000);
+        let entries = list_usage(&project.join("src"), Some(&shared));
+        assert_eq!(entries[0].category, Some(UsageCategory::BuildOutput));
+        assert_eq!(entries[0].bytes, 9000);
+        assert!(list_usage(&project.join("src"), None)[0].category.is_none());
+    }
+}
@@ -3,7 +3,13 @@
 //! This module defines the types for handling user input, application modes, and
 //! commands that can be executed by the application.
 
-use crate::project::{Dependency, Project};
+use crate::features::MatrixStatus;
+use crate::index::IndexDiff;
+use crate::project::{Dependency, Project, ProjectId};
+use crate::roots::ScanRoots;
+use crate::scan::ScanProgress;
+use crate::sweep::{SweepPlan, TargetBreakdown};
+use crate::toolchains::ToolchainStatus;
 
 /// Represents a command that can be executed in CarWash
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,15 +18,63 @@ pub enum Command {
     Cargo { command: String },
     /// Set the target directory for scanning
     SetTargetDir,
+    /// Switch to a collection of scan roots defined in the config
+    SwitchCollection { name: String },
+    /// Select every project carrying a tag
+    SelectTag { tag: String },
+    /// Run a cargo command on every configured toolchain for the selected projects
+    ToolchainMatrix { command: String },
+    /// Replace the selection with a group of projects
+    Select { selection: BulkSelection },
+    /// Save the selected projects under a name
+    SaveSelection { name: String },
+    /// Forget a saved selection
+    DeleteSelection { name: String },
     /// Quit the application
     Quit,
 }
 
+/// A group of projects selected in one go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkSelection {
+    /// Every project shown in the project list
+    Visible,
+    /// Projects matching a filter query
+    Matching(String),
+    /// Projects with outdated dependencies
+    Outdated,
+    /// Projects whose last command failed
+    Failed,
+    /// Every project not selected, and none of those that are
+    Invert,
+    /// No project at all
+    None,
+    /// A selection saved by name
+    Saved(String),
+}
+
+impl BulkSelection {
+    /// Fixed selections offered by the palette, with their names
+    pub const FIXED: [(BulkSelection, &'static str); 5] = [
+        (Self::Visible, "visible"),
+        (Self::Outdated, "outdated"),
+        (Self::Failed, "failed"),
+        (Self::Invert, "invert"),
+        (Self::None, "none"),
+    ];
+}
+
 /// Application actions that can be performed by the user or internal events
 #[derive(Debug, Clone)]
 pub enum Action {
     /// Project scanning has finished with results
-    FinishProjectScan(Vec<Project>, String), // (projects, target_directory)
+    FinishProjectScan(Vec<Project>, ScanRoots),
+    /// A batch of projects found while the scan is still running
+    ProjectsDiscovered(Vec<Project>, ScanProgress),
+    /// Projects were added, changed or removed on disk (index revalidation, file watching)
+    ProjectsChanged(IndexDiff),
+    /// Cargo.toml or Cargo.lock files changed in these directories
+    ProjectFilesChanged(Vec<std::path::PathBuf>),
     /// Move selection to next item
     SelectNext,
     /// Move selection to previous item
@@ -31,6 +85,10 @@ pub enum Action {
     SelectChild,
     /// Toggle selection on current item
     ToggleSelection,
+    /// Move the cursor to a row of the project list
+    SelectRow(usize),
+    /// Move the cursor to a row of the project list and toggle its selection
+    ToggleRowSelection(usize),
     /// Open the command palette
     ShowCommandPalette,
     /// Show help screen
@@ -51,52 +109,92 @@ pub enum Action {
     ExecuteCommand(Command),
     /// Update text input buffer
     UpdateTextInput(String),
+    /// Apply the text input to whatever it was opened for
+    SubmitTextInput,
     /// Start the update wizard
     StartUpdateWizard,
+    /// Move selection to next dependency in the dependencies pane
+    DependencySelectNext,
+    /// Move selection to previous dependency in the dependencies pane
+    DependencySelectPrevious,
+    /// Show the version history of the selected dependency
+    ShowDependencyDetail,
+    /// Switch the dependencies pane to the project details and back
+    ToggleProjectDetails,
+    /// Open the feature explorer for the project under the cursor
+    ShowFeatureExplorer,
+    /// Toggle the feature under the cursor in the feature explorer
+    ToggleFeature,
+    /// Toggle `--no-default-features` for the explored project
+    ToggleNoDefaultFeatures,
+    /// Toggle `--all-features` for the explored project
+    ToggleAllFeatures,
+    /// Check every feature combination of the explored project
+    StartFeatureMatrix,
+    /// A feature matrix run (output tab, combination) changed status
+    FeatureMatrixStatus(usize, usize, MatrixStatus),
+    /// Set up a toolchain matrix for a command, given the installed toolchains
+    StartToolchainMatrix(String, Vec<String>),
+    /// A project's run on one toolchain matrix column changed status
+    ToolchainMatrixStatus(ProjectId, usize, ToolchainStatus),
+    /// Show the results of the latest toolchain matrix
+    ShowToolchainMatrix,
     /// Toggle selection in update wizard
     ToggleUpdateSelection,
     /// Update cache duration text in settings modal
     SettingsUpdateCacheInput(String),
     /// Toggle background update preference in settings modal
     SettingsToggleBackground,
+    /// Toggle offline mode preference in settings modal
+    SettingsToggleOffline,
+    /// Toggle restoring the session on launch within the settings modal
+    SettingsToggleSessionRestore,
     /// Persist settings changes
     SaveSettings,
     /// Run selected updates
     RunUpdate,
     /// Check for dependency updates
     CheckForUpdates,
-    /// Update dependency information with project name
-    UpdateDependencies(String, Vec<Dependency>),
+    /// Update dependency information for a project
+    UpdateDependencies(ProjectId, Vec<Dependency>),
     /// Start background update checking
     StartBackgroundUpdateCheck,
     /// Update status of a specific dependency
     UpdateDependencyStatus(String, crate::project::DependencyCheckStatus),
     /// Stream start - beginning of update checks
-    UpdateDependenciesStreamStart(String),
+    UpdateDependenciesStreamStart(ProjectId),
     /// Update a single dependency with latest info
-    UpdateSingleDependency(String, Dependency),
+    UpdateSingleDependency(ProjectId, Dependency),
+    /// The registry is unreachable; switch to offline mode
+    OfflineDetected,
+    /// The registry answered a probe again after going offline
+    RegistryReachable,
     /// Update check status for a specific dependency (for UI streaming)
-    UpdateDependencyCheckStatus(String, String, crate::project::DependencyCheckStatus),
+    UpdateDependencyCheckStatus(ProjectId, String, crate::project::DependencyCheckStatus),
     /// Create a new output tab
     CreateTab(String),
     /// Add output line to a tab
     AddOutput(usize, String),
     /// Mark command execution as finished
     FinishCommand(usize),
+    /// A project was re-read after `cargo update` in this tab; re-check it
+    DependenciesReloaded(usize, ProjectId),
     /// Switch to a specific tab
     SwitchToTab(usize),
     /// Process pending background update tasks
     ProcessBackgroundUpdateQueue,
     /// Queue a project for update checking (background or priority)
-    QueueBackgroundUpdate(String, bool),
+    QueueBackgroundUpdate(ProjectId, bool),
     /// Update the visual check status of a project
-    UpdateProjectCheckStatus(String, crate::project::ProjectCheckStatus),
+    UpdateProjectCheckStatus(ProjectId, crate::project::ProjectCheckStatus),
     /// Enter filter/search mode
     EnterFilterMode,
     /// Exit filter/search mode
     ExitFilterMode,
     /// Update filter input text
     UpdateFilterInput(String),
+    /// Select every project matching the filter and leave filter mode
+    SelectFilterMatches,
     /// Clear filter
     ClearFilter,
     /// Cycle to next theme
@@ -111,22 +209,88 @@ pub enum Action {
     IncreaseTopRight,
     /// Decrease top-right pane height
     DecreaseTopRight,
+    /// Set the left pane width, in percent (dragging its border)
+    ResizeLeftPane(u16),
+    /// Set the top-right pane height, in percent (dragging its border)
+    ResizeTopRight(u16),
     /// Reset layout to defaults
     ResetLayout,
     /// Save configuration to disk
     SaveConfig,
     /// Toggle showing all folders (default: only Rust project folders)
     ToggleShowAllFolders,
+    /// Toggle grouping the project tree by tag instead of by directory
+    ToggleGroupByTag,
+    /// Switch the project list between the directory tree and the table
+    ToggleProjectTable,
+    /// Sort the project table by the next column
+    CycleTableSort,
+    /// Reverse the sort order of the project table
+    ReverseTableSort,
+    /// Group the project table by the next grouping (workspace, tag, status, none)
+    CycleTableGrouping,
+    /// Check git state and last modification of every project (background)
+    CheckProjectActivity,
+    /// Git state and last modification time of a project
+    UpdateProjectActivity(
+        ProjectId,
+        crate::project::GitStatus,
+        Option<std::time::SystemTime>,
+    ),
+    /// A command started or finished in a project
+    UpdateProjectStatus(ProjectId, crate::project::ProjectStatus),
+    /// Edit the tags assigned to the project under the cursor
+    EditProjectTags,
+    /// Select exactly the projects carrying a tag
+    SelectTag(String),
+    /// Replace the selection with a group of projects
+    BulkSelect(BulkSelection),
+    /// Save the selected projects under a name, replacing a selection of that name
+    SaveSelection(String),
+    /// Forget a saved selection
+    DeleteSelection(String),
     /// Calculate sizes for all projects (total + target directory)
     CalculateProjectSizes,
     /// Update a single project's size information
-    UpdateProjectSize(String, Option<u64>, Option<u64>),
+    UpdateProjectSize(ProjectId, Option<u64>, Option<u64>),
+    /// Target directories resolved for every project
+    TargetDirsResolved(Vec<(ProjectId, std::path::PathBuf)>),
+    /// New size of a target directory, for every project building into it
+    UpdateTargetSize(std::path::PathBuf, Option<u64>),
+    /// Open the disk reclamation view
+    ShowReclaim,
+    ReclaimSelectNext,
+    ReclaimSelectPrevious,
+    /// A target directory was measured for the breakdown
+    TargetBreakdownLoaded(std::path::PathBuf, TargetBreakdown),
+    /// The sweep criteria changed, so the preview is outdated
+    SweepCriteriaChanged,
+    /// Dry-run a sweep of the selected target directory, or of all of them
+    PreviewSweep(bool),
+    /// The dry run is ready
+    SweepPlanned(SweepPlan),
+    /// Delete what the dry run found
+    ExecuteSweep,
+    /// A sweep of target directories finished, freeing bytes, with errors
+    SweepFinished(Vec<std::path::PathBuf>, u64, Vec<String>),
+    /// Open the disk usage view of the selected project
+    ShowDiskUsage,
+    DiskUsageSelectNext,
+    DiskUsageSelectPrevious,
+    /// Descend into the selected directory of the disk usage view
+    DiskUsageOpen,
+    /// Go back to the parent directory of the disk usage view
+    DiskUsageUp,
+    /// A directory was listed for the disk usage view
+    DiskUsageLoaded(std::path::PathBuf, Vec<crate::disk_usage::UsageEntry>),
     /// Change focus to next pane
     FocusNext,
     /// Quit the application
     Quit,
-    /// Initialize the project tree with the root directory (shallow)
-    InitializeTree(String),
+    /// Initialize the project tree with the scan roots (shallow)
+    InitializeTree(ScanRoots),
+    /// Replace the scan roots and rescan (e.g. switching collections)
+    SwitchRoots(ScanRoots),
     /// Request to expand a directory node (async lazy load)
     /// Contains: (path, depth)
     ExpandDirectory(std::path::PathBuf, usize),
@@ -153,10 +317,20 @@ pub enum Mode {
     Help,
     /// Settings modal is open
     Settings,
+    /// Version history of the selected dependency is displayed
+    DependencyDetail,
+    /// Feature explorer of the selected project is open
+    FeatureExplorer,
+    /// Toolchain matrix results are displayed
+    ToolchainMatrix,
+    /// Disk reclamation view is open
+    Reclaim,
+    /// Disk usage view of a project is open
+    DiskUsage,
 }
 
 /// Tracks which pane currently has focus for keyboard input
-#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum Focus {
     /// Project list pane (left side)
     #[default]
@@ -0,0 +1,370 @@
+//! Cargo features: what a package declares, flags for commands, and feature matrices
+//!
+//! The feature explorer lists a package's features, which of them are on by
+//! default and its optional dependencies. Features picked there become
+//! [`FeatureFlags`] that are added to cargo commands run on the project. A
+//! feature matrix runs `cargo check` once per combination of features
+//! (the powerset, or a bounded part of it) and records pass/fail per run.
+
+use crate::config::FeatureMatrixConfig;
+use crate::project::{CargoToml, Project, ProjectId};
+use crate::workspace::read_manifest;
+use std::collections::BTreeSet;
+
+/// Cargo subcommands that accept `--features` and friends
+const FEATURE_COMMANDS: &[&str] = &[
+    "build", "b", "check", "c", "clippy", "doc", "d", "test", "t", "bench", "run", "r", "rustc",
+    "rustdoc", "fix", "install", "package", "publish", "tree",
+];
+
+/// A feature a package can be built with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feature {
+    pub name: String,
+    /// Features and dependencies it turns on
+    pub enables: Vec<String>,
+    /// Enabled by the `default` feature
+    pub default: bool,
+    /// Implicit feature of an optional dependency not referenced with `dep:`
+    pub implicit: bool,
+}
+
+/// The features and optional dependencies declared by a package
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectFeatures {
+    /// Declared features (without `default`) followed by implicit ones
+    pub features: Vec<Feature>,
+    /// Members of the `default` feature
+    pub defaults: Vec<String>,
+    /// Optional dependencies, sorted
+    pub optional_deps: Vec<String>,
+}
+
+impl ProjectFeatures {
+    /// Read the features of a project's manifest
+    pub fn load(project: &Project) -> Self {
+        read_manifest(&project.path.join("Cargo.toml"))
+            .map(|toml| Self::from_toml(&toml))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(toml: &CargoToml) -> Self {
+        let defaults = toml.features.get("default").cloned().unwrap_or_default();
+
+        let mut optional_deps: Vec<String> = toml
+            .dependencies
+            .iter()
+            .filter(|(_, spec)| spec.get("optional").and_then(|o| o.as_bool()) == Some(true))
+            .map(|(name, _)| name.clone())
+            .collect();
+        optional_deps.sort();
+
+        let mut features: Vec<Feature> = toml
+            .features
+            .iter()
+            .filter(|(name, _)| name.as_str() != "default")
+            .map(|(name, enables)| Feature {
+                name: name.clone(),
+                enables: enables.clone(),
+                default: defaults.contains(name),
+                implicit: false,
+            })
+            .collect();
+
+        // Since Rust 1.60, a `dep:` reference anywhere hides the dependency's implicit feature
+        let referenced: BTreeSet<&str> = toml
+            .features
+            .values()
+            .flatten()
+            .filter_map(|member| member.strip_prefix("dep:"))
+            .collect();
+        for dep in &optional_deps {
+            let shadowed = toml.features.contains_key(dep);
+            if !shadowed && !referenced.contains(dep.as_str()) {
+                features.push(Feature {
+                    name: dep.clone(),
+                    enables: vec![format!("dep:{}", dep)],
+                    default: defaults.contains(dep),
+                    implicit: true,
+                });
+            }
+        }
+
+        Self {
+            features,
+            defaults,
+            optional_deps,
+        }
+    }
+
+    /// Names of the features a matrix combines
+    pub fn names(&self) -> Vec<String> {
+        self.features.iter().map(|f| f.name.clone()).collect()
+    }
+}
+
+/// Feature flags added to cargo commands for a project
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureFlags {
+    pub features: BTreeSet<String>,
+    pub no_default_features: bool,
+    pub all_features: bool,
+}
+
+impl FeatureFlags {
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty() && !self.no_default_features && !self.all_features
+    }
+
+    /// Command-line arguments, e.g. `--no-default-features --features a,b`
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.all_features {
+            args.push("--all-features".to_string());
+        }
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.iter().cloned().collect::<Vec<_>>().join(","));
+        }
+        args
+    }
+
+    /// Add the flags to a cargo command line, before any `--` separator
+    ///
+    /// Commands that don't take feature flags (`fmt`, `clean`, `update`, ...)
+    /// are returned unchanged.
+    pub fn apply(&self, command: &str) -> String {
+        let takes_features = command
+            .split_whitespace()
+            .next()
+            .is_some_and(|sub| FEATURE_COMMANDS.contains(&sub));
+        if self.is_empty() || !takes_features {
+            return command.to_string();
+        }
+
+        let mut words: Vec<String> = command.split_whitespace().map(String::from).collect();
+        let at = words.iter().position(|w| w == "--").unwrap_or(words.len());
+        words.splice(at..at, self.args());
+        words.join(" ")
+    }
+}
+
+/// Combinations of `features` for a matrix, smallest first
+///
+/// Every combination is built with `--no-default-features`, starting from
+/// none at all. `depth` limits how many features are combined at once, and
+/// at most `max_combinations` combinations are returned. The second value is
+/// how many combinations there are without the `max_combinations` bound.
+pub fn powerset(features: &[String], config: &FeatureMatrixConfig) -> (Vec<Vec<String>>, usize) {
+    let depth = config.depth.unwrap_or(features.len()).min(features.len());
+    let mut combinations = Vec::new();
+    let mut total = 0usize;
+    for size in 0..=depth {
+        total = total.saturating_add(binomial(features.len(), size));
+        let mut indices: Vec<usize> = (0..size).collect();
+        loop {
+            if combinations.len() >= config.max_combinations {
+                break;
+            }
+            combinations.push(indices.iter().map(|&i| features[i].clone()).collect());
+            if !next_combination(&mut indices, features.len()) {
+                break;
+            }
+        }
+    }
+    (combinations, total)
+}
+
+/// Advance `indices` to the next `k`-of-`n` combination in lexicographic order
+fn next_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    for i in (0..k).rev() {
+        if indices[i] < n - k + i {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    (0..k).fold(1usize, |acc, i| acc.saturating_mul(n - i) / (i + 1))
+}
+
+/// Outcome of one combination in a feature matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixStatus {
+    Pending,
+    Running,
+    Passed,
+    Failed,
+}
+
+/// One `cargo check` of a feature matrix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixRun {
+    pub features: Vec<String>,
+    pub status: MatrixStatus,
+}
+
+impl MatrixRun {
+    /// The cargo command for this combination
+    pub fn command(&self) -> String {
+        let flags = FeatureFlags {
+            features: self.features.iter().cloned().collect(),
+            no_default_features: true,
+            all_features: false,
+        };
+        flags.apply("check")
+    }
+}
+
+/// A feature matrix run over one project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureMatrix {
+    pub project: ProjectId,
+    /// Features the combinations are made of (the grid's columns)
+    pub features: Vec<String>,
+    pub runs: Vec<MatrixRun>,
+    /// Combinations there would be without `max_combinations`
+    pub total: usize,
+    /// Output tab the runs stream into
+    pub tab_index: usize,
+}
+
+impl FeatureMatrix {
+    pub fn new(project: ProjectId, features: Vec<String>, config: &FeatureMatrixConfig) -> Self {
+        let (combinations, total) = powerset(&features, config);
+        Self {
+            project,
+            features,
+            runs: combinations
+                .into_iter()
+                .map(|features| MatrixRun {
+                    features,
+                    status: MatrixStatus::Pending,
+                })
+                .collect(),
+            total,
+            tab_index: 0,
+        }
+    }
+
+    /// Whether runs are still pending or running
+    pub fn is_running(&self) -> bool {
+        self.runs
+            .iter()
+            .any(|run| matches!(run.status, MatrixStatus::Pending | MatrixStatus::Running))
+    }
+
+    /// Number of passed and failed runs
+    pub fn counts(&self) -> (usize, usize) {
+        let passed = self
+            .runs
+            .iter()
+            .filter(|run| run.status == MatrixStatus::Passed)
+            .count();
+        let failed = self
+            .runs
+            .iter()
+            .filter(|run| run.status == MatrixStatus::Failed)
+            .count();
+        (passed, failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toml(manifest: &str) -> CargoToml {
+        toml::from_str(manifest).unwrap()
+    }
+
+    #[test]
+    fn test_features_and_optional_deps() {
+        let features = ProjectFeatures::from_toml(&toml(
+            r#"
+            [package]
+            name = "app"
+
+            [dependencies]
+            serde = { version = "1", optional = true }
+            rayon = { version = "1", optional = true }
+            log = "0.4"
+
+            [features]
+            default = ["fast"]
+            fast = []
+            json = ["dep:serde"]
+            "#,
+        ));
+
+        let names: Vec<(&str, bool, bool)> = features
+            .features
+            .iter()
+            .map(|f| (f.name.as_str(), f.default, f.implicit))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("fast", true, false),
+                ("json", false, false),
+                ("rayon", false, true)
+            ]
+        );
+        assert_eq!(features.defaults, ["fast"]);
+        assert_eq!(features.optional_deps, ["rayon", "serde"]);
+    }
+
+    #[test]
+    fn test_flags_apply_before_separator() {
+        let flags = FeatureFlags {
+            features: BTreeSet::from(["b".to_string(), "a".to_string()]),
+            no_default_features: true,
+            all_features: false,
+        };
+        assert_eq!(
+            flags.apply("clippy -- -D warnings"),
+            "clippy --no-default-features --features a,b -- -D warnings"
+        );
+        assert_eq!(flags.apply("fmt -- --check"), "fmt -- --check");
+        assert_eq!(FeatureFlags::default().apply("test"), "test");
+    }
+
+    #[test]
+    fn test_powerset_is_bounded() {
+        let features: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+
+        let (all, total) = powerset(&features, &FeatureMatrixConfig::default());
+        assert_eq!(all.len(), 8);
+        assert_eq!(total, 8);
+        assert!(all[0].is_empty());
+        assert_eq!(all[7], ["a", "b", "c"]);
+
+        let config = FeatureMatrixConfig {
+            max_combinations: 5,
+            depth: Some(2),
+        };
+        let (bounded, total) = powerset(&features, &config);
+        assert_eq!(total, 7);
+        assert_eq!(
+            bounded,
+            [vec![], vec!["a"], vec!["b"], vec!["c"], vec!["a", "b"]]
+        );
+        assert_eq!(
+            MatrixRun {
+                features: bounded[4].clone(),
+                status: MatrixStatus::Pending
+            }
+            .command(),
+            "check --no-default-features --features a,b"
+        );
+    }
+}
@@ -3,13 +3,28 @@
 //! This module contains handler functions for each Action type, providing
 //! a clean separation between action dispatch and state mutation logic.
 
-use crate::app::{AppState, Tab};
+use crate::app::{AppState, OfflineReason, Tab};
 use crate::components::PendingDirectoryCheck;
-use crate::events::{Action, Command, Mode};
-use crate::project::Project;
+use crate::components::disk_usage::DiskUsageState;
+use crate::components::reclaim::reclaim_entries;
+use crate::components::text_input::TextInputTarget;
+use crate::disk_usage::UsageEntry;
+use crate::events::{Action, BulkSelection, Command, Mode};
+use crate::features::{FeatureMatrix, MatrixStatus, ProjectFeatures};
+use crate::index::IndexDiff;
+use crate::policy::UpdatePolicy;
+use crate::project::{GitStatus, Project, ProjectId, ProjectStatus};
+use crate::project_table::TableView;
+use crate::roots::ScanRoots;
+use crate::scan::ScanProgress;
+use crate::sweep::{SweepPlan, TargetBreakdown};
+use crate::target_dir::resolve_target_dir;
+use crate::toolchains::{ToolchainMatrix, ToolchainStatus};
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Handle application quit
 pub fn handle_quit(state: &mut AppState) {
@@ -24,7 +39,7 @@ pub fn handle_enter_normal_mode(state: &mut AppState) {
         state.updater.outdated_dependencies.clear();
         state.updater.selected_dependencies.clear();
         state.updater.list_state.select(None);
-        state.updater.locked_project_name = None; // Clear project lock
+        state.updater.locked_project = None; // Clear project lock
         state.updater.user_check_in_progress = false; // Clear check flag
     }
     state.mode = Mode::Normal;
@@ -43,6 +58,8 @@ pub fn handle_show_settings(state: &mut AppState) {
         .clone()
         .with_value(state.settings.cache_ttl_minutes.to_string());
     state.settings_modal.background_updates_enabled = state.settings.background_updates_enabled;
+    state.settings_modal.offline = state.settings.offline;
+    state.settings_modal.restore_session = state.settings.restore_session;
     state.settings_modal.error_message = None;
     state.mode = Mode::Settings;
 }
@@ -53,12 +70,126 @@ pub fn handle_close_settings(state: &mut AppState) {
     state.settings_modal.error_message = None;
 }
 
-/// Handle completing project scan
-pub fn handle_finish_project_scan(
+/// Resolve what the config decides for a project: its update policy and tags
+fn resolve_project(state: &AppState, project: &mut Project) {
+    project.update_policy = Arc::new(UpdatePolicy::for_project(
+        &state.config.updates,
+        &project.path,
+        project.workspace_root.as_deref(),
+    ));
+    project.tags = crate::tags::resolve_tags(
+        project,
+        &state.config.tags,
+        state.settings.project_tags.get(&project.id),
+    );
+}
+
+/// Rebuild the rows of the project list: the table, or the tree grouped by tag or by directory
+pub fn refresh_flattened_tree(state: &mut AppState) {
+    if state.settings.table_view.enabled {
+        // Rows move when re-sorted, so the cursor follows the row it was on
+        let selected = state
+            .get_selected_node()
+            .map(|node| node.node_type.path().to_path_buf());
+        let tree = crate::project_table::build_table_tree(
+            &state.all_projects,
+            &state.settings.table_view,
+            &state.collapsed_groups,
+        );
+        state.flattened_tree = crate::tree::FlattenedTree::from_tree(&tree);
+        if let Some(index) = selected.and_then(|path| state.flattened_tree.get_index(&path)) {
+            state.tree_state.select(Some(index));
+        }
+    } else if state.settings.group_by_tag {
+        let tree = crate::tags::build_tag_tree(&state.all_projects, &state.collapsed_tags);
+        state.flattened_tree = crate::tree::FlattenedTree::from_tree(&tree);
+    } else if let Some(tree_root) = &state.tree_root {
+        state.flattened_tree = crate::tree::FlattenedTree::from_tree(tree_root);
+    }
+
+    let last = state.flattened_tree.items.len().saturating_sub(1);
+    if state.tree_state.selected().is_some_and(|i| i > last) {
+        state.tree_state.select(Some(last));
+    }
+}
+
+/// Handle a batch of projects streamed in while the scan is still running
+pub fn handle_projects_discovered(
     state: &mut AppState,
     projects: Vec<Project>,
-    target_directory: String,
+    progress: ScanProgress,
 ) {
+    state.scan_progress = Some(progress);
+
+    for mut project in projects {
+        if state.all_projects.iter().any(|p| p.id == project.id) {
+            continue;
+        }
+        resolve_project(state, &mut project);
+        if !project.dependencies.is_empty() {
+            state.projects.push(project.clone());
+        }
+        state.all_projects.push(project);
+    }
+
+    // Names may only clash once both projects have been found
+    sync_display_names(state);
+    refresh_flattened_tree(state);
+}
+
+/// Apply projects that changed on disk
+///
+/// Unchanged projects keep their state (check results, sizes, selection);
+/// changed ones are replaced and removed ones dropped.
+pub fn handle_projects_changed(state: &mut AppState, diff: IndexDiff) {
+    state.scan_progress = None;
+    if diff.is_empty() {
+        return;
+    }
+
+    let removed: HashSet<&ProjectId> = diff.removed.iter().collect();
+    state.all_projects.retain(|p| !removed.contains(&p.id));
+    state.projects.retain(|p| !removed.contains(&p.id));
+    state.selected_projects.retain(|id| !removed.contains(id));
+
+    for mut project in diff.changed {
+        resolve_project(state, &mut project);
+        state.projects.retain(|p| p.id != project.id);
+        if !project.dependencies.is_empty() {
+            state.projects.push(project.clone());
+        }
+        match state.all_projects.iter_mut().find(|p| p.id == project.id) {
+            Some(existing) => *existing = project,
+            None => state.all_projects.push(project),
+        }
+    }
+
+    crate::project::sort_projects(&mut state.all_projects);
+    crate::project::sort_projects(&mut state.projects);
+    sync_display_names(state);
+    refresh_flattened_tree(state);
+}
+
+/// Disambiguate clashing project names and copy them to the filtered list
+fn sync_display_names(state: &mut AppState) {
+    crate::project::disambiguate_display_names(&mut state.all_projects);
+    for project in &mut state.projects {
+        if let Some(found) = state.all_projects.iter().find(|p| p.id == project.id) {
+            project.display_name = found.display_name.clone();
+        }
+    }
+}
+
+/// Handle completing project scan
+pub fn handle_finish_project_scan(state: &mut AppState, projects: Vec<Project>, roots: ScanRoots) {
+    let projects: Vec<Project> = projects
+        .into_iter()
+        .map(|mut p| {
+            resolve_project(state, &mut p);
+            p
+        })
+        .collect();
+
     state.all_projects = projects.clone();
     // Only show projects with dependencies
     state.projects = projects
@@ -74,25 +205,35 @@ pub fn handle_finish_project_scan(
         .collect();
     state.collapsed_workspaces = workspace_names;
 
-    // Build the hierarchical project tree from the ACTUAL target directory
-    let mut tree_root = crate::project::build_project_tree(&target_directory);
+    // Build the hierarchical project tree from the ACTUAL scan roots
+    let mut tree_root = crate::project::build_roots_tree(&roots);
+    state.scan_roots = roots;
 
-    // Load the root level children immediately (since root starts expanded)
-    crate::project::load_directory_children(&mut tree_root, state.settings.show_all_folders);
+    // Load the root level children immediately (since roots start expanded)
+    for root in tree_root.scan_roots_mut() {
+        crate::project::load_directory_children(root, state.settings.show_all_folders);
+    }
 
     // Auto-load children for any "crates" directories (they're auto-expanded)
     load_crates_directories_recursively(&mut tree_root, state.settings.show_all_folders);
 
-    state.tree_root = Some(tree_root.clone());
+    state.tree_root = Some(tree_root);
 
     // Flatten the tree for rendering
-    state.flattened_tree = crate::tree::FlattenedTree::from_tree(&tree_root);
+    refresh_flattened_tree(state);
 
     if !state.projects.is_empty() {
         state.tree_state.select(Some(0));
     }
     state.is_scanning = false;
+    state.scan_progress = None;
     state.mode = Mode::Normal;
+
+    if let Some(session) = state.pending_session.take() {
+        if session.roots == state.scan_roots.paths {
+            session.restore(state);
+        }
+    }
 }
 
 /// Handle text input updates
@@ -100,6 +241,231 @@ pub fn handle_update_text_input(state: &mut AppState, s: String) {
     state.text_input.input = state.text_input.input.clone().with_value(s);
 }
 
+/// Handle submitting the text input
+pub fn handle_submit_text_input(state: &mut AppState) {
+    state.mode = Mode::Normal;
+    match state.text_input.target.take() {
+        Some(TextInputTarget::ProjectTags(id)) => {
+            let tags = crate::tags::parse_tags(state.text_input.input.value());
+            set_project_tags(state, id, tags);
+        }
+        None => {}
+    }
+}
+
+/// Handle opening the tag editor for the project under the cursor
+pub fn handle_edit_project_tags(state: &mut AppState) {
+    let Some(project) = state.get_selected_project() else {
+        return;
+    };
+    let id = project.id.clone();
+    let title = format!(" Tags for {} (comma separated) ", project.display_name);
+    let assigned = state
+        .settings
+        .project_tags
+        .get(&id)
+        .map(|tags| tags.iter().cloned().collect::<Vec<_>>().join(", "))
+        .unwrap_or_default();
+
+    state.text_input.title = title;
+    state.text_input.input = state.text_input.input.clone().with_value(assigned);
+    state.text_input.target = Some(TextInputTarget::ProjectTags(id));
+    state.mode = Mode::TextInput;
+}
+
+/// Replace the tags assigned to a project from the UI, and remember them
+fn set_project_tags(state: &mut AppState, id: ProjectId, tags: BTreeSet<String>) {
+    if tags.is_empty() {
+        state.settings.project_tags.remove(&id);
+    } else {
+        state.settings.project_tags.insert(id.clone(), tags);
+    }
+    if let Err(e) = state.settings.save() {
+        tracing::warn!("Failed to save settings: {}", e);
+    }
+
+    let resolved = state.all_projects.iter().find(|p| p.id == id).map(|p| {
+        crate::tags::resolve_tags(p, &state.config.tags, state.settings.project_tags.get(&id))
+    });
+    if let Some(tags) = resolved {
+        for project in state
+            .all_projects
+            .iter_mut()
+            .chain(state.projects.iter_mut())
+            .filter(|p| p.id == id)
+        {
+            project.tags = tags.clone();
+        }
+    }
+    refresh_flattened_tree(state);
+}
+
+/// Handle selecting exactly the projects carrying a tag
+pub fn handle_select_tag(state: &mut AppState, tag: String) {
+    state.selected_projects = state
+        .all_projects
+        .iter()
+        .filter(|p| p.tags.contains(&tag))
+        .map(|p| p.id.clone())
+        .collect();
+}
+
+/// Handle replacing the selection with a group of projects
+///
+/// Every group but `Visible` reaches into collapsed directories and groups.
+/// An invalid query or unknown saved selection leaves the selection as it is.
+pub fn handle_bulk_select(state: &mut AppState, selection: BulkSelection) {
+    let all = state.all_projects.iter();
+    state.selected_projects = match selection {
+        BulkSelection::Visible => state
+            .flattened_tree
+            .items
+            .iter()
+            .filter_map(|(node, _)| match &node.node_type {
+                crate::tree::TreeNodeType::Project(project) => Some(project.id.clone()),
+                crate::tree::TreeNodeType::Directory { .. } => None,
+            })
+            .collect(),
+        BulkSelection::Matching(query) => match crate::query::Query::parse(&query) {
+            Ok(query) => all
+                .filter(|p| query.matches(p))
+                .map(|p| p.id.clone())
+                .collect(),
+            Err(e) => {
+                tracing::warn!("Invalid selection query {:?}: {}", query, e);
+                return;
+            }
+        },
+        BulkSelection::Outdated => all
+            .filter(|p| crate::project_table::outdated(p).is_some_and(|count| count > 0))
+            .map(|p| p.id.clone())
+            .collect(),
+        BulkSelection::Failed => all
+            .filter(|p| p.status == crate::project::ProjectStatus::Failed)
+            .map(|p| p.id.clone())
+            .collect(),
+        BulkSelection::Invert => all
+            .filter(|p| !state.selected_projects.contains(&p.id))
+            .map(|p| p.id.clone())
+            .collect(),
+        BulkSelection::None => HashSet::new(),
+        BulkSelection::Saved(name) => match state.settings.saved_selections.get(&name) {
+            // Projects that are gone, or not scanned this time, are skipped
+            Some(saved) => all
+                .filter(|p| saved.contains(&p.id))
+                .map(|p| p.id.clone())
+                .collect(),
+            None => return,
+        },
+    };
+}
+
+/// Handle saving the selected projects under a name
+pub fn handle_save_selection(state: &mut AppState, name: String) {
+    let name = name.trim();
+    if name.is_empty() {
+        return;
+    }
+    state.settings.saved_selections.insert(
+        name.to_string(),
+        state.selected_projects.iter().cloned().collect(),
+    );
+    if let Err(e) = state.settings.save() {
+        tracing::warn!("Failed to save settings: {}", e);
+    }
+}
+
+/// Handle forgetting a saved selection
+pub fn handle_delete_selection(state: &mut AppState, name: String) {
+    if state.settings.saved_selections.remove(&name).is_some() {
+        if let Err(e) = state.settings.save() {
+            tracing::warn!("Failed to save settings: {}", e);
+        }
+    }
+}
+
+/// Handle toggling between grouping the tree by directory and by tag
+pub fn handle_toggle_group_by_tag(state: &mut AppState) {
+    state.settings.group_by_tag = !state.settings.group_by_tag;
+    if let Err(e) = state.settings.save() {
+        tracing::warn!("Failed to save settings: {}", e);
+    }
+    refresh_flattened_tree(state);
+    state.tree_state.select(Some(0));
+}
+
+/// Handle switching the project list between the directory tree and the table
+pub fn handle_toggle_project_table(state: &mut AppState) {
+    state.settings.table_view.enabled = !state.settings.table_view.enabled;
+    if let Err(e) = state.settings.save() {
+        tracing::warn!("Failed to save settings: {}", e);
+    }
+    refresh_flattened_tree(state);
+    state.tree_state.select(Some(0));
+}
+
+/// Handle changing how the project table is sorted or grouped
+pub fn handle_change_table_view(state: &mut AppState, change: impl FnOnce(&mut TableView)) {
+    if !state.settings.table_view.enabled {
+        return;
+    }
+    change(&mut state.settings.table_view);
+    if let Err(e) = state.settings.save() {
+        tracing::warn!("Failed to save settings: {}", e);
+    }
+    refresh_flattened_tree(state);
+}
+
+/// Re-sort the project table after a value it sorts or groups by changed
+fn refresh_project_table(state: &mut AppState) {
+    if state.settings.table_view.enabled {
+        refresh_flattened_tree(state);
+    }
+}
+
+/// Handle a checked git state and modification time of a project
+pub fn handle_update_project_activity(
+    state: &mut AppState,
+    project_id: ProjectId,
+    git_status: GitStatus,
+    modified: Option<std::time::SystemTime>,
+) {
+    for project in state
+        .all_projects
+        .iter_mut()
+        .chain(state.projects.iter_mut())
+        .filter(|p| p.id == project_id)
+    {
+        project.git_status = git_status.clone();
+        project.modified = modified;
+    }
+    // The tree shows the git state too
+    if let Some(tree_root) = &mut state.tree_root {
+        update_project_in_tree(tree_root, &project_id, &|project| {
+            project.git_status = git_status.clone();
+            project.modified = modified;
+        });
+    }
+    refresh_flattened_tree(state);
+}
+
+/// Handle a command starting or finishing in a project
+pub fn handle_update_project_status(
+    state: &mut AppState,
+    project_id: ProjectId,
+    status: ProjectStatus,
+) {
+    for project in state
+        .all_projects
+        .iter_mut()
+        .chain(state.projects.iter_mut())
+        .filter(|p| p.id == project_id)
+    {
+        project.status = status.clone();
+    }
+    refresh_project_table(state);
+}
+
 /// Handle selecting next item in list
 pub fn handle_select_next(state: &mut AppState) {
     // Use tree navigation if tree is available
@@ -124,6 +490,18 @@ pub fn handle_select_next(state: &mut AppState) {
         };
         state.tree_state.select(Some(i));
     }
+    // A different project has different dependencies
+    state.dependency_list.select(None);
+}
+
+/// Handle moving the cursor to a row, e.g. clicked with the mouse
+pub fn handle_select_row(state: &mut AppState, index: usize) {
+    if index >= state.flattened_tree.items.len() || state.tree_state.selected() == Some(index) {
+        return;
+    }
+    state.tree_state.select(Some(index));
+    // A different project has different dependencies
+    state.dependency_list.select(None);
 }
 
 /// Handle selecting previous item in list
@@ -150,10 +528,20 @@ pub fn handle_select_previous(state: &mut AppState) {
         };
         state.tree_state.select(Some(i));
     }
+    // A different project has different dependencies
+    state.dependency_list.select(None);
 }
 
 /// Handle selecting parent (collapse directory/workspace)
 pub fn handle_select_parent(state: &mut AppState) {
+    if state.settings.table_view.enabled {
+        toggle_selected_table_group(state, true);
+        return;
+    }
+    if state.settings.group_by_tag {
+        toggle_selected_tag_group(state, true);
+        return;
+    }
     // Use tree navigation if tree is available
     if let Some(tree_root) = &mut state.tree_root {
         if let Some(selected_idx) = state.tree_state.selected() {
@@ -180,6 +568,14 @@ pub fn handle_select_parent(state: &mut AppState) {
 
 /// Handle selecting child (expand directory/workspace)
 pub fn handle_select_child(state: &mut AppState) {
+    if state.settings.table_view.enabled {
+        toggle_selected_table_group(state, false);
+        return;
+    }
+    if state.settings.group_by_tag {
+        toggle_selected_tag_group(state, false);
+        return;
+    }
     // Use tree navigation if tree is available
     if let Some(tree_root) = &mut state.tree_root {
         if let Some(selected_idx) = state.tree_state.selected() {
@@ -204,6 +600,37 @@ pub fn handle_select_child(state: &mut AppState) {
     }
 }
 
+/// Collapse or expand the tag group under the cursor
+fn toggle_selected_tag_group(state: &mut AppState, collapse: bool) {
+    let Some(tag) = state.get_selected_node().and_then(|node| node.tag()) else {
+        return;
+    };
+    let tag = tag.to_string();
+    if collapse {
+        state.collapsed_tags.insert(tag);
+    } else {
+        state.collapsed_tags.remove(&tag);
+    }
+    refresh_flattened_tree(state);
+}
+
+/// Collapse or expand the project table group under the cursor
+fn toggle_selected_table_group(state: &mut AppState, collapse: bool) {
+    let Some(key) = state
+        .get_selected_node()
+        .filter(|node| node.is_group())
+        .map(|node| node.node_type.path().to_string_lossy().into_owned())
+    else {
+        return;
+    };
+    if collapse {
+        state.collapsed_groups.insert(key);
+    } else {
+        state.collapsed_groups.remove(&key);
+    }
+    refresh_flattened_tree(state);
+}
+
 /// Recursively load and expand all directories under a node
 fn load_all_descendants(node: &mut crate::tree::TreeNode, show_all_folders: bool) {
     // Load this node's children if not already loaded
@@ -283,13 +710,12 @@ pub fn handle_toggle_selection(state: &mut AppState) {
 
                 // Load children if not already loaded, and expand the directory
                 load_and_expand_node(tree_root, &path_clone, show_all_folders);
-
-                // Re-flatten the tree to reflect the newly loaded children
-                state.flattened_tree = crate::tree::FlattenedTree::from_tree(tree_root);
             }
+            // Re-flatten the tree to reflect the newly loaded children
+            refresh_flattened_tree(state);
 
-            // Now collect all project names in this directory's subtree
-            let mut project_names = Vec::new();
+            // Now collect all projects in this directory's subtree
+            let mut project_ids = Vec::new();
 
             // Iterate through subsequent nodes until we leave this directory
             for (child_node, _) in state.flattened_tree.items.iter().skip(selected_index + 1) {
@@ -300,39 +726,39 @@ pub fn handle_toggle_selection(state: &mut AppState) {
 
                 // If it's a project, add it to our list
                 if let crate::tree::TreeNodeType::Project(project) = &child_node.node_type {
-                    project_names.push(project.name.clone());
+                    project_ids.push(project.id.clone());
                 }
             }
 
             // Toggle all projects in this directory
-            if !project_names.is_empty() {
-                let all_selected = project_names
+            if !project_ids.is_empty() {
+                let all_selected = project_ids
                     .iter()
-                    .all(|name| state.selected_projects.contains(name));
+                    .all(|id| state.selected_projects.contains(id));
                 if all_selected {
                     // Deselect all
-                    for name in project_names {
-                        state.selected_projects.remove(&name);
+                    for id in project_ids {
+                        state.selected_projects.remove(&id);
                     }
                 } else {
                     // Select all
-                    for name in project_names {
-                        state.selected_projects.insert(name);
+                    for id in project_ids {
+                        state.selected_projects.insert(id);
                     }
                 }
             }
         }
         crate::tree::TreeNodeType::Project(project) => {
             // Toggle single project
-            let project_name = project.name.clone();
-            toggle_single_project_selection(state, &project_name);
+            let project_id = project.id.clone();
+            toggle_single_project_selection(state, project_id);
         }
     }
 }
 
-fn toggle_single_project_selection(state: &mut AppState, project_name: &str) {
-    if !state.selected_projects.remove(project_name) {
-        state.selected_projects.insert(project_name.to_string());
+fn toggle_single_project_selection(state: &mut AppState, project_id: ProjectId) {
+    if !state.selected_projects.remove(&project_id) {
+        state.selected_projects.insert(project_id);
     }
 }
 
@@ -352,6 +778,39 @@ pub fn handle_settings_toggle_background(state: &mut AppState) {
         !state.settings_modal.background_updates_enabled;
 }
 
+/// Toggle offline mode preference while editing settings
+pub fn handle_settings_toggle_offline(state: &mut AppState) {
+    state.settings_modal.offline = !state.settings_modal.offline;
+}
+
+/// Toggle restoring the session on launch within the settings modal
+pub fn handle_settings_toggle_session_restore(state: &mut AppState) {
+    state.settings_modal.restore_session = !state.settings_modal.restore_session;
+}
+
+/// Switch to offline mode after the registry became unreachable
+///
+/// Pending background checks are dropped; an explicit `--offline` or setting
+/// keeps its own reason.
+pub fn handle_offline_detected(state: &mut AppState) {
+    if state.offline.is_none() {
+        state.offline = Some(OfflineReason::Detected);
+    }
+    state.update_queue.clear();
+}
+
+/// Handle the registry answering again: leave a detected offline mode and resume checks
+pub fn handle_registry_reachable(state: &mut AppState) {
+    if state.offline != Some(OfflineReason::Detected) {
+        return;
+    }
+    state.offline = None;
+    state.registry.reset_connectivity();
+    if state.settings.background_updates_enabled {
+        queue_background_updates_on_enable(state);
+    }
+}
+
 /// Persist settings from the modal, validating input and updating background queue
 pub fn handle_save_settings(state: &mut AppState) {
     let raw_value = state.settings_modal.cache_minutes_input.value().trim();
@@ -366,8 +825,11 @@ pub fn handle_save_settings(state: &mut AppState) {
 
     let mut new_settings = state.settings.clone();
     let was_background_enabled = new_settings.background_updates_enabled;
+    let was_offline = state.is_offline();
     new_settings.cache_ttl_minutes = minutes;
     new_settings.background_updates_enabled = state.settings_modal.background_updates_enabled;
+    new_settings.offline = state.settings_modal.offline;
+    new_settings.restore_session = state.settings_modal.restore_session;
 
     if let Err(err) = new_settings.save() {
         state.settings_modal.error_message = Some(format!("Failed to save settings: {}", err));
@@ -378,13 +840,27 @@ pub fn handle_save_settings(state: &mut AppState) {
     state.settings_modal.error_message = None;
     state.mode = Mode::Normal;
 
+    // The command-line flag always wins; otherwise follow the saved preference
+    // (saving with offline off also clears an automatically detected offline state)
+    if state.offline != Some(OfflineReason::Flag) {
+        state.offline = state.settings.offline.then_some(OfflineReason::Setting);
+    }
+    if state.is_offline() {
+        state.update_queue.clear();
+        state.is_checking_updates = false;
+        return;
+    }
+    if was_offline {
+        state.registry.reset_connectivity();
+    }
+
     if !state.settings.background_updates_enabled {
         state.update_queue.clear();
         state.is_checking_updates = false;
         return;
     }
 
-    if !was_background_enabled && state.settings.background_updates_enabled {
+    if (!was_background_enabled || was_offline) && state.settings.background_updates_enabled {
         queue_background_updates_on_enable(state);
     }
 }
@@ -412,7 +888,7 @@ fn queue_background_updates_on_enable(state: &mut AppState) {
 
         if needs_check {
             state.update_queue.add_task(crate::runner::UpdateCheckTask {
-                project_name: project.name.clone(),
+                project_id: project.id.clone(),
                 is_priority: false,
             });
         }
@@ -425,11 +901,7 @@ pub fn handle_show_command_palette(state: &mut AppState) {
     // Reset input
     state.palette.input = state.palette.input.clone().with_value(String::new());
     // Populate commands
-    state.palette.filtered_commands = state
-        .command_history
-        .iter()
-        .map(|c| Command::Cargo { command: c.clone() })
-        .collect();
+    state.palette.filtered_commands = palette_commands(state, "");
     // Ensure first item is selected
     if !state.palette.filtered_commands.is_empty() {
         state.palette.list_state.select(Some(0));
@@ -441,24 +913,7 @@ pub fn handle_show_command_palette(state: &mut AppState) {
 /// Handle updating command palette input
 pub fn handle_update_palette_input(state: &mut AppState, input: String) {
     state.palette.input = state.palette.input.clone().with_value(input.clone());
-
-    if input.is_empty() {
-        // Show all commands when input is empty
-        state.palette.filtered_commands = state
-            .command_history
-            .iter()
-            .map(|c| Command::Cargo { command: c.clone() })
-            .collect();
-    } else {
-        // Filter by fuzzy match
-        let matcher = SkimMatcherV2::default();
-        state.palette.filtered_commands = state
-            .command_history
-            .iter()
-            .filter(|cmd| matcher.fuzzy_match(cmd, &input).is_some())
-            .map(|c| Command::Cargo { command: c.clone() })
-            .collect();
-    }
+    state.palette.filtered_commands = palette_commands(state, &input);
 
     // Select first item if available
     if !state.palette.filtered_commands.is_empty() {
@@ -468,6 +923,87 @@ pub fn handle_update_palette_input(state: &mut AppState, input: String) {
     }
 }
 
+/// Commands offered by the palette: cargo history, configured collections, tags,
+/// then selections
+///
+/// An empty input shows everything; otherwise commands are fuzzy matched, with
+/// collections matched as `collection <name>`, tags as `select #<tag>` and
+/// saved selections as `selection <name>`. Typing `select <query>` offers the
+/// projects matching a filter query, `save selection <name>` saves the current
+/// selection and `forget selection <name>` deletes a saved one.
+fn palette_commands(state: &AppState, input: &str) -> Vec<Command> {
+    let matcher = SkimMatcherV2::default();
+    let matches = |text: &str| input.is_empty() || matcher.fuzzy_match(text, input).is_some();
+
+    let cargo = state
+        .command_history
+        .iter()
+        .filter(|cmd| matches(cmd))
+        .map(|c| Command::Cargo { command: c.clone() });
+    let collections = state
+        .config
+        .collections
+        .keys()
+        .filter(|name| matches(&format!("collection {}", name)))
+        .map(|name| Command::SwitchCollection { name: name.clone() });
+    let tags = crate::tags::all_tags(&state.all_projects)
+        .into_iter()
+        .filter(|tag| matches(&format!("select #{}", tag)))
+        .map(|tag| Command::SelectTag { tag });
+    // Only offered once typing, so the history isn't listed twice
+    let toolchains = state
+        .command_history
+        .iter()
+        .filter(|cmd| !input.is_empty() && matches(&format!("toolchains {}", cmd)))
+        .map(|c| Command::ToolchainMatrix { command: c.clone() });
+    // Also only offered once typing
+    let fixed_selections = BulkSelection::FIXED
+        .into_iter()
+        .filter(|(_, name)| !input.is_empty() && matches(&format!("select {}", name)))
+        .map(|(selection, _)| Command::Select { selection });
+    // A query is offered once it parses, after any fixed selection it spells
+    let query_selection = input
+        .strip_prefix("select ")
+        .map(str::trim)
+        .filter(|query| !query.is_empty() && crate::query::Query::parse(query).is_ok())
+        .map(|query| Command::Select {
+            selection: BulkSelection::Matching(query.to_string()),
+        });
+    let saved = &state.settings.saved_selections;
+    let saved_selections = saved
+        .keys()
+        .filter(|name| matches(&format!("selection {}", name)))
+        .map(|name| Command::Select {
+            selection: BulkSelection::Saved(name.clone()),
+        });
+    let save_selection = input
+        .strip_prefix("save selection ")
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !state.selected_projects.is_empty())
+        .map(|name| Command::SaveSelection {
+            name: name.to_string(),
+        });
+    // Only offered when asked for, so recalling one never lists forgetting it
+    let delete_selections = saved
+        .keys()
+        .filter(|name| {
+            input.starts_with("forget") && matches(&format!("forget selection {}", name))
+        })
+        .map(|name| Command::DeleteSelection { name: name.clone() });
+
+    save_selection
+        .into_iter()
+        .chain(cargo)
+        .chain(collections)
+        .chain(tags)
+        .chain(toolchains)
+        .chain(fixed_selections)
+        .chain(query_selection)
+        .chain(saved_selections)
+        .chain(delete_selections)
+        .collect()
+}
+
 /// Handle palette next selection
 pub fn handle_palette_select_next(state: &mut AppState) {
     let i = match state.palette.list_state.selected() {
@@ -498,6 +1034,233 @@ pub fn handle_palette_select_previous(state: &mut AppState) {
     state.palette.list_state.select(Some(i));
 }
 
+/// Handle selecting the next dependency in the dependencies pane
+pub fn handle_dependency_select_next(state: &mut AppState) {
+    let count = state
+        .get_selected_project()
+        .map_or(0, |p| p.dependencies.len());
+    if count == 0 {
+        return;
+    }
+    let next = match state.dependency_list.selected() {
+        Some(i) if i + 1 < count => i + 1,
+        _ => 0,
+    };
+    state.dependency_list.select(Some(next));
+}
+
+/// Handle selecting the previous dependency in the dependencies pane
+pub fn handle_dependency_select_previous(state: &mut AppState) {
+    let count = state
+        .get_selected_project()
+        .map_or(0, |p| p.dependencies.len());
+    if count == 0 {
+        return;
+    }
+    let prev = match state.dependency_list.selected() {
+        Some(i) if i > 0 && i < count => i - 1,
+        _ => count - 1,
+    };
+    state.dependency_list.select(Some(prev));
+}
+
+/// Open the version history of the selected dependency
+pub fn handle_show_dependency_detail(state: &mut AppState) {
+    if state.get_selected_dependency().is_some() {
+        state.dependency_detail_scroll = 0;
+        state.mode = Mode::DependencyDetail;
+    }
+}
+
+/// Handle switching the dependencies pane to the project details and back
+pub fn handle_toggle_project_details(state: &mut AppState) {
+    state.details.visible = !state.details.visible;
+    state.details.scroll = 0;
+}
+
+/// Handle opening the feature explorer for the project under the cursor
+pub fn handle_show_feature_explorer(state: &mut AppState) {
+    let Some(project) = state.get_selected_project() else {
+        return;
+    };
+    let available = ProjectFeatures::load(project);
+    state.features.project = Some(project.id.clone());
+    state
+        .features
+        .list_state
+        .select((!available.features.is_empty()).then_some(0));
+    state.features.available = available;
+    state.mode = Mode::FeatureExplorer;
+}
+
+/// Handle toggling the feature under the cursor in the feature explorer
+pub fn handle_toggle_feature(state: &mut AppState) {
+    let Some(name) = state
+        .features
+        .list_state
+        .selected()
+        .and_then(|i| state.features.available.features.get(i))
+        .map(|feature| feature.name.clone())
+    else {
+        return;
+    };
+    state.features.update_flags(|flags| {
+        if !flags.features.remove(&name) {
+            flags.features.insert(name);
+        }
+    });
+}
+
+/// Handle toggling `--no-default-features` for the explored project
+pub fn handle_toggle_no_default_features(state: &mut AppState) {
+    state
+        .features
+        .update_flags(|flags| flags.no_default_features = !flags.no_default_features);
+}
+
+/// Handle toggling `--all-features` for the explored project
+pub fn handle_toggle_all_features(state: &mut AppState) {
+    state
+        .features
+        .update_flags(|flags| flags.all_features = !flags.all_features);
+}
+
+/// Handle preparing a feature matrix for the explored project
+///
+/// Sets up the matrix and its output tab; the main loop runs the checks.
+/// Only one matrix runs at a time.
+pub fn handle_start_feature_matrix(state: &mut AppState) {
+    if state
+        .features
+        .matrix
+        .as_ref()
+        .is_some_and(FeatureMatrix::is_running)
+    {
+        return;
+    }
+    let Some(project) = state
+        .features
+        .project
+        .as_ref()
+        .and_then(|id| state.all_projects.iter().find(|p| &p.id == id))
+    else {
+        return;
+    };
+    let title = format!("feature matrix: {}", project.display_name);
+    let mut matrix = FeatureMatrix::new(
+        project.id.clone(),
+        state.features.available.names(),
+        &state.config.feature_matrix,
+    );
+    matrix.tab_index = state.tabs.len();
+    handle_create_tab(state, title);
+    state.features.matrix = Some(matrix);
+}
+
+/// Handle setting up a toolchain matrix over the selected projects
+///
+/// Creates an output tab per project and shows the grid; the main loop runs
+/// the commands. Only one toolchain matrix runs at a time.
+pub fn handle_start_toolchain_matrix(
+    state: &mut AppState,
+    command: String,
+    installed: Vec<String>,
+) {
+    if state
+        .toolchains
+        .matrix
+        .as_ref()
+        .is_some_and(ToolchainMatrix::is_running)
+    {
+        state.mode = Mode::ToolchainMatrix;
+        return;
+    }
+    let projects: Vec<Project> = state
+        .projects
+        .iter()
+        .filter(|p| state.selected_projects.contains(&p.id))
+        .cloned()
+        .collect();
+    if projects.is_empty() {
+        return;
+    }
+
+    let matrix = ToolchainMatrix::new(
+        &command,
+        &state.config.toolchains.matrix,
+        installed,
+        &projects,
+        state.tabs.len(),
+    );
+    for row in &matrix.rows {
+        handle_create_tab(state, format!("toolchains {}: {}", command, row.name));
+    }
+    state.toolchains.matrix = Some(matrix);
+    state.mode = Mode::ToolchainMatrix;
+}
+
+/// Handle a status change of one project on one toolchain
+///
+/// Finished runs in the `msrv` column flag or unflag the project in the tree.
+pub fn handle_toolchain_matrix_status(
+    state: &mut AppState,
+    project_id: ProjectId,
+    column: usize,
+    status: ToolchainStatus,
+) {
+    let Some(matrix) = state.toolchains.matrix.as_mut() else {
+        return;
+    };
+    let msrv_column = matrix.msrv_column();
+    let Some(cell) = matrix
+        .rows
+        .iter_mut()
+        .find(|row| row.project == project_id)
+        .and_then(|row| row.cells.get_mut(column))
+    else {
+        return;
+    };
+    cell.status = status;
+
+    if msrv_column == Some(column) {
+        match cell.status {
+            ToolchainStatus::Failed => {
+                state.toolchains.msrv_failures.insert(project_id);
+            }
+            ToolchainStatus::Passed => {
+                state.toolchains.msrv_failures.remove(&project_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Handle showing the latest toolchain matrix
+pub fn handle_show_toolchain_matrix(state: &mut AppState) {
+    if state.toolchains.matrix.is_some() {
+        state.mode = Mode::ToolchainMatrix;
+    }
+}
+
+/// Handle a status change of one combination in the feature matrix
+pub fn handle_feature_matrix_status(
+    state: &mut AppState,
+    tab_index: usize,
+    run: usize,
+    status: MatrixStatus,
+) {
+    if let Some(matrix) = state
+        .features
+        .matrix
+        .as_mut()
+        .filter(|matrix| matrix.tab_index == tab_index)
+    {
+        if let Some(run) = matrix.runs.get_mut(run) {
+            run.status = status;
+        }
+    }
+}
+
 /// Handle starting update wizard or directory-wide update check
 ///
 /// Behavior depends on what the cursor is on:
@@ -519,19 +1282,19 @@ pub fn handle_start_update_wizard(state: &mut AppState) {
             // We use is_priority=false because we're NOT opening a wizard for each project
             // The main.rs async handler will trigger processing after these are queued
             let projects = state.get_projects_under_selected();
-            let project_names: Vec<String> = projects.iter().map(|p| p.name.clone()).collect();
-            let count = project_names.len();
+            let project_ids: Vec<ProjectId> = projects.iter().map(|p| p.id.clone()).collect();
+            let count = project_ids.len();
 
             if count == 0 {
                 // No projects under this directory - nothing to do
                 return;
             }
 
-            // Store the project names and directory info in state for async handler
+            // Store the projects and directory info in state for async handler
             // The async handler will queue them and start processing
             state.updater.pending_directory_check = Some(PendingDirectoryCheck {
                 directory_name: name.clone(),
-                project_names,
+                projects: project_ids,
             });
 
             // Show feedback - create a tab to show what we're doing
@@ -573,22 +1336,19 @@ fn handle_start_update_wizard_for_project(state: &mut AppState) {
     // CRITICAL: Lock the wizard to the currently selected project
     // This prevents background updates for other projects from changing the wizard display
     if let Some(project) = state.get_selected_project() {
-        let project_name = project.name.clone();
+        let project_id = project.id.clone();
 
         // CRITICAL: Populate wizard with CURRENT dependency data immediately
         // This ensures the wizard shows up-to-date data even if background check
         // completed BEFORE the wizard opened (race condition fix)
-        // Uses has_stable_update() to properly handle pre-release versions
-        let outdated_deps: Vec<_> = project
-            .dependencies
-            .iter()
-            .filter(|d| d.has_stable_update())
-            .cloned()
-            .collect();
+        // Filtered through the project's update policy (pre-releases, ignores, pins, cooldown)
+        let dependencies = project.dependencies.clone();
+        let policy = project.update_policy.clone();
 
         // Now we can mutate state.updater
-        state.updater.locked_project_name = Some(project_name);
-        state.updater.outdated_dependencies = outdated_deps;
+        state.updater.locked_project = Some(project_id);
+        state.updater.policy = policy;
+        state.updater.set_outdated_from(&dependencies);
 
         // Select first item if there are outdated dependencies
         if !state.updater.outdated_dependencies.is_empty() {
@@ -600,7 +1360,7 @@ fn handle_start_update_wizard_for_project(state: &mut AppState) {
         // (happens when cache hits instantly)
         state.updater.user_check_in_progress = true;
     } else {
-        state.updater.locked_project_name = None;
+        state.updater.locked_project = None;
     }
 
     state.is_checking_updates = true;
@@ -626,13 +1386,13 @@ pub fn handle_check_for_updates(state: &mut AppState) {
 /// Handle updating dependencies for a project
 pub fn handle_update_dependencies(
     state: &mut AppState,
-    project_name: String,
+    project_id: ProjectId,
     deps: Vec<crate::project::Dependency>,
 ) {
     let is_wizard_locked_project = state.mode == Mode::UpdateWizard
-        && state.updater.locked_project_name.as_ref() == Some(&project_name);
+        && state.updater.locked_project.as_ref() == Some(&project_id);
 
-    update_project_dependencies(state, &project_name, deps.clone());
+    update_project_dependencies(state, &project_id, deps.clone());
 
     if is_wizard_locked_project {
         update_wizard_dependencies(state, deps);
@@ -641,30 +1401,24 @@ pub fn handle_update_dependencies(
 
 fn update_project_dependencies(
     state: &mut AppState,
-    project_name: &str,
+    project_id: &ProjectId,
     deps: Vec<crate::project::Dependency>,
 ) {
-    let new_check_status = Project::compute_check_status_from_deps(&deps);
-
-    if let Some(all_proj) = state
-        .all_projects
-        .iter_mut()
-        .find(|p| p.name == project_name)
-    {
+    if let Some(all_proj) = state.all_projects.iter_mut().find(|p| &p.id == project_id) {
+        let new_check_status =
+            Project::compute_check_status_from_deps(&deps, &all_proj.update_policy);
         all_proj.dependencies = deps.clone();
-        all_proj.check_status = new_check_status.clone();
+        all_proj.check_status = new_check_status;
     }
 
-    if let Some(proj) = state.projects.iter_mut().find(|p| p.name == project_name) {
+    if let Some(proj) = state.projects.iter_mut().find(|p| &p.id == project_id) {
+        proj.check_status = Project::compute_check_status_from_deps(&deps, &proj.update_policy);
         proj.dependencies = deps;
-        proj.check_status = new_check_status;
     }
 }
 
 fn update_wizard_dependencies(state: &mut AppState, deps: Vec<crate::project::Dependency>) {
-    // Uses has_stable_update() to properly handle pre-release versions
-    state.updater.outdated_dependencies =
-        deps.into_iter().filter(|d| d.has_stable_update()).collect();
+    state.updater.set_outdated_from(&deps);
 
     if !state.updater.outdated_dependencies.is_empty() {
         state.updater.list_state.select(Some(0));
@@ -678,7 +1432,7 @@ fn update_wizard_dependencies(state: &mut AppState, deps: Vec<crate::project::De
 ///
 /// This is ONLY called for user-initiated checks (pressing 'u'), not background checks.
 /// NOTE: This action may arrive AFTER UpdateDependencies when cache hits instantly!
-pub fn handle_update_dependencies_stream_start(state: &mut AppState, _project_name: String) {
+pub fn handle_update_dependencies_stream_start(state: &mut AppState, _project_id: ProjectId) {
     // CRITICAL FIX: Don't blindly set is_checking_updates = true!
     // If UpdateDependencies already arrived (cache hit), it would have cleared the flag.
     // Setting it back to true here would leave the wizard stuck "checking" forever.
@@ -702,19 +1456,15 @@ pub fn handle_update_dependencies_stream_start(state: &mut AppState, _project_na
 /// Handle updating a single dependency
 pub fn handle_update_single_dependency(
     state: &mut AppState,
-    project_name: String,
+    project_id: ProjectId,
     dep: crate::project::Dependency,
 ) {
     // CRITICAL: Check if wizard is open AND if this is the LOCKED project
     let is_wizard_locked_project = state.mode == Mode::UpdateWizard
-        && state.updater.locked_project_name.as_ref() == Some(&project_name);
+        && state.updater.locked_project.as_ref() == Some(&project_id);
 
     // Update in all_projects first (source of truth)
-    if let Some(all_proj) = state
-        .all_projects
-        .iter_mut()
-        .find(|p| p.name == project_name)
-    {
+    if let Some(all_proj) = state.all_projects.iter_mut().find(|p| p.id == project_id) {
         if let Some(existing_dep) = all_proj
             .dependencies
             .iter_mut()
@@ -725,20 +1475,14 @@ pub fn handle_update_single_dependency(
     }
 
     // Also update in filtered projects if it exists
-    if let Some(proj) = state.projects.iter_mut().find(|p| p.name == project_name) {
+    if let Some(proj) = state.projects.iter_mut().find(|p| p.id == project_id) {
         if let Some(existing_dep) = proj.dependencies.iter_mut().find(|d| d.name == dep.name) {
             *existing_dep = dep.clone();
         }
 
         // ONLY update wizard display if this is the LOCKED project
         if is_wizard_locked_project {
-            // Uses has_stable_update() to properly handle pre-release versions
-            state.updater.outdated_dependencies = proj
-                .dependencies
-                .iter()
-                .filter(|d| d.has_stable_update())
-                .cloned()
-                .collect();
+            state.updater.set_outdated_from(&proj.dependencies);
         }
     }
 }
@@ -769,47 +1513,44 @@ pub fn handle_finish_command(state: &mut AppState, tab_index: usize) {
 
 /// Handle switching to tab
 pub fn handle_switch_to_tab(state: &mut AppState, tab_index: usize) {
-    if tab_index < state.tabs.len() {
+    if tab_index < state.tabs.len() && tab_index != state.active_tab {
         state.active_tab = tab_index;
+        state.output_scroll = 0;
     }
 }
 
 /// Handle updating dependency status
 pub fn handle_update_dependency_status(
     state: &mut AppState,
-    project_name: Option<String>,
+    project_id: Option<ProjectId>,
     dep_name: String,
     status: crate::project::DependencyCheckStatus,
 ) {
-    let target_names: Vec<String> = if let Some(name) = project_name {
-        vec![name]
+    let target_ids: Vec<ProjectId> = if let Some(id) = project_id {
+        vec![id]
     } else {
         state
             .get_selected_project()
-            .map(|p| p.name.clone())
+            .map(|p| p.id.clone())
             .into_iter()
             .collect()
     };
 
-    for project_name in target_names {
-        if let Some(proj) = state
-            .all_projects
-            .iter_mut()
-            .find(|p| p.name == project_name)
-        {
+    for project_id in target_ids {
+        if let Some(proj) = state.all_projects.iter_mut().find(|p| p.id == project_id) {
             if let Some(dep) = proj.dependencies.iter_mut().find(|d| d.name == dep_name) {
                 dep.check_status = status.clone();
             }
         }
 
-        if let Some(proj) = state.projects.iter_mut().find(|p| p.name == project_name) {
+        if let Some(proj) = state.projects.iter_mut().find(|p| p.id == project_id) {
             if let Some(dep) = proj.dependencies.iter_mut().find(|d| d.name == dep_name) {
                 dep.check_status = status.clone();
             }
         }
 
         if state.mode == crate::events::Mode::UpdateWizard
-            && state.updater.locked_project_name.as_ref() == Some(&project_name)
+            && state.updater.locked_project.as_ref() == Some(&project_id)
         {
             if let Some(dep) = state
                 .updater
@@ -826,11 +1567,11 @@ pub fn handle_update_dependency_status(
 /// Handle queuing background update
 pub fn handle_queue_background_update(
     state: &mut AppState,
-    project_name: String,
+    project_id: ProjectId,
     is_priority: bool,
 ) {
     state.update_queue.add_task(crate::runner::UpdateCheckTask {
-        project_name,
+        project_id,
         is_priority,
     });
 }
@@ -838,27 +1579,25 @@ pub fn handle_queue_background_update(
 /// Handle updating project check status
 pub fn handle_update_project_check_status(
     state: &mut AppState,
-    project_name: String,
+    project_id: ProjectId,
     check_status: crate::project::ProjectCheckStatus,
 ) {
     // Update in all_projects (source of truth)
-    if let Some(proj) = state
-        .all_projects
-        .iter_mut()
-        .find(|p| p.name == project_name)
-    {
+    if let Some(proj) = state.all_projects.iter_mut().find(|p| p.id == project_id) {
         proj.check_status = check_status.clone();
     }
     // Also update in filtered projects list
-    if let Some(proj) = state.projects.iter_mut().find(|p| p.name == project_name) {
+    if let Some(proj) = state.projects.iter_mut().find(|p| p.id == project_id) {
         proj.check_status = check_status;
     }
+    refresh_project_table(state);
 }
 
 /// Handle entering filter/search mode
 pub fn handle_enter_filter_mode(state: &mut AppState) {
     state.mode = crate::events::Mode::Filter;
     state.filter.clear();
+    handle_update_filter_input(state, String::new());
 }
 
 /// Handle exiting filter/search mode
@@ -867,55 +1606,28 @@ pub fn handle_exit_filter_mode(state: &mut AppState) {
     // If a match was selected, update tree selection to that item
     if let Some(idx) = state.filter.selected_tree_index() {
         state.tree_selection.selected_index = Some(idx);
+        state.tree_state.select(Some(idx));
     }
     state.filter.clear();
 }
 
 /// Handle updating filter input text
 pub fn handle_update_filter_input(state: &mut AppState, input: String) {
-    let flattened_tree = state.flattened_tree.clone();
-
-    // Create a temporary AppState-like struct for filtering
-    struct TempState {
-        flattened_tree: crate::tree::FlattenedTree,
-    }
-
-    let temp = TempState { flattened_tree };
-
-    // Update filter with input
-    state.filter.input = input;
-    state.filter.selected = 0;
-    state.filter.filtered_indices.clear();
-
-    if state.filter.input.is_empty() {
-        state.filter.filtered_indices = (0..temp.flattened_tree.items.len()).collect();
-    } else {
-        let input_lower = state.filter.input.to_lowercase();
-
-        for (idx, (node, _)) in temp.flattened_tree.items.iter().enumerate() {
-            let matches = match &node.node_type {
-                crate::tree::TreeNodeType::Directory { name, .. } => {
-                    name.to_lowercase().contains(&input_lower)
-                }
-                crate::tree::TreeNodeType::Project(project) => {
-                    project.name.to_lowercase().contains(&input_lower)
-                        || project
-                            .path
-                            .to_string_lossy()
-                            .to_lowercase()
-                            .contains(&input_lower)
-                }
-            };
-
-            if matches {
-                state.filter.filtered_indices.push(idx);
-            }
-        }
-    }
+    state
+        .filter
+        .update_input(input, &state.flattened_tree, &state.all_projects);
+}
 
-    if state.filter.selected >= state.filter.filtered_indices.len() {
-        state.filter.selected = state.filter.filtered_indices.len().saturating_sub(1);
+/// Handle selecting every project matching the filter, so commands run on them
+///
+/// Projects in collapsed directories count too. An invalid query selects
+/// nothing and keeps filter mode open to fix it.
+pub fn handle_select_filter_matches(state: &mut AppState) {
+    if state.filter.error.is_some() {
+        return;
     }
+    state.selected_projects = state.filter.matched_projects.iter().cloned().collect();
+    handle_exit_filter_mode(state);
 }
 
 /// Handle clearing filter
@@ -956,15 +1668,17 @@ pub fn handle_toggle_show_all_folders(state: &mut AppState) {
 
     // Rebuild the tree with the new setting
     if let Some(tree_root) = &mut state.tree_root {
-        // Mark all nodes as needing reload
-        mark_all_nodes_unloaded(tree_root);
-
-        // Reload root children with new setting
-        crate::project::load_directory_children(tree_root, state.settings.show_all_folders);
+        for root in tree_root.scan_roots_mut() {
+            // Mark all nodes as needing reload
+            mark_all_nodes_unloaded(root);
 
-        // Re-flatten the tree
-        state.flattened_tree = crate::tree::FlattenedTree::from_tree(tree_root);
+            // Reload root children with new setting
+            crate::project::load_directory_children(root, state.settings.show_all_folders);
+        }
     }
+
+    // Re-flatten the tree
+    refresh_flattened_tree(state);
 }
 
 /// Recursively mark all nodes as needing reload
@@ -1008,6 +1722,18 @@ pub fn handle_decrease_top_right(state: &mut AppState) {
     let _ = state.config.save();
 }
 
+/// Handle setting the left pane width while its border is dragged
+///
+/// The layout is saved once the border is released.
+pub fn handle_resize_left_pane(state: &mut AppState, percent: u16) {
+    state.config.layout.left_pane_percent = percent.clamp(20, 80);
+}
+
+/// Handle setting the top-right pane height while its border is dragged
+pub fn handle_resize_top_right(state: &mut AppState, percent: u16) {
+    state.config.layout.top_right_percent = percent.clamp(20, 80);
+}
+
 /// Handle resetting layout to defaults
 pub fn handle_reset_layout(state: &mut AppState) {
     state.config.layout = crate::config::LayoutConfig::default();
@@ -1047,119 +1773,342 @@ pub async fn handle_calculate_project_sizes(
     use std::sync::Arc;
     use tokio::sync::Semaphore;
 
-    // Limit concurrent size calculations to prevent I/O contention and UI lag
-    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_SIZE_CALCS));
+    let projects: Vec<(ProjectId, PathBuf, Option<PathBuf>)> = state
+        .all_projects
+        .iter()
+        .map(|p| (p.id.clone(), p.path.clone(), p.workspace_root.clone()))
+        .collect();
 
-    for project in &state.all_projects {
-        let project_name = project.name.clone();
-        let project_path = project.path.clone();
-        let tx = action_tx.clone();
-        let sem = semaphore.clone();
-
-        // For workspace members, we need to check the workspace root for target/
-        let workspace_root = project.workspace_root.clone();
-
-        // Spawn task to calculate sizes in background with concurrency limiting
-        tokio::spawn(async move {
-            // Acquire semaphore permit (limits concurrent calculations)
-            let _permit = sem.acquire().await.ok();
-
-            // Use spawn_blocking for the blocking WalkDir I/O operations
-            // This prevents blocking the async executor and causing UI lag
-            let (total_size, target_size) = tokio::task::spawn_blocking(move || {
-                let total_size = crate::project::calculate_directory_size(&project_path);
-
-                // For workspace members, look for target/ at workspace root
-                // For standalone projects, look in the project directory
-                let target_size = {
-                    let target_path = if let Some(ws_root) = workspace_root {
-                        ws_root.join("target")
-                    } else {
-                        project_path.join("target")
-                    };
-
-                    if target_path.exists() && target_path.is_dir() {
-                        crate::project::calculate_directory_size(&target_path)
-                    } else {
-                        Some(0)
-                    }
-                };
+    tokio::spawn(async move {
+        // Resolving reads `.cargo/config.toml` files up the tree
+        let Ok(resolved) = tokio::task::spawn_blocking(move || {
+            projects
+                .into_iter()
+                .map(|(id, path, workspace_root)| {
+                    let target_dir = resolve_target_dir(&path, workspace_root.as_deref());
+                    (id, path, target_dir)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        else {
+            return;
+        };
+        let _ = action_tx
+            .send(Action::TargetDirsResolved(
+                resolved
+                    .iter()
+                    .map(|(id, _, dir)| (id.clone(), dir.clone()))
+                    .collect(),
+            ))
+            .await;
+
+        // A shared target directory is measured once and attributed to every project using it
+        let mut groups: HashMap<PathBuf, Vec<(ProjectId, PathBuf)>> = HashMap::new();
+        for (id, path, target_dir) in resolved {
+            groups.entry(target_dir).or_default().push((id, path));
+        }
 
-                (total_size, target_size)
-            })
-            .await
-            .unwrap_or((None, None));
-
-            // Send update back to main thread
-            let _ = tx
-                .send(Action::UpdateProjectSize(
-                    project_name,
-                    total_size,
-                    target_size,
-                ))
-                .await;
-        });
-    }
+        // Limit concurrent size calculations to prevent I/O contention and UI lag
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_SIZE_CALCS));
+        for (target_dir, members) in groups {
+            let tx = action_tx.clone();
+            let sem = semaphore.clone();
+
+            tokio::spawn(async move {
+                // Acquire semaphore permit (limits concurrent calculations)
+                let _permit = sem.acquire().await.ok();
+
+                // Use spawn_blocking for the blocking directory walks
+                // This prevents blocking the async executor and causing UI lag
+                let sizes = tokio::task::spawn_blocking(move || {
+                    let target_size = crate::project::calculate_target_dir_size(&target_dir);
+                    members
+                        .into_iter()
+                        .map(|(id, path)| {
+                            (
+                                id,
+                                crate::project::calculate_directory_size(&path),
+                                target_size,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await
+                .unwrap_or_default();
+
+                // Send updates back to main thread
+                for (project_id, total_size, target_size) in sizes {
+                    let _ = tx
+                        .send(Action::UpdateProjectSize(
+                            project_id,
+                            total_size,
+                            target_size,
+                        ))
+                        .await;
+                }
+            });
+        }
+    });
 }
 
 /// Handle updating a single project's size information
 pub fn handle_update_project_size(
     state: &mut AppState,
-    project_name: String,
+    project_id: ProjectId,
     total_size: Option<u64>,
     target_size: Option<u64>,
 ) {
     // Update in all_projects
-    if let Some(project) = state
-        .all_projects
-        .iter_mut()
-        .find(|p| p.name == project_name)
-    {
+    if let Some(project) = state.all_projects.iter_mut().find(|p| p.id == project_id) {
         project.total_size = total_size;
         project.target_size = target_size;
     }
 
     // Update in projects (filtered view)
-    if let Some(project) = state.projects.iter_mut().find(|p| p.name == project_name) {
+    if let Some(project) = state.projects.iter_mut().find(|p| p.id == project_id) {
         project.total_size = total_size;
         project.target_size = target_size;
     }
 
     // Update in tree nodes
     if let Some(ref mut tree_root) = state.tree_root {
-        update_project_size_in_tree(tree_root, &project_name, total_size, target_size);
+        update_project_size_in_tree(tree_root, &project_id, total_size, target_size);
+    }
 
-        // Re-flatten tree to pick up changes
-        state.flattened_tree = crate::tree::FlattenedTree::from_tree(tree_root);
+    // Re-flatten tree to pick up changes
+    refresh_flattened_tree(state);
+}
+
+/// Handle a new size of a target directory, e.g. after `cargo clean`
+///
+/// Every project building into the directory gets the size, keeping its total.
+pub fn handle_update_target_size(state: &mut AppState, dir: PathBuf, target_size: Option<u64>) {
+    let sharing: Vec<ProjectId> = state.target_dirs.sharing(&dir).cloned().collect();
+    for project_id in sharing {
+        let total_size = state
+            .all_projects
+            .iter()
+            .find(|p| p.id == project_id)
+            .and_then(|p| p.total_size);
+        handle_update_project_size(state, project_id, total_size, target_size);
+    }
+}
+
+/// Handle opening the disk reclamation view
+pub fn handle_show_reclaim(state: &mut AppState) {
+    if state.reclaim.criteria.is_empty() {
+        state.reclaim.criteria.older_than_days = Some(state.config.sweep.older_than_days);
+    }
+    if state.reclaim.table_state.selected().is_none() {
+        state.reclaim.table_state.select(Some(0));
+    }
+    state.mode = Mode::Reclaim;
+}
+
+/// Handle moving the selection in the disk reclamation view
+pub fn handle_reclaim_select(state: &mut AppState, forward: bool) {
+    let count = reclaim_entries(state).len();
+    if count == 0 {
+        return;
+    }
+    let i = match (state.reclaim.table_state.selected(), forward) {
+        (Some(i), true) if i + 1 < count => i + 1,
+        (_, true) => 0,
+        (Some(0) | None, false) => count - 1,
+        (Some(i), false) => i - 1,
+    };
+    state.reclaim.table_state.select(Some(i));
+}
+
+/// Handle a measured target directory breakdown
+pub fn handle_target_breakdown_loaded(
+    state: &mut AppState,
+    dir: PathBuf,
+    breakdown: TargetBreakdown,
+) {
+    state.reclaim.loading.remove(&dir);
+    state.reclaim.breakdowns.insert(dir, breakdown);
+}
+
+/// Handle changed sweep criteria; the dry run no longer matches them
+pub fn handle_sweep_criteria_changed(state: &mut AppState) {
+    let stale = state
+        .reclaim
+        .plan
+        .as_ref()
+        .is_some_and(|plan| plan.criteria != state.reclaim.criteria);
+    if stale && !state.reclaim.busy {
+        state.reclaim.plan = None;
+    }
+    state.reclaim.message = None;
+}
+
+/// Handle a finished dry run
+///
+/// A dry run started before the criteria changed is dropped rather than shown.
+pub fn handle_sweep_planned(state: &mut AppState, plan: SweepPlan) {
+    state.reclaim.busy = false;
+    if plan.criteria == state.reclaim.criteria {
+        state.reclaim.message = None;
+        state.reclaim.plan = Some(plan);
+    } else {
+        state.reclaim.message = Some("Criteria changed, run the dry run again".to_string());
+        state.reclaim.plan = None;
+    }
+}
+
+/// Handle a finished sweep
+///
+/// Breakdowns of the swept directories are measured again when next shown.
+pub fn handle_sweep_finished(
+    state: &mut AppState,
+    dirs: Vec<PathBuf>,
+    freed: u64,
+    errors: Vec<String>,
+) {
+    state.reclaim.busy = false;
+    state.reclaim.plan = None;
+    for dir in &dirs {
+        state.reclaim.breakdowns.remove(dir);
+    }
+    let mut message = format!("Swept {}", Project::format_size(freed));
+    if let Some(first) = errors.first() {
+        message.push_str(&format!(" · {} failed, e.g. {}", errors.len(), first));
+    }
+    state.reclaim.message = Some(message);
+}
+
+/// Handle opening the disk usage view of the selected project
+///
+/// Listings are measured again on every open; unchanged directories come from
+/// the size cache.
+pub fn handle_show_disk_usage(state: &mut AppState) {
+    let Some(project) = state.get_selected_project() else {
+        return;
+    };
+    let target_dir = state
+        .target_dirs
+        .get(&project.id)
+        .cloned()
+        .unwrap_or_else(|| project.target_dir());
+    state.disk_usage = DiskUsageState::new(
+        project.display_name.clone(),
+        project.path.clone(),
+        target_dir,
+    );
+    state.mode = Mode::DiskUsage;
+}
+
+/// Handle moving the selection in the disk usage view
+pub fn handle_disk_usage_select(state: &mut AppState, forward: bool) {
+    let count = state.disk_usage.entries().len();
+    if count == 0 {
+        return;
+    }
+    let i = match (state.disk_usage.table_state.selected(), forward) {
+        (Some(i), true) if i + 1 < count => i + 1,
+        (_, true) => 0,
+        (Some(0) | None, false) => count - 1,
+        (Some(i), false) => i - 1,
+    };
+    state.disk_usage.table_state.select(Some(i));
+}
+
+/// Handle descending into the selected directory of the disk usage view
+pub fn handle_disk_usage_open(state: &mut AppState) {
+    let Some(dir) = state
+        .disk_usage
+        .selected_entry()
+        .filter(|entry| entry.is_dir)
+        .map(|entry| entry.path.clone())
+    else {
+        return;
+    };
+    let has_entries = state
+        .disk_usage
+        .listings
+        .get(&dir)
+        .is_some_and(|entries| !entries.is_empty());
+    state.disk_usage.stack.push(dir);
+    state
+        .disk_usage
+        .table_state
+        .select(has_entries.then_some(0));
+}
+
+/// Handle going back up in the disk usage view, selecting the directory left
+pub fn handle_disk_usage_up(state: &mut AppState) {
+    if state.disk_usage.at_root() {
+        return;
+    }
+    let Some(left) = state.disk_usage.stack.pop() else {
+        return;
+    };
+    let index = state
+        .disk_usage
+        .entries()
+        .iter()
+        .position(|entry| entry.path == left);
+    state.disk_usage.table_state.select(index);
+}
+
+/// Handle a listed directory of the disk usage view
+pub fn handle_disk_usage_loaded(state: &mut AppState, dir: PathBuf, entries: Vec<UsageEntry>) {
+    let usage = &mut state.disk_usage;
+    usage.loading.remove(&dir);
+    let shown = usage.current() == Some(dir.as_path());
+    let has_entries = !entries.is_empty();
+    usage.listings.insert(dir, entries);
+    if shown && usage.table_state.selected().is_none() {
+        usage.table_state.select(has_entries.then_some(0));
     }
 }
 
 /// Recursively update project size in tree
 fn update_project_size_in_tree(
     node: &mut crate::tree::TreeNode,
-    project_name: &str,
+    project_id: &ProjectId,
     total_size: Option<u64>,
     target_size: Option<u64>,
 ) {
     if let crate::tree::TreeNodeType::Project(ref mut project) = node.node_type {
-        if project.name == project_name {
+        if &project.id == project_id {
             project.total_size = total_size;
             project.target_size = target_size;
         }
     }
 
     for child in &mut node.children {
-        update_project_size_in_tree(child, project_name, total_size, target_size);
+        update_project_size_in_tree(child, project_id, total_size, target_size);
+    }
+}
+
+/// Recursively apply a change to a project's copy in the tree
+fn update_project_in_tree(
+    node: &mut crate::tree::TreeNode,
+    project_id: &ProjectId,
+    update: &impl Fn(&mut Project),
+) {
+    if let crate::tree::TreeNodeType::Project(ref mut project) = node.node_type {
+        if &project.id == project_id {
+            update(project);
+        }
+    }
+
+    for child in &mut node.children {
+        update_project_in_tree(child, project_id, update);
     }
 }
 
 /// Handle initializing the tree with a shallow scan (non-blocking)
-pub fn handle_initialize_tree(state: &mut AppState, target_directory: String) {
-    // Build root node only (fast, no I/O) - returns immediately
-    let tree_root = crate::project::build_project_tree(&target_directory);
+pub fn handle_initialize_tree(state: &mut AppState, roots: ScanRoots) {
+    // Build root nodes only (fast, no I/O) - returns immediately
+    let tree_root = crate::project::build_roots_tree(&roots);
+    state.scan_roots = roots;
 
-    state.tree_root = Some(tree_root.clone());
-    state.flattened_tree = crate::tree::FlattenedTree::from_tree(&tree_root);
+    state.tree_root = Some(tree_root);
+    refresh_flattened_tree(state);
 
     // Select first item
     state.tree_state.select(Some(0));
@@ -1169,6 +2118,21 @@ pub fn handle_initialize_tree(state: &mut AppState, target_directory: String) {
     state.is_scanning = true; // Keep scanning flag for other indicators
 }
 
+/// Handle switching to other scan roots: drop the current projects and rescan
+pub fn handle_switch_roots(state: &mut AppState, roots: ScanRoots) {
+    state.all_projects.clear();
+    state.projects.clear();
+    state.selected_projects.clear();
+    state.collapsed_workspaces.clear();
+    state.tree_root = None;
+    state.flattened_tree = crate::tree::FlattenedTree::new();
+    state.tree_state.select(Some(0));
+    state.scan_roots = roots;
+    state.scan_progress = None;
+    state.is_scanning = true;
+    state.mode = Mode::Loading;
+}
+
 /// Handle directory loaded async result
 pub fn handle_directory_loaded(
     state: &mut AppState,
@@ -1184,10 +2148,21 @@ pub fn handle_directory_loaded(
         ) -> bool {
             if node.node_type.path() == target_path {
                 // Fix up depths of children relative to parent
+                let old_children = std::mem::take(&mut node.children);
                 node.children = new_children
                     .iter()
                     .map(|c| {
                         let mut child = c.clone();
+                        // Subdirectories already opened (or restored) stay as they were
+                        if let Some(old) = old_children.iter().find(|old| {
+                            old.node_type.is_directory()
+                                && old.children_loaded
+                                && old.node_type.path() == c.node_type.path()
+                        }) {
+                            child.children = old.children.clone();
+                            child.children_loaded = true;
+                            child.expanded = old.expanded;
+                        }
                         child.depth = node.depth + 1;
                         fix_depth_recursive(&mut child, node.depth + 1);
                         child
@@ -1218,7 +2193,7 @@ pub fn handle_directory_loaded(
             .iter()
             .filter_map(|node| {
                 if let crate::tree::TreeNodeType::Project(p) = &node.node_type {
-                    Some(p.clone())
+                    Some(p.as_ref().clone())
                 } else {
                     None
                 }
@@ -1226,21 +2201,20 @@ pub fn handle_directory_loaded(
             .collect();
 
         if update_node(root, &path, &children) {
-            // Re-flatten
-            state.flattened_tree = crate::tree::FlattenedTree::from_tree(root);
-
-            // If this is the root directory being loaded, exit Loading mode
+            // If a root directory is being loaded, exit Loading mode
             let root_path = root.node_type.path();
-            if root_path == path {
+            if root_path == path || state.scan_roots.paths.contains(&path) {
                 state.mode = Mode::Normal;
                 state.is_scanning = false;
             }
         }
 
         // Process newly loaded projects
-        for project in loaded_projects {
+        for mut project in loaded_projects {
+            resolve_project(state, &mut project);
+
             // Add to all_projects if not present (avoid duplicates from deep scan)
-            if !state.all_projects.iter().any(|p| p.path == project.path) {
+            if !state.all_projects.iter().any(|p| p.id == project.id) {
                 state.all_projects.push(project.clone());
                 // Also add to filtered list if it has deps
                 if !project.dependencies.is_empty() {
@@ -1251,8 +2225,11 @@ pub fn handle_directory_loaded(
             // Queue background update check if enabled (not priority - those are for user-initiated checks)
             if state.settings.background_updates_enabled && !project.dependencies.is_empty() {
                 // is_priority=false: these are background checks, user-initiated checks use priority=true
-                handle_queue_background_update(state, project.name, false);
+                handle_queue_background_update(state, project.id, false);
             }
         }
+
+        // Re-flatten
+        refresh_flattened_tree(state);
     }
 }
@@ -0,0 +1,336 @@
+//! Persistent project index for instant startup
+//!
+//! After a scan, every project is written to an index next to the update
+//! cache, together with the size and modification time of the files it was
+//! built from (its manifest, the workspace root manifest and the lockfiles).
+//! On the next launch the UI is filled from the index straight away, while a
+//! background scan revalidates it: projects whose files are unchanged are
+//! reused as-is, the rest are parsed again and only the differences are applied.
+
+use crate::project::{
+    Dependency, DependencyCheckStatus, GitStatus, Project, ProjectCheckStatus, ProjectId,
+    ProjectStatus, disambiguate_display_names,
+};
+use crate::settings::DiscoveryBackend;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Version of the on-disk index format; indexes with another version are ignored
+pub const INDEX_SCHEMA_VERSION: u32 = 3;
+
+/// Size and modification time of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub modified: SystemTime,
+    pub len: u64,
+}
+
+impl FileStamp {
+    /// Stamp a file, returning `None` if it can't be read
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// A dependency as recorded in the index (check results live in the cache)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedDependency {
+    pub name: String,
+    pub current_version: String,
+    pub requirement: Option<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// A project as recorded in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedProject {
+    pub id: ProjectId,
+    pub name: String,
+    pub path: PathBuf,
+    pub version: String,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    pub edition: Option<String>,
+    pub rust_version: Option<String>,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<IndexedDependency>,
+    pub workspace_root: Option<PathBuf>,
+    pub workspace_name: Option<String>,
+    /// Files the project was built from and their stamps at the time
+    pub stamps: Vec<(PathBuf, Option<FileStamp>)>,
+}
+
+impl IndexedProject {
+    fn new(project: &Project) -> Self {
+        Self {
+            id: project.id.clone(),
+            name: project.name.clone(),
+            path: project.path.clone(),
+            version: project.version.clone(),
+            authors: project.authors.clone(),
+            edition: project.edition.clone(),
+            rust_version: project.rust_version.clone(),
+            license: project.license.clone(),
+            keywords: project.keywords.clone(),
+            categories: project.categories.clone(),
+            dependencies: project
+                .dependencies
+                .iter()
+                .map(|dep| IndexedDependency {
+                    name: dep.name.clone(),
+                    current_version: dep.current_version.clone(),
+                    requirement: dep.requirement.clone(),
+                    features: dep.features.clone(),
+                })
+                .collect(),
+            workspace_root: project.workspace_root.clone(),
+            workspace_name: project.workspace_name.clone(),
+            stamps: source_files(project)
+                .into_iter()
+                .map(|path| {
+                    let stamp = FileStamp::of(&path);
+                    (path, stamp)
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether none of the files this project was built from have changed
+    pub fn is_current(&self) -> bool {
+        self.stamps
+            .iter()
+            .all(|(path, stamp)| FileStamp::of(path) == *stamp)
+    }
+
+    /// Rebuild the project, as the manifest parser would have returned it
+    pub fn to_project(&self) -> Project {
+        Project {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            display_name: self.name.clone(),
+            path: self.path.clone(),
+            status: ProjectStatus::Pending,
+            version: self.version.clone(),
+            authors: self.authors.clone(),
+            edition: self.edition.clone(),
+            rust_version: self.rust_version.clone(),
+            license: self.license.clone(),
+            keywords: self.keywords.clone(),
+            categories: self.categories.clone(),
+            tags: Vec::new(),
+            dependencies: self
+                .dependencies
+                .iter()
+                .map(|dep| Dependency {
+                    name: dep.name.clone(),
+                    current_version: dep.current_version.clone(),
+                    latest_version: None,
+                    check_status: DependencyCheckStatus::NotChecked,
+                    last_checked: None,
+                    published_versions: Vec::new(),
+                    requirement: dep.requirement.clone(),
+                    features: dep.features.clone(),
+                })
+                .collect(),
+            workspace_root: self.workspace_root.clone(),
+            workspace_name: self.workspace_name.clone(),
+            cargo_lock_hash: None,
+            check_status: ProjectCheckStatus::Unchecked,
+            git_status: GitStatus::Unknown,
+            total_size: None,
+            target_size: None,
+            modified: None,
+            update_policy: Arc::default(),
+        }
+    }
+}
+
+/// Files a parsed project depends on
+fn source_files(project: &Project) -> Vec<PathBuf> {
+    let mut files = vec![
+        project.path.join("Cargo.toml"),
+        project.path.join("Cargo.lock"),
+    ];
+    if let Some(root) = &project.workspace_root {
+        if root != &project.path {
+            files.push(root.join("Cargo.toml"));
+            files.push(root.join("Cargo.lock"));
+        }
+    }
+    files
+}
+
+/// Every project found below one scanned directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectIndex {
+    /// Format version of this file
+    pub schema_version: u32,
+    /// The scanned root directories
+    pub roots: Vec<PathBuf>,
+    /// Backend the projects were discovered with
+    pub discovery: DiscoveryBackend,
+    pub projects: Vec<IndexedProject>,
+}
+
+/// Projects that changed between an index and a fresh scan
+#[derive(Debug, Clone, Default)]
+pub struct IndexDiff {
+    /// New projects and projects whose files changed
+    pub changed: Vec<Project>,
+    /// Projects that are gone
+    pub removed: Vec<ProjectId>,
+}
+
+impl IndexDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl ProjectIndex {
+    /// Record scanned projects, stamping the files each was built from
+    pub fn build(roots: &[PathBuf], discovery: DiscoveryBackend, projects: &[Project]) -> Self {
+        Self {
+            schema_version: INDEX_SCHEMA_VERSION,
+            roots: roots.to_vec(),
+            discovery,
+            projects: projects.iter().map(IndexedProject::new).collect(),
+        }
+    }
+
+    /// All indexed projects, in index order
+    pub fn to_projects(&self) -> Vec<Project> {
+        let mut projects: Vec<Project> = self
+            .projects
+            .iter()
+            .map(IndexedProject::to_project)
+            .collect();
+        disambiguate_display_names(&mut projects);
+        projects
+    }
+
+    /// Look up an indexed project whose files haven't changed since indexing
+    pub fn current(&self, id: &ProjectId) -> Option<&IndexedProject> {
+        self.projects
+            .iter()
+            .find(|p| &p.id == id)
+            .filter(|p| p.is_current())
+    }
+
+    /// Compare this index against a newer one built from a fresh scan
+    ///
+    /// A project counts as changed if it is new or any of its source files
+    /// has a different stamp than in this index.
+    pub fn diff(&self, newer: &ProjectIndex, projects: &[Project]) -> IndexDiff {
+        let old: HashMap<&ProjectId, &IndexedProject> =
+            self.projects.iter().map(|p| (&p.id, p)).collect();
+        let new_ids: HashSet<&ProjectId> = newer.projects.iter().map(|p| &p.id).collect();
+
+        let changed = newer
+            .projects
+            .iter()
+            .filter(|p| old.get(&p.id).is_none_or(|o| o.stamps != p.stamps))
+            .filter_map(|p| projects.iter().find(|project| project.id == p.id))
+            .cloned()
+            .collect();
+        let removed = self
+            .projects
+            .iter()
+            .filter(|p| !new_ids.contains(&p.id))
+            .map(|p| p.id.clone())
+            .collect();
+
+        IndexDiff { changed, removed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::find_rust_projects;
+    use crate::test_support::{temp_dir, write_package};
+
+    #[test]
+    fn test_round_trip_keeps_parsed_fields() {
+        let fixture =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspaces/inherited");
+        let projects = find_rust_projects(fixture.to_str().unwrap());
+        let index = ProjectIndex::build(
+            std::slice::from_ref(&fixture),
+            DiscoveryBackend::Manifest,
+            &projects,
+        );
+
+        let json = serde_json::to_string(&index).unwrap();
+        let loaded: ProjectIndex = serde_json::from_str(&json).unwrap();
+        let restored = loaded.to_projects();
+
+        assert_eq!(restored.len(), projects.len());
+        for (restored, scanned) in restored.iter().zip(&projects) {
+            assert_eq!(restored.id, scanned.id);
+            assert_eq!(restored.version, scanned.version);
+            assert_eq!(restored.edition, scanned.edition);
+            assert_eq!(restored.workspace_name, scanned.workspace_name);
+            assert_eq!(restored.dependencies.len(), scanned.dependencies.len());
+        }
+        assert!(loaded.projects.iter().all(IndexedProject::is_current));
+    }
+
+    #[test]
+    fn test_diff_reports_changed_added_and_removed() {
+        let root = temp_dir("index", "diff");
+        write_package(&root.join("kept"), "kept", "0.1.0", &[]);
+        write_package(&root.join("edited"), "edited", "0.1.0", &[]);
+        write_package(&root.join("gone"), "gone", "0.1.0", &[]);
+
+        let before = find_rust_projects(root.to_str().unwrap());
+        let old = ProjectIndex::build(
+            std::slice::from_ref(&root),
+            DiscoveryBackend::Manifest,
+            &before,
+        );
+
+        // A different length is enough to change the stamp, whatever the mtime resolution
+        write_package(&root.join("edited"), "edited", "0.10.0", &[]);
+        fs::remove_dir_all(root.join("gone")).unwrap();
+        write_package(&root.join("added"), "added", "0.1.0", &[]);
+
+        let edited = old.projects.iter().find(|p| p.name == "edited").unwrap();
+        assert!(!edited.is_current());
+        assert!(old.current(&edited.id).is_none());
+
+        let after = find_rust_projects(root.to_str().unwrap());
+        let new = ProjectIndex::build(
+            std::slice::from_ref(&root),
+            DiscoveryBackend::Manifest,
+            &after,
+        );
+        let diff = old.diff(&new, &after);
+
+        let mut changed: Vec<&str> = diff.changed.iter().map(|p| p.name.as_str()).collect();
+        changed.sort();
+        assert_eq!(changed, ["added", "edited"]);
+        assert_eq!(diff.removed.len(), 1);
+        assert!(
+            diff.removed[0]
+                .manifest_path()
+                .starts_with(root.join("gone"))
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -38,23 +38,63 @@
 //! - [`app`] - Application state management
 //! - [`cache`] - Cache management for project data
 //! - [`components`] - UI components (palette, text input, help, etc.)
+//! - [`details`] - Build targets, features, toolchain and README for the details pane
+//! - [`disk_usage`] - Parallel, cached directory sizes and per-project disk usage
 //! - [`events`] - Event handling and command processing
+//! - [`features`] - Cargo features, feature flags for commands and feature matrices
+//! - [`index`] - Persistent project index for instant startup
+//! - [`metadata`] - Optional `cargo metadata` discovery backend
+//! - [`mouse`] - Mouse support: clicks, scrolling and dragging pane borders
+//! - [`policy`] - Dependency update policy (ignores, pins, pre-releases, cooldown)
 //! - [`project`] - Project structure and dependency management
+//! - [`project_table`] - Flat, sortable and grouped table of projects
+//! - [`query`] - Filter query language (`outdated:>3 dep:tokio -git:dirty`)
+//! - [`registry`] - Registry client with retries and global rate limiting
+//! - [`roots`] - Scan roots from the command line or named collections
 //! - [`runner`] - Task execution and update checking
+//! - [`scan`] - Streaming, parallel project discovery
+//! - [`session`] - Session restore: expanded directories, selection, tabs and focus
+//! - [`sweep`] - Target directory breakdowns and sweeping stale build artifacts
+//! - [`tags`] - Project tags from manifests, config rules and the UI
+//! - [`target_dir`] - Target directory resolution following cargo's config precedence
+//! - [`toolchains`] - Toolchain matrices and MSRV checks across rustup toolchains
 //! - [`tree`] - Hierarchical project tree structure with lazy loading
 //! - [`ui`] - Terminal UI rendering
+//! - [`watcher`] - Filesystem watching for manifest and lockfile changes
+//! - [`workspace`] - Cargo workspace membership resolution
 
 pub mod app;
 pub mod cache;
 pub mod components;
 pub mod config;
+pub mod details;
+pub mod disk_usage;
 pub mod events;
+pub mod features;
 pub mod handlers;
+pub mod index;
+pub mod metadata;
+pub mod mouse;
+pub mod policy;
 pub mod project;
+pub mod project_table;
+pub mod query;
+pub mod registry;
+pub mod roots;
 pub mod runner;
+pub mod scan;
+pub mod session;
 pub mod settings;
+pub mod sweep;
+pub mod tags;
+pub mod target_dir;
+#[cfg(test)]
+mod test_support;
+pub mod toolchains;
 pub mod tree;
 pub mod ui;
+pub mod watcher;
+pub mod workspace;
 
 pub use clap::Parser;
 
@@ -62,11 +102,24 @@ pub use clap::Parser;
 ///
 /// # Fields
 ///
-/// * `target_directory` - The directory to scan for Rust projects (defaults to current directory)
+/// * `target_directories` - The directories to scan for Rust projects (defaults to current directory)
+/// * `collection` - A named collection of directories from the config file, instead of directories
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
-    /// Target directory to scan for Rust projects
+    /// Target directories to scan for Rust projects
     #[arg(default_value = ".")]
-    pub target_directory: String,
+    pub target_directories: Vec<String>,
+
+    /// Scan a collection of directories defined under `[collections]` in the config
+    #[arg(long, short, conflicts_with = "target_directories")]
+    pub collection: Option<String>,
+
+    /// Run offline: serve dependency versions from the cache only
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Discover projects with `cargo metadata` instead of parsing manifests
+    #[arg(long)]
+    pub cargo_metadata: bool,
 }
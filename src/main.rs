@@ -1,20 +1,36 @@
 use carwash::Args;
-use carwash::app::{AppState, reducer};
+use carwash::app::{AppState, OfflineReason, reducer};
 use carwash::cache::UpdateCache;
 use carwash::components::{
-    Component, dependencies::DependenciesPane, help::Help, output::TabbedOutputPane,
-    palette::CommandPalette, projects::ProjectList, settings::SettingsModal, text_input::TextInput,
-    updater::UpdateWizard,
+    Component, dependencies::DependenciesPane, dependency_detail::DependencyDetail,
+    disk_usage::DiskUsageView, features::FeatureExplorer, help::Help, output::TabbedOutputPane,
+    palette::CommandPalette, project_details::ProjectDetailsPane, projects::ProjectList,
+    reclaim::ReclaimView, reclaim::reclaim_entries, settings::SettingsModal, text_input::TextInput,
+    toolchains::ToolchainMatrixView, updater::UpdateWizard,
 };
+use carwash::disk_usage::list_usage;
 use carwash::events::{Action, Command, Focus, Mode};
-use carwash::project::{ProjectCheckStatus, find_rust_projects};
-use carwash::runner::{check_dependencies_with_cache, check_for_updates, run_command};
+use carwash::features::FeatureMatrix;
+use carwash::index::{IndexDiff, ProjectIndex};
+use carwash::metadata::apply_cargo_metadata;
+use carwash::mouse::handle_mouse_event;
+use carwash::project::{Project, ProjectCheckStatus, ProjectId, calculate_target_dir_size};
+use carwash::roots::ScanRoots;
+use carwash::runner::{
+    check_dependencies_with_cache, check_for_updates, run_command, run_feature_matrix,
+    run_toolchain_row, run_updates,
+};
+use carwash::scan::{scan_rust_projects, scan_with_index};
+use carwash::settings::DiscoveryBackend;
+use carwash::sweep::{SweepPlan, TargetBreakdown, installed_rustc_versions};
+use carwash::toolchains::{ToolchainMatrix, installed_toolchains};
 use carwash::tree::TreeNode;
 use carwash::ui::ui;
+use carwash::watcher::{ProjectWatcher, classify_changes};
 
 use clap::Parser;
 use crossterm::{
-    event::{Event, KeyCode, KeyModifiers},
+    event::{DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -23,7 +39,11 @@ use ratatui::{
     Terminal,
     backend::{Backend, CrosstermBackend},
 };
+use rayon::prelude::*;
+use std::collections::HashSet;
 use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
 use tokio::sync::mpsc;
 
 use anyhow::Context;
@@ -52,13 +72,32 @@ async fn main() -> anyhow::Result<()> {
         anyhow::bail!("CarWash requires an interactive terminal (TTY).");
     }
 
+    let mut state = AppState::new();
+    if args.offline {
+        state.offline = Some(OfflineReason::Flag);
+    }
+    if args.cargo_metadata {
+        state.discovery = DiscoveryBackend::CargoMetadata;
+    }
+    let roots = match &args.collection {
+        Some(name) => {
+            let Some(patterns) = state.config.collections.get(name) else {
+                anyhow::bail!(
+                    "Unknown collection {:?}; define it under [collections] in the config file",
+                    name
+                );
+            };
+            ScanRoots::from_collection(name, patterns)
+        }
+        None => ScanRoots::from_dirs(&args.target_directories),
+    };
+
     let mut terminal = setup_terminal().context("Failed to set up terminal")?;
 
     // Clear screen immediately to prevent any error messages from showing
     let _ = terminal.clear();
 
-    let mut state = AppState::new();
-    let res = run_app(&mut terminal, &mut state, args.target_directory).await;
+    let res = run_app(&mut terminal, &mut state, roots).await;
 
     restore_terminal().context("Failed to restore terminal")?;
 
@@ -73,165 +112,388 @@ async fn main() -> anyhow::Result<()> {
 fn setup_terminal() -> anyhow::Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen).context("Failed to enter alternate screen")?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+        .context("Failed to enter alternate screen")?;
     let backend = CrosstermBackend::new(stdout);
     Terminal::new(backend).context("Failed to create terminal")
 }
 
 fn restore_terminal() -> anyhow::Result<()> {
     disable_raw_mode().context("Failed to disable raw mode")?;
-    execute!(io::stdout(), LeaveAlternateScreen).context("Failed to leave alternate screen")?;
+    execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen)
+        .context("Failed to leave alternate screen")?;
     Ok(())
 }
 
 /// Save current dependency check progress to persistent cache
 fn save_cache_progress(state: &AppState) {
-    use std::collections::HashMap;
+    use carwash::cache::{CachedDependency, CrateCache};
+    use carwash::project::DependencyCheckStatus;
 
     let cache = UpdateCache::new();
+    let mut crates = CrateCache::default();
 
     // Use all_projects, not filtered state.projects, so we cache ALL projects including those without dependencies
     for project in &state.all_projects {
-        // Compute Cargo.lock hash if it exists
-        let lock_path = project.path.join("Cargo.lock");
-        if let Some(lock_hash) = UpdateCache::hash_cargo_lock(&lock_path) {
-            // Build cache data from current dependencies
-            let mut cached_deps = HashMap::new();
-            for dep in &project.dependencies {
-                // CRITICAL: Only save dependencies that have been CHECKED
-                // If latest_version is None, the dep was never checked, so don't cache it
-                if dep.latest_version.is_some() {
-                    cached_deps.insert(
-                        dep.name.clone(),
-                        carwash::cache::CachedDependency {
-                            latest_version: dep.latest_version.clone(),
-                            cached_at: dep.last_checked.unwrap_or_else(std::time::SystemTime::now),
-                        },
-                    );
-                }
+        let mut has_checked = false;
+        for dep in &project.dependencies {
+            // CRITICAL: Only save dependencies that have been CHECKED
+            // If latest_version is None, the dep was never checked, so don't cache it
+            // Failed lookups may carry stale versions, so they aren't cached either
+            if dep.check_status == DependencyCheckStatus::Checked && dep.latest_version.is_some() {
+                has_checked = true;
+                crates.insert(
+                    state.registry.cache_key(),
+                    dep.name.clone(),
+                    CachedDependency {
+                        latest_version: dep.latest_version.clone(),
+                        versions: dep.published_versions.clone(),
+                        cached_at: dep.last_checked.unwrap_or_else(std::time::SystemTime::now),
+                    },
+                );
             }
+        }
 
-            // Save to cache (skip if no checked dependencies)
-            if !cached_deps.is_empty() {
-                let _ = cache.save(&project.path, lock_hash, cached_deps.clone());
+        // Remember which Cargo.lock these results apply to
+        if has_checked {
+            if let Some(lock_hash) = UpdateCache::hash_cargo_lock(&project.lockfile_path()) {
+                let _ = cache.record_lock(&project.id, lock_hash);
             }
         }
     }
+
+    // Save to cache (skip if no checked dependencies)
+    if !crates.is_empty() {
+        let _ = cache.save_crates(crates);
+    }
 }
 
-/// Load dependency check progress from persistent cache
-fn load_cache_progress(projects: &mut [carwash::project::Project]) {
-    use carwash::project::{DependencyCheckStatus, Project};
+/// Probes the registry, with a growing delay, while it was detected to be unreachable
+struct ReconnectProbe {
+    next: Option<std::time::Instant>,
+    backoff: std::time::Duration,
+}
 
-    let cache = UpdateCache::new();
+impl ReconnectProbe {
+    const MIN_BACKOFF: std::time::Duration = std::time::Duration::from_secs(15);
+    const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(300);
 
-    for project in projects.iter_mut() {
-        // Compute Cargo.lock hash
-        let lock_path = project.path.join("Cargo.lock");
-        if let Some(lock_hash) = UpdateCache::hash_cargo_lock(&lock_path) {
-            // Try to load cached data
-            if let Some(cached_deps) = cache.load(&project.path, lock_hash) {
-                // Apply cached data to dependencies
-                for dep in &mut project.dependencies {
-                    if let Some(cached_dep) = cached_deps.get(&dep.name) {
-                        // Update with cached version info AND set the timestamp
-                        dep.latest_version = cached_dep.latest_version.clone();
-                        dep.check_status = DependencyCheckStatus::Checked;
-                        // Set last_checked to the cache timestamp so checks respect cache duration
-                        dep.last_checked = Some(cached_dep.cached_at);
-                    }
+    /// Start a probe when one is due; a registry that answers sends `RegistryReachable`
+    fn poll(&mut self, state: &AppState, action_tx: &mpsc::Sender<Action>) {
+        if state.offline != Some(OfflineReason::Detected) {
+            *self = Self::default();
+            return;
+        }
+
+        let now = std::time::Instant::now();
+        let due = *self.next.get_or_insert(now + self.backoff);
+        if now < due {
+            return;
+        }
+        self.backoff = (self.backoff * 2).min(Self::MAX_BACKOFF);
+        self.next = Some(now + self.backoff);
+
+        let registry = state.registry.clone();
+        let tx = action_tx.clone();
+        tokio::spawn(async move {
+            if registry.probe().await {
+                let _ = tx.send(Action::RegistryReachable).await;
+            }
+        });
+    }
+}
+
+impl Default for ReconnectProbe {
+    fn default() -> Self {
+        Self {
+            next: None,
+            backoff: Self::MIN_BACKOFF,
+        }
+    }
+}
+
+/// Save the session for the current roots, when session restore is enabled
+fn save_session(state: &AppState) {
+    if !state.settings.restore_session || state.tree_root.is_none() {
+        return;
+    }
+    let session = carwash::session::Session::capture(state);
+    if let Err(e) = UpdateCache::new().save_session(&session) {
+        tracing::warn!("Failed to save session: {}", e);
+    }
+}
+
+/// Re-read a project's Cargo.toml and Cargo.lock, returning whether the lockfile changed
+///
+/// Blocking (file reads, and `cargo metadata` with that backend): call it off the UI loop.
+fn reload_project(project: &mut Project, discovery: DiscoveryBackend) -> Result<bool, String> {
+    project.reload_dependencies()?;
+    if discovery == DiscoveryBackend::CargoMetadata {
+        apply_cargo_metadata(std::slice::from_mut(project));
+    }
+
+    let lock_hash = UpdateCache::hash_cargo_lock(&project.lockfile_path());
+    let lock_changed = lock_hash != project.cargo_lock_hash;
+    project.cargo_lock_hash = lock_hash;
+    project.check_status =
+        Project::compute_check_status_from_deps(&project.dependencies, &project.update_policy);
+    Ok(lock_changed)
+}
+
+/// Re-read projects whose Cargo.toml or Cargo.lock changed on disk
+///
+/// Runs in the background and delivers the result as a `ProjectsChanged` diff.
+/// Check results already known are kept. A project whose Cargo.lock hash
+/// changed is queued for a fresh check, since cached results no longer apply.
+fn reload_changed_projects(state: &AppState, ids: &[ProjectId], action_tx: &mpsc::Sender<Action>) {
+    let projects: Vec<Project> = state
+        .all_projects
+        .iter()
+        .filter(|p| ids.contains(&p.id))
+        .cloned()
+        .collect();
+    if projects.is_empty() {
+        return;
+    }
+
+    let recheck = state.settings.background_updates_enabled && !state.is_offline();
+    let discovery = state.discovery;
+    let tx = action_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut changed = Vec::new();
+        let mut lock_changed = Vec::new();
+        for mut project in projects {
+            match reload_project(&mut project, discovery) {
+                Ok(true) => lock_changed.push(project.id.clone()),
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!("Failed to reload {}: {}", project.path.display(), e);
+                    continue;
                 }
+            }
+            changed.push(project);
+        }
 
-                // Calculate project check status from cached data
-                project.check_status =
-                    Project::compute_check_status_from_deps(&project.dependencies);
+        let _ = tx.blocking_send(Action::ProjectsChanged(IndexDiff {
+            changed,
+            removed: Vec::new(),
+        }));
+        if recheck {
+            for id in lock_changed {
+                let _ = tx.blocking_send(Action::QueueBackgroundUpdate(id, false));
             }
         }
+    });
+}
+
+/// Bring projects and the tree in line with manifest and lockfile changes on disk
+async fn handle_project_files_changed(
+    state: &mut AppState,
+    dirs: &[std::path::PathBuf],
+    watcher: Option<&mut ProjectWatcher>,
+    action_tx: &mpsc::Sender<Action>,
+) {
+    // New directories are watched so their manifests are seen once written
+    if let Some(watcher) = watcher {
+        for dir in dirs {
+            watcher.watch_dir(dir);
+        }
+    }
+
+    let changes = classify_changes(dirs, &state.all_projects);
+    if changes.is_empty() {
+        return;
+    }
+    reload_changed_projects(state, &changes.reload, action_tx);
+
+    // Tree listings around added and removed projects need a refresh
+    let mut tree_dirs: Vec<std::path::PathBuf> = state
+        .all_projects
+        .iter()
+        .filter(|p| changes.removed.contains(&p.id))
+        .filter_map(|p| p.path.parent().map(|dir| dir.to_path_buf()))
+        .collect();
+    tree_dirs.extend(
+        changes
+            .new_dirs
+            .iter()
+            .filter_map(|dir| dir.parent().map(|parent| parent.to_path_buf())),
+    );
+    tree_dirs.sort();
+    tree_dirs.dedup();
+
+    if !changes.removed.is_empty() {
+        reducer(
+            state,
+            Action::ProjectsChanged(IndexDiff {
+                changed: Vec::new(),
+                removed: changes.removed,
+            }),
+        );
+    }
+
+    if !changes.new_dirs.is_empty() {
+        let tx = action_tx.clone();
+        let scan_config = state.config.scan.clone();
+        let discovery = state.discovery;
+        let known: HashSet<ProjectId> = state.all_projects.iter().map(|p| p.id.clone()).collect();
+        let registry_key = state.registry.cache_key().to_string();
+        let new_dirs = changes.new_dirs;
+        tokio::task::spawn_blocking(move || {
+            let mut added: Vec<Project> = Vec::new();
+            for dir in new_dirs {
+                let found = scan_rust_projects(&dir.to_string_lossy(), &scan_config, |_, _| {});
+                added.extend(found.into_iter().filter(|p| !known.contains(&p.id)));
+            }
+            if discovery == DiscoveryBackend::CargoMetadata {
+                apply_cargo_metadata(&mut added);
+            }
+            load_cache_progress(&mut added, &registry_key);
+            let _ = tx.blocking_send(Action::ProjectsChanged(IndexDiff {
+                changed: added,
+                removed: Vec::new(),
+            }));
+        });
+    }
+
+    for dir in tree_dirs {
+        let depth = state
+            .tree_root
+            .as_ref()
+            .and_then(|root| root.find(&dir))
+            .filter(|node| node.children_loaded)
+            .map(|node| node.depth);
+        if let Some(depth) = depth {
+            let _ = action_tx.send(Action::ExpandDirectory(dir, depth)).await;
+        }
     }
 }
 
+/// Load dependency check progress from persistent cache, for the registry under `registry_key`
+fn load_cache_progress(projects: &mut [carwash::project::Project], registry_key: &str) {
+    UpdateCache::new().apply_to_projects(projects, registry_key);
+}
+
 async fn handle_event(
     event: Event,
     state: &mut AppState,
     action_tx: &mpsc::Sender<Action>,
 ) -> io::Result<()> {
-    if let Event::Key(key) = event {
-        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
-            reducer(state, Action::Quit);
-        }
-
-        let action: Option<Action> = match state.mode {
-            Mode::Loading => {
-                // Allow quitting even while loading
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
-                    _ => None,
-                }
+    let action: Option<Action> = match event {
+        Event::Key(key) => {
+            if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+                reducer(state, Action::Quit);
             }
-            Mode::Normal => {
-                // Handle normal mode keys without interfering with workspace navigation
-                match key.code {
-                    KeyCode::Tab => {
-                        // Tab always cycles focus between panes
-                        // Output pane uses h/l or Left/Right for tab switching
-                        Some(Action::FocusNext)
-                    }
-                    KeyCode::BackTab => {
-                        // Shift+Tab: cycle focus backwards
-                        // Output -> Dependencies -> Projects -> Output
-                        state.focus = match state.focus {
-                            Focus::Projects => Focus::Output,
-                            Focus::Dependencies => Focus::Projects,
-                            Focus::Output => Focus::Dependencies,
-                        };
-                        None
+
+            match state.mode {
+                Mode::Loading => {
+                    // Allow quitting even while loading
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+                        _ => None,
                     }
-                    KeyCode::Char('q') => Some(Action::Quit),
-                    KeyCode::Char('?') => Some(Action::ShowHelp),
-                    KeyCode::Char('s') | KeyCode::Char('S') => Some(Action::ShowSettings),
-                    KeyCode::Char('t') | KeyCode::Char('T') => Some(Action::CycleTheme),
-                    KeyCode::Char('a') | KeyCode::Char('A') => Some(Action::ToggleShowAllFolders),
-                    KeyCode::Char(':') => Some(Action::ShowCommandPalette),
-                    KeyCode::Char('/') => Some(Action::EnterFilterMode),
-                    KeyCode::Char('u') => Some(Action::StartUpdateWizard),
-                    // Ctrl+[ and Ctrl+] for output tab navigation (works regardless of focus)
-                    KeyCode::Char('[') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        // Previous tab
-                        if state.active_tab > 0 {
-                            Some(Action::SwitchToTab(state.active_tab - 1))
-                        } else if !state.tabs.is_empty() {
-                            // Wrap around to last tab
-                            Some(Action::SwitchToTab(state.tabs.len() - 1))
-                        } else {
-                            None
+                }
+                Mode::Normal => {
+                    // Handle normal mode keys without interfering with workspace navigation
+                    match key.code {
+                        KeyCode::Tab => {
+                            // Tab always cycles focus between panes
+                            // Output pane uses h/l or Left/Right for tab switching
+                            Some(Action::FocusNext)
                         }
-                    }
-                    KeyCode::Char(']') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        // Next tab
-                        if state.active_tab < state.tabs.len().saturating_sub(1) {
-                            Some(Action::SwitchToTab(state.active_tab + 1))
-                        } else if !state.tabs.is_empty() {
-                            // Wrap around to first tab
-                            Some(Action::SwitchToTab(0))
-                        } else {
+                        KeyCode::BackTab => {
+                            // Shift+Tab: cycle focus backwards
+                            // Output -> Dependencies -> Projects -> Output
+                            state.focus = match state.focus {
+                                Focus::Projects => Focus::Output,
+                                Focus::Dependencies => Focus::Projects,
+                                Focus::Output => Focus::Dependencies,
+                            };
                             None
                         }
-                    }
-                    // Layout adjustment controls
-                    KeyCode::Char('{') | KeyCode::Char('[') => Some(Action::DecreaseLeftPane),
-                    KeyCode::Char('}') | KeyCode::Char(']') => Some(Action::IncreaseLeftPane),
-                    KeyCode::Char('(') | KeyCode::Char('-') => Some(Action::IncreaseTopRight),
-                    KeyCode::Char(')') | KeyCode::Char('+') => Some(Action::DecreaseTopRight),
-                    KeyCode::Char('r') | KeyCode::Char('R') => {
-                        if key.modifiers.contains(KeyModifiers::SHIFT) {
-                            Some(Action::ResetLayout)
-                        } else {
+                        KeyCode::Char('q') => Some(Action::Quit),
+                        KeyCode::Char('?') => Some(Action::ShowHelp),
+                        KeyCode::Char('s') | KeyCode::Char('S') => Some(Action::ShowSettings),
+                        KeyCode::Char('t') | KeyCode::Char('T') => Some(Action::CycleTheme),
+                        KeyCode::Char('a') | KeyCode::Char('A') => {
+                            Some(Action::ToggleShowAllFolders)
+                        }
+                        KeyCode::Char('g') if state.settings.table_view.enabled => {
+                            Some(Action::CycleTableGrouping)
+                        }
+                        KeyCode::Char('g') => Some(Action::ToggleGroupByTag),
+                        KeyCode::Char('v') => Some(Action::ToggleProjectTable),
+                        KeyCode::Char('o') => Some(Action::CycleTableSort),
+                        KeyCode::Char('O') => Some(Action::ReverseTableSort),
+                        KeyCode::Char('#') => Some(Action::EditProjectTags),
+                        KeyCode::Char('i') => Some(Action::ToggleProjectDetails),
+                        KeyCode::Char('f') => Some(Action::ShowFeatureExplorer),
+                        KeyCode::Char('m') => Some(Action::ShowToolchainMatrix),
+                        KeyCode::Char('d') => Some(Action::ShowReclaim),
+                        KeyCode::Char('D') => Some(Action::ShowDiskUsage),
+                        KeyCode::Char(':') => Some(Action::ShowCommandPalette),
+                        KeyCode::Char('/') => Some(Action::EnterFilterMode),
+                        KeyCode::Char('u') => Some(Action::StartUpdateWizard),
+                        // Ctrl+[ and Ctrl+] for output tab navigation (works regardless of focus)
+                        KeyCode::Char('[') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Previous tab
+                            if state.active_tab > 0 {
+                                Some(Action::SwitchToTab(state.active_tab - 1))
+                            } else if !state.tabs.is_empty() {
+                                // Wrap around to last tab
+                                Some(Action::SwitchToTab(state.tabs.len() - 1))
+                            } else {
+                                None
+                            }
+                        }
+                        KeyCode::Char(']') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Next tab
+                            if state.active_tab < state.tabs.len().saturating_sub(1) {
+                                Some(Action::SwitchToTab(state.active_tab + 1))
+                            } else if !state.tabs.is_empty() {
+                                // Wrap around to first tab
+                                Some(Action::SwitchToTab(0))
+                            } else {
+                                None
+                            }
+                        }
+                        // Layout adjustment controls
+                        KeyCode::Char('{') | KeyCode::Char('[') => Some(Action::DecreaseLeftPane),
+                        KeyCode::Char('}') | KeyCode::Char(']') => Some(Action::IncreaseLeftPane),
+                        KeyCode::Char('(') | KeyCode::Char('-') => Some(Action::IncreaseTopRight),
+                        KeyCode::Char(')') | KeyCode::Char('+') => Some(Action::DecreaseTopRight),
+                        KeyCode::Char('r') | KeyCode::Char('R') => {
+                            if key.modifiers.contains(KeyModifiers::SHIFT) {
+                                Some(Action::ResetLayout)
+                            } else {
+                                // Dispatch to focused component
+                                match state.focus {
+                                    Focus::Projects => {
+                                        let mut project_list = ProjectList::new();
+                                        project_list.handle_key_events(key.code, state)
+                                    }
+                                    Focus::Dependencies => {
+                                        let mut deps = DependenciesPane::new();
+                                        deps.handle_key_events(key.code, state)
+                                    }
+                                    Focus::Output => {
+                                        let mut output = TabbedOutputPane::new();
+                                        output.handle_key_events(key.code, state)
+                                    }
+                                }
+                            }
+                        }
+                        _ => {
                             // Dispatch to focused component
                             match state.focus {
                                 Focus::Projects => {
                                     let mut project_list = ProjectList::new();
                                     project_list.handle_key_events(key.code, state)
                                 }
+                                Focus::Dependencies if state.details.visible => {
+                                    let mut details = ProjectDetailsPane::new();
+                                    details.handle_key_events(key.code, state)
+                                }
                                 Focus::Dependencies => {
                                     let mut deps = DependenciesPane::new();
                                     deps.handle_key_events(key.code, state)
@@ -243,99 +505,105 @@ async fn handle_event(
                             }
                         }
                     }
-                    _ => {
-                        // Dispatch to focused component
-                        match state.focus {
-                            Focus::Projects => {
-                                let mut project_list = ProjectList::new();
-                                project_list.handle_key_events(key.code, state)
-                            }
-                            Focus::Dependencies => {
-                                let mut deps = DependenciesPane::new();
-                                deps.handle_key_events(key.code, state)
-                            }
-                            Focus::Output => {
-                                let mut output = TabbedOutputPane::new();
-                                output.handle_key_events(key.code, state)
-                            }
+                }
+                Mode::CommandPalette => {
+                    let mut palette = CommandPalette::new();
+                    palette.handle_key_events(key.code, state)
+                }
+                Mode::UpdateWizard => {
+                    let mut updater = UpdateWizard::new();
+                    updater.handle_key_events(key.code, state)
+                }
+                Mode::TextInput => {
+                    let mut text_input = TextInput::new();
+                    text_input.handle_key_events(key.code, state)
+                }
+                Mode::Help => {
+                    let mut help = Help::new();
+                    help.handle_key_events(key.code, state)
+                }
+                Mode::Settings => {
+                    let mut settings = SettingsModal::new();
+                    settings.handle_key_events(key.code, state)
+                }
+                Mode::DependencyDetail => {
+                    let mut detail = DependencyDetail::new();
+                    detail.handle_key_events(key.code, state)
+                }
+                Mode::FeatureExplorer => {
+                    let mut features = FeatureExplorer::new();
+                    features.handle_key_events(key.code, state)
+                }
+                Mode::Reclaim => {
+                    let mut reclaim = ReclaimView::new();
+                    reclaim.handle_key_events(key.code, state)
+                }
+                Mode::DiskUsage => {
+                    let mut disk_usage = DiskUsageView::new();
+                    disk_usage.handle_key_events(key.code, state)
+                }
+                Mode::ToolchainMatrix => {
+                    let mut matrix = ToolchainMatrixView::new();
+                    matrix.handle_key_events(key.code, state)
+                }
+                Mode::Filter => {
+                    // Handle filter mode keys
+                    match key.code {
+                        KeyCode::Esc => Some(Action::ExitFilterMode),
+                        KeyCode::Enter => Some(Action::ExitFilterMode),
+                        KeyCode::Tab => Some(Action::SelectFilterMatches),
+                        KeyCode::Char(c) => Some(Action::UpdateFilterInput(
+                            state.filter.input.clone() + &c.to_string(),
+                        )),
+                        KeyCode::Backspace => {
+                            let mut input = state.filter.input.clone();
+                            input.pop();
+                            Some(Action::UpdateFilterInput(input))
                         }
+                        KeyCode::Up => {
+                            state.filter.select_previous();
+                            None
+                        }
+                        KeyCode::Down => {
+                            state.filter.select_next();
+                            None
+                        }
+                        _ => None,
                     }
                 }
             }
-            Mode::CommandPalette => {
-                let mut palette = CommandPalette::new();
-                palette.handle_key_events(key.code, state)
-            }
-            Mode::UpdateWizard => {
-                let mut updater = UpdateWizard::new();
-                updater.handle_key_events(key.code, state)
-            }
-            Mode::TextInput => {
-                let mut text_input = TextInput::new();
-                text_input.handle_key_events(key.code, state)
-            }
-            Mode::Help => {
-                let mut help = Help::new();
-                help.handle_key_events(key.code, state)
-            }
-            Mode::Settings => {
-                let mut settings = SettingsModal::new();
-                settings.handle_key_events(key.code, state)
-            }
-            Mode::Filter => {
-                // Handle filter mode keys
-                match key.code {
-                    KeyCode::Esc => Some(Action::ExitFilterMode),
-                    KeyCode::Enter => Some(Action::ExitFilterMode),
-                    KeyCode::Char(c) => Some(Action::UpdateFilterInput(
-                        state.filter.input.clone() + &c.to_string(),
-                    )),
-                    KeyCode::Backspace => {
-                        let mut input = state.filter.input.clone();
-                        input.pop();
-                        Some(Action::UpdateFilterInput(input))
-                    }
-                    KeyCode::Up => {
-                        state.filter.select_previous();
-                        None
-                    }
-                    KeyCode::Down => {
-                        state.filter.select_next();
-                        None
-                    }
-                    _ => None,
-                }
+        }
+        Event::Mouse(mouse) => handle_mouse_event(mouse, state),
+        _ => None,
+    };
+
+    if let Some(action) = action {
+        // Some actions need to be sent through the action channel for async processing
+        match &action {
+            Action::ExecuteCommand(_)
+            | Action::StartUpdateWizard
+            | Action::RunUpdate
+            | Action::ProcessBackgroundUpdateQueue
+            | Action::UpdateDependencies(..)
+            | Action::UpdateSingleDependency(..)
+            | Action::UpdateDependenciesStreamStart(_) => {
+                // Send through channel for async handling
+                let _ = action_tx.send(action).await;
             }
-        };
-
-        if let Some(action) = action {
-            // Some actions need to be sent through the action channel for async processing
-            match &action {
-                Action::ExecuteCommand(_)
-                | Action::StartUpdateWizard
-                | Action::RunUpdate
-                | Action::ProcessBackgroundUpdateQueue
-                | Action::UpdateDependencies(..)
-                | Action::UpdateSingleDependency(..)
-                | Action::UpdateDependenciesStreamStart(_) => {
-                    // Send through channel for async handling
+            Action::FinishCommand(_) => {
+                // Handle sync first, then check if we need async reload
+                reducer(state, action.clone());
+                // Only route to async if there's a pending update reload
+                if state.updater.pending_reload_project.is_some() {
                     let _ = action_tx.send(action).await;
                 }
-                Action::FinishCommand(_) => {
-                    // Handle sync first, then check if we need async reload
-                    reducer(state, action.clone());
-                    // Only route to async if there's a pending update reload
-                    if state.updater.pending_reload_project.is_some() {
-                        let _ = action_tx.send(action).await;
-                    }
-                }
-                _ => {
-                    // Handle synchronously through reducer
-                    reducer(state, action.clone());
-                    // Save progress if quitting
-                    if matches!(action, Action::Quit) {
-                        save_cache_progress(state);
-                    }
+            }
+            _ => {
+                // Handle synchronously through reducer
+                reducer(state, action.clone());
+                // Save progress if quitting
+                if matches!(action, Action::Quit) {
+                    save_cache_progress(state);
                 }
             }
         }
@@ -356,10 +624,311 @@ fn reset_checking_status(state: &mut AppState) {
     }
 }
 
+/// Show the tree for the current scan roots and scan them for projects in the background
+fn start_scan(state: &AppState, action_tx: &mpsc::Sender<Action>) {
+    // Trigger initial shallow scan immediately
+    let init_tx = action_tx.clone();
+    let init_roots = state.scan_roots.clone();
+    tokio::spawn(async move {
+        let _ = init_tx.send(Action::InitializeTree(init_roots)).await;
+    });
+
+    // Spawn deep scan in background for search index (fire and forget)
+    let action_tx_clone = action_tx.clone();
+    let scan_roots = state.scan_roots.clone();
+    let discovery = state.discovery;
+    let scan_config = state.config.scan.clone();
+    let registry_key = state.registry.cache_key().to_string();
+    tokio::spawn(async move {
+        let roots = scan_roots.paths.clone();
+        let cache = UpdateCache::new();
+
+        // Show the projects indexed by the last scan right away, then revalidate them
+        let index = cache
+            .load_index(&roots)
+            .filter(|index| index.discovery == discovery);
+        if let Some(index) = &index {
+            let _ = action_tx_clone
+                .send(Action::FinishProjectScan(
+                    index.to_projects(),
+                    scan_roots.clone(),
+                ))
+                .await;
+        }
+
+        // This can take a while, but it won't block the UI: projects are
+        // streamed in batches as the walk finds them
+        let batch_tx = action_tx_clone.clone();
+        if let Ok(action) = tokio::task::spawn_blocking(move || {
+            let mut projects =
+                scan_with_index(&roots, &scan_config, index.as_ref(), |batch, progress| {
+                    let _ = batch_tx.blocking_send(Action::ProjectsDiscovered(batch, progress));
+                });
+            if discovery == DiscoveryBackend::CargoMetadata {
+                apply_cargo_metadata(&mut projects);
+            }
+
+            let fresh = ProjectIndex::build(&roots, discovery, &projects);
+            if let Err(e) = cache.save_index(&fresh) {
+                tracing::warn!("Failed to save project index: {}", e);
+            }
+
+            match index {
+                Some(index) => {
+                    let mut diff = index.diff(&fresh, &projects);
+                    load_cache_progress(&mut diff.changed, &registry_key);
+                    Action::ProjectsChanged(diff)
+                }
+                None => Action::FinishProjectScan(projects, scan_roots),
+            }
+        })
+        .await
+        {
+            let _ = action_tx_clone.send(action).await;
+        }
+    });
+}
+
+/// Whether a scan result belongs to the roots currently shown
+///
+/// Scans started before switching collections may still report back; their
+/// results are dropped.
+fn is_current_scan(state: &AppState, action: &Action) -> bool {
+    match action {
+        Action::FinishProjectScan(_, roots) => roots == &state.scan_roots,
+        Action::ProjectsDiscovered(projects, _) => projects
+            .first()
+            .is_none_or(|p| state.scan_roots.contains(&p.path)),
+        Action::ProjectsChanged(diff) => diff
+            .changed
+            .first()
+            .map(|p| p.path.as_path())
+            .or_else(|| diff.removed.first().map(|id| id.manifest_path()))
+            .is_none_or(|path| state.scan_roots.contains(path)),
+        _ => true,
+    }
+}
+
+/// Set up a feature matrix for the explored project and run it in the background
+///
+/// Nothing starts while a previous matrix is still running.
+fn start_feature_matrix(state: &mut AppState, action_tx: &mpsc::Sender<Action>) {
+    let already_running = state
+        .features
+        .matrix
+        .as_ref()
+        .is_some_and(FeatureMatrix::is_running);
+    reducer(state, Action::StartFeatureMatrix);
+    if already_running {
+        return;
+    }
+    let Some(matrix) = state
+        .features
+        .matrix
+        .clone()
+        .filter(FeatureMatrix::is_running)
+    else {
+        return;
+    };
+    let Some(project) = state
+        .all_projects
+        .iter()
+        .find(|p| p.id == matrix.project)
+        .cloned()
+    else {
+        return;
+    };
+    tokio::spawn(run_feature_matrix(project, matrix, action_tx.clone()));
+}
+
+/// List the installed toolchains off the UI loop, then start the matrix with them
+fn list_toolchains(state: &mut AppState, command: &str, action_tx: &mpsc::Sender<Action>) {
+    reducer(state, Action::EnterNormalMode);
+    let command = command.to_string();
+    let tx = action_tx.clone();
+    tokio::spawn(async move {
+        let installed = tokio::task::spawn_blocking(installed_toolchains)
+            .await
+            .unwrap_or_default();
+        let _ = tx
+            .send(Action::StartToolchainMatrix(command, installed))
+            .await;
+    });
+}
+
+fn start_toolchain_matrix(state: &mut AppState, action: Action, action_tx: &mpsc::Sender<Action>) {
+    let already_running = state
+        .toolchains
+        .matrix
+        .as_ref()
+        .is_some_and(ToolchainMatrix::is_running);
+    reducer(state, action);
+    if already_running {
+        return;
+    }
+    let Some(matrix) = state.toolchains.matrix.clone() else {
+        return;
+    };
+    // Projects run side by side, each going through its toolchains in turn
+    for row in matrix.rows {
+        let Some(project) = state
+            .all_projects
+            .iter()
+            .find(|p| p.id == row.project)
+            .cloned()
+        else {
+            continue;
+        };
+        tokio::spawn(run_toolchain_row(
+            project,
+            row,
+            matrix.columns.clone(),
+            matrix.command.clone(),
+            action_tx.clone(),
+        ));
+    }
+}
+
+/// Measure the selected target directory for the breakdown, unless already known
+fn load_selected_breakdown(state: &mut AppState, action_tx: &mpsc::Sender<Action>) {
+    let entries = reclaim_entries(state);
+    let Some(dir) = state.reclaim.selected_dir(&entries) else {
+        return;
+    };
+    if state.reclaim.breakdowns.contains_key(&dir) || !state.reclaim.loading.insert(dir.clone()) {
+        return;
+    }
+    let tx = action_tx.clone();
+    tokio::spawn(async move {
+        let measured = dir.clone();
+        let breakdown = tokio::task::spawn_blocking(move || TargetBreakdown::load(&measured))
+            .await
+            .unwrap_or_default();
+        let _ = tx.send(Action::TargetBreakdownLoaded(dir, breakdown)).await;
+    });
+}
+
+/// Check the git state and last modification of every project in the background
+fn check_project_activity(state: &AppState, action_tx: &mpsc::Sender<Action>) {
+    let projects: Vec<(ProjectId, PathBuf)> = state
+        .all_projects
+        .iter()
+        .map(|p| (p.id.clone(), p.path.clone()))
+        .collect();
+    let tx = action_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        projects.into_par_iter().for_each(|(id, path)| {
+            let git_status = Project::check_git_status(&path);
+            let modified = Project::last_modified(&path);
+            let _ = tx.blocking_send(Action::UpdateProjectActivity(id, git_status, modified));
+        });
+    });
+}
+
+/// List the directory shown in the disk usage view, unless already known
+fn load_disk_usage(state: &mut AppState, action_tx: &mpsc::Sender<Action>) {
+    let usage = &mut state.disk_usage;
+    let Some(dir) = usage.current().map(PathBuf::from) else {
+        return;
+    };
+    if usage.listings.contains_key(&dir) || !usage.loading.insert(dir.clone()) {
+        return;
+    }
+    // Entries of the project root are categorized, the target directory included
+    let target_dir = usage.at_root().then(|| usage.target_dir.clone());
+    let tx = action_tx.clone();
+    tokio::spawn(async move {
+        let listed = dir.clone();
+        let entries =
+            tokio::task::spawn_blocking(move || list_usage(&listed, target_dir.as_deref()))
+                .await
+                .unwrap_or_default();
+        let _ = tx.send(Action::DiskUsageLoaded(dir, entries)).await;
+    });
+}
+
+/// Dry-run a sweep of the selected target directory, or of all of them
+fn preview_sweep(state: &mut AppState, all: bool, action_tx: &mpsc::Sender<Action>) {
+    if state.reclaim.busy || state.reclaim.criteria.is_empty() {
+        return;
+    }
+    let entries = reclaim_entries(state);
+    let target_dirs: Vec<PathBuf> = if all {
+        entries.into_iter().map(|entry| entry.target_dir).collect()
+    } else {
+        state.reclaim.selected_dir(&entries).into_iter().collect()
+    };
+    if target_dirs.is_empty() {
+        return;
+    }
+    state.reclaim.busy = true;
+    state.reclaim.plan = None;
+
+    let criteria = state.reclaim.criteria.clone();
+    let tx = action_tx.clone();
+    tokio::spawn(async move {
+        let plan = tokio::task::spawn_blocking(move || {
+            let installed = if criteria.uninstalled_toolchains {
+                installed_rustc_versions()
+            } else {
+                Vec::new()
+            };
+            SweepPlan::new(target_dirs, criteria, &installed, SystemTime::now())
+        })
+        .await
+        .unwrap_or_default();
+        let _ = tx.send(Action::SweepPlanned(plan)).await;
+    });
+}
+
+/// Delete what the last dry run found, then measure the swept directories again
+fn execute_sweep(state: &mut AppState, action_tx: &mpsc::Sender<Action>) {
+    if state.reclaim.busy {
+        return;
+    }
+    let Some(plan) = state.reclaim.plan.take() else {
+        return;
+    };
+    if plan.entries.is_empty() {
+        return;
+    }
+    state.reclaim.busy = true;
+
+    let tx = action_tx.clone();
+    tokio::spawn(async move {
+        let dirs = plan.target_dirs.clone();
+        let (freed, errors, sizes) = tokio::task::spawn_blocking(move || {
+            let (freed, errors) = plan.execute();
+            let sizes: Vec<(PathBuf, Option<u64>)> = plan
+                .target_dirs
+                .iter()
+                .map(|dir| (dir.clone(), calculate_target_dir_size(dir)))
+                .collect();
+            (freed, errors, sizes)
+        })
+        .await
+        .unwrap_or_else(|e| (0, vec![e.to_string()], Vec::new()));
+        for (dir, size) in sizes {
+            let _ = tx.send(Action::UpdateTargetSize(dir, size)).await;
+        }
+        let _ = tx.send(Action::SweepFinished(dirs, freed, errors)).await;
+    });
+}
+
+fn create_watcher(action_tx: &mpsc::Sender<Action>) -> Option<ProjectWatcher> {
+    match ProjectWatcher::new(action_tx.clone()) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            tracing::warn!("File watching unavailable: {}", e);
+            None
+        }
+    }
+}
+
 async fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     state: &mut AppState,
-    target_directory: String,
+    roots: ScanRoots,
 ) -> io::Result<()> {
     let (action_tx, mut action_rx) = mpsc::channel(100);
     let mut event_stream = crossterm::event::EventStream::new();
@@ -371,29 +940,16 @@ async fn run_app<B: Backend>(
 
     // Track last cache save time for periodic persistence
     let mut last_cache_save = std::time::Instant::now();
+    let mut reconnect = ReconnectProbe::default();
 
-    // Trigger initial shallow scan immediately
-    let init_tx = action_tx.clone();
-    let init_target = target_directory.clone();
-    tokio::spawn(async move {
-        let _ = init_tx.send(Action::InitializeTree(init_target)).await;
-    });
-
-    // Spawn deep scan in background for search index (fire and forget)
-    let action_tx_clone = action_tx.clone();
-    let target_directory_clone = target_directory.clone();
-    tokio::spawn(async move {
-        let target_dir_for_scan = target_directory_clone.clone();
+    if state.settings.restore_session {
+        state.pending_session = UpdateCache::new().load_session(&roots.paths);
+    }
+    state.scan_roots = roots;
+    start_scan(state, &action_tx);
 
-        // This can take a while, but it won't block the UI
-        if let Ok(projects) =
-            tokio::task::spawn_blocking(move || find_rust_projects(&target_dir_for_scan)).await
-        {
-            let _ = action_tx_clone
-                .send(Action::FinishProjectScan(projects, target_directory_clone))
-                .await;
-        }
-    });
+    // Keep projects in sync with edits made outside carwash (editor, git, cargo)
+    let mut watcher = create_watcher(&action_tx);
 
     loop {
         tokio::select! {
@@ -413,24 +969,34 @@ async fn run_app<B: Backend>(
             }
             Some(action) = action_rx.recv() => {
                 match &action {
-                    Action::InitializeTree(target_dir) => {
-                        let target_dir = target_dir.clone();
+                    // Results of a scan for roots that were switched away from
+                    _ if !is_current_scan(state, &action) => {}
+                    Action::InitializeTree(roots) => {
+                        let roots = roots.paths.clone();
                         reducer(state, action);
 
-                        // Spawn async load of root directory children (non-blocking)
-                        let tx = action_tx.clone();
+                        // Spawn async load of each root's children (non-blocking)
                         let show_all = state.settings.show_all_folders;
-
-                        tokio::task::spawn_blocking(move || {
-                            // Load children of root directory (depth 1, since root is depth 0)
-                            let children = carwash::project::load_directory_children_async(
-                                std::path::Path::new(&target_dir),
-                                1,  // Root children are at depth 1
-                                show_all
-                            );
-                            let root_path = std::path::PathBuf::from(&target_dir);
-                            let _ = tx.blocking_send(Action::DirectoryLoaded(root_path, children));
-                        });
+                        // Roots are depth 0, or depth 1 when grouped under a collection
+                        let depth = if roots.len() > 1 { 2 } else { 1 };
+
+                        for root_path in roots {
+                            let tx = action_tx.clone();
+                            tokio::task::spawn_blocking(move || {
+                                let children = carwash::project::load_directory_children_async(
+                                    &root_path,
+                                    depth,
+                                    show_all
+                                );
+                                let _ = tx.blocking_send(Action::DirectoryLoaded(root_path, children));
+                            });
+                        }
+                    }
+                    Action::SwitchRoots(_) => {
+                        reducer(state, action);
+                        // Watch only the new roots; the old watcher stops when dropped
+                        watcher = create_watcher(&action_tx);
+                        start_scan(state, &action_tx);
                     }
                     Action::DirectoryLoaded(..) => {
                         reducer(state, action);
@@ -512,13 +1078,59 @@ async fn run_app<B: Backend>(
                             let _ = tx.blocking_send(Action::DirectoryLoaded(path_clone, children));
                         });
                     }
+                    Action::StartFeatureMatrix => start_feature_matrix(state, &action_tx),
+                    Action::StartToolchainMatrix(..) => {
+                        start_toolchain_matrix(state, action, &action_tx)
+                    }
+                    Action::ShowReclaim
+                    | Action::ReclaimSelectNext
+                    | Action::ReclaimSelectPrevious
+                    | Action::SweepFinished(..) => {
+                        reducer(state, action);
+                        load_selected_breakdown(state, &action_tx);
+                    }
+                    Action::ShowDiskUsage | Action::DiskUsageOpen | Action::DiskUsageUp => {
+                        reducer(state, action);
+                        load_disk_usage(state, &action_tx);
+                    }
+                    Action::PreviewSweep(all) => preview_sweep(state, *all, &action_tx),
+                    Action::ExecuteSweep => execute_sweep(state, &action_tx),
                     Action::ExecuteCommand(command) => {
-                        if let Command::Cargo { command } = command {
-                            let action_tx_clone = action_tx.clone();
-                            let command_str = command.clone();
-                            // Always run on selected projects (on_all = false)
-                            run_command(&command_str, state, action_tx_clone).await;
-                            reducer(state, Action::EnterNormalMode);
+                        match command {
+                            Command::Cargo { command } => {
+                                let action_tx_clone = action_tx.clone();
+                                let command_str = command.clone();
+                                // Always run on selected projects (on_all = false)
+                                run_command(&command_str, state, action_tx_clone).await;
+                                reducer(state, Action::EnterNormalMode);
+                            }
+                            Command::ToolchainMatrix { command } => {
+                                list_toolchains(state, command, &action_tx);
+                            }
+                            Command::SelectTag { tag } => {
+                                reducer(state, Action::SelectTag(tag.clone()));
+                                reducer(state, Action::EnterNormalMode);
+                            }
+                            Command::Select { selection } => {
+                                reducer(state, Action::BulkSelect(selection.clone()));
+                                reducer(state, Action::EnterNormalMode);
+                            }
+                            Command::SaveSelection { name } => {
+                                reducer(state, Action::SaveSelection(name.clone()));
+                                reducer(state, Action::EnterNormalMode);
+                            }
+                            Command::DeleteSelection { name } => {
+                                reducer(state, Action::DeleteSelection(name.clone()));
+                                reducer(state, Action::EnterNormalMode);
+                            }
+                            Command::SwitchCollection { name } => {
+                                reducer(state, Action::EnterNormalMode);
+                                if let Some(patterns) = state.config.collections.get(name) {
+                                    let roots = ScanRoots::from_collection(name, patterns);
+                                    let _ = action_tx.send(Action::SwitchRoots(roots)).await;
+                                }
+                            }
+                            _ => {}
                         }
                     }
                     Action::FinishProjectScan(_, _) => {
@@ -526,15 +1138,20 @@ async fn run_app<B: Backend>(
                         reducer(state, action);
 
                         // THEN load cached dependency data (updates state with cache)
-                        load_cache_progress(&mut state.all_projects);
+                        let registry_key = state.registry.cache_key().to_string();
+                        load_cache_progress(&mut state.all_projects, &registry_key);
 
                         // Also update the filtered projects list with the cache data
-                        load_cache_progress(&mut state.projects);
+                        load_cache_progress(&mut state.projects, &registry_key);
 
                         // Reset any "Checking" status to "Unchecked" (app was interrupted)
                         reset_checking_status(state);
 
-                        if state.settings.background_updates_enabled {
+                        if let Some(watcher) = &mut watcher {
+                            watcher.watch_projects(&state.scan_roots.paths, &state.all_projects);
+                        }
+
+                        if state.settings.background_updates_enabled && !state.is_offline() {
                             // NOW queue projects for background checks (after cache is loaded)
                             // Queue projects that:
                             // 1. Have expired cache (> cache TTL)
@@ -569,7 +1186,7 @@ async fn run_app<B: Backend>(
 
                                 if needs_check {
                                     let tx = action_tx.clone();
-                                    let project_name = project.name.clone();
+                                    let project_id = project.id.clone();
 
                                     // Stagger the queue operations to keep UI responsive
                                     let delay = std::time::Duration::from_millis(100 * queue_idx);
@@ -579,7 +1196,7 @@ async fn run_app<B: Backend>(
                                         tokio::time::sleep(delay).await;
                                         let _ = tx
                                             .send(Action::QueueBackgroundUpdate(
-                                                project_name,
+                                                project_id,
                                                 false,
                                             ))
                                             .await;
@@ -594,25 +1211,27 @@ async fn run_app<B: Backend>(
                             // Small delay to let UI render first
                             tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                             let _ = tx.send(Action::CalculateProjectSizes).await;
+                            let _ = tx.send(Action::CheckProjectActivity).await;
                         });
                     }
+                    Action::CheckProjectActivity => check_project_activity(state, &action_tx),
                     Action::CalculateProjectSizes => {
                         // Spawn size calculation tasks for all projects
                         carwash::handlers::handle_calculate_project_sizes(state, action_tx.clone()).await;
                     }
                     Action::StartUpdateWizard => {
-                        let selected_project_name = state
+                        let selected_project_id = state
                             .get_selected_project()
-                            .map(|p| p.name.clone());
-                        let is_currently_checking_same_project = selected_project_name
+                            .map(|p| p.id.clone());
+                        let is_currently_checking_same_project = selected_project_id
                             .as_ref()
-                            .map(|name| {
+                            .map(|id| {
                                 state.is_checking_updates
                                     && state
                                         .updater
-                                        .locked_project_name
+                                        .locked_project
                                         .as_ref()
-                                        == Some(name)
+                                        == Some(id)
                             })
                             .unwrap_or(false);
 
@@ -622,19 +1241,19 @@ async fn run_app<B: Backend>(
                         // Check if this is a directory check (multiple projects)
                         if let Some(pending) = state.updater.pending_directory_check.take() {
                             // Queue all projects for background checking (not priority - no wizard)
-                            for project_name in pending.project_names {
+                            for project_id in pending.projects {
                                 let _ = action_tx
-                                    .send(Action::QueueBackgroundUpdate(project_name, false))
+                                    .send(Action::QueueBackgroundUpdate(project_id, false))
                                     .await;
                             }
-                        } else if let Some(project_name) = state.updater.locked_project_name.clone() {
+                        } else if let Some(project_id) = state.updater.locked_project.clone() {
                             // Single project - open wizard and queue priority check
                             if is_currently_checking_same_project {
                                 // Already processing this project; just keep displaying the wizard
                                 continue;
                             }
                             let _ = action_tx
-                                .send(Action::QueueBackgroundUpdate(project_name, true))
+                                .send(Action::QueueBackgroundUpdate(project_id, true))
                                 .await;
                         }
                     }
@@ -644,17 +1263,23 @@ async fn run_app<B: Backend>(
                         reducer(state, action);
                     }
                     Action::ProcessBackgroundUpdateQueue => {
+                        // Offline: background checks would only time out, drop them.
+                        // User-initiated (priority) checks still run and are served from cache.
+                        if state.is_offline() {
+                            state.update_queue.queue.retain(|t| t.is_priority);
+                        }
+
                         // Check if there are tasks to process in the queue
                         if let Some(task) = state.update_queue.get_next_task() {
                             let action_tx_clone = action_tx.clone();
-                            let project_name = task.project_name.clone();
+                            let project_id = task.project_id.clone();
                             let is_priority = task.is_priority;
 
-                            // Find the project by name in all_projects (not filtered list) so background checks work for all projects
-                            if let Some(project) = state.all_projects.iter().find(|p| p.name == project_name) {
+                            // Find the project by id in all_projects (not filtered list) so background checks work for all projects
+                            if let Some(project) = state.all_projects.iter().find(|p| p.id == project_id) {
                                 let deps = project.dependencies.clone();
-                                let project_path = project.path.clone();
-                                let proj_name = project.name.clone();
+                                let lockfile_path = project.lockfile_path();
+                                let proj_id = project.id.clone();
 
                                 // For priority tasks (user pressed 'u'), enter wizard mode IMMEDIATELY
                                 if is_priority {
@@ -663,12 +1288,9 @@ async fn run_app<B: Backend>(
                                     state.is_checking_updates = true;
 
                                     // Show cached data immediately if available
-                                    // Uses has_stable_update() to properly handle pre-release versions
-                                    state.updater.outdated_dependencies = deps
-                                        .iter()
-                                        .filter(|d| d.has_stable_update())
-                                        .cloned()
-                                        .collect();
+                                    // Filtered through the project's update policy
+                                    state.updater.policy = project.update_policy.clone();
+                                    state.updater.set_outdated_from(&deps);
 
                                     state.updater.selected_dependencies.clear();
 
@@ -710,23 +1332,27 @@ async fn run_app<B: Backend>(
                                 } else {
                                     // Some deps need checking - set status and spawn async check
                                     reducer(state, Action::UpdateProjectCheckStatus(
-                                        proj_name.clone(),
+                                        proj_id.clone(),
                                         ProjectCheckStatus::Checking
                                     ));
 
                                     let action_tx_clone_2 = action_tx_clone.clone();
                                     let is_priority_task = is_priority;
                                     let cache_duration = state.settings.cache_duration();
+                                    let registry = state.registry.clone();
+                                    let offline = state.is_offline();
 
                                     // Perform the update check asynchronously
                                     tokio::spawn(async move {
                                         check_dependencies_with_cache(
-                                            proj_name,
+                                            proj_id,
                                             deps,
                                             action_tx_clone,
                                             true,  // use_cache=true to respect TTL
-                                            Some(project_path),
+                                            Some(lockfile_path),
                                             cache_duration,
+                                            registry,
+                                            offline,
                                         )
                                         .await;
 
@@ -744,6 +1370,15 @@ async fn run_app<B: Backend>(
                             }
                         }
                     }
+                    Action::ProjectsChanged(_) => {
+                        reducer(state, action);
+                        if let Some(watcher) = &mut watcher {
+                            watcher.watch_projects(&state.scan_roots.paths, &state.all_projects);
+                        }
+                    }
+                    Action::ProjectFilesChanged(dirs) => {
+                        handle_project_files_changed(state, dirs, watcher.as_mut(), &action_tx).await;
+                    }
                     Action::QueueBackgroundUpdate(_, _) => {
                         // Add to queue and start processing
                         reducer(state, action);
@@ -765,7 +1400,7 @@ async fn run_app<B: Backend>(
                         // Stream has started
                         reducer(state, action);
                     }
-                    Action::SaveSettings => {
+                    Action::SaveSettings | Action::RegistryReachable => {
                         reducer(state, action.clone());
 
                         if state.settings.background_updates_enabled
@@ -777,50 +1412,35 @@ async fn run_app<B: Backend>(
                         }
                     }
                     Action::RunUpdate => {
-                        // Build the cargo update command for selected dependencies
-                        // Use name@version format to avoid ambiguity when multiple versions exist
-                        let selected_deps: Vec<String> = state
-                            .updater
-                            .selected_dependencies
-                            .iter()
-                            .filter_map(|name| {
-                                // Look up the current version from outdated_dependencies
-                                state
-                                    .updater
-                                    .outdated_dependencies
-                                    .iter()
-                                    .find(|d| &d.name == name)
-                                    .map(|d| format!("{}@{}", d.name, d.current_version))
-                            })
-                            .collect();
-                        if !selected_deps.is_empty() {
-                            let update_cmd = format!("update -p {}", selected_deps.join(" -p "));
-
-                            // Run update only on the currently highlighted project
-                            // (the one whose dependencies are shown in the update wizard)
-                            if let Some(project) = state.get_selected_project() {
-                                let project_name = project.name.clone();
-
-                                // Temporarily clear selected projects and set only the current one
-                                let previous_selection = state.selected_projects.clone();
-                                state.selected_projects.clear();
-                                state.selected_projects.insert(project_name.clone());
-
-                                // Set pending reload - will be processed when FinishCommand is received
-                                state.updater.pending_reload_project = Some(project_name);
-
-                                // Clear wizard selections (but keep wizard open until command finishes)
-                                state.updater.selected_dependencies.clear();
-
-                                // Run the update command (spawns async task)
-                                run_command(&update_cmd, state, action_tx.clone()).await;
-
-                                // Restore previous selection state
-                                state.selected_projects = previous_selection;
-
-                                // Exit wizard mode - the reload will happen via FinishCommand
-                                reducer(state, Action::EnterNormalMode);
-                            }
+                        // One `cargo update --precise` per dependency, to the versions shown
+                        let commands = state.updater.update_commands();
+                        // Run update only on the project whose dependencies the wizard shows
+                        let project = match &state.updater.locked_project {
+                            Some(locked) => state.all_projects.iter().find(|p| &p.id == locked),
+                            None => state.get_selected_project(),
+                        }
+                        .cloned();
+                        if let Some(project) = project.filter(|_| !commands.is_empty()) {
+                            // Set pending reload - will be processed when FinishCommand is received
+                            state.updater.pending_reload_project = Some(project.id.clone());
+
+                            // Clear wizard selections (but keep wizard open until command finishes)
+                            state.updater.selected_dependencies.clear();
+
+                            let tab_index = state.tabs.len();
+                            reducer(
+                                state,
+                                Action::CreateTab(format!("update: {}", project.display_name)),
+                            );
+                            tokio::spawn(run_updates(
+                                project,
+                                commands,
+                                tab_index,
+                                action_tx.clone(),
+                            ));
+
+                            // Exit wizard mode - the reload will happen via FinishCommand
+                            reducer(state, Action::EnterNormalMode);
                         }
                     }
                     Action::FinishCommand(tab_index) => {
@@ -828,47 +1448,63 @@ async fn run_app<B: Backend>(
                         // This async handler only runs when there's a pending reload
 
                         // Check if we have a pending dependency reload after update
-                        if let Some(project_name) = state.updater.pending_reload_project.take() {
-                            // Find and reload the project in all_projects (source of truth)
-                            if let Some(all_proj) = state.all_projects.iter_mut().find(|p| p.name == project_name) {
-                                if let Ok(()) = all_proj.reload_dependencies() {
-                                    // Successfully reloaded! Now sync to filtered projects list
-                                    if let Some(proj) = state.projects.iter_mut().find(|p| p.name == project_name) {
-                                        proj.dependencies = all_proj.dependencies.clone();
+                        if let Some(project_id) = state.updater.pending_reload_project.take() {
+                            // Re-read it from disk off the UI loop (all_projects is the source of truth)
+                            if let Some(mut project) = state.all_projects.iter().find(|p| p.id == project_id).cloned() {
+                                let discovery = state.discovery;
+                                let tab_index = *tab_index;
+                                let tx = action_tx.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    if let Err(e) = reload_project(&mut project, discovery) {
+                                        tracing::warn!("Failed to reload {}: {}", project.path.display(), e);
+                                        return;
                                     }
+                                    let id = project.id.clone();
+                                    let _ = tx.blocking_send(Action::ProjectsChanged(IndexDiff {
+                                        changed: vec![project],
+                                        removed: Vec::new(),
+                                    }));
+                                    let _ = tx.blocking_send(Action::DependenciesReloaded(tab_index, id));
+                                });
+                            }
+                        }
+                    }
+                    Action::DependenciesReloaded(tab_index, project_id) => {
+                        // Clear stale wizard state
+                        state.updater.outdated_dependencies.clear();
+
+                        // Now re-check with the FRESH dependencies to get latest versions
+                        if let Some(project) = state.all_projects.iter().find(|p| p.id == *project_id) {
+                            let fresh_deps = project.dependencies.clone();
+                            let lockfile_path = project.lockfile_path();
+                            let proj_id = project.id.clone();
+                            let cache_duration = state.settings.cache_duration();
+                            let registry = state.registry.clone();
+                            let offline = state.is_offline();
+                            let action_tx_clone = action_tx.clone();
 
-                                    // Clear stale wizard state
-                                    state.updater.outdated_dependencies.clear();
-
-                                    // Now re-check with the FRESH dependencies to get latest versions
-                                    let fresh_deps = all_proj.dependencies.clone();
-                                    let project_path = all_proj.path.clone();
-                                    let proj_name = all_proj.name.clone();
-                                    let cache_duration = state.settings.cache_duration();
-                                    let action_tx_clone = action_tx.clone();
-
-                                    tokio::spawn(async move {
-                                        // Re-check with fresh dependencies from disk
-                                        check_dependencies_with_cache(
-                                            proj_name,
-                                            fresh_deps,
-                                            action_tx_clone,
-                                            false,  // Don't use cache - force fresh check
-                                            Some(project_path),
-                                            cache_duration,
-                                        )
-                                        .await;
-                                    });
+                            tokio::spawn(async move {
+                                // Re-check with fresh dependencies from disk
+                                check_dependencies_with_cache(
+                                    proj_id,
+                                    fresh_deps,
+                                    action_tx_clone,
+                                    false,  // Don't use cache - force fresh check
+                                    Some(lockfile_path),
+                                    cache_duration,
+                                    registry,
+                                    offline,
+                                )
+                                .await;
+                            });
 
-                                    // Add notification to the tab
-                                    let _ = action_tx
-                                        .send(Action::AddOutput(
-                                            *tab_index,
-                                            "Dependencies reloaded. Re-checking for updates...".into(),
-                                        ))
-                                        .await;
-                                }
-                            }
+                            // Add notification to the tab
+                            let _ = action_tx
+                                .send(Action::AddOutput(
+                                    *tab_index,
+                                    "Dependencies reloaded. Re-checking for updates...".into(),
+                                ))
+                                .await;
                         }
                     }
                     _ => {
@@ -882,11 +1518,13 @@ async fn run_app<B: Backend>(
                     save_cache_progress(state);
                     last_cache_save = std::time::Instant::now();
                 }
+                reconnect.poll(state, &action_tx);
                 continue;
             }
         };
 
         if state.should_quit {
+            save_session(state);
             return Ok(());
         }
     }
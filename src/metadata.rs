@@ -0,0 +1,332 @@
+//! `cargo metadata` discovery backend
+//!
+//! The manifest parser in [`crate::project`] is fast but only sees what is
+//! literally written in each Cargo.toml. This backend asks Cargo itself, once per
+//! workspace root, so renamed and target-specific dependencies and inherited
+//! package fields come out right. Projects whose metadata can't be loaded keep
+//! what the manifest parser found.
+
+use crate::project::{Dependency, DependencyCheckStatus, Project};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Output of `cargo metadata --format-version 1`, limited to what we use
+#[derive(Debug, Deserialize)]
+pub struct Metadata {
+    pub packages: Vec<MetadataPackage>,
+    #[serde(default)]
+    pub workspace_members: Vec<String>,
+    pub resolve: Option<Resolve>,
+    pub workspace_root: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MetadataPackage {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// `None` for path dependencies and workspace members
+    pub source: Option<String>,
+    pub manifest_path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Resolve {
+    pub nodes: Vec<ResolveNode>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveNode {
+    pub id: String,
+    #[serde(default)]
+    pub deps: Vec<NodeDep>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeDep {
+    /// Package id of the resolved dependency (its `name` is the renamed one)
+    pub pkg: String,
+}
+
+impl Metadata {
+    /// Run `cargo metadata` for the workspace (or package) in `dir`
+    ///
+    /// Runs offline, so discovery never touches the network.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let output = Command::new("cargo")
+            .args(["metadata", "--format-version", "1", "--offline"])
+            .arg("--manifest-path")
+            .arg(dir.join("Cargo.toml"))
+            .output()
+            .map_err(|e| format!("Failed to run cargo metadata: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("cargo metadata failed: {}", stderr.trim()));
+        }
+
+        Self::parse(&output.stdout)
+    }
+
+    /// Parse the JSON printed by `cargo metadata`
+    pub fn parse(json: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(json).map_err(|e| format!("Invalid cargo metadata output: {}", e))
+    }
+
+    /// Find a workspace member by its manifest path
+    ///
+    /// Both paths are canonicalized first, as `ProjectId` does, so a root
+    /// reached through a symlink or a relative path still matches.
+    pub fn member(&self, manifest_path: &Path) -> Option<&MetadataPackage> {
+        let wanted = canonical(manifest_path);
+        self.packages.iter().find(|pkg| {
+            self.workspace_members.contains(&pkg.id) && canonical(&pkg.manifest_path) == wanted
+        })
+    }
+
+    /// Registry dependencies of a package as resolved in the lockfile
+    ///
+    /// Covers normal, dev, build and target-specific dependencies, named after
+    /// the crate rather than any `package = "..."` rename. Path and git
+    /// dependencies are left out since the registry can't be asked about them.
+    pub fn dependencies_of(&self, package_id: &str) -> Vec<Dependency> {
+        let Some(node) = self
+            .resolve
+            .as_ref()
+            .and_then(|r| r.nodes.iter().find(|n| n.id == package_id))
+        else {
+            return Vec::new();
+        };
+
+        let packages: HashMap<&str, &MetadataPackage> =
+            self.packages.iter().map(|p| (p.id.as_str(), p)).collect();
+
+        let mut deps: Vec<Dependency> = node
+            .deps
+            .iter()
+            .filter_map(|dep| packages.get(dep.pkg.as_str()))
+            .filter(|pkg| pkg.source.as_deref().is_some_and(is_registry_source))
+            .map(|pkg| Dependency {
+                name: pkg.name.clone(),
+                current_version: pkg.version.clone(),
+                latest_version: None,
+                check_status: DependencyCheckStatus::NotChecked,
+                last_checked: None,
+                published_versions: Vec::new(),
+                requirement: None,
+                features: Vec::new(),
+            })
+            .collect();
+
+        // Sort for consistent ordering (same as the manifest parser)
+        deps.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| a.current_version.cmp(&b.current_version))
+        });
+        deps.dedup_by(|a, b| a.name == b.name && a.current_version == b.current_version);
+        deps
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| crate::workspace::normalize(path))
+}
+
+fn is_registry_source(source: &str) -> bool {
+    source.starts_with("registry+") || source.starts_with("sparse+")
+}
+
+/// Refresh projects from `cargo metadata`, one invocation per workspace root
+///
+/// Dependencies, version and authors are replaced with what Cargo reports;
+/// check results already known for a dependency are kept. Projects whose
+/// metadata fails to load are left as the manifest parser built them.
+pub fn apply_cargo_metadata(projects: &mut [Project]) {
+    let mut by_root: HashMap<PathBuf, Vec<&mut Project>> = HashMap::new();
+    for project in projects.iter_mut() {
+        let root = project
+            .workspace_root
+            .clone()
+            .unwrap_or_else(|| project.path.clone());
+        by_root.entry(root).or_default().push(project);
+    }
+
+    for (root, projects) in by_root {
+        let metadata = match Metadata::load(&root) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                tracing::warn!(
+                    "Falling back to manifest parsing for {}: {}",
+                    root.display(),
+                    e
+                );
+                continue;
+            }
+        };
+
+        for project in projects {
+            apply_to_project(&metadata, project);
+        }
+    }
+}
+
+/// Update one project from already-loaded metadata
+///
+/// Returns `false` if the project isn't a member of that workspace.
+pub fn apply_to_project(metadata: &Metadata, project: &mut Project) -> bool {
+    let Some(package) = metadata.member(project.id.manifest_path()) else {
+        return false;
+    };
+
+    let mut dependencies = metadata.dependencies_of(&package.id);
+    for dep in &mut dependencies {
+        if let Some(existing) = project
+            .dependencies
+            .iter()
+            .find(|d| d.name == dep.name && d.current_version == dep.current_version)
+        {
+            dep.latest_version = existing.latest_version.clone();
+            dep.check_status = existing.check_status.clone();
+            dep.last_checked = existing.last_checked;
+            dep.published_versions = existing.published_versions.clone();
+        }
+        // The resolve graph carries no requirements; keep the ones from the manifest
+        if let Some(declared) = project.dependencies.iter().find(|d| d.name == dep.name) {
+            dep.requirement = declared.requirement.clone();
+            dep.features = declared.features.clone();
+        }
+    }
+
+    project.dependencies = dependencies;
+    project.version = package.version.clone();
+    project.authors = package.authors.clone();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{GitStatus, ProjectCheckStatus, ProjectId, ProjectStatus};
+
+    fn fixture() -> Metadata {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/metadata/app.json");
+        Metadata::parse(&std::fs::read(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_dependencies_use_crate_names_and_skip_path_deps() {
+        let metadata = fixture();
+        let app = metadata.member(Path::new("/work/app/Cargo.toml")).unwrap();
+
+        let deps: Vec<(String, String)> = metadata
+            .dependencies_of(&app.id)
+            .into_iter()
+            .map(|d| (d.name, d.current_version))
+            .collect();
+
+        // `serde_json` is renamed to `json`; `winapi` is target-specific;
+        // `helper` is a path dependency
+        assert_eq!(
+            deps,
+            [
+                ("serde_json".to_string(), "1.0.128".to_string()),
+                ("tokio".to_string(), "1.40.0".to_string()),
+                ("winapi".to_string(), "0.3.9".to_string()),
+            ]
+        );
+    }
+
+    fn project(path: &str, dependencies: Vec<Dependency>) -> Project {
+        Project {
+            id: ProjectId::from_dir(Path::new(path)),
+            name: "app".into(),
+            display_name: "app".into(),
+            path: PathBuf::from(path),
+            status: ProjectStatus::Pending,
+            version: "0.0.0".into(),
+            authors: Vec::new(),
+            edition: None,
+            rust_version: None,
+            license: None,
+            keywords: Vec::new(),
+            categories: Vec::new(),
+            tags: Vec::new(),
+            dependencies,
+            workspace_root: Some(PathBuf::from("/work")),
+            workspace_name: Some("work".into()),
+            cargo_lock_hash: None,
+            check_status: ProjectCheckStatus::Unchecked,
+            git_status: GitStatus::Unknown,
+            total_size: None,
+            target_size: None,
+            modified: None,
+            update_policy: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_apply_to_project_keeps_check_results() {
+        let metadata = fixture();
+        let checked = Dependency {
+            name: "tokio".into(),
+            current_version: "1.40.0".into(),
+            latest_version: Some("1.41.0".into()),
+            check_status: DependencyCheckStatus::Checked,
+            last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
+        };
+        let mut project = project("/work/app", vec![checked]);
+
+        assert!(apply_to_project(&metadata, &mut project));
+        // Inherited fields come from Cargo
+        assert_eq!(project.version, "0.3.0");
+        assert_eq!(project.authors, ["Jane Doe"]);
+        assert_eq!(project.dependencies.len(), 3);
+
+        let tokio = project
+            .dependencies
+            .iter()
+            .find(|d| d.name == "tokio")
+            .unwrap();
+        assert_eq!(tokio.latest_version.as_deref(), Some("1.41.0"));
+    }
+
+    #[test]
+    fn test_non_member_is_left_alone() {
+        let metadata = fixture();
+        let mut project = project("/elsewhere", Vec::new());
+
+        assert!(!apply_to_project(&metadata, &mut project));
+        assert_eq!(project.version, "0.0.0");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_member_matches_through_symlink() {
+        let dir = crate::test_support::temp_dir("metadata", "symlink");
+        let real = dir.join("real");
+        crate::test_support::write_package(&real, "app", "0.1.0", &[]);
+        let link = dir.join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let json = format!(
+            r#"{{"packages":[{{"id":"app 0.1.0","name":"app","version":"0.1.0","source":null,"manifest_path":{:?}}}],"workspace_members":["app 0.1.0"],"resolve":null,"workspace_root":{:?}}}"#,
+            real.join("Cargo.toml"),
+            real
+        );
+        let metadata = Metadata::parse(json.as_bytes()).unwrap();
+
+        assert!(metadata.member(&link.join("Cargo.toml")).is_some());
+        assert!(metadata.member(&link.join("../real/Cargo.toml")).is_some());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,448 @@
+//! Mouse support across panes
+//!
+//! The panes record where they were drawn, and mouse events are matched
+//! against those areas: clicks select tree rows, toggle checkboxes, switch
+//! output tabs and run palette commands, the wheel scrolls the pane under the
+//! pointer, and the borders between panes can be dragged to resize them.
+
+use crate::app::AppState;
+use crate::events::{Action, Focus, Mode};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+/// Lines or rows moved per step of the scroll wheel
+pub const SCROLL_LINES: usize = 3;
+
+/// A border between panes that can be dragged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splitter {
+    /// Between the project list and the right-hand panes
+    Left,
+    /// Between the dependencies and output panes
+    TopRight,
+}
+
+/// Areas of the panes as last drawn, and the splitter being dragged
+#[derive(Debug, Clone, Default)]
+pub struct MouseState {
+    /// Panes below the status bar, split by the left splitter
+    pub body: Rect,
+    /// Right-hand column, split by the top-right splitter
+    pub right: Rect,
+    pub projects: Rect,
+    pub dependencies: Rect,
+    /// Bar with the output tab titles
+    pub output_tabs: Rect,
+    /// Lines of the active output tab
+    pub output: Rect,
+    /// Title of each output tab in the tabs bar
+    pub tab_titles: Vec<Rect>,
+    /// List of the command palette, while it is open
+    pub palette_list: Rect,
+    pub dragging: Option<Splitter>,
+}
+
+/// Turn a mouse event into an action, given where the panes were drawn
+///
+/// Scrolling and focus are applied directly; everything else goes through
+/// the returned action.
+pub fn handle_mouse_event(event: MouseEvent, state: &mut AppState) -> Option<Action> {
+    let position = Position::new(event.column, event.row);
+    match state.mode {
+        Mode::Normal => {}
+        Mode::CommandPalette => return handle_palette(event.kind, position, state),
+        _ => return None,
+    }
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(splitter) = splitter_at(&state.mouse, position) {
+                state.mouse.dragging = Some(splitter);
+                return None;
+            }
+            click(position, state)
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            let mouse = &state.mouse;
+            match mouse.dragging? {
+                Splitter::Left => Some(Action::ResizeLeftPane(percent_of(
+                    event.column.saturating_sub(mouse.body.x),
+                    mouse.body.width,
+                ))),
+                Splitter::TopRight => Some(Action::ResizeTopRight(percent_of(
+                    event.row.saturating_sub(mouse.right.y),
+                    mouse.right.height,
+                ))),
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            state.mouse.dragging.take()?;
+            Some(Action::SaveConfig)
+        }
+        MouseEventKind::ScrollDown => scroll(position, state, true),
+        MouseEventKind::ScrollUp => scroll(position, state, false),
+        _ => None,
+    }
+}
+
+/// The splitter on the border under the pointer
+fn splitter_at(mouse: &MouseState, position: Position) -> Option<Splitter> {
+    let projects = mouse.projects;
+    if projects.width > 0
+        && (position.x + 1 == projects.right() || position.x == projects.right())
+        && (projects.top()..projects.bottom()).contains(&position.y)
+    {
+        return Some(Splitter::Left);
+    }
+    let dependencies = mouse.dependencies;
+    if dependencies.height > 0
+        && (position.y + 1 == dependencies.bottom() || position.y == dependencies.bottom())
+        && (dependencies.left()..dependencies.right()).contains(&position.x)
+    {
+        return Some(Splitter::TopRight);
+    }
+    None
+}
+
+/// Percentage of `length` at `offset`, kept within the range of the pane size keys
+fn percent_of(offset: u16, length: u16) -> u16 {
+    if length == 0 {
+        return 50;
+    }
+    ((offset as u32 * 100 / length as u32) as u16).clamp(20, 80)
+}
+
+/// Row of a bordered list under the pointer, counting from its first item
+fn list_row(area: Rect, position: Position, header: u16, offset: usize) -> Option<usize> {
+    let first = area.y + 1 + header;
+    if position.y < first || position.y + 1 >= area.bottom() {
+        return None;
+    }
+    Some(offset + (position.y - first) as usize)
+}
+
+fn click(position: Position, state: &mut AppState) -> Option<Action> {
+    let mouse = &state.mouse;
+    if mouse.projects.contains(position) {
+        state.focus = Focus::Projects;
+        let table = state.settings.table_view.enabled;
+        let header = if table { 1 } else { 0 };
+        let index = list_row(
+            state.mouse.projects,
+            position,
+            header,
+            state.tree_state.offset(),
+        )?;
+        let (node, _) = state.flattened_tree.items.get(index)?;
+        // The checkbox is the table's first column; in the tree it follows the cursor and indent
+        let checkbox = if table { 0 } else { 2 + 2 * node.depth as u16 };
+        let column = position.x.saturating_sub(state.mouse.projects.x + 1);
+        if column == checkbox || column == checkbox + 1 {
+            Some(Action::ToggleRowSelection(index))
+        } else {
+            Some(Action::SelectRow(index))
+        }
+    } else if mouse.dependencies.contains(position) {
+        state.focus = Focus::Dependencies;
+        if !state.details.visible {
+            let count = state
+                .get_selected_project()
+                .map_or(0, |p| p.dependencies.len());
+            let index = list_row(
+                state.mouse.dependencies,
+                position,
+                0,
+                state.dependency_list.offset(),
+            )
+            .filter(|&index| index < count)?;
+            state.dependency_list.select(Some(index));
+        }
+        None
+    } else if mouse.output_tabs.contains(position) {
+        state.focus = Focus::Output;
+        let tab = mouse
+            .tab_titles
+            .iter()
+            .position(|title| title.contains(position))?;
+        Some(Action::SwitchToTab(tab))
+    } else if mouse.output.contains(position) {
+        state.focus = Focus::Output;
+        None
+    } else {
+        None
+    }
+}
+
+fn scroll(position: Position, state: &mut AppState, down: bool) -> Option<Action> {
+    let mouse = &state.mouse;
+    if mouse.projects.contains(position) {
+        let last = state.flattened_tree.items.len().checked_sub(1)?;
+        let current = state.tree_state.selected().unwrap_or(0);
+        let index = if down {
+            (current + SCROLL_LINES).min(last)
+        } else {
+            current.saturating_sub(SCROLL_LINES)
+        };
+        (index != current).then_some(Action::SelectRow(index))
+    } else if mouse.dependencies.contains(position) {
+        if state.details.visible {
+            state.details.scroll = step(state.details.scroll, down);
+            return None;
+        }
+        let last = state
+            .get_selected_project()
+            .and_then(|p| p.dependencies.len().checked_sub(1))?;
+        let index = match state.dependency_list.selected() {
+            Some(current) if down => (current + SCROLL_LINES).min(last),
+            Some(current) => current.saturating_sub(SCROLL_LINES),
+            None => 0,
+        };
+        state.dependency_list.select(Some(index));
+        None
+    } else if mouse.output.contains(position) || mouse.output_tabs.contains(position) {
+        // Drawing keeps the scroll within the tab's lines
+        state.output_scroll = step(state.output_scroll, down);
+        None
+    } else {
+        None
+    }
+}
+
+fn step(scroll: usize, down: bool) -> usize {
+    if down {
+        scroll.saturating_add(SCROLL_LINES)
+    } else {
+        scroll.saturating_sub(SCROLL_LINES)
+    }
+}
+
+fn handle_palette(
+    kind: MouseEventKind,
+    position: Position,
+    state: &mut AppState,
+) -> Option<Action> {
+    let list = state.mouse.palette_list;
+    if !list.contains(position) {
+        return None;
+    }
+    match kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let index = list_row(list, position, 0, state.palette.list_state.offset())?;
+            let command = state.palette.filtered_commands.get(index)?.clone();
+            state.palette.list_state.select(Some(index));
+            Some(Action::ExecuteCommand(command))
+        }
+        MouseEventKind::ScrollDown if !state.palette.filtered_commands.is_empty() => {
+            Some(Action::PaletteSelectNext)
+        }
+        MouseEventKind::ScrollUp if !state.palette.filtered_commands.is_empty() => {
+            Some(Action::PaletteSelectPrevious)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{Tab, reducer};
+    use crate::project::find_rust_projects;
+    use crate::roots::ScanRoots;
+    use crossterm::event::KeyModifiers;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Projects of the glob fixture listed, with panes laid out as on a 100x40 screen
+    fn launch() -> AppState {
+        let mut state = AppState::new();
+        state.settings.group_by_tag = false;
+        state.settings.table_view.enabled = false;
+        let dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/workspaces/glob"
+        );
+        let roots = ScanRoots::from_dirs(&[dir.to_string()]);
+        reducer(
+            &mut state,
+            Action::FinishProjectScan(find_rust_projects(dir), roots),
+        );
+        state.mouse = MouseState {
+            body: Rect::new(0, 0, 100, 39),
+            right: Rect::new(40, 0, 60, 39),
+            projects: Rect::new(0, 0, 40, 39),
+            dependencies: Rect::new(40, 0, 60, 16),
+            output_tabs: Rect::new(40, 16, 60, 3),
+            output: Rect::new(40, 19, 60, 20),
+            tab_titles: vec![Rect::new(41, 17, 8, 1), Rect::new(50, 17, 9, 1)],
+            palette_list: Rect::default(),
+            dragging: None,
+        };
+        state
+    }
+
+    fn send(state: &mut AppState, event: MouseEvent) {
+        if let Some(action) = handle_mouse_event(event, state) {
+            reducer(state, action);
+        }
+    }
+
+    #[test]
+    fn test_click_selects_rows_and_toggles_checkboxes() {
+        let mut state = launch();
+        let rows = state.flattened_tree.items.len();
+        assert!(rows > 2);
+
+        // The third row, clicked on its name
+        state.focus = Focus::Output;
+        send(
+            &mut state,
+            mouse(MouseEventKind::Down(MouseButton::Left), 20, 3),
+        );
+        assert_eq!(state.tree_state.selected(), Some(2));
+        assert_eq!(state.focus, Focus::Projects);
+        assert!(state.selected_projects.is_empty());
+
+        // The checkbox of a project row toggles it
+        let (index, depth) = state
+            .flattened_tree
+            .items
+            .iter()
+            .enumerate()
+            .find(|(_, (node, _))| node.node_type.is_project())
+            .map(|(index, (node, _))| (index, node.depth as u16))
+            .unwrap();
+        let checkbox = 1 + 2 + 2 * depth;
+        send(
+            &mut state,
+            mouse(
+                MouseEventKind::Down(MouseButton::Left),
+                checkbox,
+                1 + index as u16,
+            ),
+        );
+        assert_eq!(state.tree_state.selected(), Some(index));
+        assert_eq!(state.selected_projects.len(), 1);
+
+        // Below the last row nothing happens
+        send(
+            &mut state,
+            mouse(MouseEventKind::Down(MouseButton::Left), 20, 37),
+        );
+        assert_eq!(state.tree_state.selected(), Some(index));
+
+        // The wheel moves the cursor without wrapping around
+        state.tree_state.select(Some(0));
+        for _ in 0..rows {
+            send(&mut state, mouse(MouseEventKind::ScrollDown, 20, 10));
+        }
+        assert_eq!(state.tree_state.selected(), Some(rows - 1));
+        send(&mut state, mouse(MouseEventKind::ScrollUp, 20, 10));
+        assert_eq!(
+            state.tree_state.selected(),
+            Some(rows - 1 - SCROLL_LINES.min(rows - 1))
+        );
+    }
+
+    #[test]
+    fn test_tabs_output_scroll_and_splitters() {
+        let mut state = launch();
+        state.tabs = vec![
+            Tab {
+                title: "check".to_string(),
+                buffer: vec!["Checking".to_string(); 100],
+                is_finished: true,
+            },
+            Tab {
+                title: "build".to_string(),
+                buffer: Vec::new(),
+                is_finished: false,
+            },
+        ];
+
+        // Clicking a tab title switches to it, the divider between titles doesn't
+        send(
+            &mut state,
+            mouse(MouseEventKind::Down(MouseButton::Left), 52, 17),
+        );
+        assert_eq!(state.active_tab, 1);
+        assert_eq!(state.focus, Focus::Output);
+        send(
+            &mut state,
+            mouse(MouseEventKind::Down(MouseButton::Left), 49, 17),
+        );
+        assert_eq!(state.active_tab, 1);
+        send(
+            &mut state,
+            mouse(MouseEventKind::Down(MouseButton::Left), 42, 17),
+        );
+        assert_eq!(state.active_tab, 0);
+
+        send(&mut state, mouse(MouseEventKind::ScrollDown, 60, 25));
+        send(&mut state, mouse(MouseEventKind::ScrollDown, 60, 25));
+        assert_eq!(state.output_scroll, 2 * SCROLL_LINES);
+        // Switching tabs starts at the top of the other tab
+        reducer(&mut state, Action::SwitchToTab(1));
+        assert_eq!(state.output_scroll, 0);
+
+        // Dragging the border of the project list resizes it, within bounds
+        send(
+            &mut state,
+            mouse(MouseEventKind::Down(MouseButton::Left), 39, 10),
+        );
+        assert_eq!(state.mouse.dragging, Some(Splitter::Left));
+        send(
+            &mut state,
+            mouse(MouseEventKind::Drag(MouseButton::Left), 55, 10),
+        );
+        assert_eq!(state.config.layout.left_pane_percent, 55);
+        send(
+            &mut state,
+            mouse(MouseEventKind::Drag(MouseButton::Left), 95, 10),
+        );
+        assert_eq!(state.config.layout.left_pane_percent, 80);
+        // Releasing saves the layout, so stop dragging by hand here
+        state.mouse.dragging = None;
+
+        send(
+            &mut state,
+            mouse(MouseEventKind::Down(MouseButton::Left), 70, 15),
+        );
+        assert_eq!(state.mouse.dragging, Some(Splitter::TopRight));
+        send(
+            &mut state,
+            mouse(MouseEventKind::Drag(MouseButton::Left), 70, 26),
+        );
+        assert_eq!(state.config.layout.top_right_percent, 66);
+    }
+
+    #[test]
+    fn test_click_runs_palette_command() {
+        let mut state = launch();
+        reducer(&mut state, Action::ShowCommandPalette);
+        state.mouse.palette_list = Rect::new(20, 10, 60, 20);
+        let commands = state.palette.filtered_commands.clone();
+        assert!(commands.len() > 1);
+
+        // Clicks outside the palette leave the panes behind it alone
+        assert!(
+            handle_mouse_event(
+                mouse(MouseEventKind::Down(MouseButton::Left), 5, 5),
+                &mut state
+            )
+            .is_none()
+        );
+        let action = handle_mouse_event(
+            mouse(MouseEventKind::Down(MouseButton::Left), 30, 12),
+            &mut state,
+        );
+        assert!(matches!(action, Some(Action::ExecuteCommand(command)) if command == commands[1]));
+        assert_eq!(state.palette.list_state.selected(), Some(1));
+    }
+}
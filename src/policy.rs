@@ -0,0 +1,402 @@
+//! Dependency update policy evaluation
+//!
+//! Compiles the declarative `[updates]` rules from the global config and a
+//! repository's `.carwash.toml` into an [`UpdatePolicy`], and decides for each
+//! dependency which available version, if any, should be offered as an update.
+
+use crate::config::{RepoConfig, UpdatePolicyConfig};
+use crate::project::Dependency;
+use semver::{Version, VersionReq};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Why an available update is being held back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeferReason {
+    /// The crate (or this version range) is on the ignore list
+    Ignored,
+    /// The newer versions fall outside the pinned requirement
+    Pinned(String),
+    /// The newer versions were released too recently
+    Cooldown,
+}
+
+impl DeferReason {
+    /// Short label for display next to the dependency
+    pub fn label(&self) -> String {
+        match self {
+            DeferReason::Ignored => "ignored".to_string(),
+            DeferReason::Pinned(req) => format!("pinned {}", req),
+            DeferReason::Cooldown => "cooldown".to_string(),
+        }
+    }
+}
+
+/// Outcome of applying an [`UpdatePolicy`] to a checked dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateVerdict {
+    /// No newer version is available (or the dependency hasn't been checked)
+    UpToDate,
+    /// An update to this version is allowed by the policy
+    Available(String),
+    /// Newer versions exist, but the policy holds them back
+    Deferred {
+        /// The newest version that would otherwise be offered
+        latest: String,
+        /// The rule that deferred it
+        reason: DeferReason,
+    },
+}
+
+/// Compiled update policy for a single project
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdatePolicy {
+    /// Ignore rules per crate; `None` ignores every version of the crate
+    ignored: HashMap<String, Vec<Option<VersionReq>>>,
+    /// Requirements candidate versions must satisfy, per crate
+    pins: HashMap<String, VersionReq>,
+    /// Crates for which pre-releases count as updates
+    allow_prerelease: HashSet<String>,
+    /// Minimum age of a release before it is offered
+    cooldown: Option<Duration>,
+}
+
+impl UpdatePolicy {
+    /// Compile a policy from its configuration, skipping malformed rules
+    pub fn from_config(config: &UpdatePolicyConfig) -> Self {
+        let mut policy = UpdatePolicy::default();
+
+        for entry in &config.ignore {
+            match entry.split_once('@') {
+                Some((name, req)) => match VersionReq::parse(req.trim()) {
+                    Ok(req) => policy
+                        .ignored
+                        .entry(name.trim().to_string())
+                        .or_default()
+                        .push(Some(req)),
+                    Err(e) => tracing::warn!("Invalid ignore rule '{}': {}", entry, e),
+                },
+                None => policy
+                    .ignored
+                    .entry(entry.trim().to_string())
+                    .or_default()
+                    .push(None),
+            }
+        }
+
+        for (name, req) in &config.pin {
+            match VersionReq::parse(req.trim()) {
+                Ok(req) => {
+                    policy.pins.insert(name.clone(), req);
+                }
+                Err(e) => tracing::warn!("Invalid pin for '{}' ({}): {}", name, req, e),
+            }
+        }
+
+        policy.allow_prerelease = config.allow_prerelease.iter().cloned().collect();
+        policy.cooldown = config
+            .cooldown_days
+            .filter(|days| *days > 0)
+            .map(|days| Duration::from_secs(days * 24 * 60 * 60));
+
+        policy
+    }
+
+    /// Build the effective policy for a project
+    ///
+    /// The global section is layered with `.carwash.toml` from the workspace root
+    /// (if any) and then from the project directory itself.
+    pub fn for_project(
+        global: &UpdatePolicyConfig,
+        project_path: &Path,
+        workspace_root: Option<&Path>,
+    ) -> Self {
+        let mut config = global.clone();
+
+        if let Some(ws_root) = workspace_root {
+            if let Some(repo) = RepoConfig::load(ws_root) {
+                config = config.merged_with(&repo.updates);
+            }
+        }
+        if workspace_root != Some(project_path) {
+            if let Some(repo) = RepoConfig::load(project_path) {
+                config = config.merged_with(&repo.updates);
+            }
+        }
+
+        Self::from_config(&config)
+    }
+
+    fn allows_prerelease(&self, name: &str) -> bool {
+        self.allow_prerelease.contains("*") || self.allow_prerelease.contains(name)
+    }
+
+    /// Find the first rule that rejects a candidate version, if any
+    fn rejection(
+        &self,
+        name: &str,
+        version: &Version,
+        released_at: Option<SystemTime>,
+        now: SystemTime,
+    ) -> Option<DeferReason> {
+        if let Some(rules) = self.ignored.get(name) {
+            let ignored = rules.iter().any(|rule| match rule {
+                Some(req) => req.matches(version),
+                None => true,
+            });
+            if ignored {
+                return Some(DeferReason::Ignored);
+            }
+        }
+
+        if let Some(req) = self.pins.get(name) {
+            if !req.matches(version) {
+                return Some(DeferReason::Pinned(req.to_string()));
+            }
+        }
+
+        if let (Some(cooldown), Some(released_at)) = (self.cooldown, released_at) {
+            let age = now.duration_since(released_at).unwrap_or_default();
+            if age < cooldown {
+                return Some(DeferReason::Cooldown);
+            }
+        }
+
+        None
+    }
+
+    /// Decide which update, if any, to offer for a dependency
+    ///
+    /// Candidates are taken from the dependency's published versions when known,
+    /// otherwise from its latest version alone. Yanked releases are never offered,
+    /// and pre-releases only count when the current version is itself a
+    /// pre-release or the crate is listed under `allow-prerelease`.
+    pub fn evaluate(&self, dep: &Dependency, now: SystemTime) -> UpdateVerdict {
+        let Some(latest) = dep.latest_version.as_ref() else {
+            return UpdateVerdict::UpToDate;
+        };
+
+        let (Ok(current), Ok(_)) = (Version::parse(&dep.current_version), Version::parse(latest))
+        else {
+            // Unparseable versions: fall back to plain comparison
+            if !dep.has_stable_update() {
+                return UpdateVerdict::UpToDate;
+            }
+            return if self
+                .ignored
+                .get(&dep.name)
+                .is_some_and(|r| r.contains(&None))
+            {
+                UpdateVerdict::Deferred {
+                    latest: latest.clone(),
+                    reason: DeferReason::Ignored,
+                }
+            } else {
+                UpdateVerdict::Available(latest.clone())
+            };
+        };
+
+        let candidates = self.candidates(dep, latest, &current);
+        let Some((newest, newest_released)) = candidates.first().cloned() else {
+            return UpdateVerdict::UpToDate;
+        };
+
+        if let Some((version, _)) = candidates
+            .iter()
+            .find(|(v, released)| self.rejection(&dep.name, v, *released, now).is_none())
+        {
+            return UpdateVerdict::Available(version.to_string());
+        }
+
+        let reason = self
+            .rejection(&dep.name, &newest, newest_released, now)
+            .unwrap_or(DeferReason::Ignored);
+        UpdateVerdict::Deferred {
+            latest: newest.to_string(),
+            reason,
+        }
+    }
+
+    /// The newest allowed version that `cargo update` can reach without a Cargo.toml change
+    ///
+    /// `None` when every allowed update crosses the declared requirement (or
+    /// the default caret rules), as `cargo update --precise` would reject it.
+    pub fn compatible_target(&self, dep: &Dependency, now: SystemTime) -> Option<String> {
+        let latest = dep.latest_version.as_ref()?;
+        let current = Version::parse(&dep.current_version).ok()?;
+        self.candidates(dep, latest, &current)
+            .into_iter()
+            .filter(|(v, released)| self.rejection(&dep.name, v, *released, now).is_none())
+            .map(|(v, _)| v.to_string())
+            .find(|v| !dep.is_major_bump(v))
+    }
+
+    /// Versions newer than `current`, newest first, with their release times
+    fn candidates(
+        &self,
+        dep: &Dependency,
+        latest: &str,
+        current: &Version,
+    ) -> Vec<(Version, Option<SystemTime>)> {
+        let allow_pre = !current.pre.is_empty() || self.allows_prerelease(&dep.name);
+
+        let mut candidates: Vec<(Version, Option<SystemTime>)> =
+            if dep.published_versions.is_empty() {
+                Version::parse(latest)
+                    .ok()
+                    .map(|v| (v, None))
+                    .into_iter()
+                    .collect()
+            } else {
+                dep.published_versions
+                    .iter()
+                    .filter(|pv| !pv.yanked)
+                    .filter_map(|pv| {
+                        Version::parse(&pv.version)
+                            .ok()
+                            .map(|v| (v, pv.released_at))
+                    })
+                    .collect()
+            };
+        candidates.retain(|(v, _)| *v > *current && (allow_pre || v.pre.is_empty()));
+        candidates.sort_by(|a, b| b.0.cmp(&a.0));
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{DependencyCheckStatus, PublishedVersion};
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn dep(current: &str, versions: &[(&str, u64)]) -> Dependency {
+        let now = SystemTime::now();
+        Dependency {
+            name: "tokio".into(),
+            current_version: current.into(),
+            latest_version: versions.first().map(|(v, _)| v.to_string()),
+            check_status: DependencyCheckStatus::Checked,
+            last_checked: None,
+            published_versions: versions
+                .iter()
+                .map(|(v, age_days)| PublishedVersion {
+                    version: v.to_string(),
+                    released_at: Some(now - DAY * (*age_days as u32)),
+                    yanked: false,
+                })
+                .collect(),
+            requirement: None,
+            features: Vec::new(),
+        }
+    }
+
+    fn policy(toml_str: &str) -> UpdatePolicy {
+        UpdatePolicy::from_config(&toml::from_str(toml_str).expect("valid policy"))
+    }
+
+    #[test]
+    fn test_default_policy_matches_stable_update() {
+        let d = dep("1.0.0", &[("1.2.0", 30), ("1.1.0", 60)]);
+        assert_eq!(
+            UpdatePolicy::default().evaluate(&d, SystemTime::now()),
+            UpdateVerdict::Available("1.2.0".into())
+        );
+
+        let d = dep("1.0.0", &[("2.0.0-beta.1", 30)]);
+        assert_eq!(
+            UpdatePolicy::default().evaluate(&d, SystemTime::now()),
+            UpdateVerdict::UpToDate
+        );
+    }
+
+    #[test]
+    fn test_ignore_whole_crate() {
+        let d = dep("1.0.0", &[("1.2.0", 30)]);
+        let verdict = policy(r#"ignore = ["tokio"]"#).evaluate(&d, SystemTime::now());
+        assert_eq!(
+            verdict,
+            UpdateVerdict::Deferred {
+                latest: "1.2.0".into(),
+                reason: DeferReason::Ignored
+            }
+        );
+    }
+
+    #[test]
+    fn test_ignore_version_range_falls_back_to_older() {
+        let d = dep("1.0.0", &[("2.0.0", 30), ("1.5.0", 60)]);
+        let verdict = policy(r#"ignore = ["tokio@>=2"]"#).evaluate(&d, SystemTime::now());
+        assert_eq!(verdict, UpdateVerdict::Available("1.5.0".into()));
+    }
+
+    #[test]
+    fn test_pin_below_major() {
+        let d = dep("1.0.0", &[("2.1.0", 30), ("1.9.0", 60)]);
+        let p = policy("[pin]\ntokio = \"1\"");
+        assert_eq!(
+            p.evaluate(&d, SystemTime::now()),
+            UpdateVerdict::Available("1.9.0".into())
+        );
+
+        let d = dep("1.9.0", &[("2.1.0", 30), ("1.9.0", 60)]);
+        assert!(matches!(
+            p.evaluate(&d, SystemTime::now()),
+            UpdateVerdict::Deferred {
+                reason: DeferReason::Pinned(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_allow_prerelease() {
+        let d = dep("1.0.0", &[("2.0.0-beta.1", 30)]);
+        let p = policy(r#"allow-prerelease = ["tokio"]"#);
+        assert_eq!(
+            p.evaluate(&d, SystemTime::now()),
+            UpdateVerdict::Available("2.0.0-beta.1".into())
+        );
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let d = dep("1.0.0", &[("1.2.0", 1), ("1.1.0", 10)]);
+        let p = policy("cooldown-days = 3");
+        assert_eq!(
+            p.evaluate(&d, SystemTime::now()),
+            UpdateVerdict::Available("1.1.0".into())
+        );
+
+        let d = dep("1.1.0", &[("1.2.0", 1), ("1.1.0", 10)]);
+        assert_eq!(
+            p.evaluate(&d, SystemTime::now()),
+            UpdateVerdict::Deferred {
+                latest: "1.2.0".into(),
+                reason: DeferReason::Cooldown
+            }
+        );
+    }
+
+    #[test]
+    fn test_yanked_versions_are_skipped() {
+        let mut d = dep("1.0.0", &[("1.2.0", 30), ("1.1.0", 60)]);
+        d.published_versions[0].yanked = true;
+        assert_eq!(
+            UpdatePolicy::default().evaluate(&d, SystemTime::now()),
+            UpdateVerdict::Available("1.1.0".into())
+        );
+    }
+
+    #[test]
+    fn test_unchecked_dependency_is_up_to_date() {
+        let mut d = dep("1.0.0", &[]);
+        d.latest_version = None;
+        assert_eq!(
+            policy(r#"ignore = ["tokio"]"#).evaluate(&d, SystemTime::now()),
+            UpdateVerdict::UpToDate
+        );
+    }
+}
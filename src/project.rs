@@ -3,12 +3,16 @@
 //! This module defines the core types for managing Rust projects and their dependencies.
 //! It handles project discovery, metadata parsing, and dependency tracking.
 
+use crate::config::ScanConfig;
+use crate::policy::{UpdatePolicy, UpdateVerdict};
+use crate::roots::ScanRoots;
+use crate::workspace::{ResolvedWorkspace, read_manifest};
 use cargo_lock::{Lockfile, Package as LockPackage};
-use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::sync::Arc;
 
 /// Status of a project's command execution
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,6 +36,8 @@ pub enum DependencyCheckStatus {
     Checking,
     /// Dependencies have been checked
     Checked,
+    /// The registry lookup failed (after retries), with the reason
+    Failed(String),
 }
 
 /// Status of git repository
@@ -55,6 +61,63 @@ pub enum ProjectCheckStatus {
     HasUpdates,
     /// All dependencies up to date (Green)
     UpToDate,
+    /// Some dependency lookups failed and no updates were found (Red)
+    CheckFailed,
+}
+
+/// Stable identity of a project: the canonical path of its Cargo.toml
+///
+/// Package names aren't unique across checkouts (two clones of one crate, or
+/// a `core` crate in several repos), so projects are keyed by manifest path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ProjectId(PathBuf);
+
+impl ProjectId {
+    /// Identify the project whose manifest lives in `dir`
+    ///
+    /// Symlinks are resolved when the manifest exists, so the same project
+    /// reached through different paths gets the same id.
+    pub fn from_dir(dir: &Path) -> Self {
+        let manifest = dir.join("Cargo.toml");
+        Self(fs::canonicalize(&manifest).unwrap_or_else(|_| crate::workspace::normalize(&manifest)))
+    }
+
+    /// Path of the project's Cargo.toml
+    pub fn manifest_path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ProjectId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.display())
+    }
+}
+
+/// A version of a crate as published on the registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublishedVersion {
+    /// Version number
+    pub version: String,
+    /// When the version was published, if known
+    pub released_at: Option<std::time::SystemTime>,
+    /// Whether the version has been yanked
+    pub yanked: bool,
+}
+
+/// One published version on the way from the locked version to the latest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionStep {
+    /// Version number
+    pub version: String,
+    /// When the version was published, if known
+    pub released_at: Option<std::time::SystemTime>,
+    /// Whether the version has been yanked
+    pub yanked: bool,
+    /// Whether `cargo update` can reach it without a Cargo.toml change
+    pub compatible: bool,
+    /// Whether this is the currently locked version
+    pub is_current: bool,
 }
 
 /// Represents a single dependency with version information
@@ -70,6 +133,12 @@ pub struct Dependency {
     pub check_status: DependencyCheckStatus,
     /// Timestamp of the last check
     pub last_checked: Option<std::time::SystemTime>,
+    /// Versions published on the registry, newest first (empty if unknown)
+    pub published_versions: Vec<PublishedVersion>,
+    /// Version requirement declared in Cargo.toml, if known
+    pub requirement: Option<String>,
+    /// Features enabled in Cargo.toml on top of the defaults
+    pub features: Vec<String>,
 }
 
 impl From<&LockPackage> for Dependency {
@@ -80,11 +149,21 @@ impl From<&LockPackage> for Dependency {
             latest_version: None,
             check_status: DependencyCheckStatus::NotChecked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         }
     }
 }
 
 impl Dependency {
+    /// Attach the requirement and features declared in Cargo.toml
+    pub fn with_spec(mut self, spec: &DependencySpec) -> Self {
+        self.requirement = spec.requirement.clone();
+        self.features = spec.features.clone();
+        self
+    }
+
     /// Check if a version string is a pre-release (beta, rc, alpha, etc.)
     pub fn is_prerelease(version: &str) -> bool {
         semver::Version::parse(version)
@@ -146,16 +225,47 @@ impl Dependency {
         }
     }
 
+    /// Apply an update policy to this dependency
+    pub fn update_verdict(&self, policy: &UpdatePolicy) -> UpdateVerdict {
+        policy.evaluate(self, std::time::SystemTime::now())
+    }
+
+    /// The newest update the policy allows that needs no Cargo.toml change
+    pub fn compatible_update(&self, policy: &UpdatePolicy) -> Option<String> {
+        policy.compatible_target(self, std::time::SystemTime::now())
+    }
+
+    /// Check if this dependency has an update that the policy allows
+    pub fn has_update(&self, policy: &UpdatePolicy) -> bool {
+        matches!(self.update_verdict(policy), UpdateVerdict::Available(_))
+    }
+
     /// Check if the update requires a major version bump
     /// Returns true if the latest version has a different major version than current
     /// This typically means `cargo update` can't auto-update - requires Cargo.toml change
     pub fn is_major_update(&self) -> bool {
-        let Some(ref latest) = self.latest_version else {
-            return false;
-        };
+        match self.latest_version {
+            Some(ref latest) => self.is_major_bump(latest),
+            None => false,
+        }
+    }
 
+    /// Check if moving to `target` crosses a semver-incompatible boundary
+    ///
+    /// Uses the declared requirement when it covers the locked version (so
+    /// `=1.2.3` or `~1.2` are respected), otherwise Cargo's default caret rules.
+    pub fn is_major_bump(&self, target: &str) -> bool {
         let current_semver = semver::Version::parse(&self.current_version).ok();
-        let latest_semver = semver::Version::parse(latest).ok();
+        let requirement = self
+            .requirement
+            .as_deref()
+            .and_then(|r| semver::VersionReq::parse(r).ok())
+            .filter(|req| current_semver.as_ref().is_some_and(|v| req.matches(v)));
+        if let (Some(requirement), Ok(target)) = (requirement, semver::Version::parse(target)) {
+            return !requirement.matches(&target);
+        }
+
+        let latest_semver = semver::Version::parse(target).ok();
 
         match (current_semver, latest_semver) {
             (Some(current), Some(latest_ver)) => {
@@ -170,6 +280,50 @@ impl Dependency {
         }
     }
 
+    /// Published versions from the locked one up to the latest, newest first
+    ///
+    /// Anything above the latest version (such as newer pre-releases) is left
+    /// out, as are versions that don't parse as semver.
+    pub fn version_steps(&self) -> Vec<VersionStep> {
+        let Ok(current) = semver::Version::parse(&self.current_version) else {
+            return Vec::new();
+        };
+        let latest = self
+            .latest_version
+            .as_deref()
+            .and_then(|v| semver::Version::parse(v).ok())
+            .map_or_else(|| current.clone(), |latest| latest.max(current.clone()));
+
+        let mut steps: Vec<(semver::Version, VersionStep)> = self
+            .published_versions
+            .iter()
+            .filter_map(|published| {
+                let version = semver::Version::parse(&published.version).ok()?;
+                if version < current || version > latest {
+                    return None;
+                }
+                let step = VersionStep {
+                    version: published.version.clone(),
+                    released_at: published.released_at,
+                    yanked: published.yanked,
+                    compatible: !self.is_major_bump(&published.version),
+                    is_current: version == current,
+                };
+                Some((version, step))
+            })
+            .collect();
+        steps.sort_by(|a, b| b.0.cmp(&a.0));
+
+        steps.into_iter().map(|(_, step)| step).collect()
+    }
+
+    /// Check if the locked version has been yanked from the registry
+    pub fn is_current_yanked(&self) -> bool {
+        self.published_versions
+            .iter()
+            .any(|v| v.yanked && v.version == self.current_version)
+    }
+
     /// Get a note about the update constraint, if any
     pub fn update_note(&self) -> Option<&'static str> {
         if !self.has_stable_update() {
@@ -191,8 +345,12 @@ impl Dependency {
 /// of a workspace.
 #[derive(Debug, Clone)]
 pub struct Project {
+    /// Stable identity (canonical manifest path) used to key the project everywhere
+    pub id: ProjectId,
     /// The name of the project from Cargo.toml
     pub name: String,
+    /// Name shown in the UI, with a path hint when several projects share `name`
+    pub display_name: String,
     /// The path to the project's root directory
     pub path: PathBuf,
     /// Current status of command execution
@@ -201,6 +359,18 @@ pub struct Project {
     pub version: String,
     /// List of project authors
     pub authors: Vec<String>,
+    /// Rust edition, if declared
+    pub edition: Option<String>,
+    /// Minimum supported Rust version (`rust-version`), if declared
+    pub rust_version: Option<String>,
+    /// SPDX license expression, if declared
+    pub license: Option<String>,
+    /// `keywords` from the manifest
+    pub keywords: Vec<String>,
+    /// `categories` from the manifest
+    pub categories: Vec<String>,
+    /// Tags from the manifest, the config and the UI (resolved when added to app state)
+    pub tags: Vec<String>,
     /// All dependencies of the project
     pub dependencies: Vec<Dependency>,
     /// If part of a workspace, the path to the workspace root
@@ -217,11 +387,17 @@ pub struct Project {
     pub total_size: Option<u64>,
     /// Size of the target/ directory in bytes (potential savings from cargo clean)
     pub target_size: Option<u64>,
+    /// Newest modification time of the manifest, lockfile and sources (checked asynchronously)
+    pub modified: Option<std::time::SystemTime>,
+    /// Effective update policy (global config layered with `.carwash.toml`)
+    ///
+    /// Shared so that cloning projects into the tree and wizard stays cheap.
+    pub update_policy: Arc<UpdatePolicy>,
 }
 
 impl Project {
-    #[allow(dead_code)] // Kept for potential async git checking in future
-    fn check_git_status(path: &Path) -> GitStatus {
+    /// Whether the project has uncommitted changes, from `git status`
+    pub fn check_git_status(path: &Path) -> GitStatus {
         use std::process::Command;
         // Run git status --porcelain to check for modifications
         // We run it on the specific path to handle monorepos correctly
@@ -244,32 +420,19 @@ impl Project {
         }
     }
 
-    fn from_toml(
+    pub(crate) fn from_toml(
         path: &Path,
         toml: &CargoToml,
-        workspace_root: Option<PathBuf>,
-        workspace_name: Option<String>,
+        workspace: Option<&ResolvedWorkspace>,
     ) -> Option<Self> {
         let package = toml.package.as_ref()?;
         let project_path = path.parent()?.to_path_buf();
+        let workspace_root = workspace.map(|ws| ws.root.clone());
+        let inherited = workspace.map(|ws| &ws.inherited);
+        let inherited_package = inherited.map(|i| &i.package);
 
-        // Collect dependency names from this crate's Cargo.toml
-        let mut declared_deps: HashSet<String> = HashSet::new();
-
-        // Add regular dependencies
-        for dep_name in toml.dependencies.keys() {
-            declared_deps.insert(dep_name.clone());
-        }
-
-        // Add dev-dependencies
-        for dep_name in toml.dev_dependencies.keys() {
-            declared_deps.insert(dep_name.clone());
-        }
-
-        // Add build-dependencies
-        for dep_name in toml.build_dependencies.keys() {
-            declared_deps.insert(dep_name.clone());
-        }
+        // Dependencies declared in this crate's Cargo.toml (regular, dev and build)
+        let declared_deps = declared_dependencies(toml, inherited);
 
         // For workspace members, try to load Cargo.lock from workspace root first
         let lockfile_path = if let Some(ref ws_root) = workspace_root {
@@ -288,32 +451,53 @@ impl Project {
                 .packages
                 .iter()
                 // Filter to only dependencies declared in this crate's Cargo.toml
-                .filter(|pkg| declared_deps.contains(pkg.name.as_str()))
-                .map(Dependency::from)
+                .filter_map(|pkg| {
+                    let spec = declared_deps.get(pkg.name.as_str())?;
+                    Some(Dependency::from(pkg).with_spec(spec))
+                })
                 .collect()
         } else {
             Vec::new()
         };
 
-        let authors = package.authors_vec();
-
         Some(Self {
+            id: ProjectId::from_dir(&project_path),
             name: package.name.clone(),
+            display_name: package.name.clone(),
             path: project_path,
             status: ProjectStatus::Pending,
-            version: package.version_string(),
-            authors,
+            version: package.version_string(inherited_package),
+            authors: package.authors_vec(inherited_package),
+            edition: package.edition(inherited_package),
+            rust_version: package.rust_version(inherited_package),
+            license: package.license(inherited_package),
+            keywords: package.keywords(inherited_package),
+            categories: package.categories(inherited_package),
+            tags: Vec::new(), // Resolved when added to app state
             dependencies,
             workspace_root,
-            workspace_name,
+            workspace_name: workspace.map(|ws| ws.name.clone()),
             cargo_lock_hash: None, // No hash available here, will be calculated later
             check_status: ProjectCheckStatus::Unchecked, // Start as unchecked
             git_status: GitStatus::Unknown, // Check git status asynchronously
             total_size: None,      // Calculate on demand
             target_size: None,     // Calculate on demand
+            modified: None,        // Check asynchronously
+            update_policy: Arc::default(), // Resolved when added to app state
         })
     }
 
+    /// The Cargo.lock this project resolves against (the workspace's, if it has one)
+    pub fn lockfile_path(&self) -> PathBuf {
+        if let Some(ws_root) = &self.workspace_root {
+            let ws_lockfile = ws_root.join("Cargo.lock");
+            if ws_lockfile.exists() {
+                return ws_lockfile;
+            }
+        }
+        self.path.join("Cargo.lock")
+    }
+
     /// Reload dependencies from Cargo.lock after an update
     ///
     /// This method re-parses Cargo.toml and Cargo.lock from disk to get the latest
@@ -330,52 +514,33 @@ impl Project {
         let toml: CargoToml = toml::from_str(&toml_content)
             .map_err(|e| format!("Failed to parse Cargo.toml: {}", e))?;
 
-        // Collect declared dependency names
-        let mut declared_deps: HashSet<String> = HashSet::new();
-        for dep_name in toml.dependencies.keys() {
-            declared_deps.insert(dep_name.clone());
-        }
-        for dep_name in toml.dev_dependencies.keys() {
-            declared_deps.insert(dep_name.clone());
-        }
-        for dep_name in toml.build_dependencies.keys() {
-            declared_deps.insert(dep_name.clone());
-        }
-
-        // Determine which Cargo.lock to use (workspace or project)
-        let lockfile_path = if let Some(ref ws_root) = self.workspace_root {
-            let ws_lockfile = ws_root.join("Cargo.lock");
-            if ws_lockfile.exists() {
-                ws_lockfile
-            } else {
-                self.path.join("Cargo.lock")
-            }
-        } else {
-            self.path.join("Cargo.lock")
-        };
+        // Collect declared dependencies, resolving workspace inheritance
+        let inherited = self.workspace_root.as_deref().map(InheritableFields::load);
+        let declared_deps = declared_dependencies(&toml, inherited.as_ref());
 
         // Re-parse Cargo.lock
-        let lockfile = Lockfile::load(&lockfile_path)
+        let lockfile = Lockfile::load(self.lockfile_path())
             .map_err(|e| format!("Failed to load Cargo.lock: {}", e))?;
 
         // Extract current versions for declared dependencies
         let mut new_deps: Vec<Dependency> = lockfile
             .packages
             .iter()
-            .filter(|pkg| declared_deps.contains(pkg.name.as_str()))
-            .map(|pkg| {
+            .filter_map(|pkg| Some((pkg, declared_deps.get(pkg.name.as_str())?)))
+            .map(|(pkg, spec)| {
                 // Try to preserve latest_version, check_status, and last_checked from existing deps
                 let existing = self
                     .dependencies
                     .iter()
                     .find(|d| d.name == pkg.name.as_str());
 
-                let mut dep = Dependency::from(pkg);
+                let mut dep = Dependency::from(pkg).with_spec(spec);
                 if let Some(existing_dep) = existing {
                     // Preserve the cached check results if they exist
                     dep.latest_version = existing_dep.latest_version.clone();
                     dep.check_status = existing_dep.check_status.clone();
                     dep.last_checked = existing_dep.last_checked;
+                    dep.published_versions = existing_dep.published_versions.clone();
                 }
                 dep
             })
@@ -393,33 +558,67 @@ impl Project {
     /// Compute the project check status based on current dependencies
     ///
     /// Examines all dependencies to determine if any have available updates.
-    /// Uses `has_stable_update()` to properly handle pre-release versions -
-    /// a stable version won't be flagged for update to a pre-release.
-    /// Returns `HasUpdates` if any dependency has a newer stable version available,
-    /// otherwise returns `UpToDate`.
-    pub fn compute_check_status_from_deps(deps: &[Dependency]) -> ProjectCheckStatus {
-        let has_updates = deps.iter().any(|d| d.has_stable_update());
+    /// The project's update policy decides what counts: ignored, pinned and
+    /// cooling-down releases are deferred rather than flagged, and a stable
+    /// version won't be flagged for update to a pre-release unless allowed.
+    /// Returns `HasUpdates` if any dependency has an allowed update,
+    /// `CheckFailed` if any lookup failed, otherwise returns `UpToDate`.
+    pub fn compute_check_status_from_deps(
+        deps: &[Dependency],
+        policy: &UpdatePolicy,
+    ) -> ProjectCheckStatus {
+        let has_updates = deps.iter().any(|d| d.has_update(policy));
 
         if has_updates {
             ProjectCheckStatus::HasUpdates
+        } else if deps
+            .iter()
+            .any(|d| matches!(d.check_status, DependencyCheckStatus::Failed(_)))
+        {
+            ProjectCheckStatus::CheckFailed
         } else {
             ProjectCheckStatus::UpToDate
         }
     }
 
+    /// Dependencies with an update the project's policy allows
+    pub fn outdated_count(&self) -> usize {
+        self.dependencies
+            .iter()
+            .filter(|d| d.has_update(&self.update_policy))
+            .count()
+    }
+
+    /// Newest modification time of the manifest, lockfile, build script and sources
+    pub fn last_modified(path: &Path) -> Option<std::time::SystemTime> {
+        let files = ["Cargo.toml", "Cargo.lock", "build.rs"]
+            .iter()
+            .filter_map(|name| fs::metadata(path.join(name)).ok());
+        let sources = walkdir::WalkDir::new(path.join("src"))
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.metadata().ok());
+        files
+            .chain(sources)
+            .filter_map(|metadata| metadata.modified().ok())
+            .max()
+    }
+
     /// Calculate the total size of the project directory
     pub fn calculate_total_size(&self) -> Option<u64> {
         calculate_directory_size(&self.path)
     }
 
-    /// Calculate the size of the target/ directory (potential cargo clean savings)
+    /// Calculate the size of the target directory (potential cargo clean savings)
+    ///
+    /// The directory is resolved like cargo does, so it may be shared with other projects.
     pub fn calculate_target_size(&self) -> Option<u64> {
-        let target_path = self.path.join("target");
-        if target_path.exists() && target_path.is_dir() {
-            calculate_directory_size(&target_path)
-        } else {
-            Some(0)
-        }
+        calculate_target_dir_size(&self.target_dir())
+    }
+
+    /// The target directory cargo builds this project into
+    pub fn target_dir(&self) -> PathBuf {
+        crate::target_dir::resolve_target_dir(&self.path, self.workspace_root.as_deref())
     }
 
     /// Format size in human-readable format (KB, MB, GB, TB)
@@ -443,18 +642,21 @@ impl Project {
     }
 }
 
+/// Size of a target directory, zero if nothing was built yet
+pub fn calculate_target_dir_size(path: &Path) -> Option<u64> {
+    if path.is_dir() {
+        calculate_directory_size(path)
+    } else {
+        Some(0)
+    }
+}
+
 /// Calculate the total size of a directory and all its contents
+///
+/// Walks in parallel and reuses cached results for unchanged directories; see
+/// [`crate::disk_usage`].
 pub fn calculate_directory_size(path: &Path) -> Option<u64> {
-    use walkdir::WalkDir;
-
-    WalkDir::new(path)
-        .follow_links(false) // Don't follow symlinks to avoid infinite loops
-        .into_iter()
-        .filter_map(|entry| entry.ok())
-        .filter_map(|entry| entry.metadata().ok())
-        .filter(|metadata| metadata.is_file())
-        .map(|metadata| metadata.len())
-        .reduce(|acc, size| acc + size)
+    crate::disk_usage::directory_size(path)
 }
 
 #[derive(Debug, Deserialize)]
@@ -467,6 +669,24 @@ pub struct CargoToml {
     pub dev_dependencies: HashMap<String, toml::Value>,
     #[serde(default, rename = "build-dependencies")]
     pub build_dependencies: HashMap<String, toml::Value>,
+    /// Features and the features or dependencies each one enables
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub lib: Option<TargetSpec>,
+    #[serde(default)]
+    pub bin: Vec<TargetSpec>,
+    #[serde(default)]
+    pub example: Vec<TargetSpec>,
+    #[serde(default)]
+    pub bench: Vec<TargetSpec>,
+}
+
+/// A `[lib]`, `[[bin]]`, `[[example]]` or `[[bench]]` section
+#[derive(Debug, Default, Deserialize)]
+pub struct TargetSpec {
+    pub name: Option<String>,
+    pub path: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -478,48 +698,253 @@ pub struct Package {
     /// Authors can be an array OR workspace-inherited (authors.workspace = true)
     #[serde(default)]
     pub authors: Option<toml::Value>,
+    #[serde(default)]
+    pub edition: Option<toml::Value>,
+    #[serde(default, rename = "rust-version")]
+    pub rust_version: Option<toml::Value>,
+    #[serde(default)]
+    pub license: Option<toml::Value>,
+    #[serde(default)]
+    pub keywords: Option<toml::Value>,
+    #[serde(default)]
+    pub categories: Option<toml::Value>,
+    /// Build script path, or `false` to disable the default `build.rs`
+    #[serde(default)]
+    pub build: Option<toml::Value>,
+    /// README path, `false` for none, or workspace-inherited
+    #[serde(default)]
+    pub readme: Option<toml::Value>,
+    #[serde(default)]
+    pub autobins: Option<bool>,
+    #[serde(default)]
+    pub autoexamples: Option<bool>,
+    #[serde(default)]
+    pub autobenches: Option<bool>,
+    /// Explicit path to the workspace root (`package.workspace`)
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+/// Whether a manifest value is `{ workspace = true }`
+fn is_inherited(value: &toml::Value) -> bool {
+    value.get("workspace").and_then(|w| w.as_bool()) == Some(true)
+}
+
+/// Read a list of strings, taking it from `[workspace.package]` when inherited
+fn inherit_list(value: &Option<toml::Value>, inherited: Option<&Vec<String>>) -> Vec<String> {
+    match value {
+        Some(toml::Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        Some(v) if is_inherited(v) => inherited.cloned().unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+/// Read a string field, taking it from `[workspace.package]` when inherited
+fn inherit_string(value: &Option<toml::Value>, inherited: Option<&String>) -> Option<String> {
+    match value {
+        Some(toml::Value::String(s)) => Some(s.clone()),
+        Some(v) if is_inherited(v) => inherited.cloned(),
+        _ => None,
+    }
 }
 
 impl Package {
-    /// Extract version as a string, handling workspace inheritance
-    pub fn version_string(&self) -> String {
-        match &self.version {
-            Some(toml::Value::String(s)) => s.clone(),
-            Some(toml::Value::Table(t))
-                if t.get("workspace") == Some(&toml::Value::Boolean(true)) =>
-            {
-                "workspace".to_string()
-            }
-            _ => "0.0.0".to_string(),
-        }
+    /// Extract version as a string, resolving workspace inheritance
+    pub fn version_string(&self, workspace: Option<&WorkspacePackage>) -> String {
+        inherit_string(&self.version, workspace.and_then(|w| w.version.as_ref()))
+            .unwrap_or_else(|| "0.0.0".to_string())
     }
 
-    /// Extract authors as a vector, handling workspace inheritance
-    pub fn authors_vec(&self) -> Vec<String> {
-        match &self.authors {
-            Some(toml::Value::Array(arr)) => arr
-                .iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect(),
-            Some(toml::Value::Table(t))
-                if t.get("workspace") == Some(&toml::Value::Boolean(true)) =>
-            {
-                vec![] // Workspace-inherited, actual values unknown
-            }
-            _ => vec![],
-        }
+    /// Extract authors as a vector, resolving workspace inheritance
+    pub fn authors_vec(&self, workspace: Option<&WorkspacePackage>) -> Vec<String> {
+        inherit_list(&self.authors, workspace.and_then(|w| w.authors.as_ref()))
+    }
+
+    /// `keywords`, resolving workspace inheritance
+    pub fn keywords(&self, workspace: Option<&WorkspacePackage>) -> Vec<String> {
+        inherit_list(&self.keywords, workspace.and_then(|w| w.keywords.as_ref()))
+    }
+
+    /// `categories`, resolving workspace inheritance
+    pub fn categories(&self, workspace: Option<&WorkspacePackage>) -> Vec<String> {
+        inherit_list(
+            &self.categories,
+            workspace.and_then(|w| w.categories.as_ref()),
+        )
+    }
+
+    /// Rust edition, resolving workspace inheritance
+    pub fn edition(&self, workspace: Option<&WorkspacePackage>) -> Option<String> {
+        inherit_string(&self.edition, workspace.and_then(|w| w.edition.as_ref()))
+    }
+
+    /// `rust-version`, resolving workspace inheritance
+    pub fn rust_version(&self, workspace: Option<&WorkspacePackage>) -> Option<String> {
+        inherit_string(
+            &self.rust_version,
+            workspace.and_then(|w| w.rust_version.as_ref()),
+        )
+    }
+
+    /// License expression, resolving workspace inheritance
+    pub fn license(&self, workspace: Option<&WorkspacePackage>) -> Option<String> {
+        inherit_string(&self.license, workspace.and_then(|w| w.license.as_ref()))
     }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Workspace {
+    #[serde(default)]
     pub members: Vec<String>,
-    // Capture all other workspace fields (package, lints, dependencies, etc.)
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default, rename = "default-members")]
+    pub default_members: Option<Vec<String>>,
+    /// Fields members can inherit with `field.workspace = true`
+    #[serde(default)]
+    pub package: Option<WorkspacePackage>,
+    /// Dependencies members can inherit with `dep.workspace = true`
+    #[serde(default)]
+    pub dependencies: HashMap<String, toml::Value>,
+    // Capture all other workspace fields (lints, metadata, etc.)
     // This ensures we can parse ANY workspace Cargo.toml without failures
     #[serde(flatten)]
     pub other: std::collections::HashMap<String, toml::Value>,
 }
 
+/// The inheritable subset of `[workspace.package]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WorkspacePackage {
+    pub version: Option<String>,
+    pub authors: Option<Vec<String>>,
+    pub edition: Option<String>,
+    pub rust_version: Option<String>,
+    pub license: Option<String>,
+    pub keywords: Option<Vec<String>>,
+    pub categories: Option<Vec<String>>,
+}
+
+/// What a workspace root hands down to its members
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InheritableFields {
+    /// `[workspace.package]`
+    pub package: WorkspacePackage,
+    /// `[workspace.dependencies]`, keyed by the name members refer to them by
+    pub dependencies: HashMap<String, DependencySpec>,
+}
+
+impl InheritableFields {
+    /// Collect the inheritable fields of a `[workspace]` section
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        Self {
+            package: workspace.package.clone().unwrap_or_default(),
+            dependencies: workspace
+                .dependencies
+                .iter()
+                .map(|(name, value)| (name.clone(), DependencySpec::parse(value)))
+                .collect(),
+        }
+    }
+
+    /// Read the inheritable fields from the workspace root in `root`
+    pub fn load(root: &Path) -> Self {
+        read_manifest(&root.join("Cargo.toml"))
+            .and_then(|toml| toml.workspace)
+            .map(|workspace| Self::from_workspace(&workspace))
+            .unwrap_or_default()
+    }
+}
+
+/// A dependency as declared in Cargo.toml, after workspace inheritance
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencySpec {
+    /// Crate name when the dependency is renamed with `package = "..."`
+    pub package: Option<String>,
+    /// Version requirement (absent for some path and git dependencies)
+    pub requirement: Option<String>,
+    /// Features enabled on top of the defaults
+    pub features: Vec<String>,
+}
+
+impl DependencySpec {
+    /// Parse a dependency entry as written, without resolving inheritance
+    pub fn parse(value: &toml::Value) -> Self {
+        match value {
+            toml::Value::String(requirement) => Self {
+                requirement: Some(requirement.clone()),
+                ..Self::default()
+            },
+            toml::Value::Table(table) => Self {
+                package: table
+                    .get("package")
+                    .and_then(|p| p.as_str())
+                    .map(String::from),
+                requirement: table
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                features: table
+                    .get("features")
+                    .and_then(|f| f.as_array())
+                    .map(|features| {
+                        features
+                            .iter()
+                            .filter_map(|f| f.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Parse a member's dependency entry, resolving `workspace = true`
+    ///
+    /// As in Cargo, features listed by the member add to the workspace ones.
+    pub fn resolve(name: &str, value: &toml::Value, inherited: Option<&InheritableFields>) -> Self {
+        let spec = Self::parse(value);
+        if !is_inherited(value) {
+            return spec;
+        }
+
+        let mut resolved = inherited
+            .and_then(|i| i.dependencies.get(name))
+            .cloned()
+            .unwrap_or_default();
+        for feature in spec.features {
+            if !resolved.features.contains(&feature) {
+                resolved.features.push(feature);
+            }
+        }
+        resolved
+    }
+}
+
+/// Dependencies declared in a manifest, keyed by crate name
+///
+/// Normal dependencies win over dev- and build-dependencies of the same crate.
+pub fn declared_dependencies(
+    toml: &CargoToml,
+    inherited: Option<&InheritableFields>,
+) -> HashMap<String, DependencySpec> {
+    let mut declared = HashMap::new();
+    for (name, value) in toml
+        .dependencies
+        .iter()
+        .chain(&toml.dev_dependencies)
+        .chain(&toml.build_dependencies)
+    {
+        let spec = DependencySpec::resolve(name, value, inherited);
+        let crate_name = spec.package.clone().unwrap_or_else(|| name.clone());
+        declared.entry(crate_name).or_insert(spec);
+    }
+    declared
+}
+
 /// Recursively finds all Rust projects in the given directory path.
 ///
 /// This function scans for Cargo.toml files, identifies workspaces,
@@ -543,114 +968,12 @@ pub struct Workspace {
 /// // Projects will be sorted with workspace members grouped together
 /// ```
 pub fn find_rust_projects(path: &str) -> Vec<Project> {
-    let mut projects = HashMap::new();
-    let mut workspaces: HashMap<PathBuf, (String, Vec<PathBuf>)> = HashMap::new();
-
-    // Convert relative path to absolute path
-    let base_path = if Path::new(path).is_absolute() {
-        PathBuf::from(path)
-    } else {
-        std::env::current_dir()
-            .ok()
-            .map(|cwd| cwd.join(path))
-            .unwrap_or_else(|| PathBuf::from(path))
-    };
-
-    // Build gitignore matcher
-    let mut builder = ignore::WalkBuilder::new(&base_path);
-    builder
-        .follow_links(true)
-        .git_ignore(true)
-        .git_global(false)
-        .git_exclude(false)
-        .hidden(true) // We'll filter hidden ourselves
-        .filter_entry(|e| {
-            // Skip common directories that won't contain projects
-            let file_name = e.file_name().to_string_lossy();
-            !file_name.starts_with('.') && file_name != "target" && file_name != "node_modules"
-        });
-
-    // First pass: Find all Cargo.toml files and identify workspaces
-    for entry in builder
-        .build()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_name() == "Cargo.toml")
-    {
-        let manifest_path = entry.path();
-
-        if let Ok(content) = fs::read_to_string(manifest_path) {
-            if let Ok(toml) = toml::from_str::<CargoToml>(&content) {
-                // Check if this is a workspace root
-                if let Some(workspace) = &toml.workspace {
-                    let root_path = manifest_path.parent().unwrap().to_path_buf();
-                    let mut member_paths = Vec::new();
-
-                    // Resolve workspace members
-                    for member in &workspace.members {
-                        let member_path = root_path.join(member).join("Cargo.toml");
-                        if member_path.exists() {
-                            member_paths.push(member_path);
-                        }
-                    }
-
-                    // Get workspace name (use directory name as fallback)
-                    let workspace_name = if let Some(pkg) = &toml.package {
-                        pkg.name.clone()
-                    } else {
-                        root_path
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("workspace")
-                            .to_string()
-                    };
-
-                    workspaces.insert(root_path, (workspace_name, member_paths));
-                }
-            }
-        }
-    }
-
-    // Second pass: Create projects with workspace information
-    for entry in WalkDir::new(&base_path)
-        .follow_links(true)
-        .into_iter()
-        .filter_entry(|e| {
-            let file_name = e.file_name().to_string_lossy();
-            !file_name.starts_with('.') && file_name != "target" && file_name != "node_modules"
-        })
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_name() == "Cargo.toml")
-    {
-        let manifest_path = entry.path();
-
-        if let Ok(content) = fs::read_to_string(manifest_path) {
-            if let Ok(toml) = toml::from_str::<CargoToml>(&content) {
-                // Find if this project belongs to a workspace
-                let mut workspace_info: Option<(PathBuf, String)> = None;
-
-                for (ws_root, (ws_name, members)) in &workspaces {
-                    if members.contains(&manifest_path.to_path_buf()) {
-                        workspace_info = Some((ws_root.clone(), ws_name.clone()));
-                        break;
-                    }
-                }
-
-                // Add the project if it has a package section
-                if let Some(project) = Project::from_toml(
-                    manifest_path,
-                    &toml,
-                    workspace_info.as_ref().map(|(root, _)| root.clone()),
-                    workspace_info.as_ref().map(|(_, name)| name.clone()),
-                ) {
-                    projects.insert(manifest_path.to_path_buf(), project);
-                }
-            }
-        }
-    }
+    crate::scan::scan_rust_projects(path, &ScanConfig::default(), |_, _| {})
+}
 
-    // Convert to vec and sort by workspace and name
-    let mut result: Vec<Project> = projects.into_values().collect();
-    result.sort_by(|a, b| {
+/// Sort projects by workspace and name, workspace members first
+pub fn sort_projects(projects: &mut [Project]) {
+    projects.sort_by(|a, b| {
         match (&a.workspace_name, &b.workspace_name) {
             (Some(ws_a), Some(ws_b)) if ws_a == ws_b => {
                 // Same workspace: sort by project name
@@ -665,8 +988,53 @@ pub fn find_rust_projects(path: &str) -> Vec<Project> {
             (None, None) => a.name.cmp(&b.name), // Standalone projects sorted by name
         }
     });
+}
+
+/// Give projects that share a package name distinct display names
+///
+/// Each clashing project gets the shortest trailing part of its path that
+/// tells it apart from the others, e.g. `core (repo-a/core)`.
+pub fn disambiguate_display_names(projects: &mut [Project]) {
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, project) in projects.iter().enumerate() {
+        by_name.entry(project.name.clone()).or_default().push(index);
+    }
+
+    for indices in by_name.into_values() {
+        if indices.len() < 2 {
+            let project = &mut projects[indices[0]];
+            project.display_name = project.name.clone();
+            continue;
+        }
 
-    result
+        let components: Vec<Vec<String>> = indices
+            .iter()
+            .map(|&i| {
+                projects[i]
+                    .path
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .collect();
+        let suffix = |parts: &[String], len: usize| -> String {
+            parts[parts.len().saturating_sub(len)..].join("/")
+        };
+
+        let longest = components.iter().map(Vec::len).max().unwrap_or(0);
+        let len = (1..=longest)
+            .find(|&len| {
+                let suffixes: HashSet<String> =
+                    components.iter().map(|parts| suffix(parts, len)).collect();
+                suffixes.len() == components.len()
+            })
+            .unwrap_or(longest);
+
+        for (&i, parts) in indices.iter().zip(&components) {
+            let project = &mut projects[i];
+            project.display_name = format!("{} ({})", project.name, suffix(parts, len));
+        }
+    }
 }
 
 /// Build a hierarchical tree of projects organized by directory structure
@@ -698,6 +1066,27 @@ pub fn build_project_tree(path: &str) -> crate::tree::TreeNode {
     build_tree_level_only(&base_path, 0)
 }
 
+/// Build the tree root for a set of scan roots (only the root level)
+///
+/// A single root is the tree root itself; several roots are grouped so each
+/// becomes a top-level node.
+pub fn build_roots_tree(roots: &ScanRoots) -> crate::tree::TreeNode {
+    match roots.paths.as_slice() {
+        [root] => build_tree_level_only(root, 0),
+        paths => {
+            let nodes = paths
+                .iter()
+                .map(|path| {
+                    let mut node = build_tree_level_only(path, 1);
+                    node.expanded = true;
+                    node
+                })
+                .collect();
+            crate::tree::TreeNode::roots_group(roots.label(), nodes)
+        }
+    }
+}
+
 /// Build a single level of the tree (non-recursive)
 fn build_tree_level_only(dir_path: &Path, depth: usize) -> crate::tree::TreeNode {
     let dir_name = dir_path
@@ -845,23 +1234,43 @@ pub fn load_directory_children_async(
                                 }
                             } else if toml.package.is_some() {
                                 // It's a standalone project (no workspace) - add as project node
+                                let name = toml
+                                    .package
+                                    .as_ref()
+                                    .map(|p| p.name.clone())
+                                    .unwrap_or_else(|| file_name.to_string());
                                 let project = Project {
-                                    name: toml
-                                        .package
-                                        .as_ref()
-                                        .map(|p| p.name.clone())
-                                        .unwrap_or_else(|| file_name.to_string()),
+                                    id: ProjectId::from_dir(&path),
+                                    display_name: name.clone(),
+                                    name,
                                     path: path.clone(),
                                     version: toml
                                         .package
                                         .as_ref()
-                                        .map(|p| p.version_string())
+                                        .map(|p| p.version_string(None))
                                         .unwrap_or_default(),
                                     authors: toml
                                         .package
                                         .as_ref()
-                                        .map(|p| p.authors_vec())
+                                        .map(|p| p.authors_vec(None))
+                                        .unwrap_or_default(),
+                                    edition: toml.package.as_ref().and_then(|p| p.edition(None)),
+                                    rust_version: toml
+                                        .package
+                                        .as_ref()
+                                        .and_then(|p| p.rust_version(None)),
+                                    license: toml.package.as_ref().and_then(|p| p.license(None)),
+                                    keywords: toml
+                                        .package
+                                        .as_ref()
+                                        .map(|p| p.keywords(None))
                                         .unwrap_or_default(),
+                                    categories: toml
+                                        .package
+                                        .as_ref()
+                                        .map(|p| p.categories(None))
+                                        .unwrap_or_default(),
+                                    tags: Vec::new(),
                                     dependencies: Vec::new(), // Will be loaded on-demand when needed
                                     workspace_root: None,
                                     workspace_name: None,
@@ -871,6 +1280,8 @@ pub fn load_directory_children_async(
                                     git_status: GitStatus::Unknown, // Check asynchronously
                                     total_size: None,               // Calculate on demand
                                     target_size: None,              // Calculate on demand
+                                    modified: None,                 // Check asynchronously
+                                    update_policy: Arc::default(),
                                 };
                                 let project_node =
                                     crate::tree::TreeNode::project(project, depth + 1);
@@ -1011,6 +1422,8 @@ version.workspace = true
 edition.workspace = true
 authors.workspace = true
 license.workspace = true
+keywords.workspace = true
+categories = ["development-tools"]
 publish = true
 
 [dependencies]
@@ -1028,8 +1441,27 @@ serde = { workspace = true }
 
         let package = parsed.package.unwrap();
         assert_eq!(package.name, "test-crate");
-        assert_eq!(package.version_string(), "workspace");
-        assert_eq!(package.authors_vec(), Vec::<String>::new()); // workspace-inherited, so empty
+        // Without the workspace root the inherited values are unknown
+        assert_eq!(package.version_string(None), "0.0.0");
+        assert_eq!(package.authors_vec(None), Vec::<String>::new());
+
+        let inherited = WorkspacePackage {
+            version: Some("1.2.0".into()),
+            authors: Some(vec!["Jane Doe".into()]),
+            edition: Some("2021".into()),
+            rust_version: Some("1.80".into()),
+            license: Some("MIT".into()),
+            keywords: Some(vec!["cli".into()]),
+            categories: None,
+        };
+        assert_eq!(package.version_string(Some(&inherited)), "1.2.0");
+        assert_eq!(package.authors_vec(Some(&inherited)), ["Jane Doe"]);
+        assert_eq!(package.edition(Some(&inherited)).as_deref(), Some("2021"));
+        assert_eq!(package.license(Some(&inherited)).as_deref(), Some("MIT"));
+        assert_eq!(package.keywords(Some(&inherited)), ["cli"]);
+        assert_eq!(package.categories(Some(&inherited)), ["development-tools"]);
+        // Not marked as inherited, so not taken from the workspace
+        assert_eq!(package.rust_version(Some(&inherited)), None);
     }
 
     #[test]
@@ -1096,6 +1528,9 @@ serde = { workspace = true }
             latest_version: Some("1.1.0".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert!(dep.has_stable_update());
     }
@@ -1109,6 +1544,9 @@ serde = { workspace = true }
             latest_version: Some("2.0.0-beta.1".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert!(!dep.has_stable_update());
     }
@@ -1122,6 +1560,9 @@ serde = { workspace = true }
             latest_version: Some("2.0.0".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert!(dep.has_stable_update());
     }
@@ -1135,6 +1576,9 @@ serde = { workspace = true }
             latest_version: Some("2.0.0-beta.2".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert!(dep.has_stable_update());
     }
@@ -1148,6 +1592,9 @@ serde = { workspace = true }
             latest_version: Some("1.0.0".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert!(!dep.has_stable_update());
     }
@@ -1161,6 +1608,9 @@ serde = { workspace = true }
             latest_version: None,
             check_status: DependencyCheckStatus::NotChecked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert!(!dep.has_stable_update());
     }
@@ -1174,6 +1624,9 @@ serde = { workspace = true }
             latest_version: Some("2.0.0".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert_eq!(dep1.update_type(), Some("stable"));
 
@@ -1184,6 +1637,9 @@ serde = { workspace = true }
             latest_version: Some("2.0.0-beta.1".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert_eq!(dep2.update_type(), Some("pre-release"));
 
@@ -1194,6 +1650,9 @@ serde = { workspace = true }
             latest_version: Some("2.0.0".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert_eq!(dep3.update_type(), Some("stable"));
 
@@ -1204,6 +1663,9 @@ serde = { workspace = true }
             latest_version: Some("1.0.0".into()),
             check_status: DependencyCheckStatus::Checked,
             last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
         };
         assert_eq!(dep4.update_type(), None);
     }
@@ -1266,4 +1728,85 @@ serde = { workspace = true }
         // Flattened tree should have at least the root node
         assert!(!flattened.items.is_empty());
     }
+
+    #[test]
+    fn test_compute_check_status_with_failed_lookup() {
+        let policy = UpdatePolicy::default();
+        let failed = Dependency {
+            name: "failed".into(),
+            current_version: "1.0.0".into(),
+            latest_version: None,
+            check_status: DependencyCheckStatus::Failed("timed out".into()),
+            last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
+        };
+        let outdated = Dependency {
+            name: "outdated".into(),
+            current_version: "1.0.0".into(),
+            latest_version: Some("1.1.0".into()),
+            check_status: DependencyCheckStatus::Checked,
+            last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
+        };
+
+        assert_eq!(
+            Project::compute_check_status_from_deps(std::slice::from_ref(&failed), &policy),
+            ProjectCheckStatus::CheckFailed
+        );
+        // Known updates take precedence over failed lookups
+        assert_eq!(
+            Project::compute_check_status_from_deps(&[failed, outdated], &policy),
+            ProjectCheckStatus::HasUpdates
+        );
+    }
+
+    #[test]
+    fn test_version_steps_between_current_and_latest() {
+        let published = |version: &str, yanked: bool| PublishedVersion {
+            version: version.into(),
+            released_at: None,
+            yanked,
+        };
+        let dep = Dependency {
+            name: "toml".into(),
+            current_version: "0.8.1".into(),
+            latest_version: Some("0.9.0".into()),
+            check_status: DependencyCheckStatus::Checked,
+            last_checked: None,
+            published_versions: vec![
+                published("0.10.0-alpha.1", false),
+                published("0.8.2", false),
+                published("0.9.0", false),
+                published("0.8.1", true),
+                published("0.8.0", false),
+            ],
+            requirement: None,
+            features: Vec::new(),
+        };
+
+        let steps = dep.version_steps();
+        let versions: Vec<&str> = steps.iter().map(|s| s.version.as_str()).collect();
+        assert_eq!(versions, ["0.9.0", "0.8.2", "0.8.1"]);
+        assert!(!steps[0].compatible);
+        assert!(steps[1].compatible);
+        assert!(steps[2].is_current && steps[2].yanked);
+        assert!(dep.is_current_yanked());
+    }
+
+    #[test]
+    fn test_same_name_projects_are_distinct() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/same_name");
+        let projects = find_rust_projects(root.to_str().unwrap());
+
+        assert_eq!(projects.len(), 2);
+        assert_ne!(projects[0].id, projects[1].id);
+
+        let mut names: Vec<&str> = projects.iter().map(|p| p.display_name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["core (repo-a/core)", "core (repo-b/core)"]);
+    }
 }
@@ -0,0 +1,372 @@
+//! Flat, sortable table of projects
+//!
+//! Instead of the directory tree, projects can be listed as a table with a
+//! row per project: name, version, outdated dependencies, git state, target
+//! size, the result of the last command run and when it was last modified.
+//! The table sorts on any column and can be grouped by workspace, tag or
+//! update status. It is built as a [`TreeNode`] of groups, so flattening,
+//! navigation and selection work the same as for the tree.
+
+use crate::project::{GitStatus, Project, ProjectCheckStatus, ProjectStatus};
+use crate::tree::TreeNode;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+/// A column of the project table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortColumn {
+    #[default]
+    Name,
+    Version,
+    Outdated,
+    Git,
+    Size,
+    LastRun,
+    Modified,
+}
+
+impl SortColumn {
+    /// Every column, in display order
+    pub const ALL: [SortColumn; 7] = [
+        Self::Name,
+        Self::Version,
+        Self::Outdated,
+        Self::Git,
+        Self::Size,
+        Self::LastRun,
+        Self::Modified,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Version => "Version",
+            Self::Outdated => "Outdated",
+            Self::Git => "Git",
+            Self::Size => "Target",
+            Self::LastRun => "Last run",
+            Self::Modified => "Modified",
+        }
+    }
+
+    /// The column after this one, wrapping around
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Whether the column starts out largest, newest or worst first
+    pub fn descending_by_default(self) -> bool {
+        matches!(
+            self,
+            Self::Outdated | Self::Git | Self::Size | Self::LastRun | Self::Modified
+        )
+    }
+
+    /// Order two projects by this column; unknown values always go last
+    fn compare(self, a: &Project, b: &Project, descending: bool) -> Ordering {
+        match self {
+            Self::Name => compare_known(
+                Some(a.display_name.to_lowercase()),
+                Some(b.display_name.to_lowercase()),
+                descending,
+            ),
+            Self::Version => compare_known(
+                semver::Version::parse(&a.version).ok(),
+                semver::Version::parse(&b.version).ok(),
+                descending,
+            ),
+            Self::Outdated => compare_known(outdated(a), outdated(b), descending),
+            Self::Git => compare_known(git_rank(a), git_rank(b), descending),
+            Self::Size => compare_known(a.target_size, b.target_size, descending),
+            Self::LastRun => compare_known(run_rank(a), run_rank(b), descending),
+            Self::Modified => compare_known(a.modified, b.modified, descending),
+        }
+    }
+}
+
+fn compare_known<T: Ord>(a: Option<T>, b: Option<T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Outdated dependencies, once the project has been checked
+pub fn outdated(project: &Project) -> Option<usize> {
+    match project.check_status {
+        ProjectCheckStatus::Unchecked | ProjectCheckStatus::Checking => None,
+        _ => Some(project.outdated_count()),
+    }
+}
+
+fn git_rank(project: &Project) -> Option<u8> {
+    match project.git_status {
+        GitStatus::Unknown => None,
+        GitStatus::Clean => Some(0),
+        GitStatus::Dirty => Some(1),
+    }
+}
+
+fn run_rank(project: &Project) -> Option<u8> {
+    match project.status {
+        ProjectStatus::Pending => None,
+        ProjectStatus::Success => Some(0),
+        ProjectStatus::Running => Some(1),
+        ProjectStatus::Failed => Some(2),
+    }
+}
+
+/// What the project table is grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableGrouping {
+    #[default]
+    None,
+    Workspace,
+    Tag,
+    /// Update check status
+    Status,
+}
+
+impl TableGrouping {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "ungrouped",
+            Self::Workspace => "by workspace",
+            Self::Tag => "by tag",
+            Self::Status => "by status",
+        }
+    }
+
+    /// The grouping after this one, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            Self::None => Self::Workspace,
+            Self::Workspace => Self::Tag,
+            Self::Tag => Self::Status,
+            Self::Status => Self::None,
+        }
+    }
+}
+
+/// How the project list is shown, remembered across sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableView {
+    /// Show the table instead of the directory tree
+    pub enabled: bool,
+    pub sort: SortColumn,
+    pub descending: bool,
+    pub grouping: TableGrouping,
+}
+
+impl TableView {
+    /// Sort by the next column, in that column's natural direction
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.descending = self.sort.descending_by_default();
+    }
+
+    /// Projects in table order; ties are broken by name, then path
+    pub fn sorted<'a>(&self, projects: &'a [Project]) -> Vec<&'a Project> {
+        let mut sorted: Vec<&Project> = projects.iter().collect();
+        sorted.sort_by(|a, b| {
+            self.sort
+                .compare(a, b, self.descending)
+                .then_with(|| SortColumn::Name.compare(a, b, false))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        sorted
+    }
+}
+
+/// Rank of a check status among the status groups, most pressing first
+fn status_group(status: &ProjectCheckStatus) -> (u8, &'static str) {
+    match status {
+        ProjectCheckStatus::HasUpdates => (0, "outdated"),
+        ProjectCheckStatus::CheckFailed => (1, "check failed"),
+        ProjectCheckStatus::Checking => (2, "checking"),
+        ProjectCheckStatus::Unchecked => (3, "unchecked"),
+        ProjectCheckStatus::UpToDate => (4, "up to date"),
+    }
+}
+
+/// Build the table as a tree: sorted project rows, inside a group per
+/// workspace, tag or status when grouped
+///
+/// Groups are keyed by their path (`#tag`, `@workspace` or `!status`), and
+/// those whose key is in `collapsed` start collapsed. As with the tag tree, a
+/// project with several tags shows up in each of their groups.
+pub fn build_table_tree(
+    projects: &[Project],
+    view: &TableView,
+    collapsed: &HashSet<String>,
+) -> TreeNode {
+    let sorted = view.sorted(projects);
+    let row = |project: &Project, depth| TreeNode::project(project.clone(), depth);
+
+    // Groups in display order: (sort key, path, name, rows)
+    let mut groups: BTreeMap<(u8, String), (String, String, Vec<TreeNode>)> = BTreeMap::new();
+    let mut add = |order: (u8, String), path: String, name: String, project: &Project| {
+        groups
+            .entry(order)
+            .or_insert_with(|| (path, name, Vec::new()))
+            .2
+            .push(row(project, 2));
+    };
+    for project in &sorted {
+        match view.grouping {
+            TableGrouping::None => {}
+            TableGrouping::Workspace => match &project.workspace_name {
+                Some(workspace) => add(
+                    (0, workspace.clone()),
+                    format!("@{}", workspace),
+                    workspace.clone(),
+                    project,
+                ),
+                None => add(
+                    (1, String::new()),
+                    "@".to_string(),
+                    "(no workspace)".to_string(),
+                    project,
+                ),
+            },
+            TableGrouping::Tag if project.tags.is_empty() => {
+                add((1, String::new()), "#".to_string(), String::new(), project)
+            }
+            TableGrouping::Tag => {
+                for tag in &project.tags {
+                    add((0, tag.clone()), format!("#{}", tag), tag.clone(), project);
+                }
+            }
+            TableGrouping::Status => {
+                let (rank, name) = status_group(&project.check_status);
+                add(
+                    (rank, String::new()),
+                    format!("!{}", name),
+                    name.to_string(),
+                    project,
+                );
+            }
+        }
+    }
+
+    let nodes = if view.grouping == TableGrouping::None {
+        sorted.into_iter().map(|project| row(project, 1)).collect()
+    } else {
+        groups
+            .into_values()
+            .map(|(path, name, rows)| {
+                let expanded = !collapsed.contains(&path);
+                if view.grouping == TableGrouping::Tag {
+                    TreeNode::tag_group(&name, rows, expanded)
+                } else {
+                    TreeNode::group(name, path, rows, expanded)
+                }
+            })
+            .collect()
+    };
+    TreeNode::roots_group("table".to_string(), nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::find_rust_projects;
+    use crate::tree::FlattenedTree;
+    use std::path::Path;
+
+    fn fixture_projects() -> Vec<Project> {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspaces/glob");
+        find_rust_projects(fixture.to_str().unwrap())
+    }
+
+    fn names(flattened: &FlattenedTree) -> Vec<String> {
+        flattened
+            .items
+            .iter()
+            .map(|(node, _)| format!("{}{}", "  ".repeat(node.depth), node.node_type.name()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_puts_unknown_values_last() {
+        let mut projects = fixture_projects();
+        assert!(projects.len() >= 3);
+        projects.truncate(3);
+        projects[0].target_size = Some(10);
+        projects[1].target_size = None;
+        projects[2].target_size = Some(900);
+
+        let mut view = TableView {
+            sort: SortColumn::Size,
+            descending: true,
+            ..Default::default()
+        };
+        let sizes: Vec<Option<u64>> = view
+            .sorted(&projects)
+            .iter()
+            .map(|p| p.target_size)
+            .collect();
+        assert_eq!(sizes, [Some(900), Some(10), None]);
+
+        view.descending = false;
+        let sizes: Vec<Option<u64>> = view
+            .sorted(&projects)
+            .iter()
+            .map(|p| p.target_size)
+            .collect();
+        assert_eq!(sizes, [Some(10), Some(900), None]);
+
+        // Picking a column sorts it in its natural direction
+        view.cycle_sort();
+        assert_eq!((view.sort, view.descending), (SortColumn::LastRun, true));
+        view.sort = SortColumn::Modified;
+        view.cycle_sort();
+        assert_eq!((view.sort, view.descending), (SortColumn::Name, false));
+    }
+
+    #[test]
+    fn test_table_groups() {
+        let mut projects = fixture_projects();
+        projects.truncate(3);
+        projects[0].check_status = ProjectCheckStatus::UpToDate;
+        projects[1].check_status = ProjectCheckStatus::HasUpdates;
+        projects[2].check_status = ProjectCheckStatus::UpToDate;
+        let mut view = TableView::default();
+
+        let flat = FlattenedTree::from_tree(&build_table_tree(&projects, &view, &HashSet::new()));
+        assert_eq!(flat.items.len(), 3);
+        assert!(flat.items.iter().all(|(node, _)| node.depth == 0));
+
+        view.grouping = TableGrouping::Status;
+        let collapsed = HashSet::from(["!up to date".to_string()]);
+        let flat = FlattenedTree::from_tree(&build_table_tree(&projects, &view, &collapsed));
+        assert_eq!(
+            names(&flat),
+            [
+                "outdated".to_string(),
+                format!("  {}", projects[1].name),
+                "up to date".to_string(),
+            ]
+        );
+        assert!(flat.get_index(Path::new("!up to date")).is_some());
+
+        view.grouping = TableGrouping::Workspace;
+        let flat = FlattenedTree::from_tree(&build_table_tree(&projects, &view, &HashSet::new()));
+        let groups: Vec<&str> = flat
+            .items
+            .iter()
+            .filter(|(node, _)| node.node_type.is_directory())
+            .map(|(node, _)| node.node_type.name())
+            .collect();
+        assert!(!groups.is_empty());
+        assert_eq!(flat.items.len(), groups.len() + 3);
+    }
+}
@@ -0,0 +1,517 @@
+//! Filter queries for the project tree
+//!
+//! The `/` filter takes a small query language. Words are terms, all of which
+//! must match; `OR` (or `|`) joins alternatives, `NOT` (or a leading `!` or
+//! `-`) negates a term, and parentheses group:
+//!
+//! ```text
+//! outdated:>3 git:dirty
+//! dep:serde@<1.0.200 OR dep:tokio
+//! tag:service -status:failed
+//! (ws:backend | #cli) size:>1G
+//! ```
+//!
+//! A `field:value` term is a predicate on a project field:
+//!
+//! | Field            | Matches                                                   |
+//! |------------------|-----------------------------------------------------------|
+//! | `outdated:>3`    | outdated dependencies (`>`, `>=`, `<`, `<=`, `=`), once checked |
+//! | `dep:name`       | depends on a crate, optionally `dep:name@<req>` on its locked version |
+//! | `git:dirty`      | git state: `clean` or `dirty`                             |
+//! | `size:>1G`       | target directory size, with `K`, `M`, `G` or `T` units    |
+//! | `ws:name`        | workspace name                                            |
+//! | `status:failed`  | last run (`ok`, `failed`, `running`, `never`) or update check (`outdated`, `up-to-date`, `unchecked`, `checking`, `check-failed`) |
+//! | `tag:name`       | a tag starting with `name`; `#name` is shorthand          |
+//! | `name:text`      | fuzzy match on the name                                   |
+//!
+//! Any other word, including one with an unknown `field:`, fuzzily matches the
+//! project name or is found in its path.
+
+use crate::project::{GitStatus, Project, ProjectCheckStatus, ProjectStatus};
+use crate::project_table::outdated;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use once_cell::sync::Lazy;
+use semver::{Version, VersionReq};
+use std::cmp::Ordering;
+
+static MATCHER: Lazy<SkimMatcherV2> = Lazy::new(SkimMatcherV2::default);
+
+/// A numeric comparison such as `>3` or `<=1G`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    /// Orderings of the field against `value` that match
+    accepted: [Option<Ordering>; 2],
+    value: u64,
+}
+
+impl Comparison {
+    /// Parse an operator followed by a value read by `parse_value`
+    fn parse(input: &str, parse_value: impl Fn(&str) -> Option<u64>) -> Result<Self, String> {
+        let (accepted, rest) = if let Some(rest) = input.strip_prefix(">=") {
+            ([Some(Ordering::Greater), Some(Ordering::Equal)], rest)
+        } else if let Some(rest) = input.strip_prefix("<=") {
+            ([Some(Ordering::Less), Some(Ordering::Equal)], rest)
+        } else if let Some(rest) = input.strip_prefix('>') {
+            ([Some(Ordering::Greater), None], rest)
+        } else if let Some(rest) = input.strip_prefix('<') {
+            ([Some(Ordering::Less), None], rest)
+        } else {
+            (
+                [Some(Ordering::Equal), None],
+                input.strip_prefix('=').unwrap_or(input),
+            )
+        };
+        let value = parse_value(rest).ok_or_else(|| format!("invalid number `{}`", rest))?;
+        Ok(Self { accepted, value })
+    }
+
+    pub fn matches(&self, value: u64) -> bool {
+        self.accepted.contains(&Some(value.cmp(&self.value)))
+    }
+}
+
+/// Parse a byte size such as `512`, `300M` or `1.5GB`, in powers of 1024
+fn parse_size(input: &str) -> Option<u64> {
+    let upper = input.to_ascii_uppercase();
+    let number = upper
+        .strip_suffix("IB")
+        .or_else(|| upper.strip_suffix('B'))
+        .unwrap_or(&upper);
+    let (number, unit) = match number.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&number[..i], c),
+        _ => (number, ' '),
+    };
+    let multiplier: u64 = match unit {
+        ' ' => 1,
+        'K' => 1 << 10,
+        'M' => 1 << 20,
+        'G' => 1 << 30,
+        'T' => 1 << 40,
+        _ => return None,
+    };
+    let value: f64 = number.parse().ok()?;
+    (value >= 0.0).then_some((value * multiplier as f64) as u64)
+}
+
+/// A status a project can be filtered on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusFilter {
+    /// Result of the last command run
+    Run(ProjectStatus),
+    /// State of the dependency update check
+    Check(ProjectCheckStatus),
+}
+
+impl StatusFilter {
+    fn parse(input: &str) -> Result<Self, String> {
+        Ok(match input {
+            "ok" | "success" | "passed" => Self::Run(ProjectStatus::Success),
+            "failed" | "failure" => Self::Run(ProjectStatus::Failed),
+            "running" => Self::Run(ProjectStatus::Running),
+            "never" | "pending" => Self::Run(ProjectStatus::Pending),
+            "outdated" => Self::Check(ProjectCheckStatus::HasUpdates),
+            "up-to-date" | "uptodate" | "current" => Self::Check(ProjectCheckStatus::UpToDate),
+            "unchecked" => Self::Check(ProjectCheckStatus::Unchecked),
+            "checking" => Self::Check(ProjectCheckStatus::Checking),
+            "check-failed" => Self::Check(ProjectCheckStatus::CheckFailed),
+            _ => return Err(format!("unknown status `{}`", input)),
+        })
+    }
+}
+
+/// A parsed filter query
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    /// Fuzzy match on the name, or substring of the path
+    Text(String),
+    /// Fuzzy match on the name only
+    Name(String),
+    /// A tag starting with the given prefix
+    Tag(String),
+    Outdated(Comparison),
+    Dependency {
+        name: String,
+        /// Requirement the locked version must meet
+        requirement: Option<VersionReq>,
+    },
+    Git(GitStatus),
+    Size(Comparison),
+    Workspace(String),
+    Status(StatusFilter),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+impl Query {
+    /// Parse a query; an empty one matches everything
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens = tokenize(input);
+        if tokens.is_empty() {
+            return Ok(Self::And(Vec::new()));
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(query),
+            Some(token) => Err(format!("unexpected `{}`", token)),
+        }
+    }
+
+    /// Whether a project matches
+    pub fn matches(&self, project: &Project) -> bool {
+        match self {
+            Self::Text(text) => {
+                fuzzy_name(&project.display_name, text)
+                    || project
+                        .path
+                        .to_string_lossy()
+                        .to_lowercase()
+                        .contains(text.as_str())
+            }
+            Self::Name(text) => fuzzy_name(&project.display_name, text),
+            Self::Tag(prefix) => project.tags.iter().any(|tag| tag.starts_with(prefix)),
+            Self::Outdated(comparison) => {
+                outdated(project).is_some_and(|count| comparison.matches(count as u64))
+            }
+            Self::Dependency { name, requirement } => project.dependencies.iter().any(|dep| {
+                dep.name.eq_ignore_ascii_case(name)
+                    && requirement.as_ref().is_none_or(|requirement| {
+                        Version::parse(&dep.current_version)
+                            .is_ok_and(|version| requirement.matches(&version))
+                    })
+            }),
+            Self::Git(status) => project.git_status == *status,
+            Self::Size(comparison) => project
+                .target_size
+                .is_some_and(|size| comparison.matches(size)),
+            Self::Workspace(name) => project
+                .workspace_name
+                .as_ref()
+                .is_some_and(|workspace| workspace.eq_ignore_ascii_case(name)),
+            Self::Status(StatusFilter::Run(status)) => project.status == *status,
+            Self::Status(StatusFilter::Check(status)) => project.check_status == *status,
+            Self::Not(query) => !query.matches(project),
+            Self::And(queries) => queries.iter().all(|q| q.matches(project)),
+            Self::Or(queries) => queries.iter().any(|q| q.matches(project)),
+        }
+    }
+
+    /// Whether a directory or group matches, by its name and tag
+    ///
+    /// Only queries made of words, names and tags can match groups; anything
+    /// asking about project fields is for projects alone.
+    pub fn matches_group(&self, name: &str, tag: Option<&str>) -> bool {
+        if !self.is_textual() {
+            return false;
+        }
+        match self {
+            Self::Text(text) | Self::Name(text) => fuzzy_name(name, text),
+            Self::Tag(prefix) => tag.is_some_and(|tag| !tag.is_empty() && tag.starts_with(prefix)),
+            Self::Not(query) => !query.matches_group(name, tag),
+            Self::And(queries) => queries.iter().all(|q| q.matches_group(name, tag)),
+            Self::Or(queries) => queries.iter().any(|q| q.matches_group(name, tag)),
+            _ => false,
+        }
+    }
+
+    fn is_textual(&self) -> bool {
+        match self {
+            Self::Text(_) | Self::Name(_) | Self::Tag(_) => true,
+            Self::Not(query) => query.is_textual(),
+            Self::And(queries) | Self::Or(queries) => queries.iter().all(Self::is_textual),
+            _ => false,
+        }
+    }
+}
+
+fn fuzzy_name(name: &str, text: &str) -> bool {
+    MATCHER.fuzzy_match(name, text).is_some()
+}
+
+/// Split a query into words and parentheses
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in input.chars() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        } else {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+/// Recursive descent over the tokens: `OR` binds loosest, then `AND`, then `NOT`
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn or(&mut self) -> Result<Query, String> {
+        let mut alternatives = vec![self.and()?];
+        while matches!(self.peek(), Some("OR" | "or" | "|" | "||")) {
+            self.pos += 1;
+            alternatives.push(self.and()?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.remove(0)
+        } else {
+            Query::Or(alternatives)
+        })
+    }
+
+    fn and(&mut self) -> Result<Query, String> {
+        let mut terms = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some(")" | "OR" | "or" | "|" | "||") => break,
+                Some("AND" | "and" | "&&") => self.pos += 1,
+                Some(_) => terms.push(self.unary()?),
+            }
+        }
+        match terms.len() {
+            0 => Err(match self.peek() {
+                Some(token) => format!("unexpected `{}`", token),
+                None => "unexpected end of query".to_string(),
+            }),
+            1 => Ok(terms.remove(0)),
+            _ => Ok(Query::And(terms)),
+        }
+    }
+
+    fn unary(&mut self) -> Result<Query, String> {
+        let token = self
+            .peek()
+            .ok_or_else(|| "unexpected end of query".to_string())?
+            .to_string();
+        self.pos += 1;
+        match token.as_str() {
+            "NOT" | "not" | "!" | "-" => Ok(Query::Not(Box::new(self.unary()?))),
+            "(" => {
+                let query = self.or()?;
+                if self.peek() != Some(")") {
+                    return Err("missing `)`".to_string());
+                }
+                self.pos += 1;
+                Ok(query)
+            }
+            ")" => Err("unexpected `)`".to_string()),
+            _ => match token.strip_prefix(['!', '-']) {
+                Some(rest) if !rest.is_empty() => Ok(Query::Not(Box::new(term(rest)?))),
+                _ => term(&token),
+            },
+        }
+    }
+}
+
+/// Fields a `field:value` term can name
+const FIELDS: &[&str] = &[
+    "name",
+    "tag",
+    "outdated",
+    "size",
+    "dep",
+    "git",
+    "ws",
+    "workspace",
+    "status",
+];
+
+/// Parse a single term: a `field:value` predicate, a `#tag` or a word
+fn term(word: &str) -> Result<Query, String> {
+    let word = word.to_lowercase();
+    if let Some(tag) = word.strip_prefix('#') {
+        return Ok(Query::Tag(tag.to_string()));
+    }
+    let Some((field, value)) = word.split_once(':') else {
+        return Ok(Query::Text(word));
+    };
+    if !FIELDS.contains(&field) {
+        return Ok(Query::Text(word));
+    }
+    if value.is_empty() {
+        return Err(format!("`{}:` needs a value", field));
+    }
+    Ok(match field {
+        "name" => Query::Name(value.to_string()),
+        "tag" => Query::Tag(value.to_string()),
+        "outdated" => Query::Outdated(Comparison::parse(value, |n| n.parse().ok())?),
+        "size" => Query::Size(Comparison::parse(value, parse_size)?),
+        "dep" => match value.split_once('@') {
+            Some((name, requirement)) => Query::Dependency {
+                name: name.to_string(),
+                requirement: Some(
+                    VersionReq::parse(requirement)
+                        .map_err(|e| format!("invalid requirement `{}`: {}", requirement, e))?,
+                ),
+            },
+            None => Query::Dependency {
+                name: value.to_string(),
+                requirement: None,
+            },
+        },
+        "git" => Query::Git(match value {
+            "clean" => GitStatus::Clean,
+            "dirty" => GitStatus::Dirty,
+            _ => return Err(format!("unknown git state `{}`", value)),
+        }),
+        "ws" | "workspace" => Query::Workspace(value.to_string()),
+        "status" => Query::Status(StatusFilter::parse(value)?),
+        _ => unreachable!("`{}` is not in FIELDS", field),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{Dependency, DependencyCheckStatus, find_rust_projects};
+    use std::path::{Path, PathBuf};
+
+    fn project(name: &str) -> Project {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspaces/glob");
+        let mut project = find_rust_projects(fixture.to_str().unwrap()).remove(0);
+        project.display_name = name.to_string();
+        project.path = PathBuf::from(format!("/code/{}", name));
+        project.workspace_name = None;
+        project.dependencies = vec![
+            dependency("serde", "1.0.190"),
+            dependency("tokio", "1.37.0"),
+        ];
+        project
+    }
+
+    fn dependency(name: &str, version: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            current_version: version.to_string(),
+            latest_version: None,
+            check_status: DependencyCheckStatus::NotChecked,
+            last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
+        }
+    }
+
+    fn matches(query: &str, project: &Project) -> bool {
+        Query::parse(query).unwrap().matches(project)
+    }
+
+    #[test]
+    fn test_predicates() {
+        let mut api = project("billing-api");
+        api.git_status = GitStatus::Dirty;
+        api.target_size = Some(3 << 30);
+        api.workspace_name = Some("backend".to_string());
+        api.status = ProjectStatus::Failed;
+        api.tags = vec!["services".to_string()];
+
+        assert!(matches("bilapi", &api));
+        assert!(matches("/code/", &api));
+        assert!(!matches("name:code", &api));
+        assert!(matches("dep:tokio", &api));
+        assert!(matches("dep:serde@<1.0.200", &api));
+        assert!(!matches("dep:serde@>=1.0.200", &api));
+        assert!(!matches("dep:rand", &api));
+        assert!(matches("git:dirty", &api));
+        assert!(matches("size:>1G size:<=3GB", &api));
+        assert!(!matches("size:>3g", &api));
+        assert!(matches("ws:Backend", &api));
+        assert!(matches("status:failed", &api));
+        assert!(matches("tag:serv #services", &api));
+
+        // Unchecked projects have no known outdated count
+        assert!(!matches("outdated:<1", &api));
+        api.check_status = ProjectCheckStatus::HasUpdates;
+        api.dependencies[0].latest_version = Some("1.0.210".to_string());
+        assert!(matches("outdated:1 outdated:>=1 status:outdated", &api));
+        assert!(!matches("outdated:>3", &api));
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        let mut cli = project("cli");
+        cli.git_status = GitStatus::Clean;
+        let mut api = project("api");
+        api.git_status = GitStatus::Dirty;
+        api.tags = vec!["services".to_string()];
+
+        let query = Query::parse("git:dirty OR #cli").unwrap();
+        assert!(query.matches(&api));
+        assert!(!query.matches(&cli));
+
+        let query = Query::parse("dep:tokio -git:dirty").unwrap();
+        assert!(query.matches(&cli));
+        assert!(!query.matches(&api));
+
+        let query = Query::parse("NOT (tag:services | name:cli) AND dep:serde").unwrap();
+        assert!(!query.matches(&cli));
+        assert!(!query.matches(&api));
+        assert!(Query::parse("").unwrap().matches(&cli));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (query, error) in [
+            ("size:>", "invalid number ``"),
+            ("outdated:many", "invalid number `many`"),
+            ("git:", "`git:` needs a value"),
+            ("status:broken", "unknown status `broken`"),
+            ("(dep:tokio", "missing `)`"),
+            ("dep:tokio)", "unexpected `)`"),
+            ("git:dirty OR", "unexpected end of query"),
+        ] {
+            assert_eq!(Query::parse(query).unwrap_err(), error, "{}", query);
+        }
+        assert!(Query::parse("dep:serde@<<1").is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_text() {
+        assert_eq!(
+            Query::parse("foo::bar").unwrap(),
+            Query::Text("foo::bar".to_string())
+        );
+        assert_eq!(
+            Query::parse("colour:").unwrap(),
+            Query::Text("colour:".to_string())
+        );
+
+        let mut project = project("carwash");
+        project.path = PathBuf::from("/code/foo::bar");
+        assert!(matches("foo::bar", &project));
+        assert!(!matches("colour:red", &project));
+    }
+
+    #[test]
+    fn test_groups_match_only_textual_queries() {
+        let query = Query::parse("#serv").unwrap();
+        assert!(query.matches_group("#services", Some("services")));
+        assert!(!query.matches_group("services", None));
+
+        let query = Query::parse("back").unwrap();
+        assert!(query.matches_group("backend", None));
+        assert!(query.matches_group("#backend", Some("backend")));
+        assert!(
+            !Query::parse("back git:dirty")
+                .unwrap()
+                .matches_group("backend", None)
+        );
+    }
+}
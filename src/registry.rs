@@ -0,0 +1,638 @@
+//! Registry client for dependency update checks
+//!
+//! A single [`RegistryClient`] is shared by every update check. It owns the
+//! global request budget: a semaphore bounding in-flight lookups and a rate
+//! limiter spacing requests out, so checking several projects at once does not
+//! multiply the load on the registry. Transient failures are retried with
+//! exponential backoff and surfaced as a [`RegistryError`] once retries run out.
+//!
+//! It also keeps the crate cache in memory for the session: versions found by
+//! one check are seen by the next, and concurrent lookups of the same crate
+//! share a single request.
+//!
+//! Repeated connectivity failures are tracked so the application can switch to
+//! offline mode, where versions come from the cache or a local index checkout.
+
+use crate::cache::{CachedDependency, CrateCache, UpdateCache, registry_key};
+use crate::config::CheckerConfig;
+use crate::project::PublishedVersion;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{Mutex, OnceCell, Semaphore};
+use tokio::time::Instant;
+
+const USER_AGENT: &str = concat!(
+    "carwash/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/epistates/carwash)"
+);
+
+/// Consecutive connectivity failures after which the registry is considered unreachable
+const OFFLINE_THRESHOLD: u32 = 3;
+
+/// Crate looked up to tell whether the registry is reachable again
+const PROBE_CRATE: &str = "serde";
+
+/// Why a registry lookup failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The crate doesn't exist on the registry
+    NotFound,
+    /// The request didn't complete within the configured timeout
+    Timeout,
+    /// The registry answered with an unexpected HTTP status
+    Status(u16),
+    /// The request couldn't be sent or the connection failed
+    Network(String),
+    /// The response body couldn't be understood
+    InvalidResponse(String),
+}
+
+impl RegistryError {
+    /// Whether retrying the request could succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RegistryError::Timeout | RegistryError::Network(_) => true,
+            RegistryError::Status(code) => *code == 429 || *code >= 500,
+            RegistryError::NotFound | RegistryError::InvalidResponse(_) => false,
+        }
+    }
+
+    /// Whether the failure suggests the registry can't be reached at all
+    pub fn is_connectivity(&self) -> bool {
+        matches!(self, RegistryError::Timeout | RegistryError::Network(_))
+    }
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::NotFound => write!(f, "not found"),
+            RegistryError::Timeout => write!(f, "timed out"),
+            RegistryError::Status(code) => write!(f, "HTTP {}", code),
+            RegistryError::Network(msg) => write!(f, "network error: {}", msg),
+            RegistryError::InvalidResponse(msg) => write!(f, "invalid response: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Version information for a crate as reported by the registry
+#[derive(Debug, Clone)]
+pub struct CrateVersions {
+    /// Highest published version
+    pub latest_version: String,
+    /// All published versions, newest first
+    pub versions: Vec<PublishedVersion>,
+}
+
+#[derive(Deserialize)]
+struct CrateResponse {
+    #[serde(rename = "crate")]
+    krate: CrateData,
+    #[serde(default)]
+    versions: Vec<VersionData>,
+}
+
+#[derive(Deserialize)]
+struct CrateData {
+    max_version: String,
+}
+
+#[derive(Deserialize)]
+struct VersionData {
+    num: String,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+impl From<CrateResponse> for CrateVersions {
+    fn from(response: CrateResponse) -> Self {
+        let versions = response
+            .versions
+            .into_iter()
+            .map(|v| PublishedVersion {
+                version: v.num,
+                released_at: v
+                    .created_at
+                    .and_then(|ts| chrono::DateTime::parse_from_rfc3339(&ts).ok())
+                    .map(SystemTime::from),
+                yanked: v.yanked,
+            })
+            .collect();
+
+        Self {
+            latest_version: response.krate.max_version,
+            versions,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct IndexEntry {
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// Relative path of a crate's file in a crates.io-style index
+fn index_path(name: &str) -> PathBuf {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => Path::new("1").join(&name),
+        2 => Path::new("2").join(&name),
+        3 => Path::new("3").join(&name[..1]).join(&name),
+        _ => Path::new(&name[..2]).join(&name[2..4]).join(&name),
+    }
+}
+
+/// Read a crate's versions from a local crates.io-style index checkout
+///
+/// The index has no release dates, so cooldown rules can't apply to these.
+pub fn lookup_local_index(index_root: &Path, name: &str) -> Option<CrateVersions> {
+    let contents = fs::read_to_string(index_root.join(index_path(name))).ok()?;
+
+    let mut versions: Vec<(semver::Version, PublishedVersion)> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<IndexEntry>(line).ok())
+        .filter_map(|entry| {
+            let parsed = semver::Version::parse(&entry.vers).ok()?;
+            Some((
+                parsed,
+                PublishedVersion {
+                    version: entry.vers,
+                    released_at: None,
+                    yanked: entry.yanked,
+                },
+            ))
+        })
+        .collect();
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let latest_version = versions
+        .iter()
+        .find(|(_, v)| !v.yanked)
+        .or(versions.first())
+        .map(|(_, v)| v.version.clone())?;
+
+    Some(CrateVersions {
+        latest_version,
+        versions: versions.into_iter().map(|(_, v)| v).collect(),
+    })
+}
+
+/// Spaces requests out so that at most one starts per interval
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait until the next request slot is available and claim it
+    async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// Shared client for looking up crate versions on a registry
+#[derive(Debug)]
+pub struct RegistryClient {
+    http: reqwest::Client,
+    base_url: String,
+    timeout: Duration,
+    max_retries: u32,
+    retry_backoff: Duration,
+    limiter: RateLimiter,
+    permits: Semaphore,
+    local_index: Option<PathBuf>,
+    connectivity_failures: AtomicU32,
+    /// Key of this registry in the crate cache
+    cache_key: String,
+    /// Crate cache for the session, loaded from disk on first use
+    crates: std::sync::Mutex<Option<CrateCache>>,
+    /// Lookups in flight, awaited by every check that wants the same crate
+    in_flight: std::sync::Mutex<HashMap<String, Arc<OnceCell<LookupResult>>>>,
+}
+
+type LookupResult = Result<CrateVersions, RegistryError>;
+
+impl RegistryClient {
+    /// Create a client from the `[checker]` configuration
+    pub fn new(config: &CheckerConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            http,
+            base_url: config.registry_url.trim_end_matches('/').to_string(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+            max_retries: config.max_retries,
+            retry_backoff: Duration::from_millis(config.retry_backoff_ms),
+            limiter: RateLimiter::new(Duration::from_millis(config.min_request_interval_ms)),
+            permits: Semaphore::new(config.concurrency.max(1)),
+            local_index: config.local_index.clone(),
+            connectivity_failures: AtomicU32::new(0),
+            cache_key: registry_key(&config.registry_url),
+            crates: std::sync::Mutex::new(None),
+            in_flight: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Key of this registry in the crate cache
+    pub fn cache_key(&self) -> &str {
+        &self.cache_key
+    }
+
+    fn with_crates<R>(&self, f: impl FnOnce(&mut CrateCache) -> R) -> R {
+        let mut crates = self.crates.lock().unwrap_or_else(|e| e.into_inner());
+        f(crates.get_or_insert_with(|| UpdateCache::new().load_crates()))
+    }
+
+    /// Copy of the crate cache as known this session
+    pub fn crate_cache(&self) -> CrateCache {
+        self.with_crates(|crates| crates.clone())
+    }
+
+    /// Versions of a crate known this session, from the cache or an earlier check
+    pub fn cached(&self, name: &str) -> Option<CachedDependency> {
+        self.with_crates(|crates| crates.get(&self.cache_key, name).cloned())
+    }
+
+    /// Remember a crate's versions for the rest of the session
+    pub fn remember(&self, name: &str, entry: CachedDependency) {
+        let mut update = CrateCache::default();
+        update.insert(&self.cache_key, name.to_string(), entry);
+        self.with_crates(|crates| crates.merge(update));
+    }
+
+    /// Whether recent lookups keep failing to reach the registry
+    pub fn looks_offline(&self) -> bool {
+        self.connectivity_failures.load(Ordering::Relaxed) >= OFFLINE_THRESHOLD
+    }
+
+    /// Forget past connectivity failures (e.g. when leaving offline mode)
+    pub fn reset_connectivity(&self) {
+        self.connectivity_failures.store(0, Ordering::Relaxed);
+    }
+
+    /// Whether the registry answers at all, to find out when to leave offline mode
+    ///
+    /// Any response counts, even an error status; it also clears past
+    /// connectivity failures.
+    pub async fn probe(&self) -> bool {
+        self.limiter.acquire().await;
+        match self.fetch_once(PROBE_CRATE).await {
+            Err(e) if e.is_connectivity() => false,
+            _ => {
+                self.reset_connectivity();
+                true
+            }
+        }
+    }
+
+    /// Look a crate up in the configured local index, if any
+    pub fn local_versions(&self, name: &str) -> Option<CrateVersions> {
+        lookup_local_index(self.local_index.as_deref()?, name)
+    }
+
+    /// Fetch a crate's published versions, retrying transient failures
+    ///
+    /// Callers asking for a crate already being fetched wait for that request.
+    pub async fn fetch_versions(&self, name: &str) -> LookupResult {
+        let lookup = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(name.to_string())
+            .or_default()
+            .clone();
+        let result = lookup
+            .get_or_init(|| self.fetch_counted(name))
+            .await
+            .clone();
+
+        // Done: the next check of this crate fetches it again
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        if in_flight
+            .get(name)
+            .is_some_and(|current| Arc::ptr_eq(current, &lookup))
+        {
+            in_flight.remove(name);
+        }
+        result
+    }
+
+    async fn fetch_counted(&self, name: &str) -> LookupResult {
+        let result = self.fetch_with_retries(name).await;
+
+        match &result {
+            Err(e) if e.is_connectivity() => {
+                self.connectivity_failures.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => self.connectivity_failures.store(0, Ordering::Relaxed),
+        }
+
+        result
+    }
+
+    async fn fetch_with_retries(&self, name: &str) -> Result<CrateVersions, RegistryError> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| RegistryError::Network(e.to_string()))?;
+
+        let mut attempt = 0;
+        loop {
+            self.limiter.acquire().await;
+
+            match self.fetch_once(name).await {
+                Ok(versions) => return Ok(versions),
+                Err(e) if e.is_retryable() && attempt < self.max_retries => {
+                    let backoff = self.retry_backoff * 2u32.saturating_pow(attempt);
+                    tracing::debug!(
+                        "Lookup of {} failed ({}), retrying in {:?}",
+                        name,
+                        e,
+                        backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn fetch_once(&self, name: &str) -> Result<CrateVersions, RegistryError> {
+        let url = format!("{}/api/v1/crates/{}", self.base_url, name);
+
+        let request = async {
+            let response = self
+                .http
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| RegistryError::Network(e.to_string()))?;
+
+            match response.status().as_u16() {
+                200 => {}
+                404 => return Err(RegistryError::NotFound),
+                code => return Err(RegistryError::Status(code)),
+            }
+
+            response
+                .json::<CrateResponse>()
+                .await
+                .map(CrateVersions::from)
+                .map_err(|e| RegistryError::InvalidResponse(e.to_string()))
+        };
+
+        tokio::time::timeout(self.timeout, request)
+            .await
+            .unwrap_or(Err(RegistryError::Timeout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const TOKIO_JSON: &str = r#"{
+        "crate": {"max_version": "1.40.0"},
+        "versions": [
+            {"num": "1.40.0", "created_at": "2024-09-01T12:00:00Z", "yanked": false},
+            {"num": "1.39.9", "created_at": "2024-08-01T12:00:00Z", "yanked": true}
+        ]
+    }"#;
+
+    /// Scripted response of the mock registry
+    #[derive(Clone)]
+    enum Reply {
+        Json(u16, &'static str),
+        Hang,
+    }
+
+    /// Serve `replies` in order (repeating the last one) on a local port
+    async fn mock_registry(replies: Vec<Reply>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let hits_clone = hits.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let n = hits_clone.fetch_add(1, Ordering::SeqCst);
+                let reply = replies[n.min(replies.len() - 1)].clone();
+
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    match reply {
+                        Reply::Json(status, body) => {
+                            let response = format!(
+                                "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                                status,
+                                body.len(),
+                                body
+                            );
+                            let _ = socket.write_all(response.as_bytes()).await;
+                        }
+                        Reply::Hang => {
+                            tokio::time::sleep(Duration::from_secs(30)).await;
+                        }
+                    }
+                });
+            }
+        });
+
+        (format!("http://{}", addr), hits)
+    }
+
+    fn client(base_url: String) -> RegistryClient {
+        RegistryClient::new(&CheckerConfig {
+            registry_url: base_url,
+            timeout_secs: 1,
+            concurrency: 2,
+            max_retries: 2,
+            retry_backoff_ms: 10,
+            min_request_interval_ms: 0,
+            local_index: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fetch_versions_parses_response() {
+        let (url, hits) = mock_registry(vec![Reply::Json(200, TOKIO_JSON)]).await;
+
+        let versions = client(url).fetch_versions("tokio").await.unwrap();
+        assert_eq!(versions.latest_version, "1.40.0");
+        assert_eq!(versions.versions.len(), 2);
+        assert!(versions.versions[1].yanked);
+        assert!(versions.versions[0].released_at.is_some());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_lookups_share_a_request() {
+        let (url, hits) = mock_registry(vec![Reply::Json(200, TOKIO_JSON)]).await;
+        let client = client(url);
+
+        let (first, second) = tokio::join!(
+            client.fetch_versions("tokio"),
+            client.fetch_versions("tokio")
+        );
+        assert_eq!(first.unwrap().latest_version, "1.40.0");
+        assert_eq!(second.unwrap().latest_version, "1.40.0");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Once answered, the next lookup asks the registry again
+        client.fetch_versions("tokio").await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        let (url, hits) = mock_registry(vec![
+            Reply::Json(503, "{}"),
+            Reply::Json(429, "{}"),
+            Reply::Json(200, TOKIO_JSON),
+        ])
+        .await;
+
+        let versions = client(url).fetch_versions("tokio").await.unwrap();
+        assert_eq!(versions.latest_version, "1.40.0");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (url, hits) = mock_registry(vec![Reply::Json(500, "{}")]).await;
+
+        let err = client(url).fetch_versions("tokio").await.unwrap_err();
+        assert_eq!(err, RegistryError::Status(500));
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_not_retried() {
+        let (url, hits) = mock_registry(vec![Reply::Json(404, "{}")]).await;
+
+        let err = client(url).fetch_versions("nope").await.unwrap_err();
+        assert_eq!(err, RegistryError::NotFound);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_reported() {
+        let (url, _) = mock_registry(vec![Reply::Hang]).await;
+        let mut client = client(url);
+        client.max_retries = 0;
+
+        let err = client.fetch_versions("tokio").await.unwrap_err();
+        assert_eq!(err, RegistryError::Timeout);
+    }
+
+    #[tokio::test]
+    async fn test_connectivity_failures_mark_offline() {
+        // Nothing listens on this port once the listener is dropped
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut client = client(format!("http://{}", addr));
+        client.max_retries = 0;
+
+        for _ in 0..OFFLINE_THRESHOLD {
+            assert!(!client.looks_offline());
+            let err = client.fetch_versions("tokio").await.unwrap_err();
+            assert!(err.is_connectivity());
+        }
+        assert!(client.looks_offline());
+        assert!(!client.probe().await);
+
+        // Once the registry answers again, even with an error status, it is back
+        let (url, _) = mock_registry(vec![Reply::Json(404, "{}")]).await;
+        client.base_url = url;
+        assert!(client.probe().await);
+        assert!(!client.looks_offline());
+    }
+
+    #[test]
+    fn test_index_path_layout() {
+        assert_eq!(index_path("a"), Path::new("1/a"));
+        assert_eq!(index_path("cc"), Path::new("2/cc"));
+        assert_eq!(index_path("syn"), Path::new("3/s/syn"));
+        assert_eq!(index_path("Tokio"), Path::new("to/ki/tokio"));
+    }
+
+    #[test]
+    fn test_lookup_local_index() {
+        let root = temp_dir("registry", "local-index");
+        let file = root.join(index_path("tokio"));
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(
+            &file,
+            concat!(
+                r#"{"name":"tokio","vers":"1.39.0","yanked":false}"#,
+                "\n",
+                r#"{"name":"tokio","vers":"1.40.0","yanked":true}"#,
+                "\n",
+                r#"{"name":"tokio","vers":"1.39.1","yanked":false}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let versions = lookup_local_index(&root, "tokio").unwrap();
+        assert_eq!(versions.latest_version, "1.39.1");
+        assert_eq!(versions.versions.len(), 3);
+        assert!(lookup_local_index(&root, "serde").is_none());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(Duration::from_millis(50));
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,166 @@
+//! Scan roots: the directories carwash looks for projects in
+//!
+//! Roots come either from the command line or from a named collection in the
+//! config file, where entries may start with `~` and contain glob patterns
+//! (`~/src/company/*`). Each root is shown as its own top-level tree node.
+
+use crate::scan::scan_root;
+use std::path::{Path, PathBuf};
+
+/// The directories being scanned, and the collection they came from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanRoots {
+    /// Collection name, if the roots came from one
+    pub collection: Option<String>,
+    /// Absolute root directories, in the order given
+    pub paths: Vec<PathBuf>,
+}
+
+impl ScanRoots {
+    /// Roots given as directories (relative ones are resolved against the cwd)
+    pub fn from_dirs<S: AsRef<str>>(dirs: &[S]) -> Self {
+        let mut paths = Vec::new();
+        for dir in dirs {
+            push_unique(&mut paths, scan_root(dir.as_ref()));
+        }
+        Self {
+            collection: None,
+            paths,
+        }
+    }
+
+    /// Roots of a named collection, expanding `~` and glob patterns
+    ///
+    /// Patterns only match directories; entries matching nothing are dropped.
+    pub fn from_collection<S: AsRef<str>>(name: &str, patterns: &[S]) -> Self {
+        let mut paths = Vec::new();
+        for pattern in patterns {
+            for path in expand_pattern(pattern.as_ref()) {
+                push_unique(&mut paths, path);
+            }
+        }
+        Self {
+            collection: Some(name.to_string()),
+            paths,
+        }
+    }
+
+    /// Whether `path` lies below one of the roots
+    pub fn contains(&self, path: &Path) -> bool {
+        self.paths.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Short description for titles, e.g. `work`, `projects` or `3 roots`
+    pub fn label(&self) -> String {
+        if let Some(name) = &self.collection {
+            return name.clone();
+        }
+        match self.paths.as_slice() {
+            [root] => root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| root.display().to_string()),
+            paths => format!("{} roots", paths.len()),
+        }
+    }
+}
+
+fn push_unique(paths: &mut Vec<PathBuf>, path: PathBuf) {
+    if !paths.contains(&path) {
+        paths.push(path);
+    }
+}
+
+/// Expand a leading `~` and any glob pattern into existing directories
+fn expand_pattern(pattern: &str) -> Vec<PathBuf> {
+    let expanded = expand_home(pattern);
+    let expanded = expanded.to_string_lossy();
+
+    if !expanded.contains(['*', '?', '[']) {
+        return vec![scan_root(&expanded)];
+    }
+
+    let absolute = scan_root(&expanded);
+    match glob::glob(&absolute.to_string_lossy()) {
+        Ok(paths) => {
+            let mut dirs: Vec<PathBuf> = paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_dir())
+                .collect();
+            dirs.sort();
+            dirs
+        }
+        Err(e) => {
+            tracing::warn!("Ignoring invalid collection pattern {:?}: {}", pattern, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Replace a leading `~` with the home directory
+pub(crate) fn expand_home(pattern: &str) -> PathBuf {
+    let home = || directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    if pattern == "~" {
+        if let Some(home) = home() {
+            return home;
+        }
+    } else if let Some(rest) = pattern.strip_prefix("~/") {
+        if let Some(home) = home() {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(path: &str) -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_collection_expands_globs_to_directories() {
+        let roots = ScanRoots::from_collection(
+            "fixtures",
+            &[
+                fixture("workspaces/glob/crates/*"),
+                fixture("same_name"),
+                fixture("same_name"),
+            ],
+        );
+
+        let names: Vec<String> = roots
+            .paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        // `crates/*` also matches a directory without a manifest; duplicates are dropped
+        assert_eq!(names, ["alpha", "beta", "not-a-crate", "same_name"]);
+        assert_eq!(roots.label(), "fixtures");
+        assert!(roots.contains(&roots.paths[0].join("src")));
+    }
+
+    #[test]
+    fn test_dirs_label() {
+        let one = ScanRoots::from_dirs(&[fixture("same_name")]);
+        assert_eq!(one.label(), "same_name");
+        assert_eq!(one.collection, None);
+
+        let two = ScanRoots::from_dirs(&[fixture("same_name"), fixture("workspaces")]);
+        assert_eq!(two.label(), "2 roots");
+    }
+
+    #[test]
+    fn test_expand_home() {
+        if let Some(dirs) = directories::BaseDirs::new() {
+            assert_eq!(expand_home("~/src"), dirs.home_dir().join("src"));
+        }
+        assert_eq!(expand_home("/opt/src"), PathBuf::from("/opt/src"));
+    }
+}
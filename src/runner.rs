@@ -4,26 +4,28 @@
 //! It manages a queue of tasks that are executed with proper concurrency limits and caching.
 
 use crate::app::AppState;
-use crate::cache::UpdateCache;
+use crate::cache::{CachedDependency, CrateCache, UpdateCache};
 use crate::events::Action;
-use crate::project::{Dependency, DependencyCheckStatus, Project};
-use crates_io_api::AsyncClient;
+use crate::features::{FeatureMatrix, MatrixStatus};
+use crate::project::{Dependency, DependencyCheckStatus, Project, ProjectId, ProjectStatus};
+use crate::registry::RegistryClient;
+use crate::toolchains::{ToolchainRow, ToolchainStatus};
 use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
     process::Command as TokioCommand,
-    sync::{Semaphore, mpsc},
+    sync::mpsc,
 };
 
-const PARALLEL_UPDATE_CHECKS: usize = 5;
 /// A task to check for dependency updates on a project
 #[derive(Debug, Clone)]
 pub struct UpdateCheckTask {
-    /// Name of the project to check
-    pub project_name: String,
+    /// Project to check
+    pub project_id: ProjectId,
     /// Whether this is a priority task (user-initiated)
     pub is_priority: bool,
 }
@@ -38,28 +40,30 @@ pub struct UpdateQueue {
     pub queue: VecDeque<UpdateCheckTask>,
     /// Number of tasks currently in progress
     pub in_progress: usize,
+    /// Maximum number of projects checked concurrently, from `[checker] concurrency`
+    ///
+    /// Registry requests themselves are bounded globally by [`RegistryClient`].
+    pub max_parallel: usize,
 }
 
 impl UpdateQueue {
-    pub fn new() -> Self {
+    pub fn new(max_parallel: usize) -> Self {
         Self {
             queue: VecDeque::new(),
             in_progress: 0,
+            max_parallel: max_parallel.max(1),
         }
     }
 
     pub fn add_task(&mut self, task: UpdateCheckTask) {
         // Check if this project is already in the queue
-        let already_queued = self
-            .queue
-            .iter()
-            .any(|t| t.project_name == task.project_name);
+        let already_queued = self.queue.iter().any(|t| t.project_id == task.project_id);
 
         if already_queued {
             // If it's a priority task and the existing one isn't, upgrade it
             if task.is_priority {
                 // Remove the existing non-priority task
-                self.queue.retain(|t| t.project_name != task.project_name);
+                self.queue.retain(|t| t.project_id != task.project_id);
                 // Add the priority version at the front
                 self.queue.push_front(task);
             }
@@ -77,7 +81,7 @@ impl UpdateQueue {
     }
 
     pub fn get_next_task(&mut self) -> Option<UpdateCheckTask> {
-        if self.in_progress >= PARALLEL_UPDATE_CHECKS {
+        if self.in_progress >= self.max_parallel {
             return None;
         }
 
@@ -99,9 +103,12 @@ impl UpdateQueue {
         !self.queue.is_empty() || self.in_progress > 0
     }
 
+    /// Drop queued tasks
+    ///
+    /// Tasks already running keep counting until they report back through
+    /// [`task_completed`](Self::task_completed).
     pub fn clear(&mut self) {
         self.queue.clear();
-        self.in_progress = 0;
     }
 }
 
@@ -112,8 +119,8 @@ pub async fn check_for_updates(state: &AppState, tx: mpsc::Sender<Action>) {
     // User might have moved cursor after opening wizard
     let project_to_check = if state.mode == crate::events::Mode::UpdateWizard {
         // Wizard is open - use the locked project
-        if let Some(ref locked_name) = state.updater.locked_project_name {
-            state.all_projects.iter().find(|p| &p.name == locked_name)
+        if let Some(ref locked) = state.updater.locked_project {
+            state.all_projects.iter().find(|p| &p.id == locked)
         } else {
             state.get_selected_project()
         }
@@ -123,22 +130,24 @@ pub async fn check_for_updates(state: &AppState, tx: mpsc::Sender<Action>) {
 
     if let Some(project) = project_to_check {
         let deps = project.dependencies.clone();
-        let project_name = project.name.clone();
-        let project_path = project.path.clone();
+        let project_id = project.id.clone();
+        let lockfile_path = project.lockfile_path();
 
         // Send initial action to show we're checking
         let _ = tx
-            .send(Action::UpdateDependenciesStreamStart(project_name.clone()))
+            .send(Action::UpdateDependenciesStreamStart(project_id.clone()))
             .await;
 
         // Perform checks asynchronously - don't await here
         check_dependencies_with_cache(
-            project_name,
+            project_id,
             deps,
             tx,
             true,
-            Some(project_path),
+            Some(lockfile_path),
             state.settings.cache_duration(),
+            state.registry.clone(),
+            state.is_offline(),
         )
         .await;
     }
@@ -147,9 +156,9 @@ pub async fn check_for_updates(state: &AppState, tx: mpsc::Sender<Action>) {
 /// Check dependencies with intelligent caching and streaming updates
 async fn check_single_dependency(
     dep: Dependency,
-    client: &AsyncClient,
+    registry: &RegistryClient,
     tx: &mpsc::Sender<Action>,
-    project_name: &str,
+    project_id: &ProjectId,
     use_cache: bool,
     cache_duration: std::time::Duration,
     now: SystemTime,
@@ -169,38 +178,66 @@ async fn check_single_dependency(
         true
     };
 
-    if should_check {
+    // Another project may already have fetched this crate recently
+    let shared_entry = if use_cache && should_check {
+        registry
+            .cached(&updated_dep.name)
+            .filter(|entry| entry.is_fresh(cache_duration, now))
+    } else {
+        None
+    };
+
+    if let Some(entry) = shared_entry {
+        updated_dep.latest_version = entry.latest_version.clone();
+        updated_dep.published_versions = entry.versions.clone();
+        updated_dep.check_status = DependencyCheckStatus::Checked;
+        updated_dep.last_checked = Some(entry.cached_at);
+
+        let _ = tx
+            .send(Action::UpdateSingleDependency(
+                project_id.clone(),
+                updated_dep.clone(),
+            ))
+            .await;
+    } else if should_check {
         updated_dep.check_status = DependencyCheckStatus::Checking;
 
         let _ = tx
             .send(Action::UpdateDependencyCheckStatus(
-                project_name.to_string(),
+                project_id.clone(),
                 updated_dep.name.clone(),
                 DependencyCheckStatus::Checking,
             ))
             .await;
 
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(5),
-            client.get_crate(&updated_dep.name),
-        )
-        .await
-        {
-            Ok(Ok(crate_info)) => {
-                updated_dep.latest_version = Some(crate_info.crate_data.max_version);
+        match registry.fetch_versions(&updated_dep.name).await {
+            Ok(crate_info) => {
+                updated_dep.latest_version = Some(crate_info.latest_version);
+                updated_dep.published_versions = crate_info.versions;
                 updated_dep.check_status = DependencyCheckStatus::Checked;
                 updated_dep.last_checked = Some(SystemTime::now());
+                registry.remember(
+                    &updated_dep.name,
+                    CachedDependency {
+                        latest_version: updated_dep.latest_version.clone(),
+                        versions: updated_dep.published_versions.clone(),
+                        cached_at: SystemTime::now(),
+                    },
+                );
             }
-            _ => {
-                updated_dep.check_status = DependencyCheckStatus::Checked;
-                updated_dep.last_checked = Some(SystemTime::now());
+            Err(e) => {
+                // Keep any previously known versions, but don't treat the failure as
+                // fresh data: leaving last_checked unset makes the next check retry it
+                tracing::warn!("Update check for {} failed: {}", updated_dep.name, e);
+                updated_dep.check_status = DependencyCheckStatus::Failed(e.to_string());
+                updated_dep.last_checked = None;
             }
         }
 
         // Only send update for deps we actually checked (avoid redundant UI updates)
         let _ = tx
             .send(Action::UpdateSingleDependency(
-                project_name.to_string(),
+                project_id.clone(),
                 updated_dep.clone(),
             ))
             .await;
@@ -210,44 +247,71 @@ async fn check_single_dependency(
     Some(updated_dep)
 }
 
+/// Fill in dependency versions without touching the network
+///
+/// Cached entries are used regardless of age (their timestamp is kept so the UI
+/// can show how old they are), falling back to the configured local index.
+fn resolve_offline(
+    deps: Vec<Dependency>,
+    crate_cache: &CrateCache,
+    registry: &RegistryClient,
+) -> Vec<Dependency> {
+    deps.into_iter()
+        .map(|mut dep| {
+            if let Some(entry) = crate_cache.get(registry.cache_key(), &dep.name) {
+                dep.latest_version = entry.latest_version.clone();
+                dep.published_versions = entry.versions.clone();
+                dep.check_status = DependencyCheckStatus::Checked;
+                dep.last_checked = Some(entry.cached_at);
+            } else if let Some(versions) = registry.local_versions(&dep.name) {
+                dep.latest_version = Some(versions.latest_version);
+                dep.published_versions = versions.versions;
+                dep.check_status = DependencyCheckStatus::Checked;
+                // Index data has no fetch time; it is re-checked once back online
+                dep.last_checked = None;
+            } else if dep.check_status == DependencyCheckStatus::Checking {
+                dep.check_status = DependencyCheckStatus::NotChecked;
+            }
+            dep
+        })
+        .collect()
+}
+
 pub async fn check_dependencies_with_cache(
-    project_name: String,
+    project_id: ProjectId,
     deps: Vec<Dependency>,
     tx: mpsc::Sender<Action>,
     use_cache: bool,
-    project_path: Option<std::path::PathBuf>,
+    lockfile_path: Option<std::path::PathBuf>,
     cache_duration: std::time::Duration,
+    registry: Arc<RegistryClient>,
+    offline: bool,
 ) {
     let cache = UpdateCache::new();
-    let semaphore = Arc::new(Semaphore::new(PARALLEL_UPDATE_CHECKS));
-    let client = match AsyncClient::new(
-        "carwash/0.1.0 (https://github.com/epistates/carwash)",
-        std::time::Duration::from_secs(1),
-    ) {
-        Ok(client) => client,
-        Err(_) => {
-            let _ = tx
-                .send(Action::UpdateDependencies(project_name, deps))
-                .await;
-            return;
-        }
-    };
     let now = SystemTime::now();
+
+    if offline {
+        let resolved = resolve_offline(deps, &registry.crate_cache(), &registry);
+        let _ = tx
+            .send(Action::UpdateDependencies(project_id, resolved))
+            .await;
+        return;
+    }
+
     let mut tasks = Vec::new();
 
     for dep in deps {
-        let semaphore_clone = semaphore.clone();
-        let client_clone = client.clone();
+        let registry_clone = registry.clone();
         let tx_clone = tx.clone();
-        let project_name_clone = project_name.clone();
+        let project_id_clone = project_id.clone();
 
+        // Concurrency, rate limiting and deduplication are handled by the registry client
         let task = tokio::spawn(async move {
-            let _permit = semaphore_clone.acquire().await.ok()?;
             check_single_dependency(
                 dep,
-                &client_clone,
+                &registry_clone,
                 &tx_clone,
-                &project_name_clone,
+                &project_id_clone,
                 use_cache,
                 cache_duration,
                 now,
@@ -264,82 +328,111 @@ pub async fn check_dependencies_with_cache(
         }
     }
 
-    if let Some(path) = project_path {
-        if let Some(lock_hash) = UpdateCache::hash_cargo_lock(&path.join("Cargo.lock")) {
-            let mut cached_deps = std::collections::HashMap::new();
-            for dep in &updated_deps {
-                if dep.latest_version.is_some() {
-                    cached_deps.insert(
-                        dep.name.clone(),
-                        crate::cache::CachedDependency {
-                            latest_version: dep.latest_version.clone(),
-                            cached_at: dep.last_checked.unwrap_or_else(SystemTime::now),
-                        },
-                    );
-                }
-            }
+    // Share results with other projects through the per-crate cache
+    let mut fetched = CrateCache::default();
+    for dep in &updated_deps {
+        if dep.check_status == DependencyCheckStatus::Checked && dep.latest_version.is_some() {
+            fetched.insert(
+                registry.cache_key(),
+                dep.name.clone(),
+                CachedDependency {
+                    latest_version: dep.latest_version.clone(),
+                    versions: dep.published_versions.clone(),
+                    cached_at: dep.last_checked.unwrap_or_else(SystemTime::now),
+                },
+            );
+        }
+    }
+
+    if !fetched.is_empty() {
+        let _ = cache.save_crates(fetched);
 
-            if !cached_deps.is_empty() {
-                let _ = cache.save(&path, lock_hash, cached_deps.clone());
+        if let Some(path) = lockfile_path {
+            if let Some(lock_hash) = UpdateCache::hash_cargo_lock(&path) {
+                let _ = cache.record_lock(&project_id, lock_hash);
             }
         }
     }
 
     let _ = tx
-        .send(Action::UpdateDependencies(project_name, updated_deps))
+        .send(Action::UpdateDependencies(project_id, updated_deps))
         .await;
+
+    // Lookups keep failing to connect: switch the app to offline mode
+    if registry.looks_offline() {
+        let _ = tx.send(Action::OfflineDetected).await;
+    }
 }
 
 /// Recalculate the size of a single project after a command (e.g., cargo clean)
+///
+/// The target directory's new size also goes to every project sharing it.
 async fn recalculate_project_size(project: &Project, tx: &mpsc::Sender<Action>) {
-    let project_name = project.name.clone();
-    let project_path = project.path.clone();
-    let workspace_root = project.workspace_root.clone();
+    let project = project.clone();
     let tx = tx.clone();
 
     tokio::spawn(async move {
-        // Use spawn_blocking for blocking WalkDir I/O to prevent UI lag
-        let (total_size, target_size) = tokio::task::spawn_blocking(move || {
-            let total_size = crate::project::calculate_directory_size(&project_path);
-
-            // For workspace members, look for target/ at workspace root
-            // For standalone projects, look in the project directory
-            let target_size = {
-                let target_path = if let Some(ws_root) = workspace_root {
-                    ws_root.join("target")
-                } else {
-                    project_path.join("target")
-                };
-
-                if target_path.exists() && target_path.is_dir() {
-                    crate::project::calculate_directory_size(&target_path)
-                } else {
-                    Some(0)
-                }
-            };
-
-            (total_size, target_size)
+        let project_id = project.id.clone();
+        // Use spawn_blocking for blocking directory walks to prevent UI lag
+        let (total_size, target_dir, target_size) = tokio::task::spawn_blocking(move || {
+            let target_dir = project.target_dir();
+            crate::disk_usage::forget_below(&target_dir);
+            let total_size = crate::project::calculate_directory_size(&project.path);
+            let target_size = crate::project::calculate_target_dir_size(&target_dir);
+            (total_size, Some(target_dir), target_size)
         })
         .await
-        .unwrap_or((None, None));
+        .unwrap_or((None, None, None));
 
         // Send update back to main thread
         let _ = tx
             .send(Action::UpdateProjectSize(
-                project_name,
+                project_id,
                 total_size,
                 target_size,
             ))
             .await;
+        if let Some(target_dir) = target_dir {
+            let _ = tx
+                .send(Action::UpdateTargetSize(target_dir, target_size))
+                .await;
+        }
     });
 }
 
+/// Run a cargo command in a project, streaming its output to a tab
+///
+/// The project's status follows the run, so the project table can show the
+/// result of the last command.
 async fn spawn_and_stream_command(
     command_str: &str,
     project: &Project,
     tx: &mpsc::Sender<Action>,
     tab_index: usize,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<bool> {
+    let _ = tx
+        .send(Action::UpdateProjectStatus(
+            project.id.clone(),
+            ProjectStatus::Running,
+        ))
+        .await;
+    let result = stream_command(command_str, project, tx, tab_index).await;
+    let status = match result {
+        Ok(true) => ProjectStatus::Success,
+        _ => ProjectStatus::Failed,
+    };
+    let _ = tx
+        .send(Action::UpdateProjectStatus(project.id.clone(), status))
+        .await;
+    result
+}
+
+async fn stream_command(
+    command_str: &str,
+    project: &Project,
+    tx: &mpsc::Sender<Action>,
+    tab_index: usize,
+) -> anyhow::Result<bool> {
     let start_time = std::time::Instant::now();
     let args: Vec<&str> = command_str.split_whitespace().collect();
 
@@ -417,7 +510,7 @@ async fn spawn_and_stream_command(
             .await;
     }
 
-    Ok(())
+    Ok(status.success())
 }
 
 pub async fn run_command(command_str: &str, state: &AppState, tx: mpsc::Sender<Action>) {
@@ -428,7 +521,7 @@ pub async fn run_command(command_str: &str, state: &AppState, tx: mpsc::Sender<A
     let projects_to_run: Vec<Project> = state
         .projects
         .iter()
-        .filter(|p| state.selected_projects.contains(&p.name))
+        .filter(|p| state.selected_projects.contains(&p.id))
         .cloned()
         .collect();
 
@@ -449,78 +542,66 @@ pub async fn run_command(command_str: &str, state: &AppState, tx: mpsc::Sender<A
     let start_tab_count = state.tabs.len();
     let is_clean_command = command_str.contains("clean");
 
-    // For clean commands on workspace members, we need to run sequentially to avoid race conditions
-    // Group projects by workspace root
+    // Cleaning projects that share a target directory (workspace members, a common
+    // CARGO_TARGET_DIR or build.target-dir) must not wipe it once per project
+    // Group projects by the target directory they build into
     if is_clean_command {
-        use std::collections::HashMap;
-        use std::path::PathBuf;
-
-        let mut workspace_groups: HashMap<Option<PathBuf>, Vec<(usize, Project)>> = HashMap::new();
-        for (i, project) in projects_to_run.into_iter().enumerate() {
-            workspace_groups
-                .entry(project.workspace_root.clone())
-                .or_default()
-                .push((i, project));
+        use std::collections::BTreeMap;
+
+        let mut target_groups: BTreeMap<PathBuf, Vec<Project>> = BTreeMap::new();
+        for project in projects_to_run {
+            let target_dir = state
+                .target_dirs
+                .get(&project.id)
+                .cloned()
+                .unwrap_or_else(|| project.target_dir());
+            target_groups.entry(target_dir).or_default().push(project);
         }
 
-        // Process each workspace group
-        for (_ws_root, group) in workspace_groups {
-            let has_shared_target = group.len() > 1 && group[0].1.workspace_root.is_some();
-
-            if has_shared_target {
-                // Spawn a single background task to run workspace member cleans sequentially
-                // This prevents race conditions while keeping the UI responsive
-                let tx = tx.clone();
-                let command_str = command_str.to_string();
-
-                tokio::spawn(async move {
-                    for (i, project) in group {
-                        let tab_title = format!("{}: {}", command_str, project.name);
-                        let tab_index = start_tab_count + i;
-
-                        let _ = tx.send(Action::CreateTab(tab_title)).await;
-
-                        if let Err(e) =
-                            spawn_and_stream_command(&command_str, &project, &tx, tab_index).await
-                        {
-                            let _ = tx
-                                .send(Action::AddOutput(tab_index, format!("❌ Error: {}", e)))
-                                .await;
-                        }
-                        recalculate_project_size(&project, &tx).await;
-                        let _ = tx.send(Action::FinishCommand(tab_index)).await;
+        let mut tab_index = start_tab_count;
+        for (target_dir, group) in target_groups {
+            let shared_by = state.target_dirs.sharing(&target_dir).count();
+            let jobs = clean_jobs(command_str, &target_dir, group, shared_by);
+
+            // Tabs are created up front so their indices follow the order they were sent in
+            let mut tabs = Vec::with_capacity(jobs.len());
+            for job in jobs {
+                let _ = tx.send(Action::CreateTab(job.title.clone())).await;
+                tabs.push((tab_index, job));
+                tab_index += 1;
+            }
+
+            // Jobs on one target directory run one after another to avoid race conditions,
+            // while directories of their own are cleaned in parallel
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                for (tab_index, job) in tabs {
+                    let _ = tx.send(Action::AddOutput(tab_index, job.notice)).await;
+                    if let Err(e) =
+                        spawn_and_stream_command(&job.command, &job.projects[0], &tx, tab_index)
+                            .await
+                    {
+                        let _ = tx
+                            .send(Action::AddOutput(tab_index, format!("❌ Error: {}", e)))
+                            .await;
                     }
-                });
-            } else {
-                // Run in parallel for standalone projects
-                for (i, project) in group {
-                    let tx = tx.clone();
-                    let command_str = command_str.to_string();
-                    let tab_title = format!("{}: {}", command_str, project.name);
-                    let tab_index = start_tab_count + i;
-
-                    let _ = tx.send(Action::CreateTab(tab_title)).await;
-
-                    tokio::spawn(async move {
-                        if let Err(e) =
-                            spawn_and_stream_command(&command_str, &project, &tx, tab_index).await
-                        {
-                            let _ = tx
-                                .send(Action::AddOutput(tab_index, format!("❌ Error: {}", e)))
-                                .await;
-                        }
-                        recalculate_project_size(&project, &tx).await;
-                        let _ = tx.send(Action::FinishCommand(tab_index)).await;
-                    });
+                    for project in &job.projects {
+                        recalculate_project_size(project, &tx).await;
+                    }
+                    let _ = tx.send(Action::FinishCommand(tab_index)).await;
                 }
-            }
+            });
         }
     } else {
         // Non-clean commands: run in parallel as before
         for (i, project) in projects_to_run.into_iter().enumerate() {
             let tx = tx.clone();
-            let command_str = command_str.to_string();
-            let tab_title = format!("{}: {}", command_str, project.name);
+            // Feature flags picked in the feature explorer apply per project
+            let command_str = match state.features.flags.get(&project.id) {
+                Some(flags) => flags.apply(command_str),
+                None => command_str.to_string(),
+            };
+            let tab_title = format!("{}: {}", command_str, project.display_name);
             let tab_index = start_tab_count + i;
 
             let _ = tx.send(Action::CreateTab(tab_title)).await;
@@ -538,3 +619,327 @@ pub async fn run_command(command_str: &str, state: &AppState, tx: mpsc::Sender<A
         }
     }
 }
+
+/// A `cargo clean` run, shown in a tab of its own
+#[derive(Debug, Clone)]
+struct CleanJob {
+    title: String,
+    /// Command to run, in the first project's directory
+    command: String,
+    /// Projects whose artifacts it removes
+    projects: Vec<Project>,
+    /// What will be removed, shown at the top of the tab
+    notice: String,
+}
+
+/// Plan the clean runs for the selected projects building into `target_dir`
+///
+/// `shared_by` is how many known projects build into it. When all of them are
+/// selected the directory is removed by a single `cargo clean`; otherwise each
+/// selected project only removes its own package with `cargo clean -p`, so the
+/// others keep their artifacts.
+fn clean_jobs(
+    command_str: &str,
+    target_dir: &Path,
+    projects: Vec<Project>,
+    shared_by: usize,
+) -> Vec<CleanJob> {
+    let shared_by = shared_by.max(projects.len());
+    if shared_by == projects.len() {
+        let names: Vec<&str> = projects.iter().map(|p| p.display_name.as_str()).collect();
+        let notice = if projects.len() > 1 {
+            format!(
+                "Removing {}, shared by {}",
+                target_dir.display(),
+                names.join(", ")
+            )
+        } else {
+            format!("Removing {}", target_dir.display())
+        };
+        return vec![CleanJob {
+            title: format!("{}: {}", command_str, names.join(", ")),
+            command: command_str.to_string(),
+            notice,
+            projects,
+        }];
+    }
+
+    projects
+        .into_iter()
+        .map(|project| CleanJob {
+            title: format!("{}: {}", command_str, project.display_name),
+            command: package_clean_command(command_str, &project.name),
+            notice: format!(
+                "{} is shared with {} other project(s): removing only the artifacts of {}",
+                target_dir.display(),
+                shared_by - 1,
+                project.name
+            ),
+            projects: vec![project],
+        })
+        .collect()
+}
+
+/// Limit a clean command to one package, unless it already names one
+fn package_clean_command(command_str: &str, package: &str) -> String {
+    let args: Vec<&str> = command_str.split_whitespace().collect();
+    let has_package = args
+        .iter()
+        .any(|arg| *arg == "-p" || *arg == "--package" || arg.starts_with("--package="));
+    if has_package {
+        return command_str.to_string();
+    }
+
+    let mut limited = Vec::with_capacity(args.len() + 2);
+    for arg in args {
+        limited.push(arg);
+        if arg == "clean" {
+            limited.extend(["-p", package]);
+        }
+    }
+    limited.join(" ")
+}
+
+/// Run the update wizard's `cargo update` commands on a project, one after another
+///
+/// All output goes to one tab; a failed update doesn't stop the ones after it.
+pub async fn run_updates(
+    project: Project,
+    commands: Vec<String>,
+    tab_index: usize,
+    tx: mpsc::Sender<Action>,
+) {
+    for command in &commands {
+        if let Err(e) = spawn_and_stream_command(command, &project, &tx, tab_index).await {
+            let _ = tx
+                .send(Action::AddOutput(tab_index, format!("❌ Error: {}", e)))
+                .await;
+        }
+        let _ = tx.send(Action::AddOutput(tab_index, "".to_string())).await;
+    }
+    let _ = tx.send(Action::FinishCommand(tab_index)).await;
+}
+
+/// Check every combination of a feature matrix, one after another
+///
+/// All output goes to the matrix's tab, followed by a summary of the results.
+pub async fn run_feature_matrix(project: Project, matrix: FeatureMatrix, tx: mpsc::Sender<Action>) {
+    let tab_index = matrix.tab_index;
+    let _ = tx
+        .send(Action::AddOutput(
+            tab_index,
+            format!(
+                "Checking {} of {} feature combinations",
+                matrix.runs.len(),
+                matrix.total
+            ),
+        ))
+        .await;
+
+    let mut results = Vec::with_capacity(matrix.runs.len());
+    for (i, run) in matrix.runs.iter().enumerate() {
+        let command = run.command();
+        let _ = tx
+            .send(Action::FeatureMatrixStatus(
+                tab_index,
+                i,
+                MatrixStatus::Running,
+            ))
+            .await;
+        let _ = tx.send(Action::AddOutput(tab_index, "".to_string())).await;
+
+        let passed = match spawn_and_stream_command(&command, &project, &tx, tab_index).await {
+            Ok(passed) => passed,
+            Err(e) => {
+                let _ = tx
+                    .send(Action::AddOutput(tab_index, format!("❌ Error: {}", e)))
+                    .await;
+                false
+            }
+        };
+        let status = if passed {
+            MatrixStatus::Passed
+        } else {
+            MatrixStatus::Failed
+        };
+        let _ = tx
+            .send(Action::FeatureMatrixStatus(tab_index, i, status))
+            .await;
+        results.push((command, passed));
+    }
+
+    let failed = results.iter().filter(|(_, passed)| !passed).count();
+    let _ = tx.send(Action::AddOutput(tab_index, "".to_string())).await;
+    let _ = tx
+        .send(Action::AddOutput(
+            tab_index,
+            format!(
+                "Feature matrix: {} passed, {} failed",
+                results.len() - failed,
+                failed
+            ),
+        ))
+        .await;
+    for (command, passed) in results {
+        let icon = if passed { "✓" } else { "✗" };
+        let _ = tx
+            .send(Action::AddOutput(
+                tab_index,
+                format!("  {} cargo {}", icon, command),
+            ))
+            .await;
+    }
+    let _ = tx.send(Action::FinishCommand(tab_index)).await;
+}
+
+/// Run a command on every toolchain of one project's matrix row, one after another
+///
+/// Columns that don't apply or whose toolchain isn't installed are noted in
+/// the row's tab and skipped.
+pub async fn run_toolchain_row(
+    project: Project,
+    row: ToolchainRow,
+    columns: Vec<String>,
+    command: String,
+    tx: mpsc::Sender<Action>,
+) {
+    let tab_index = row.tab_index;
+    for (i, (cell, column)) in row.cells.iter().zip(&columns).enumerate() {
+        let toolchain = cell.toolchain.as_deref().unwrap_or(column);
+        match cell.status {
+            ToolchainStatus::NotApplicable => {
+                let _ = tx
+                    .send(Action::AddOutput(
+                        tab_index,
+                        format!("— {}: not applicable to this project", column),
+                    ))
+                    .await;
+                continue;
+            }
+            ToolchainStatus::NotInstalled => {
+                let _ = tx
+                    .send(Action::AddOutput(
+                        tab_index,
+                        format!("⊘ {}: toolchain {} is not installed", column, toolchain),
+                    ))
+                    .await;
+                continue;
+            }
+            _ => {}
+        }
+
+        let _ = tx
+            .send(Action::ToolchainMatrixStatus(
+                project.id.clone(),
+                i,
+                ToolchainStatus::Running,
+            ))
+            .await;
+        let _ = tx.send(Action::AddOutput(tab_index, "".to_string())).await;
+        let command_str = format!("+{} {}", toolchain, command);
+        let passed = match spawn_and_stream_command(&command_str, &project, &tx, tab_index).await {
+            Ok(passed) => passed,
+            Err(e) => {
+                let _ = tx
+                    .send(Action::AddOutput(tab_index, format!("❌ Error: {}", e)))
+                    .await;
+                false
+            }
+        };
+        let status = if passed {
+            ToolchainStatus::Passed
+        } else {
+            ToolchainStatus::Failed
+        };
+        let _ = tx
+            .send(Action::ToolchainMatrixStatus(project.id.clone(), i, status))
+            .await;
+    }
+    let _ = tx.send(Action::FinishCommand(tab_index)).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CheckerConfig;
+    use std::path::Path;
+
+    #[test]
+    fn test_clean_jobs_for_shared_target_dir() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspaces/glob");
+        let mut members = crate::project::find_rust_projects(root.to_str().unwrap());
+        members.retain(|p| p.name == "alpha" || p.name == "beta");
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+        let target = root.join("target");
+
+        // Every project sharing the directory is selected: one clean removes it
+        let jobs = clean_jobs("clean", &target, members.clone(), 2);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].command, "clean");
+        assert_eq!(jobs[0].projects.len(), 2);
+        assert!(jobs[0].notice.starts_with("Removing "));
+
+        // Others keep their artifacts: each selected project cleans its own package
+        let jobs = clean_jobs("clean --release", &target, members[..1].to_vec(), 3);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].command, "clean -p alpha --release");
+        assert!(jobs[0].notice.contains("shared with 2 other project(s)"));
+
+        assert_eq!(
+            package_clean_command("clean --package beta", "alpha"),
+            "clean --package beta"
+        );
+    }
+
+    #[test]
+    fn test_update_queue_respects_max_parallel() {
+        let mut queue = UpdateQueue::new(2);
+        for id in 0..3 {
+            queue.add_task(UpdateCheckTask {
+                project_id: ProjectId::from_dir(Path::new(&format!("/tmp/queued-{id}"))),
+                is_priority: false,
+            });
+        }
+
+        assert!(queue.get_next_task().is_some());
+        assert!(queue.get_next_task().is_some());
+        assert!(queue.get_next_task().is_none());
+
+        queue.task_completed();
+        assert!(queue.get_next_task().is_some());
+        assert_eq!(UpdateQueue::new(0).max_parallel, 1);
+    }
+
+    #[test]
+    fn test_resolve_offline_version_steps_from_local_index() {
+        let registry = RegistryClient::new(&CheckerConfig {
+            local_index: Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/index")),
+            ..CheckerConfig::default()
+        });
+        let dep = Dependency {
+            name: "toml".into(),
+            current_version: "0.8.1".into(),
+            latest_version: None,
+            check_status: DependencyCheckStatus::Checking,
+            last_checked: None,
+            published_versions: Vec::new(),
+            requirement: None,
+            features: Vec::new(),
+        };
+
+        let resolved = resolve_offline(vec![dep], &CrateCache::default(), &registry);
+        let dep = &resolved[0];
+        assert_eq!(dep.check_status, DependencyCheckStatus::Checked);
+        assert_eq!(dep.latest_version.as_deref(), Some("0.9.0"));
+        assert!(dep.is_current_yanked());
+
+        let steps = dep.version_steps();
+        let versions: Vec<&str> = steps.iter().map(|s| s.version.as_str()).collect();
+        assert_eq!(versions, ["0.9.0", "0.8.2", "0.8.1"]);
+        assert!(!steps[0].compatible);
+        assert!(steps[1].compatible && !steps[1].yanked);
+        assert!(steps[2].is_current && steps[2].yanked);
+        // The index carries no release dates
+        assert!(steps.iter().all(|s| s.released_at.is_none()));
+    }
+}
@@ -0,0 +1,313 @@
+//! Streaming project discovery
+//!
+//! A single parallel walk finds every Cargo.toml below the scanned directory.
+//! Manifests are turned into projects as they arrive and handed out in small
+//! batches, so the UI can list projects while the rest of the tree is still
+//! being walked.
+
+use crate::config::ScanConfig;
+use crate::index::{IndexedProject, ProjectIndex};
+use crate::project::{CargoToml, Project, ProjectId, disambiguate_display_names, sort_projects};
+use crate::workspace::{WorkspaceIndex, read_manifest};
+use ignore::{WalkBuilder, WalkState};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long discovered projects are collected before a batch is handed out
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Running totals of a scan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanProgress {
+    /// Directories walked so far
+    pub scanned: usize,
+    /// Projects found so far
+    pub found: usize,
+}
+
+/// Scan `path` for Rust projects, reporting them in batches as they are found
+///
+/// `on_batch` receives each batch of new projects together with the progress
+/// so far. The returned list holds every project, sorted by workspace and name
+/// with clashing names disambiguated, like [`crate::project::find_rust_projects`].
+pub fn scan_rust_projects(
+    path: &str,
+    config: &ScanConfig,
+    on_batch: impl FnMut(Vec<Project>, ScanProgress),
+) -> Vec<Project> {
+    scan_with_index(&[scan_root(path)], config, None, on_batch)
+}
+
+/// Scan several roots in one walk, reusing indexed projects whose files are unchanged
+///
+/// Only manifests that are new or changed since `index` was built have their
+/// lockfiles parsed again.
+pub fn scan_with_index(
+    roots: &[PathBuf],
+    config: &ScanConfig,
+    index: Option<&ProjectIndex>,
+    mut on_batch: impl FnMut(Vec<Project>, ScanProgress),
+) -> Vec<Project> {
+    let Some(builder) = walk_builder(roots, config) else {
+        return Vec::new();
+    };
+
+    let scanned = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = mpsc::channel::<(PathBuf, CargoToml)>();
+    let walker = {
+        let scanned = Arc::clone(&scanned);
+        std::thread::spawn(move || {
+            builder.build_parallel().run(|| {
+                let tx = tx.clone();
+                let scanned = Arc::clone(&scanned);
+                Box::new(move |entry| {
+                    let Ok(entry) = entry else {
+                        return WalkState::Continue;
+                    };
+                    if entry.file_type().is_some_and(|t| t.is_dir()) {
+                        scanned.fetch_add(1, Ordering::Relaxed);
+                    } else if entry.file_name() == "Cargo.toml" {
+                        if let Some(toml) = read_manifest(entry.path()) {
+                            if tx.send((entry.into_path(), toml)).is_err() {
+                                return WalkState::Quit;
+                            }
+                        }
+                    }
+                    WalkState::Continue
+                })
+            });
+        })
+    };
+
+    // Projects below a root may belong to a workspace rooted above it
+    let mut workspaces = WorkspaceIndex::new();
+    for root in roots {
+        workspaces.insert_ancestors(root);
+    }
+
+    let mut projects = Vec::new();
+    let mut seen = HashSet::new();
+    let mut batch = Vec::new();
+    let mut last_flush = Instant::now();
+
+    loop {
+        let done = match rx.recv_timeout(BATCH_INTERVAL) {
+            Ok((manifest, toml)) => {
+                // Workspace roots are looked up on demand, so manifests can
+                // arrive in whatever order the walker finds them
+                workspaces.discover(&manifest, &toml);
+                let workspace = workspaces.workspace_for(&manifest, &toml);
+                let workspace_root = workspace.map(|ws| &ws.root);
+                let reused = index
+                    .zip(manifest.parent())
+                    .and_then(|(index, dir)| index.current(&ProjectId::from_dir(dir)))
+                    .filter(|indexed| indexed.workspace_root.as_ref() == workspace_root)
+                    .map(IndexedProject::to_project);
+                let project = reused.or_else(|| Project::from_toml(&manifest, &toml, workspace));
+                if let Some(project) = project {
+                    if seen.insert(project.id.clone()) {
+                        batch.push(project);
+                    }
+                }
+                false
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => false,
+            Err(mpsc::RecvTimeoutError::Disconnected) => true,
+        };
+
+        if !batch.is_empty() && (done || last_flush.elapsed() >= BATCH_INTERVAL) {
+            projects.extend(batch.iter().cloned());
+            let progress = ScanProgress {
+                scanned: scanned.load(Ordering::Relaxed),
+                found: projects.len(),
+            };
+            on_batch(std::mem::take(&mut batch), progress);
+            last_flush = Instant::now();
+        }
+
+        if done {
+            break;
+        }
+    }
+
+    if walker.join().is_err() {
+        tracing::warn!("Project scan panicked");
+    }
+
+    sort_projects(&mut projects);
+    disambiguate_display_names(&mut projects);
+    projects
+}
+
+/// Absolute path of the directory a scan of `path` covers
+pub fn scan_root(path: &str) -> PathBuf {
+    if Path::new(path).is_absolute() {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir()
+            .ok()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|| PathBuf::from(path))
+    }
+}
+
+/// Walker over `roots` that skips build output, hidden and excluded directories
+fn walk_builder(roots: &[PathBuf], config: &ScanConfig) -> Option<WalkBuilder> {
+    let (first, rest) = roots.split_first()?;
+    let exclude: Vec<glob::Pattern> = config
+        .exclude
+        .iter()
+        .filter_map(|pattern| match glob::Pattern::new(pattern) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid scan exclude pattern {:?}: {}", pattern, e);
+                None
+            }
+        })
+        .collect();
+    let bases = roots.to_vec();
+
+    let mut builder = WalkBuilder::new(first);
+    for root in rest {
+        builder.add(root);
+    }
+    builder
+        .follow_links(true)
+        .git_ignore(true)
+        .git_global(false)
+        .git_exclude(false)
+        .hidden(true) // We'll filter hidden ourselves
+        // A manifest sits one level below the deepest directory we look into
+        .max_depth(Some(config.max_depth.saturating_add(1)))
+        .filter_entry(move |e| {
+            // Skip common directories that won't contain projects
+            let file_name = e.file_name().to_string_lossy();
+            if file_name.starts_with('.') || file_name == "target" || file_name == "node_modules" {
+                return false;
+            }
+            let relative = bases
+                .iter()
+                .find_map(|base| e.path().strip_prefix(base).ok())
+                .unwrap_or(e.path());
+            !exclude
+                .iter()
+                .any(|pattern| pattern.matches(&file_name) || pattern.matches_path(relative))
+        });
+    Some(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/workspaces")
+            .join(name)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn names(projects: &[Project]) -> Vec<String> {
+        let mut names: Vec<String> = projects.iter().map(|p| p.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_batches_add_up_to_result() {
+        let mut streamed = Vec::new();
+        let mut last = ScanProgress::default();
+        let projects = scan_rust_projects(&fixture("glob"), &ScanConfig::default(), |batch, p| {
+            streamed.extend(batch);
+            last = p;
+        });
+
+        assert_eq!(names(&streamed), names(&projects));
+        assert_eq!(last.found, projects.len());
+        assert!(last.scanned > 0);
+        assert!(
+            projects
+                .iter()
+                .all(|p| p.workspace_name.as_deref() == Some("glob"))
+        );
+    }
+
+    #[test]
+    fn test_unchanged_projects_come_from_index() {
+        use crate::settings::DiscoveryBackend;
+
+        let root = fixture("glob");
+        let projects = scan_rust_projects(&root, &ScanConfig::default(), |_, _| {});
+        let mut index = ProjectIndex::build(
+            &[PathBuf::from(&root)],
+            DiscoveryBackend::Manifest,
+            &projects,
+        );
+        for indexed in &mut index.projects {
+            indexed.version = "9.9.9".to_string();
+        }
+
+        let rescanned = scan_with_index(
+            &[PathBuf::from(&root)],
+            &ScanConfig::default(),
+            Some(&index),
+            |_, _| {},
+        );
+        assert_eq!(rescanned.len(), projects.len());
+        assert!(rescanned.iter().all(|p| p.version == "9.9.9"));
+    }
+
+    #[test]
+    fn test_several_roots_in_one_walk() {
+        let roots = [
+            PathBuf::from(fixture("glob")),
+            PathBuf::from(fixture("nested")),
+        ];
+        let projects = scan_with_index(&roots, &ScanConfig::default(), None, |_, _| {});
+
+        let glob = scan_rust_projects(&fixture("glob"), &ScanConfig::default(), |_, _| {});
+        let nested = scan_rust_projects(&fixture("nested"), &ScanConfig::default(), |_, _| {});
+        assert_eq!(projects.len(), glob.len() + nested.len());
+        assert!(scan_with_index(&[], &ScanConfig::default(), None, |_, _| {}).is_empty());
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let config = ScanConfig {
+            max_depth: 2,
+            ..ScanConfig::default()
+        };
+        let projects = scan_rust_projects(&fixture("nested"), &config, |_, _| {});
+        assert!(!projects.is_empty());
+        assert!(
+            projects
+                .iter()
+                .all(|p| !p.path.ends_with("crates/inner/member"))
+        );
+
+        let all = scan_rust_projects(&fixture("nested"), &ScanConfig::default(), |_, _| {});
+        assert_eq!(all.len(), projects.len() + 1);
+    }
+
+    #[test]
+    fn test_exclude_patterns() {
+        let config = ScanConfig {
+            exclude: vec!["tools".into()],
+            ..ScanConfig::default()
+        };
+        let projects = scan_rust_projects(&fixture("glob"), &config, |_, _| {});
+        assert_eq!(names(&projects), ["alpha", "beta"]);
+
+        let config = ScanConfig {
+            exclude: vec!["crates/b*".into()],
+            ..ScanConfig::default()
+        };
+        let projects = scan_rust_projects(&fixture("glob"), &config, |_, _| {});
+        assert_eq!(names(&projects), ["alpha", "cli"]);
+    }
+}
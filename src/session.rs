@@ -0,0 +1,295 @@
+//! Session restore across launches
+//!
+//! When enabled in the settings, the state of the UI is saved on quit next to
+//! the project index, keyed by the scan roots: expanded and collapsed
+//! directories and groups, the row under the cursor, the selected projects,
+//! the output tabs, pane focus and a filter being typed. The next launch for
+//! the same roots puts it back once the projects are listed.
+
+use crate::app::{AppState, Tab};
+use crate::events::{Focus, Mode};
+use crate::project::ProjectId;
+use crate::tree::{TreeNode, TreeNodeType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Version of the on-disk session format; sessions with another version are ignored
+pub const SESSION_SCHEMA_VERSION: u32 = 1;
+
+/// Output lines kept per tab, the most recent ones
+pub const MAX_TAB_LINES: usize = 2000;
+
+/// An output tab as saved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedTab {
+    pub title: String,
+    pub buffer: Vec<String>,
+    /// Whether the command had finished; one still running is cut short on quit
+    pub is_finished: bool,
+}
+
+/// The state of the UI for a set of scan roots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub schema_version: u32,
+    pub roots: Vec<PathBuf>,
+    /// Directories expanded in the tree
+    pub expanded: Vec<PathBuf>,
+    /// Directories whose children were loaded, but which were collapsed
+    pub collapsed: Vec<PathBuf>,
+    pub collapsed_tags: Vec<String>,
+    pub collapsed_groups: Vec<String>,
+    /// Path of the row under the cursor
+    pub cursor: Option<PathBuf>,
+    pub selected: Vec<ProjectId>,
+    pub tabs: Vec<SavedTab>,
+    pub active_tab: usize,
+    pub focus: Focus,
+    /// Filter query being typed
+    pub filter: Option<String>,
+}
+
+impl Session {
+    /// Capture the state of the UI
+    pub fn capture(state: &AppState) -> Self {
+        let mut expanded = Vec::new();
+        let mut collapsed = Vec::new();
+        if let Some(root) = &state.tree_root {
+            collect_directories(root, &mut expanded, &mut collapsed);
+        }
+
+        let mut selected: Vec<ProjectId> = state.selected_projects.iter().cloned().collect();
+        selected.sort();
+        let mut collapsed_tags: Vec<String> = state.collapsed_tags.iter().cloned().collect();
+        collapsed_tags.sort();
+        let mut collapsed_groups: Vec<String> = state.collapsed_groups.iter().cloned().collect();
+        collapsed_groups.sort();
+
+        Self {
+            schema_version: SESSION_SCHEMA_VERSION,
+            roots: state.scan_roots.paths.clone(),
+            expanded,
+            collapsed,
+            collapsed_tags,
+            collapsed_groups,
+            cursor: state
+                .get_selected_node()
+                .map(|node| node.node_type.path().to_path_buf()),
+            selected,
+            tabs: state
+                .tabs
+                .iter()
+                .map(|tab| SavedTab {
+                    title: tab.title.clone(),
+                    buffer: tab.buffer[tab.buffer.len().saturating_sub(MAX_TAB_LINES)..].to_vec(),
+                    is_finished: tab.is_finished,
+                })
+                .collect(),
+            active_tab: state.active_tab,
+            focus: state.focus.clone(),
+            filter: (state.mode == Mode::Filter && !state.filter.input.is_empty())
+                .then(|| state.filter.input.clone()),
+        }
+    }
+
+    /// Put the UI back as it was, once the projects of the roots are listed
+    ///
+    /// Directories that are gone and projects no longer found are skipped.
+    /// Tabs are only brought back while no command has been run yet.
+    pub fn restore(self, state: &mut AppState) {
+        let expanded: HashSet<PathBuf> = self.expanded.into_iter().collect();
+        let collapsed: HashSet<PathBuf> = self.collapsed.into_iter().collect();
+        let show_all_folders = state.settings.show_all_folders;
+        if let Some(root) = &mut state.tree_root {
+            restore_directories(root, &expanded, &collapsed, show_all_folders);
+        }
+        state.collapsed_tags = self.collapsed_tags.into_iter().collect();
+        state.collapsed_groups = self.collapsed_groups.into_iter().collect();
+        crate::handlers::refresh_flattened_tree(state);
+
+        if let Some(index) = self
+            .cursor
+            .and_then(|path| state.flattened_tree.get_index(&path))
+        {
+            state.tree_state.select(Some(index));
+        }
+
+        let known: HashSet<&ProjectId> = state.all_projects.iter().map(|p| &p.id).collect();
+        state.selected_projects = self
+            .selected
+            .into_iter()
+            .filter(|id| known.contains(id))
+            .collect();
+
+        if state.tabs.is_empty() && !self.tabs.is_empty() {
+            state.tabs = self
+                .tabs
+                .into_iter()
+                .map(|tab| {
+                    let mut buffer = tab.buffer;
+                    if !tab.is_finished {
+                        buffer.push("(interrupted when carwash quit)".to_string());
+                    }
+                    Tab {
+                        title: tab.title,
+                        buffer,
+                        is_finished: true,
+                    }
+                })
+                .collect();
+            state.active_tab = self.active_tab.min(state.tabs.len() - 1);
+        }
+        state.focus = self.focus;
+
+        if let Some(filter) = self.filter {
+            state.mode = Mode::Filter;
+            crate::handlers::handle_update_filter_input(state, filter);
+        }
+    }
+}
+
+/// Sort loaded directories into expanded and collapsed ones
+fn collect_directories(node: &TreeNode, expanded: &mut Vec<PathBuf>, collapsed: &mut Vec<PathBuf>) {
+    if let TreeNodeType::Directory { path, .. } = &node.node_type {
+        if node.expanded {
+            expanded.push(path.clone());
+        } else if node.children_loaded {
+            collapsed.push(path.clone());
+        }
+    }
+    for child in &node.children {
+        collect_directories(child, expanded, collapsed);
+    }
+}
+
+/// Expand and collapse directories top-down, loading those expanded on the way
+fn restore_directories(
+    node: &mut TreeNode,
+    expanded: &HashSet<PathBuf>,
+    collapsed: &HashSet<PathBuf>,
+    show_all_folders: bool,
+) {
+    let path = node.node_type.path().to_path_buf();
+    if node.node_type.is_directory() {
+        if expanded.contains(&path) {
+            crate::project::load_directory_children(node, show_all_folders);
+            node.expanded = true;
+        } else if collapsed.contains(&path) {
+            node.expanded = false;
+        }
+    }
+    for child in &mut node.children {
+        restore_directories(child, expanded, collapsed, show_all_folders);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::reducer;
+    use crate::events::Action;
+    use crate::project::find_rust_projects;
+    use crate::roots::ScanRoots;
+    use crate::test_support::{temp_dir, write_package};
+    use std::fs;
+    use std::path::Path;
+
+    /// A fresh UI with the projects under `dir` listed
+    fn launch(dir: &Path, session: Option<Session>) -> AppState {
+        let mut state = AppState::new();
+        state.settings.group_by_tag = false;
+        state.settings.table_view.enabled = false;
+        state.pending_session = session;
+        let roots = ScanRoots::from_dirs(&[dir.to_string_lossy().to_string()]);
+        let projects = find_rust_projects(dir.to_str().unwrap());
+        reducer(&mut state, Action::FinishProjectScan(projects, roots));
+        state
+    }
+
+    fn rows(state: &AppState) -> Vec<String> {
+        state
+            .flattened_tree
+            .items
+            .iter()
+            .map(|(node, _)| node.node_type.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let dir = temp_dir("session", "round-trip");
+        write_package(&dir.join("apps/api"), "api", "0.1.0", &[("serde", "1")]);
+        write_package(&dir.join("apps/web"), "web", "0.1.0", &[("serde", "1")]);
+        write_package(&dir.join("tools/lint"), "lint", "0.1.0", &[("serde", "1")]);
+
+        let mut state = launch(&dir, None);
+        let closed = rows(&state);
+        assert!(!closed.contains(&"api".to_string()));
+
+        // Open "apps", move onto "web" and select it
+        let apps = state.flattened_tree.get_index(&dir.join("apps")).unwrap();
+        state.tree_state.select(Some(apps));
+        reducer(&mut state, Action::SelectChild);
+        let apps_node = state.tree_root.as_ref().unwrap().find(&dir.join("apps"));
+        assert!(apps_node.unwrap().expanded);
+        // Children load asynchronously in the app; load them here
+        if let Some(root) = &mut state.tree_root {
+            restore_directories(
+                root,
+                &HashSet::from([dir.join("apps")]),
+                &HashSet::new(),
+                false,
+            );
+        }
+        crate::handlers::refresh_flattened_tree(&mut state);
+        let web = state
+            .flattened_tree
+            .get_index(&dir.join("apps/web"))
+            .unwrap();
+        state.tree_state.select(Some(web));
+        reducer(&mut state, Action::ToggleSelection);
+        state.tabs = vec![
+            Tab {
+                title: "test".to_string(),
+                buffer: (0..MAX_TAB_LINES + 5).map(|i| i.to_string()).collect(),
+                is_finished: true,
+            },
+            Tab {
+                title: "build".to_string(),
+                buffer: vec!["Compiling".to_string()],
+                is_finished: false,
+            },
+        ];
+        state.active_tab = 1;
+        state.focus = Focus::Output;
+
+        let json = serde_json::to_string(&Session::capture(&state)).unwrap();
+        let session: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(session.tabs[0].buffer.len(), MAX_TAB_LINES);
+        assert_eq!(session.tabs[0].buffer[0], "5");
+
+        let restored = launch(&dir, Some(session));
+        assert!(restored.pending_session.is_none());
+        assert_eq!(rows(&restored), rows(&state));
+        assert_eq!(restored.tree_state.selected(), Some(web));
+        assert_eq!(restored.selected_projects, state.selected_projects);
+        assert_eq!(restored.tabs.len(), 2);
+        assert_eq!(restored.active_tab, 1);
+        assert!(restored.tabs[1].is_finished);
+        assert_eq!(
+            restored.tabs[1].buffer.last().unwrap(),
+            "(interrupted when carwash quit)"
+        );
+        assert_eq!(restored.focus, Focus::Output);
+
+        // Another set of roots starts from scratch
+        let mut session = Session::capture(&state);
+        session.roots = vec![PathBuf::from("/elsewhere")];
+        let other = launch(&dir, Some(session));
+        assert_eq!(rows(&other), closed);
+        assert!(other.tabs.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,18 +1,53 @@
+use crate::project::ProjectId;
+use crate::project_table::TableView;
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::PathBuf;
 use std::time::Duration;
 
 const DEFAULT_CACHE_TTL_MINUTES: u64 = 5;
 
+/// How projects and their dependencies are discovered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiscoveryBackend {
+    /// Parse Cargo.toml and Cargo.lock directly (fast)
+    #[default]
+    Manifest,
+    /// Ask `cargo metadata`, falling back to the manifest parser on failure
+    CargoMetadata,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub background_updates_enabled: bool,
     pub cache_ttl_minutes: u64,
     #[serde(default)]
     pub show_all_folders: bool,
+    /// Serve update checks from the cache only, never hitting the network
+    #[serde(default)]
+    pub offline: bool,
+    /// Backend used to discover projects and dependencies
+    #[serde(default)]
+    pub discovery: DiscoveryBackend,
+    /// Group the project tree by tag instead of by directory
+    #[serde(default)]
+    pub group_by_tag: bool,
+    /// Tags assigned to projects from the UI
+    #[serde(default)]
+    pub project_tags: BTreeMap<ProjectId, BTreeSet<String>>,
+    /// Whether projects are shown as a table, and how it is sorted and grouped
+    #[serde(default)]
+    pub table_view: TableView,
+    /// Named selections of projects, recalled from the palette
+    #[serde(default)]
+    pub saved_selections: BTreeMap<String, BTreeSet<ProjectId>>,
+    /// Save the session on quit and restore it on the next launch for the same roots
+    #[serde(default)]
+    pub restore_session: bool,
 }
 
 impl Default for AppSettings {
@@ -21,6 +56,13 @@ impl Default for AppSettings {
             background_updates_enabled: false,
             cache_ttl_minutes: DEFAULT_CACHE_TTL_MINUTES,
             show_all_folders: false,
+            offline: false,
+            discovery: DiscoveryBackend::Manifest,
+            group_by_tag: false,
+            project_tags: BTreeMap::new(),
+            table_view: TableView::default(),
+            saved_selections: BTreeMap::new(),
+            restore_session: false,
         }
     }
 }
@@ -69,3 +111,29 @@ fn settings_path() -> PathBuf {
         PathBuf::from("./carwash-settings.json")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_project_tags_round_trip() {
+        let mut settings = AppSettings::default();
+        settings.project_tags.insert(
+            ProjectId::from_dir(Path::new("/src/api")),
+            BTreeSet::from(["services".to_string()]),
+        );
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.project_tags, settings.project_tags);
+
+        // Settings written before tags existed still load
+        let old: AppSettings =
+            serde_json::from_str(r#"{"background_updates_enabled":false,"cache_ttl_minutes":5}"#)
+                .unwrap();
+        assert!(old.project_tags.is_empty());
+        assert!(old.saved_selections.is_empty());
+    }
+}
@@ -0,0 +1,567 @@
+//! Reclaiming disk space from target directories
+//!
+//! A target directory is broken down by what it holds (`debug`, `release`,
+//! `doc`, profiles per target triple, ...), and stale build artifacts can be
+//! swept out of it the way `cargo-sweep` does: every compiled unit has a
+//! fingerprint directory named `<crate>-<hash>`, and the same hash names its
+//! files under `deps/`, `build/` and `examples/`. A unit is stale when it
+//! wasn't used for a number of days, or when the compiler that built it is no
+//! longer installed. Sweeping always goes through a [`SweepPlan`] first, which
+//! doubles as the dry-run preview of what would be deleted.
+
+use crate::project::calculate_directory_size;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// Subdirectories of a profile whose entries are named `<crate>-<hash>[.ext]`
+const UNIT_DIRS: &[&str] = &[".fingerprint", "build", "deps", "examples"];
+
+/// Space taken by one part of a target directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetUsage {
+    /// `debug`, `release`, `doc`, `x86_64-unknown-linux-gnu/release`, ...
+    pub name: String,
+    pub bytes: u64,
+}
+
+/// What a target directory holds, largest part first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetBreakdown {
+    pub usages: Vec<TargetUsage>,
+    pub total: u64,
+}
+
+impl TargetBreakdown {
+    pub fn load(target_dir: &Path) -> Self {
+        let mut usages = Vec::new();
+        let mut loose = 0;
+        for entry in read_dir(target_dir) {
+            let name = file_name(&entry);
+            if !entry.is_dir() {
+                loose += file_size(&entry);
+            } else if name != "doc" && !is_profile_dir(&entry) && has_profile_dirs(&entry) {
+                // A target triple: one usage per profile built for it
+                for profile in read_dir(&entry) {
+                    let profile_name = file_name(&profile);
+                    usages.push(TargetUsage {
+                        name: format!("{}/{}", name, profile_name),
+                        bytes: size_of(&profile),
+                    });
+                }
+            } else {
+                usages.push(TargetUsage {
+                    bytes: size_of(&entry),
+                    name,
+                });
+            }
+        }
+        if loose > 0 {
+            usages.push(TargetUsage {
+                name: "other files".to_string(),
+                bytes: loose,
+            });
+        }
+        usages.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        let total = usages.iter().map(|u| u.bytes).sum();
+        Self { usages, total }
+    }
+}
+
+/// What makes a build artifact stale
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SweepCriteria {
+    /// Unused for this many days
+    pub older_than_days: Option<u32>,
+    /// Built by a compiler that isn't installed anymore
+    pub uninstalled_toolchains: bool,
+}
+
+impl SweepCriteria {
+    pub fn is_empty(&self) -> bool {
+        self.older_than_days.is_none() && !self.uninstalled_toolchains
+    }
+
+    /// Short description, e.g. `unused for 30+ days or built by uninstalled toolchains`
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(days) = self.older_than_days {
+            parts.push(format!("unused for {}+ days", days));
+        }
+        if self.uninstalled_toolchains {
+            parts.push("built by uninstalled toolchains".to_string());
+        }
+        if parts.is_empty() {
+            "nothing".to_string()
+        } else {
+            parts.join(" or ")
+        }
+    }
+}
+
+/// A file or directory a sweep deletes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepEntry {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Stale artifacts of one or more target directories: the dry-run preview of a sweep
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SweepPlan {
+    pub criteria: SweepCriteria,
+    /// Target directories looked at
+    pub target_dirs: Vec<PathBuf>,
+    pub entries: Vec<SweepEntry>,
+    pub bytes: u64,
+    /// Compiled units found stale
+    pub units: usize,
+}
+
+impl SweepPlan {
+    /// Find the stale artifacts in `target_dirs`
+    ///
+    /// `installed` are the `rustc -V` versions of the installed toolchains;
+    /// when it's empty the toolchain criterion is skipped rather than
+    /// treating every artifact as stale.
+    pub fn new(
+        target_dirs: Vec<PathBuf>,
+        criteria: SweepCriteria,
+        installed: &[String],
+        now: SystemTime,
+    ) -> Self {
+        let cutoff = criteria
+            .older_than_days
+            .and_then(|days| now.checked_sub(Duration::from_secs(u64::from(days) * 86_400)));
+        let installed: Option<HashSet<&str>> = (criteria.uninstalled_toolchains
+            && !installed.is_empty())
+        .then(|| installed.iter().map(|v| v.trim()).collect());
+
+        let mut plan = Self {
+            criteria,
+            ..Self::default()
+        };
+        for target_dir in &target_dirs {
+            for profile in profile_dirs(target_dir) {
+                plan.sweep_profile(&profile, cutoff, installed.as_ref());
+            }
+        }
+        plan.entries
+            .sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+        plan.bytes = plan.entries.iter().map(|e| e.bytes).sum();
+        plan.target_dirs = target_dirs;
+        plan
+    }
+
+    fn sweep_profile(
+        &mut self,
+        profile: &Path,
+        cutoff: Option<SystemTime>,
+        installed: Option<&HashSet<&str>>,
+    ) {
+        let units = Unit::load_all(profile, installed.is_some());
+        let versions = if installed.is_some() {
+            compiler_versions(profile, &units)
+        } else {
+            HashMap::new()
+        };
+        let stale: HashSet<&str> = units
+            .iter()
+            .filter(|unit| {
+                let unused = cutoff.is_some_and(|cutoff| unit.last_used < cutoff);
+                // Units of an unknown compiler are kept
+                let uninstalled = installed.is_some_and(|installed| {
+                    unit.rustc
+                        .and_then(|rustc| versions.get(&rustc))
+                        .is_some_and(|version| !installed.contains(version.as_str()))
+                });
+                unused || uninstalled
+            })
+            .map(|unit| unit.hash.as_str())
+            .collect();
+        self.units += stale.len();
+
+        for dir in UNIT_DIRS {
+            for entry in read_dir(&profile.join(dir)) {
+                if unit_hash(&entry).is_some_and(|hash| stale.contains(hash.as_str())) {
+                    self.push(entry);
+                }
+            }
+        }
+        // Incremental caches are named by a different hash, so only their age counts
+        if let Some(cutoff) = cutoff {
+            for entry in read_dir(&profile.join("incremental")) {
+                if last_used(&entry) < cutoff {
+                    self.push(entry);
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, path: PathBuf) {
+        let bytes = size_of(&path);
+        self.entries.push(SweepEntry { path, bytes });
+    }
+
+    /// Delete everything in the plan, returning the bytes freed and any errors
+    pub fn execute(&self) -> (u64, Vec<String>) {
+        let mut freed = 0;
+        let mut errors = Vec::new();
+        for entry in &self.entries {
+            let result = if entry.path.is_dir() {
+                fs::remove_dir_all(&entry.path)
+            } else {
+                fs::remove_file(&entry.path)
+            };
+            match result {
+                Ok(()) => freed += entry.bytes,
+                // Already gone, e.g. swept through a shared target directory
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => errors.push(format!("{}: {}", entry.path.display(), e)),
+            }
+        }
+        for dir in &self.target_dirs {
+            crate::disk_usage::forget_below(dir);
+        }
+        (freed, errors)
+    }
+}
+
+/// A compiled unit, found through its fingerprint directory
+struct Unit {
+    hash: String,
+    /// Fingerprint of the compiler, as recorded by cargo
+    rustc: Option<u64>,
+    last_used: SystemTime,
+}
+
+impl Unit {
+    /// Units of a profile; the compiler is only read from fingerprints when `with_rustc`
+    fn load_all(profile: &Path, with_rustc: bool) -> Vec<Self> {
+        read_dir(&profile.join(".fingerprint"))
+            .into_iter()
+            .filter_map(|dir| {
+                let hash = unit_hash(&dir)?;
+                // Before reading anything, which may update access times
+                let last_used = last_used(&dir);
+                let rustc = read_dir(&dir)
+                    .into_iter()
+                    .filter(|file| with_rustc && is_json(file))
+                    .find_map(|file| {
+                        let json: Value = serde_json::from_slice(&fs::read(file).ok()?).ok()?;
+                        json.get("rustc")?.as_u64()
+                    });
+                Some(Self {
+                    hash,
+                    rustc,
+                    last_used,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Map cargo's compiler fingerprints to `rustc -V` versions
+///
+/// Library metadata (`.rmeta`, `.rlib`) starts with the version of the
+/// compiler that wrote it; every unit sharing a library's compiler
+/// fingerprint was built by that version.
+fn compiler_versions(profile: &Path, units: &[Unit]) -> HashMap<u64, String> {
+    let by_hash: HashMap<&str, u64> = units
+        .iter()
+        .filter_map(|unit| Some((unit.hash.as_str(), unit.rustc?)))
+        .collect();
+    let mut versions = HashMap::new();
+    for file in read_dir(&profile.join("deps")) {
+        let is_metadata = file
+            .extension()
+            .is_some_and(|ext| ext == "rmeta" || ext == "rlib");
+        let Some(rustc) = unit_hash(&file).and_then(|hash| by_hash.get(hash.as_str()).copied())
+        else {
+            continue;
+        };
+        if is_metadata && !versions.contains_key(&rustc) {
+            if let Some(version) = metadata_rustc_version(&file) {
+                versions.insert(rustc, version);
+            }
+        }
+    }
+    versions
+}
+
+/// The `rustc x.y.z (hash date)` version in a library's metadata header
+pub fn metadata_rustc_version(path: &Path) -> Option<String> {
+    let mut header = [0u8; 256];
+    let read = fs::File::open(path).ok()?.read(&mut header).ok()?;
+    let header = &header[..read];
+    let start = header.windows(6).position(|w| w == b"rustc ")?;
+    let end = start + header[start..].iter().position(|&b| b == b')')?;
+    String::from_utf8(header[start..=end].to_vec()).ok()
+}
+
+/// `rustc -V` of every installed rustup toolchain
+pub fn installed_rustc_versions() -> Vec<String> {
+    crate::toolchains::installed_toolchains()
+        .iter()
+        .filter_map(|toolchain| {
+            let output = Command::new("rustc")
+                .arg(format!("+{}", toolchain))
+                .arg("-V")
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .collect()
+}
+
+/// The hash in `<crate>-<hash>` or `lib<crate>-<hash>.rlib`
+fn unit_hash(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name.split('.').next()?;
+    let (_, hash) = stem.rsplit_once('-')?;
+    (hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit())).then(|| hash.to_string())
+}
+
+/// Profile directories (`debug`, `release`, custom ones), also under target triples
+fn profile_dirs(target_dir: &Path) -> Vec<PathBuf> {
+    let mut profiles = Vec::new();
+    for entry in read_dir(target_dir) {
+        if is_profile_dir(&entry) {
+            profiles.push(entry);
+        } else if entry.is_dir() {
+            profiles.extend(read_dir(&entry).into_iter().filter(|p| is_profile_dir(p)));
+        }
+    }
+    profiles
+}
+
+fn is_profile_dir(path: &Path) -> bool {
+    path.join(".fingerprint").is_dir() || path.join("deps").is_dir()
+}
+
+fn has_profile_dirs(path: &Path) -> bool {
+    read_dir(path).iter().any(|p| is_profile_dir(p))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// When a file or any file in a directory was last written or read
+///
+/// Cargo reads a unit's fingerprint hash on every build, even when nothing is
+/// rebuilt. The `.json` next to it is left out, since carwash reads it itself.
+fn last_used(path: &Path) -> SystemTime {
+    let times = |meta: fs::Metadata| {
+        let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let accessed = meta.accessed().unwrap_or(SystemTime::UNIX_EPOCH);
+        modified.max(accessed)
+    };
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && !is_json(entry.path()))
+        .filter_map(|entry| entry.metadata().ok())
+        .map(times)
+        .max()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn read_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+fn size_of(path: &Path) -> u64 {
+    if path.is_dir() {
+        calculate_directory_size(path).unwrap_or(0)
+    } else {
+        file_size(path)
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::symlink_metadata(path).map_or(0, |meta| meta.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::fs::FileTimes;
+
+    const OLD: &str = "0123456789abcdef";
+    const NEW: &str = "fedcba9876543210";
+    const CURRENT: &str = "rustc 1.95.0 (59807616e 2026-04-14)";
+
+    fn write(path: &Path, content: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn metadata(version: &str) -> Vec<u8> {
+        [
+            b"rust\0\0\0\x0a#".as_slice(),
+            version.as_bytes(),
+            b"\xc1\x02",
+        ]
+        .concat()
+    }
+
+    fn age(path: &Path, days: u64) {
+        let time = SystemTime::now() - Duration::from_secs(days * 86_400);
+        let times = FileTimes::new().set_accessed(time).set_modified(time);
+        for entry in walkdir::WalkDir::new(path).into_iter().flatten() {
+            if entry.file_type().is_file() {
+                let file = fs::File::options().write(true).open(entry.path()).unwrap();
+                file.set_times(times).unwrap();
+            }
+        }
+    }
+
+    /// A target dir with a unit unused for 40 days built by an old compiler, and a fresh one
+    fn target_dir(name: &str) -> PathBuf {
+        let target = temp_dir("sweep", name).join("target");
+        let debug = target.join("debug");
+        for (name, hash, rustc, version) in [
+            ("old", OLD, 1, "rustc 1.70.0 (90c541806 2023-05-31)"),
+            ("new", NEW, 2, CURRENT),
+        ] {
+            let fingerprint = debug.join(format!(".fingerprint/{}-{}", name, hash));
+            write(&fingerprint.join(format!("lib-{}", name)), b"1234");
+            write(
+                &fingerprint.join(format!("lib-{}.json", name)),
+                format!("{{\"rustc\":{}}}", rustc).as_bytes(),
+            );
+            write(
+                &debug.join(format!("deps/lib{}-{}.rmeta", name, hash)),
+                &metadata(version),
+            );
+            write(&debug.join(format!("deps/{}-{}.d", name, hash)), b"deps");
+        }
+        write(
+            &debug.join(format!("build/old-{}/out/gen.rs", OLD)),
+            b"generated",
+        );
+        write(
+            &debug.join("incremental/old-1abc/s-dep-graph.bin"),
+            b"graph",
+        );
+        write(
+            &debug.join("incremental/new-2def/s-dep-graph.bin"),
+            b"graph",
+        );
+        age(&debug.join(format!(".fingerprint/old-{}", OLD)), 40);
+        age(&debug.join("incremental/old-1abc"), 40);
+
+        write(
+            &target.join("x86_64-unknown-linux-gnu/release/deps/libnew.rlib"),
+            &[0; 64],
+        );
+        write(&target.join("doc/index.html"), b"<html>");
+        write(&target.join(".rustc_info.json"), b"{}");
+        target
+    }
+
+    fn names(plan: &SweepPlan, target: &Path) -> Vec<String> {
+        let mut names: Vec<String> = plan
+            .entries
+            .iter()
+            .map(|e| e.path.strip_prefix(target).unwrap().display().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_breakdown_by_profile_and_triple() {
+        let target = target_dir("breakdown");
+        let breakdown = TargetBreakdown::load(&target);
+
+        let mut names: Vec<&str> = breakdown.usages.iter().map(|u| u.name.as_str()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "debug",
+                "doc",
+                "other files",
+                "x86_64-unknown-linux-gnu/release"
+            ]
+        );
+        assert_eq!(breakdown.usages[0].name, "debug");
+        assert_eq!(breakdown.total, calculate_directory_size(&target).unwrap());
+        assert_eq!(
+            metadata_rustc_version(&target.join(format!("debug/deps/libnew-{}.rmeta", NEW)))
+                .as_deref(),
+            Some(CURRENT)
+        );
+    }
+
+    #[test]
+    fn test_sweep_plan_by_age_and_toolchain() {
+        let target = target_dir("plan");
+        let now = SystemTime::now();
+        let by_age = SweepCriteria {
+            older_than_days: Some(30),
+            uninstalled_toolchains: false,
+        };
+        let plan = SweepPlan::new(vec![target.clone()], by_age, &[], now);
+        assert_eq!(
+            names(&plan, &target),
+            [
+                format!("debug/.fingerprint/old-{}", OLD),
+                format!("debug/build/old-{}", OLD),
+                format!("debug/deps/libold-{}.rmeta", OLD),
+                format!("debug/deps/old-{}.d", OLD),
+                "debug/incremental/old-1abc".to_string(),
+            ]
+        );
+        assert_eq!(plan.units, 1);
+
+        let by_toolchain = SweepCriteria {
+            older_than_days: None,
+            uninstalled_toolchains: true,
+        };
+        let plan = SweepPlan::new(
+            vec![target.clone()],
+            by_toolchain.clone(),
+            &[CURRENT.to_string()],
+            now,
+        );
+        assert_eq!(plan.units, 1);
+        assert_eq!(plan.entries.len(), 4);
+        // Without knowing what's installed, nothing counts as uninstalled
+        assert!(
+            SweepPlan::new(vec![target.clone()], by_toolchain, &[], now)
+                .entries
+                .is_empty()
+        );
+
+        let (freed, errors) = plan.execute();
+        assert!(errors.is_empty());
+        assert_eq!(freed, plan.bytes);
+        assert!(!target.join(format!("debug/build/old-{}", OLD)).exists());
+        assert!(
+            target
+                .join(format!("debug/deps/libnew-{}.rmeta", NEW))
+                .exists()
+        );
+    }
+}
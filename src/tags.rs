@@ -0,0 +1,175 @@
+//! Project tags: cross-cutting groups beside directories and workspaces
+//!
+//! A project's tags come from its manifest `keywords` and `categories`, from
+//! name or path patterns under `[tags]` in the config, and from tags assigned
+//! in the UI (kept in the settings file). Tags can group the project tree, and
+//! work as selection and filter targets (`#services`).
+
+use crate::project::Project;
+use crate::roots::expand_home;
+use crate::tree::TreeNode;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Resolve a project's tags from its manifest, the config rules and UI assignments
+pub fn resolve_tags(
+    project: &Project,
+    rules: &BTreeMap<String, Vec<String>>,
+    assigned: Option<&BTreeSet<String>>,
+) -> Vec<String> {
+    let mut tags: BTreeSet<String> = project
+        .keywords
+        .iter()
+        .chain(&project.categories)
+        .map(|tag| normalize(tag))
+        .collect();
+    for (tag, patterns) in rules {
+        if patterns
+            .iter()
+            .any(|pattern| rule_matches(pattern, project))
+        {
+            tags.insert(normalize(tag));
+        }
+    }
+    if let Some(assigned) = assigned {
+        tags.extend(assigned.iter().map(|tag| normalize(tag)));
+    }
+    tags.remove("");
+    tags.into_iter().collect()
+}
+
+/// Parse tags typed by the user, separated by commas or spaces
+pub fn parse_tags(input: &str) -> BTreeSet<String> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(normalize)
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Every tag used by `projects`, sorted
+pub fn all_tags(projects: &[Project]) -> Vec<String> {
+    let tags: BTreeSet<&String> = projects.iter().flat_map(|p| &p.tags).collect();
+    tags.into_iter().cloned().collect()
+}
+
+/// Tags compare case-insensitively and may be written with a leading `#`
+fn normalize(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Whether a `[tags]` pattern applies to a project
+///
+/// Patterns containing a `/` match the project path (`~` is expanded), any
+/// other pattern matches the package name.
+fn rule_matches(pattern: &str, project: &Project) -> bool {
+    let (pattern, target) = if pattern.contains('/') {
+        let expanded = expand_home(pattern);
+        (
+            expanded.to_string_lossy().into_owned(),
+            project.path.to_string_lossy().into_owned(),
+        )
+    } else {
+        (pattern.to_string(), project.name.clone())
+    };
+    match glob::Pattern::new(&pattern) {
+        Ok(glob) => glob.matches(&target),
+        Err(e) => {
+            tracing::warn!("Ignoring invalid tag pattern {:?}: {}", pattern, e);
+            false
+        }
+    }
+}
+
+/// Build a tree with a group per tag, then one for untagged projects
+///
+/// A project with several tags shows up in each of their groups. Groups
+/// whose tag is in `collapsed` start collapsed.
+pub fn build_tag_tree(projects: &[Project], collapsed: &HashSet<String>) -> TreeNode {
+    let mut groups: BTreeMap<&str, Vec<TreeNode>> = BTreeMap::new();
+    let mut untagged = Vec::new();
+    for project in projects {
+        let node = TreeNode::project(project.clone(), 2);
+        if project.tags.is_empty() {
+            untagged.push(node);
+        } else {
+            for tag in &project.tags {
+                groups.entry(tag).or_default().push(node.clone());
+            }
+        }
+    }
+
+    let mut nodes: Vec<TreeNode> = groups
+        .into_iter()
+        .map(|(tag, projects)| TreeNode::tag_group(tag, projects, !collapsed.contains(tag)))
+        .collect();
+    if !untagged.is_empty() {
+        nodes.push(TreeNode::tag_group("", untagged, !collapsed.contains("")));
+    }
+    TreeNode::roots_group("tags".to_string(), nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::find_rust_projects;
+    use crate::tree::FlattenedTree;
+    use std::path::Path;
+
+    fn fixture_projects() -> Vec<Project> {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspaces/glob");
+        find_rust_projects(fixture.to_str().unwrap())
+    }
+
+    #[test]
+    fn test_resolve_tags_merges_sources() {
+        let mut project = fixture_projects().remove(0);
+        project.keywords = vec!["CLI".to_string()];
+        project.categories = vec!["command-line-utilities".to_string()];
+
+        let rules = BTreeMap::from([
+            ("libs".to_string(), vec![project.name.clone()]),
+            ("fixtures".to_string(), vec!["**/fixtures/**".to_string()]),
+            ("other".to_string(), vec!["no-such-crate".to_string()]),
+        ]);
+        let assigned = parse_tags("#team-foo, cli");
+
+        assert_eq!(
+            resolve_tags(&project, &rules, Some(&assigned)),
+            [
+                "cli",
+                "command-line-utilities",
+                "fixtures",
+                "libs",
+                "team-foo"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tag_tree_groups_projects() {
+        let mut projects = fixture_projects();
+        projects[0].tags = vec!["services".to_string(), "team-foo".to_string()];
+        projects[1].tags = vec!["services".to_string()];
+
+        let tree = build_tag_tree(&projects, &HashSet::from(["team-foo".to_string()]));
+        let flattened = FlattenedTree::from_tree(&tree);
+        let rows: Vec<(&str, usize)> = flattened
+            .items
+            .iter()
+            .map(|(node, _)| (node.node_type.name(), node.depth))
+            .collect();
+
+        let mut expected = vec![
+            ("#services", 0),
+            (projects[0].name.as_str(), 1),
+            (projects[1].name.as_str(), 1),
+            ("#team-foo", 0),
+            ("(untagged)", 0),
+        ];
+        expected.extend(projects[2..].iter().map(|p| (p.name.as_str(), 1)));
+        assert_eq!(rows, expected);
+        assert_eq!(flattened.items[0].0.tag(), Some("services"));
+        assert_eq!(flattened.items[4].0.tag(), Some(""));
+        assert_eq!(all_tags(&projects), ["services", "team-foo"]);
+    }
+}
@@ -0,0 +1,183 @@
+//! Where cargo puts build output for a project
+//!
+//! Follows cargo's precedence for the target directory: the `CARGO_TARGET_DIR`
+//! (or `CARGO_BUILD_TARGET_DIR`) environment variable, then `build.target-dir`
+//! from the nearest `.cargo/config.toml` up from the project, then the one in
+//! `$CARGO_HOME`, and finally `target/` under the workspace root or project.
+//! Several projects can end up with the same directory, which [`TargetDirs`]
+//! keeps track of so sizes and `cargo clean` runs account for sharing.
+
+use crate::project::ProjectId;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The target directory cargo uses when run in `project_dir`
+pub fn resolve_target_dir(project_dir: &Path, workspace_root: Option<&Path>) -> PathBuf {
+    let env_dir = ["CARGO_TARGET_DIR", "CARGO_BUILD_TARGET_DIR"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .find(|value| !value.is_empty())
+        .map(PathBuf::from);
+    resolve_with(project_dir, workspace_root, env_dir, cargo_home())
+}
+
+/// [`resolve_target_dir`] with the environment passed in
+fn resolve_with(
+    project_dir: &Path,
+    workspace_root: Option<&Path>,
+    env_dir: Option<PathBuf>,
+    cargo_home: Option<PathBuf>,
+) -> PathBuf {
+    // Relative paths from the environment are relative to where cargo runs
+    if let Some(dir) = env_dir {
+        return project_dir.join(dir);
+    }
+
+    let project_configs = project_dir
+        .ancestors()
+        .filter_map(|dir| config_file(&dir.join(".cargo")));
+    let home_config = cargo_home.as_deref().and_then(config_file);
+    project_configs
+        .chain(home_config)
+        .find_map(|config| {
+            let dir = config_target_dir(&config)?;
+            // Relative to the directory holding `.cargo/` (or `$CARGO_HOME`)
+            let base = config
+                .parent()
+                .and_then(Path::parent)
+                .unwrap_or(project_dir);
+            Some(base.join(dir))
+        })
+        .unwrap_or_else(|| workspace_root.unwrap_or(project_dir).join("target"))
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".cargo")))
+}
+
+/// The config file in a `.cargo` directory; cargo prefers the legacy `config`
+fn config_file(dir: &Path) -> Option<PathBuf> {
+    ["config", "config.toml"]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+fn config_target_dir(config: &Path) -> Option<String> {
+    let content = fs::read_to_string(config).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+    value
+        .get("build")?
+        .get("target-dir")?
+        .as_str()
+        .map(String::from)
+}
+
+/// Resolved target directory of every project
+#[derive(Debug, Clone, Default)]
+pub struct TargetDirs {
+    dirs: HashMap<ProjectId, PathBuf>,
+}
+
+impl TargetDirs {
+    pub fn new(dirs: impl IntoIterator<Item = (ProjectId, PathBuf)>) -> Self {
+        Self {
+            dirs: dirs.into_iter().collect(),
+        }
+    }
+
+    pub fn get(&self, id: &ProjectId) -> Option<&PathBuf> {
+        self.dirs.get(id)
+    }
+
+    /// Projects building into `dir`
+    pub fn sharing<'a>(&'a self, dir: &'a Path) -> impl Iterator<Item = &'a ProjectId> + 'a {
+        self.dirs
+            .iter()
+            .filter(move |(_, d)| d.as_path() == dir)
+            .map(|(id, _)| id)
+    }
+
+    /// How many projects build into the same directory as `id` (itself included)
+    pub fn shared_by(&self, id: &ProjectId) -> usize {
+        self.get(id).map_or(0, |dir| self.sharing(dir).count())
+    }
+
+    /// Projects grouped by the directory they build into
+    pub fn groups(&self) -> HashMap<&Path, Vec<&ProjectId>> {
+        let mut groups: HashMap<&Path, Vec<&ProjectId>> = HashMap::new();
+        for (id, dir) in &self.dirs {
+            groups.entry(dir.as_path()).or_default().push(id);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(path: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/target_dir")
+            .join(path)
+    }
+
+    #[test]
+    fn test_target_dir_precedence() {
+        let app = fixture("app");
+        let pinned = fixture("pinned");
+
+        // Nearest config wins, relative to the directory holding `.cargo/`
+        assert_eq!(
+            resolve_with(&app, None, None, None),
+            fixture("shared-target")
+        );
+        assert_eq!(
+            resolve_with(&pinned, None, None, Some(fixture(".cargo"))),
+            fixture("pinned/out")
+        );
+        // The environment beats config files
+        assert_eq!(
+            resolve_with(&app, None, Some(PathBuf::from("/tmp/ct")), None),
+            PathBuf::from("/tmp/ct")
+        );
+
+        // Without project configs: `$CARGO_HOME`, then `target/` under the workspace root
+        let plain = std::env::temp_dir().join("carwash-no-config");
+        let home = fixture("pinned/.cargo");
+        assert_eq!(
+            resolve_with(&plain.join("member"), Some(&plain), None, None),
+            plain.join("target")
+        );
+        assert_eq!(
+            resolve_with(&plain, None, None, Some(home)),
+            fixture("pinned/out")
+        );
+    }
+
+    #[test]
+    fn test_shared_target_dirs() {
+        let shared = PathBuf::from("/work/target");
+        let dirs = TargetDirs::new([
+            (ProjectId::from_dir(Path::new("/work/a")), shared.clone()),
+            (ProjectId::from_dir(Path::new("/work/b")), shared.clone()),
+            (
+                ProjectId::from_dir(Path::new("/c")),
+                PathBuf::from("/c/target"),
+            ),
+        ]);
+
+        assert_eq!(
+            dirs.shared_by(&ProjectId::from_dir(Path::new("/work/a"))),
+            2
+        );
+        assert_eq!(dirs.shared_by(&ProjectId::from_dir(Path::new("/c"))), 1);
+        assert_eq!(dirs.shared_by(&ProjectId::from_dir(Path::new("/d"))), 0);
+        assert_eq!(dirs.groups()[shared.as_path()].len(), 2);
+    }
+}
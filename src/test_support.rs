@@ -0,0 +1,32 @@
+//! Helpers shared by the unit tests
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A fresh, empty directory for one test, unique to this process
+///
+/// `module` and `name` keep tests running in parallel apart.
+pub fn temp_dir(module: &str, name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "carwash-{}-test-{}-{}",
+        module,
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Write a package manifest in `dir` with the given `(name, requirement)` dependencies
+pub fn write_package(dir: &Path, name: &str, version: &str, dependencies: &[(&str, &str)]) {
+    let mut manifest = format!("[package]\nname = \"{name}\"\nversion = \"{version}\"\n");
+    if !dependencies.is_empty() {
+        manifest.push_str("\n[dependencies]\n");
+        for (dependency, requirement) in dependencies {
+            manifest.push_str(&format!("{dependency} = \"{requirement}\"\n"));
+        }
+    }
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+}
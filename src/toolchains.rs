@@ -0,0 +1,242 @@
+//! Toolchain matrices: running a command on several rustup toolchains
+//!
+//! A toolchain matrix runs `cargo +<toolchain> <command>` for every selected
+//! project on each configured toolchain. Besides plain toolchain names the
+//! configured columns may be `msrv` (the project's `rust-version`, matched
+//! against the installed toolchains) and `pinned` (the channel of the
+//! `rust-toolchain` file that applies to the project).
+
+use crate::details::find_toolchain;
+use crate::project::{Project, ProjectId};
+use std::process::Command;
+
+/// Column resolved to each project's `rust-version`
+pub const MSRV: &str = "msrv";
+/// Column resolved to each project's `rust-toolchain` file
+pub const PINNED: &str = "pinned";
+
+/// Toolchains installed with rustup, e.g. `stable-x86_64-unknown-linux-gnu`
+///
+/// Empty if rustup isn't available.
+pub fn installed_toolchains() -> Vec<String> {
+    match Command::new("rustup").args(["toolchain", "list"]).output() {
+        Ok(output) if output.status.success() => {
+            parse_toolchain_list(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(_) | Err(_) => {
+            tracing::warn!("Could not list rustup toolchains");
+            Vec::new()
+        }
+    }
+}
+
+/// Toolchain names from `rustup toolchain list` output
+pub fn parse_toolchain_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| *name != "no")
+        .map(String::from)
+        .collect()
+}
+
+/// Whether `toolchain` (`stable`, `1.80.0`, ...) is installed, for any host
+pub fn is_installed(toolchain: &str, installed: &[String]) -> bool {
+    installed
+        .iter()
+        .any(|name| name == toolchain || name.starts_with(&format!("{}-", toolchain)))
+}
+
+/// The installed toolchain to check a `rust-version` with
+///
+/// `1.80` picks the newest installed `1.80.x`; without one the MSRV itself is
+/// returned, which then shows up as not installed.
+pub fn resolve_msrv(msrv: &str, installed: &[String]) -> String {
+    let prefix = format!("{}.", msrv);
+    installed
+        .iter()
+        .filter_map(|name| name.split('-').next())
+        .filter(|channel| *channel == msrv || channel.starts_with(&prefix))
+        .max_by_key(|channel| version_key(channel))
+        .unwrap_or(msrv)
+        .to_string()
+}
+
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// State of one project on one toolchain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolchainStatus {
+    /// The column doesn't apply (no `rust-version` or toolchain file)
+    NotApplicable,
+    /// The toolchain isn't installed; nothing was run
+    NotInstalled,
+    Pending,
+    Running,
+    Passed,
+    Failed,
+}
+
+/// One cell of the grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainCell {
+    /// Toolchain the column resolved to for this project
+    pub toolchain: Option<String>,
+    pub status: ToolchainStatus,
+}
+
+/// One project of the grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainRow {
+    pub project: ProjectId,
+    pub name: String,
+    /// Output tab the project's runs stream into
+    pub tab_index: usize,
+    pub cells: Vec<ToolchainCell>,
+}
+
+/// A projects × toolchains run of one command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainMatrix {
+    /// Cargo command run on every toolchain, e.g. `check`
+    pub command: String,
+    /// Configured columns, e.g. `stable`, `msrv`, `nightly`
+    pub columns: Vec<String>,
+    /// Toolchains installed when the matrix started
+    pub installed: Vec<String>,
+    pub rows: Vec<ToolchainRow>,
+}
+
+impl ToolchainMatrix {
+    /// Resolve every column for every project; rows get consecutive tabs from `first_tab`
+    pub fn new(
+        command: &str,
+        columns: &[String],
+        installed: Vec<String>,
+        projects: &[Project],
+        first_tab: usize,
+    ) -> Self {
+        let rows = projects
+            .iter()
+            .enumerate()
+            .map(|(i, project)| ToolchainRow {
+                project: project.id.clone(),
+                name: project.display_name.clone(),
+                tab_index: first_tab + i,
+                cells: columns
+                    .iter()
+                    .map(|column| {
+                        let toolchain = match column.as_str() {
+                            MSRV => project
+                                .rust_version
+                                .as_deref()
+                                .map(|msrv| resolve_msrv(msrv, &installed)),
+                            PINNED => find_toolchain(&project.path).and_then(|t| t.channel),
+                            name => Some(name.to_string()),
+                        };
+                        let status = match &toolchain {
+                            None => ToolchainStatus::NotApplicable,
+                            Some(t) if !is_installed(t, &installed) => {
+                                ToolchainStatus::NotInstalled
+                            }
+                            Some(_) => ToolchainStatus::Pending,
+                        };
+                        ToolchainCell { toolchain, status }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            command: command.to_string(),
+            columns: columns.to_vec(),
+            installed,
+            rows,
+        }
+    }
+
+    /// Whether runs are still pending or running
+    pub fn is_running(&self) -> bool {
+        self.rows.iter().flat_map(|row| &row.cells).any(|cell| {
+            matches!(
+                cell.status,
+                ToolchainStatus::Pending | ToolchainStatus::Running
+            )
+        })
+    }
+
+    /// Index of the `msrv` column, if configured
+    pub fn msrv_column(&self) -> Option<usize> {
+        self.columns.iter().position(|column| column == MSRV)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::find_rust_projects;
+    use std::path::Path;
+
+    const LIST: &str = "stable-x86_64-unknown-linux-gnu (default)\n\
+                        nightly-x86_64-unknown-linux-gnu\n\
+                        1.80.0-x86_64-unknown-linux-gnu\n\
+                        1.80.1-x86_64-unknown-linux-gnu (active)\n";
+
+    #[test]
+    fn test_parse_and_resolve_toolchains() {
+        let installed = parse_toolchain_list(LIST);
+        assert_eq!(installed.len(), 4);
+        assert!(parse_toolchain_list("no installed toolchains\n").is_empty());
+
+        assert!(is_installed("stable", &installed));
+        assert!(!is_installed("beta", &installed));
+        assert_eq!(resolve_msrv("1.80", &installed), "1.80.1");
+        assert_eq!(resolve_msrv("1.80.0", &installed), "1.80.0");
+        assert_eq!(resolve_msrv("1.70", &installed), "1.70");
+    }
+
+    #[test]
+    fn test_matrix_resolves_columns_per_project() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/details");
+        let mut projects = find_rust_projects(fixture.to_str().unwrap());
+        let mut unpinned = projects[0].clone();
+        unpinned.rust_version = None;
+        unpinned.path = std::env::temp_dir();
+        projects.push(unpinned);
+
+        let columns: Vec<String> = ["stable", MSRV, PINNED, "beta"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let matrix =
+            ToolchainMatrix::new("check", &columns, parse_toolchain_list(LIST), &projects, 3);
+
+        let row = &matrix.rows[0];
+        assert_eq!(row.tab_index, 3);
+        let cells: Vec<(Option<&str>, &ToolchainStatus)> = row
+            .cells
+            .iter()
+            .map(|c| (c.toolchain.as_deref(), &c.status))
+            .collect();
+        assert_eq!(
+            cells,
+            [
+                (Some("stable"), &ToolchainStatus::Pending),
+                (Some("1.80.1"), &ToolchainStatus::Pending),
+                (Some("1.85.0"), &ToolchainStatus::NotInstalled),
+                (Some("beta"), &ToolchainStatus::NotInstalled),
+            ]
+        );
+        assert_eq!(
+            matrix.rows[1].cells[1].status,
+            ToolchainStatus::NotApplicable
+        );
+        assert_eq!(matrix.msrv_column(), Some(1));
+        assert!(matrix.is_running());
+    }
+}
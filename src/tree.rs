@@ -6,7 +6,7 @@
 //! - Subdirectories are only scanned when expanded (lazy loading)
 //! - The tree state is persisted in AppState
 
-use crate::project::Project;
+use crate::project::{Project, ProjectId};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
@@ -21,7 +21,7 @@ pub enum TreeNodeType {
         path: PathBuf,
     },
     /// A leaf node representing a Rust project
-    Project(Project),
+    Project(Box<Project>),
 }
 
 impl TreeNodeType {
@@ -85,7 +85,7 @@ impl TreeNode {
     /// Create a new project node
     pub fn project(project: Project, depth: usize) -> Self {
         Self {
-            node_type: TreeNodeType::Project(project),
+            node_type: TreeNodeType::Project(Box::new(project)),
             children: Vec::new(),
             expanded: false,
             children_loaded: true, // Projects don't have children
@@ -101,13 +101,97 @@ impl TreeNode {
         }
     }
 
+    /// Create a node grouping several scan roots
+    ///
+    /// The group itself is left out of the flattened view, so each root shows
+    /// up as a top-level node.
+    pub fn roots_group(name: String, roots: Vec<TreeNode>) -> Self {
+        Self {
+            node_type: TreeNodeType::Directory {
+                name,
+                path: PathBuf::new(),
+            },
+            children: roots,
+            expanded: true,
+            children_loaded: true,
+            loading: false,
+            depth: 0,
+        }
+    }
+
+    /// Whether this is a group of scan roots rather than a real directory
+    pub fn is_roots_group(&self) -> bool {
+        self.node_type.is_directory() && self.node_type.path().as_os_str().is_empty()
+    }
+
+    /// The scan root nodes: the group's children, or this node itself
+    pub fn scan_roots_mut(&mut self) -> Vec<&mut TreeNode> {
+        if self.is_roots_group() {
+            self.children.iter_mut().collect()
+        } else {
+            vec![self]
+        }
+    }
+
+    /// Create a node listing the projects carrying `tag` (an empty tag lists untagged ones)
+    ///
+    /// Its path is `#tag`, which being relative can't clash with a real directory.
+    pub fn tag_group(tag: &str, projects: Vec<TreeNode>, expanded: bool) -> Self {
+        let name = if tag.is_empty() {
+            "(untagged)".to_string()
+        } else {
+            format!("#{}", tag)
+        };
+        Self::group(name, format!("#{}", tag), projects, expanded)
+    }
+
+    /// Create a node grouping projects under a relative `key` rather than a directory
+    pub fn group(name: String, key: String, projects: Vec<TreeNode>, expanded: bool) -> Self {
+        Self {
+            node_type: TreeNodeType::Directory {
+                name,
+                path: PathBuf::from(key),
+            },
+            children: projects,
+            expanded,
+            children_loaded: true,
+            loading: false,
+            depth: 1,
+        }
+    }
+
+    /// Whether this is a group of projects (by tag, workspace or status) rather than a directory
+    pub fn is_group(&self) -> bool {
+        self.node_type.is_directory()
+            && self.node_type.path().is_relative()
+            && !self.is_roots_group()
+    }
+
+    /// The tag this node groups projects by, if it is a tag group
+    pub fn tag(&self) -> Option<&str> {
+        match &self.node_type {
+            TreeNodeType::Directory { path, .. } if path.is_relative() => {
+                path.to_str()?.strip_prefix('#')
+            }
+            _ => None,
+        }
+    }
+
+    /// Find the node for `path` in this subtree
+    pub fn find(&self, path: &Path) -> Option<&TreeNode> {
+        if self.node_type.path() == path {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(path))
+    }
+
     /// Get all projects in this subtree
     pub fn collect_projects(&self) -> Vec<&Project> {
         let mut projects = Vec::new();
 
         match &self.node_type {
             TreeNodeType::Project(p) => {
-                projects.push(p);
+                projects.push(p.as_ref());
             }
             TreeNodeType::Directory { .. } => {
                 for child in &self.children {
@@ -154,7 +238,7 @@ impl FlattenedTree {
             false
         };
 
-        if is_crates_dir {
+        if is_crates_dir || node.is_roots_group() {
             // Skip this node in the display, but process children with increased depth adjustment
             if node.expanded {
                 for child in &node.children {
@@ -191,8 +275,8 @@ impl FlattenedTree {
 pub struct TreeSelectionState {
     /// Index of the currently selected item in the flattened tree
     pub selected_index: Option<usize>,
-    /// Set of selected project paths (for multi-select)
-    pub selected_projects: HashSet<String>,
+    /// Set of selected projects (for multi-select)
+    pub selected_projects: HashSet<ProjectId>,
 }
 
 impl TreeSelectionState {
@@ -223,17 +307,17 @@ impl TreeSelectionState {
     }
 
     /// Toggle selection of a project
-    pub fn toggle_project(&mut self, name: String) {
-        if self.selected_projects.contains(&name) {
-            self.selected_projects.remove(&name);
+    pub fn toggle_project(&mut self, id: ProjectId) {
+        if self.selected_projects.contains(&id) {
+            self.selected_projects.remove(&id);
         } else {
-            self.selected_projects.insert(name);
+            self.selected_projects.insert(id);
         }
     }
 
     /// Check if a project is selected
-    pub fn is_project_selected(&self, name: &str) -> bool {
-        self.selected_projects.contains(name)
+    pub fn is_project_selected(&self, id: &ProjectId) -> bool {
+        self.selected_projects.contains(id)
     }
 }
 
@@ -275,10 +359,11 @@ mod tests {
         state.select_prev();
         assert_eq!(state.selected_index, Some(0));
 
-        state.toggle_project("proj1".to_string());
-        assert!(state.is_project_selected("proj1"));
+        let id = ProjectId::from_dir(Path::new("proj1"));
+        state.toggle_project(id.clone());
+        assert!(state.is_project_selected(&id));
 
-        state.toggle_project("proj1".to_string());
-        assert!(!state.is_project_selected("proj1"));
+        state.toggle_project(id.clone());
+        assert!(!state.is_project_selected(&id));
     }
 }
@@ -10,9 +10,11 @@ pub mod theme;
 
 use crate::app::AppState;
 use crate::components::{
-    Component, dependencies::DependenciesPane, help::Help, output::TabbedOutputPane,
-    palette::CommandPalette, projects::ProjectList, settings::SettingsModal, spinner::Spinner,
-    status::StatusBar, text_input::TextInput, updater::UpdateWizard,
+    Component, dependencies::DependenciesPane, dependency_detail::DependencyDetail,
+    disk_usage::DiskUsageView, features::FeatureExplorer, help::Help, output::TabbedOutputPane,
+    palette::CommandPalette, project_details::ProjectDetailsPane, projects::ProjectList,
+    reclaim::ReclaimView, settings::SettingsModal, spinner::Spinner, status::StatusBar,
+    text_input::TextInput, toolchains::ToolchainMatrixView, updater::UpdateWizard,
 };
 use crate::events::Mode;
 use ratatui::{
@@ -27,6 +29,7 @@ pub use theme::{Theme, ThemeManager};
 /// Main UI rendering function
 pub fn ui(f: &mut Frame, app: &mut AppState) {
     let mut dependencies = DependenciesPane::new();
+    let mut details = ProjectDetailsPane::new();
     let mut output = TabbedOutputPane::new();
     let mut status = StatusBar::new();
     let mut project_list = ProjectList::new();
@@ -70,8 +73,17 @@ pub fn ui(f: &mut Frame, app: &mut AppState) {
         )
         .split(top_chunks[1]);
 
+    app.mouse.body = main_chunks[0];
+    app.mouse.right = top_chunks[1];
+    app.mouse.projects = top_chunks[0];
+    app.mouse.dependencies = right_chunks[0];
+
     project_list.draw(f, app, top_chunks[0]);
-    dependencies.draw(f, app, right_chunks[0]);
+    if app.details.visible {
+        details.draw(f, app, right_chunks[0]);
+    } else {
+        dependencies.draw(f, app, right_chunks[0]);
+    }
     output.draw(f, app, right_chunks[1]);
     status.draw(f, app, main_chunks[1]);
 
@@ -90,5 +102,20 @@ pub fn ui(f: &mut Frame, app: &mut AppState) {
     } else if app.mode == Mode::Settings {
         let mut settings = SettingsModal::new();
         settings.draw(f, app, f.area());
+    } else if app.mode == Mode::DependencyDetail {
+        let mut detail = DependencyDetail::new();
+        detail.draw(f, app, f.area());
+    } else if app.mode == Mode::FeatureExplorer {
+        let mut features = FeatureExplorer::new();
+        features.draw(f, app, f.area());
+    } else if app.mode == Mode::Reclaim {
+        let mut reclaim = ReclaimView::new();
+        reclaim.draw(f, app, f.area());
+    } else if app.mode == Mode::DiskUsage {
+        let mut disk_usage = DiskUsageView::new();
+        disk_usage.draw(f, app, f.area());
+    } else if app.mode == Mode::ToolchainMatrix {
+        let mut matrix = ToolchainMatrixView::new();
+        matrix.draw(f, app, f.area());
     }
 }
@@ -0,0 +1,255 @@
+//! Filesystem watching for discovered projects
+//!
+//! Project directories (and the directories above them) are watched without
+//! recursion, so build output under `target/` never generates events. Changes
+//! to a Cargo.toml or Cargo.lock are collected for a short while and delivered
+//! as a single [`Action::ProjectFilesChanged`] with the affected directories;
+//! [`classify_changes`] then decides what each directory means for the
+//! projects already known.
+
+use crate::events::Action;
+use crate::project::{Project, ProjectId};
+use notify::event::CreateKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long to wait for related changes (an editor save, a `git pull`) to settle
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches project directories and reports manifest and lockfile changes
+pub struct ProjectWatcher {
+    watcher: RecommendedWatcher,
+    watched: HashSet<PathBuf>,
+}
+
+impl ProjectWatcher {
+    /// Start watching; changes are sent to `action_tx` once they settle
+    pub fn new(action_tx: tokio::sync::mpsc::Sender<Action>) -> notify::Result<Self> {
+        let (dir_tx, dir_rx) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                for dir in changed_dirs(&event) {
+                    let _ = dir_tx.send(dir);
+                }
+            }
+        })?;
+        std::thread::spawn(move || debounce(dir_rx, action_tx));
+
+        Ok(Self {
+            watcher,
+            watched: HashSet::new(),
+        })
+    }
+
+    /// Watch the scan roots and every project directory, its parent and its workspace root
+    pub fn watch_projects(&mut self, roots: &[PathBuf], projects: &[Project]) {
+        for root in roots {
+            self.watch_dir(root);
+        }
+        for project in projects {
+            self.watch_dir(&project.path);
+            if let Some(parent) = project.path.parent() {
+                self.watch_dir(parent);
+            }
+            if let Some(workspace_root) = &project.workspace_root {
+                self.watch_dir(workspace_root);
+            }
+        }
+    }
+
+    /// Watch a single directory (non-recursively), once
+    pub fn watch_dir(&mut self, dir: &Path) {
+        if self.watched.contains(dir) || !dir.is_dir() {
+            return;
+        }
+        match self.watcher.watch(dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                self.watched.insert(dir.to_path_buf());
+            }
+            Err(e) => tracing::warn!("Failed to watch {}: {}", dir.display(), e),
+        }
+    }
+}
+
+/// Collect directories until changes stop for [`DEBOUNCE`], then report them together
+fn debounce(dir_rx: mpsc::Receiver<PathBuf>, action_tx: tokio::sync::mpsc::Sender<Action>) {
+    while let Ok(first) = dir_rx.recv() {
+        let mut dirs = BTreeSet::from([first]);
+        let mut deadline = Instant::now() + DEBOUNCE;
+        loop {
+            match dir_rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(dir) => {
+                    dirs.insert(dir);
+                    deadline = Instant::now() + DEBOUNCE;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => break,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        let dirs = dirs.into_iter().collect();
+        if action_tx
+            .blocking_send(Action::ProjectFilesChanged(dirs))
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Directories an event is relevant for
+///
+/// A Cargo.toml or Cargo.lock change reports its directory. A newly created
+/// directory is reported too, so it gets watched before its manifest appears.
+pub fn changed_dirs(event: &Event) -> Vec<PathBuf> {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return Vec::new();
+    }
+
+    event
+        .paths
+        .iter()
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_string_lossy();
+            if file_name == "Cargo.toml" || file_name == "Cargo.lock" {
+                return path.parent().map(Path::to_path_buf);
+            }
+            let is_new_dir = matches!(event.kind, EventKind::Create(CreateKind::Folder))
+                || (matches!(event.kind, EventKind::Create(_)) && path.is_dir());
+            let skipped =
+                file_name.starts_with('.') || file_name == "target" || file_name == "node_modules";
+            (is_new_dir && !skipped).then(|| path.clone())
+        })
+        .collect()
+}
+
+/// What a batch of changed directories means for the known projects
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ProjectChanges {
+    /// Projects whose manifest, lockfile or workspace root changed
+    pub reload: Vec<ProjectId>,
+    /// Projects whose manifest is gone
+    pub removed: Vec<ProjectId>,
+    /// Directories to scan for new projects: a manifest no known project lives
+    /// in, or a new directory (such as `crates/` from a checkout) whose projects
+    /// were written before it was watched
+    pub new_dirs: Vec<PathBuf>,
+}
+
+impl ProjectChanges {
+    pub fn is_empty(&self) -> bool {
+        self.reload.is_empty() && self.removed.is_empty() && self.new_dirs.is_empty()
+    }
+}
+
+/// Sort changed directories into reloads, removals and new projects
+pub fn classify_changes(dirs: &[PathBuf], projects: &[Project]) -> ProjectChanges {
+    let mut changes = ProjectChanges::default();
+
+    for dir in dirs {
+        let has_manifest = dir.join("Cargo.toml").is_file();
+        // A workspace root's lockfile and inherited fields apply to all its members
+        let affected: Vec<&Project> = projects
+            .iter()
+            .filter(|p| &p.path == dir || p.workspace_root.as_ref() == Some(dir))
+            .collect();
+
+        if !has_manifest {
+            if let Some(project) = affected.iter().find(|p| &p.path == dir) {
+                changes.removed.push(project.id.clone());
+            } else if dir.is_dir() {
+                changes.new_dirs.push(dir.clone());
+            }
+        } else if affected.is_empty() {
+            changes.new_dirs.push(dir.clone());
+        } else {
+            for project in affected {
+                if !changes.reload.contains(&project.id) {
+                    changes.reload.push(project.id.clone());
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::find_rust_projects;
+    use crate::test_support::{temp_dir, write_package};
+    use notify::event::{DataChange, ModifyKind};
+    use std::fs;
+
+    #[test]
+    fn test_changed_dirs_only_reports_manifests_and_new_dirs() {
+        let event = Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Any)))
+            .add_path(PathBuf::from("/src/app/Cargo.lock"))
+            .add_path(PathBuf::from("/src/app/src/main.rs"));
+        assert_eq!(changed_dirs(&event), [PathBuf::from("/src/app")]);
+
+        let event = Event::new(EventKind::Create(CreateKind::Folder))
+            .add_path(PathBuf::from("/src/new-crate"))
+            .add_path(PathBuf::from("/src/app/target"));
+        assert_eq!(changed_dirs(&event), [PathBuf::from("/src/new-crate")]);
+    }
+
+    #[test]
+    fn test_watcher_reports_lockfile_changes() {
+        let root = temp_dir("watch", "watch");
+        write_package(&root.join("app"), "app", "0.1.0", &[]);
+        let projects = find_rust_projects(root.to_str().unwrap());
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+        let mut watcher = ProjectWatcher::new(tx).unwrap();
+        watcher.watch_projects(std::slice::from_ref(&root), &projects);
+        fs::write(root.join("app/Cargo.lock"), "version = 4\n").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let dirs = loop {
+            match rx.try_recv() {
+                Ok(Action::ProjectFilesChanged(dirs)) => break dirs,
+                _ if Instant::now() > deadline => panic!("no change reported"),
+                _ => std::thread::sleep(Duration::from_millis(20)),
+            }
+        };
+        assert_eq!(dirs, [projects[0].path.clone()]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_classify_changes() {
+        let root = temp_dir("watch", "classify");
+        write_package(&root.join("kept"), "kept", "0.1.0", &[]);
+        write_package(&root.join("gone"), "gone", "0.1.0", &[]);
+        let projects = find_rust_projects(root.to_str().unwrap());
+        let id = |name: &str| {
+            projects
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| p.id.clone())
+                .unwrap()
+        };
+
+        fs::remove_dir_all(root.join("gone")).unwrap();
+        write_package(&root.join("added"), "added", "0.1.0", &[]);
+        // A checkout creates `crates/` and its members in one go
+        write_package(&root.join("crates/nested"), "nested", "0.1.0", &[]);
+
+        let dirs: Vec<PathBuf> = ["kept", "gone", "added", "crates", "missing"]
+            .iter()
+            .map(|name| projects[0].path.parent().unwrap().join(name))
+            .collect();
+        let changes = classify_changes(&dirs, &projects);
+
+        assert_eq!(changes.reload, [id("kept")]);
+        assert_eq!(changes.removed, [id("gone")]);
+        assert_eq!(changes.new_dirs, [dirs[2].clone(), dirs[3].clone()]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,530 @@
+//! Cargo workspace resolution
+//!
+//! Decides which packages belong to which workspace the way Cargo does: member
+//! globs are expanded, `exclude` is honoured, path dependencies inside the
+//! workspace become implicit members, `package.workspace` points a package at its
+//! root explicitly, and otherwise the nearest enclosing workspace root wins.
+
+use crate::project::{CargoToml, InheritableFields};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A workspace root with its resolved members
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedWorkspace {
+    /// Directory containing the workspace's Cargo.toml
+    pub root: PathBuf,
+    /// Root package name, or the directory name for virtual workspaces
+    pub name: String,
+    /// Manifest paths of all members, sorted
+    pub members: Vec<PathBuf>,
+    /// Manifest paths that `cargo build` at the root operates on, sorted
+    pub default_members: Vec<PathBuf>,
+    /// `[workspace.package]` and `[workspace.dependencies]` for members to inherit
+    pub inherited: InheritableFields,
+    /// `exclude` entries, resolved against the root
+    exclude: Vec<PathBuf>,
+    /// Non-glob `members` entries, resolved against the root (these beat `exclude`)
+    explicit_members: Vec<PathBuf>,
+}
+
+impl ResolvedWorkspace {
+    /// Resolve the members of the workspace whose root manifest is `root_manifest`
+    ///
+    /// Returns `None` if the manifest has no `[workspace]` section.
+    pub fn resolve(root_manifest: &Path, toml: &CargoToml) -> Option<Self> {
+        let workspace = toml.workspace.as_ref()?;
+        let root = normalize(root_manifest.parent()?);
+
+        let name = match &toml.package {
+            Some(pkg) => pkg.name.clone(),
+            None => root
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("workspace")
+                .to_string(),
+        };
+
+        let mut resolved = Self {
+            name,
+            members: Vec::new(),
+            default_members: Vec::new(),
+            inherited: InheritableFields::from_workspace(workspace),
+            exclude: workspace
+                .exclude
+                .iter()
+                .map(|e| normalize(&root.join(e)))
+                .collect(),
+            explicit_members: workspace
+                .members
+                .iter()
+                .filter(|m| !is_glob(m))
+                .map(|m| normalize(&root.join(m)))
+                .collect(),
+            root,
+        };
+
+        let root_manifest = resolved.root.join("Cargo.toml");
+        if toml.package.is_some() {
+            resolved.add_member(root_manifest.clone(), toml);
+        }
+        let member_dirs: Vec<PathBuf> = workspace
+            .members
+            .iter()
+            .flat_map(|m| expand_member(&resolved.root, m))
+            .collect();
+        for member_dir in member_dirs {
+            let manifest = member_dir.join("Cargo.toml");
+            if resolved.is_excluded(&manifest) {
+                continue;
+            }
+            match read_manifest(&manifest) {
+                // A nested workspace root is never a member of this one
+                Some(member_toml) if member_toml.workspace.is_none() => {
+                    resolved.add_member(manifest, &member_toml);
+                }
+                _ => {}
+            }
+        }
+        resolved.members.sort();
+
+        resolved.default_members = match &workspace.default_members {
+            Some(defaults) => defaults
+                .iter()
+                .flat_map(|m| expand_member(&resolved.root, m))
+                .map(|dir| dir.join("Cargo.toml"))
+                .filter(|manifest| resolved.contains(manifest))
+                .collect(),
+            None if toml.package.is_some() => vec![root_manifest],
+            None => resolved.members.clone(),
+        };
+        resolved.default_members.sort();
+        resolved.default_members.dedup();
+
+        Some(resolved)
+    }
+
+    /// Add a member, then its path dependencies that live inside the workspace
+    fn add_member(&mut self, manifest: PathBuf, toml: &CargoToml) {
+        if self.members.contains(&manifest) {
+            return;
+        }
+        let Some(dir) = manifest.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        self.members.push(manifest);
+
+        for dep_dir in path_dependencies(&dir, toml, &self.root) {
+            let dep_manifest = dep_dir.join("Cargo.toml");
+            if !dep_dir.starts_with(&self.root) || self.is_excluded(&dep_manifest) {
+                continue;
+            }
+            match read_manifest(&dep_manifest) {
+                Some(dep_toml) if dep_toml.workspace.is_none() => {
+                    self.add_member(dep_manifest, &dep_toml);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether the package with this manifest is a member of the workspace
+    pub fn contains(&self, manifest: &Path) -> bool {
+        self.members.iter().any(|m| m == manifest)
+    }
+
+    /// Whether the package with this manifest is one of the default members
+    pub fn is_default_member(&self, manifest: &Path) -> bool {
+        self.default_members.iter().any(|m| m == manifest)
+    }
+
+    /// Whether `exclude` keeps this manifest out of the workspace
+    ///
+    /// As in Cargo, a package listed literally under `members` stays a member
+    /// even when it sits below an excluded path.
+    pub fn is_excluded(&self, manifest: &Path) -> bool {
+        let Some(dir) = manifest.parent() else {
+            return false;
+        };
+        let excluded = self.exclude.iter().any(|ex| dir.starts_with(ex));
+        let explicit = self.explicit_members.iter().any(|m| dir.starts_with(m));
+        excluded && !explicit
+    }
+}
+
+/// All workspaces found during a scan, keyed by root directory
+#[derive(Debug, Default)]
+pub struct WorkspaceIndex {
+    workspaces: HashMap<PathBuf, ResolvedWorkspace>,
+    /// Directories whose Cargo.toml has already been looked at
+    inspected: HashSet<PathBuf>,
+}
+
+impl WorkspaceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a manifest if it is a workspace root
+    pub fn insert(&mut self, manifest: &Path, toml: &CargoToml) {
+        if let Some(dir) = manifest.parent() {
+            if !self.inspected.insert(normalize(dir)) {
+                return;
+            }
+        }
+        if let Some(workspace) = ResolvedWorkspace::resolve(manifest, toml) {
+            self.workspaces.insert(workspace.root.clone(), workspace);
+        }
+    }
+
+    /// Register workspace roots above `dir`, so scanning inside a workspace still
+    /// finds the root it belongs to
+    pub fn insert_ancestors(&mut self, dir: &Path) {
+        for ancestor in normalize(dir).ancestors().skip(1) {
+            if !self.inspected.contains(ancestor) {
+                self.insert_dir(ancestor);
+            }
+        }
+    }
+
+    /// Register every workspace root that could claim this manifest
+    ///
+    /// Lets manifests be fed in any order: the package's own root, its
+    /// ancestors and any `package.workspace` target are read from disk as needed.
+    pub fn discover(&mut self, manifest: &Path, toml: &CargoToml) {
+        self.insert(manifest, toml);
+        let Some(dir) = manifest.parent() else {
+            return;
+        };
+        self.insert_ancestors(dir);
+        if let Some(root) = toml.package.as_ref().and_then(|p| p.workspace.as_ref()) {
+            let root = normalize(&dir.join(root));
+            if !self.inspected.contains(&root) {
+                self.insert_dir(&root);
+            }
+        }
+    }
+
+    fn insert_dir(&mut self, dir: &Path) {
+        let manifest = dir.join("Cargo.toml");
+        match read_manifest(&manifest) {
+            Some(toml) => self.insert(&manifest, &toml),
+            None => {
+                self.inspected.insert(dir.to_path_buf());
+            }
+        }
+    }
+
+    /// Find the workspace a package belongs to, if any
+    ///
+    /// Mirrors Cargo's root search: an explicit `package.workspace` wins,
+    /// otherwise the nearest workspace root above the package that doesn't
+    /// exclude it. A workspace root's own package belongs to that workspace.
+    pub fn workspace_for(&self, manifest: &Path, toml: &CargoToml) -> Option<&ResolvedWorkspace> {
+        let manifest = normalize(manifest);
+        let dir = manifest.parent()?;
+
+        if toml.workspace.is_some() {
+            return self.workspaces.get(dir);
+        }
+
+        if let Some(root) = toml.package.as_ref().and_then(|p| p.workspace.as_ref()) {
+            return self.workspaces.get(&normalize(&dir.join(root)));
+        }
+
+        for ancestor in dir.ancestors().skip(1) {
+            let Some(workspace) = self.workspaces.get(ancestor) else {
+                continue;
+            };
+            if workspace.contains(&manifest) {
+                return Some(workspace);
+            }
+            if !workspace.is_excluded(&manifest) {
+                // Cargo would reject this package; treat it as standalone
+                return None;
+            }
+        }
+        None
+    }
+}
+
+/// Parse a Cargo.toml, returning `None` if it is missing or malformed
+pub fn read_manifest(manifest: &Path) -> Option<CargoToml> {
+    let content = fs::read_to_string(manifest).ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// Whether a `members` entry is a glob pattern
+fn is_glob(member: &str) -> bool {
+    member.contains(['*', '?', '['])
+}
+
+/// Expand a `members` / `default-members` entry into existing package directories
+fn expand_member(root: &Path, member: &str) -> Vec<PathBuf> {
+    if !is_glob(member) {
+        let dir = normalize(&root.join(member));
+        return if dir.join("Cargo.toml").is_file() {
+            vec![dir]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let pattern = format!(
+        "{}/{}",
+        glob::Pattern::escape(&root.to_string_lossy()),
+        member
+    );
+    let mut dirs: Vec<PathBuf> = match glob::glob(&pattern) {
+        Ok(paths) => paths
+            .filter_map(|p| p.ok())
+            .map(|p| normalize(&p))
+            .filter(|p| p.join("Cargo.toml").is_file())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    dirs.sort();
+    dirs
+}
+
+/// Directories of the `path` dependencies declared in a manifest
+///
+/// `workspace = true` entries are looked up in the root's
+/// `[workspace.dependencies]`, whose paths are relative to the root.
+fn path_dependencies(dir: &Path, toml: &CargoToml, root: &Path) -> Vec<PathBuf> {
+    let root_deps =
+        read_manifest(&root.join("Cargo.toml")).and_then(|root_toml| root_toml.workspace);
+
+    toml.dependencies
+        .iter()
+        .chain(&toml.dev_dependencies)
+        .chain(&toml.build_dependencies)
+        .filter_map(|(name, spec)| {
+            let table = spec.as_table()?;
+            if let Some(path) = table.get("path").and_then(|p| p.as_str()) {
+                return Some(normalize(&dir.join(path)));
+            }
+            if table.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+                let path = root_deps
+                    .as_ref()?
+                    .dependencies
+                    .get(name)?
+                    .get("path")?
+                    .as_str()?;
+                return Some(normalize(&root.join(path)));
+            }
+            None
+        })
+        .collect()
+}
+
+/// Lexically resolve `.` and `..` so paths from different sources compare equal
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::find_rust_projects;
+
+    fn fixture(layout: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/workspaces")
+            .join(layout)
+    }
+
+    fn resolve(root: &Path) -> ResolvedWorkspace {
+        let manifest = root.join("Cargo.toml");
+        ResolvedWorkspace::resolve(&manifest, &read_manifest(&manifest).unwrap()).unwrap()
+    }
+
+    fn member_names(root: &Path, manifests: &[PathBuf]) -> Vec<String> {
+        manifests
+            .iter()
+            .map(|m| {
+                m.parent()
+                    .unwrap()
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    /// (project name, workspace name) for every discovered project, sorted
+    fn discovered(root: &Path) -> Vec<(String, Option<String>)> {
+        let mut projects: Vec<_> = find_rust_projects(root.to_str().unwrap())
+            .into_iter()
+            .map(|p| (p.name, p.workspace_name))
+            .collect();
+        projects.sort();
+        projects
+    }
+
+    #[test]
+    fn test_glob_members() {
+        let root = fixture("glob");
+        let workspace = resolve(&root);
+
+        assert_eq!(
+            member_names(&root, &workspace.members),
+            ["crates/alpha", "crates/beta", "tools/cli"]
+        );
+        assert_eq!(workspace.default_members, workspace.members);
+
+        let projects = discovered(&root);
+        assert_eq!(projects.len(), 3);
+        assert!(projects.iter().all(|(_, ws)| ws.as_deref() == Some("glob")));
+    }
+
+    #[test]
+    fn test_exclude() {
+        let root = fixture("exclude");
+        let workspace = resolve(&root);
+
+        // `crates/legacy` is excluded; `crates/experimental/kept` is listed literally
+        assert_eq!(
+            member_names(&root, &workspace.members),
+            ["crates/core", "crates/experimental/kept"]
+        );
+        assert!(workspace.is_excluded(&root.join("crates/legacy/Cargo.toml")));
+
+        let projects = discovered(&root);
+        assert!(projects.contains(&("legacy".to_string(), None)));
+        assert!(projects.contains(&("kept".to_string(), Some("exclude".to_string()))));
+    }
+
+    #[test]
+    fn test_default_members() {
+        let root = fixture("default_members");
+        let workspace = resolve(&root);
+
+        assert_eq!(
+            member_names(&root, &workspace.members),
+            ["", "crates/app", "crates/lib"]
+        );
+        assert_eq!(
+            member_names(&root, &workspace.default_members),
+            ["crates/app"]
+        );
+        assert!(!workspace.is_default_member(&root.join("Cargo.toml")));
+    }
+
+    #[test]
+    fn test_root_package_is_default_member() {
+        let root = fixture("path_deps");
+        let workspace = resolve(&root);
+
+        assert_eq!(member_names(&root, &workspace.default_members), [""]);
+    }
+
+    #[test]
+    fn test_path_dependencies_are_members() {
+        let root = fixture("path_deps");
+        let workspace = resolve(&root);
+
+        // `shared` via a direct path, `macros` via `[workspace.dependencies]`;
+        // `outside` lives above the root and stays standalone
+        assert_eq!(
+            member_names(&root, &workspace.members),
+            ["", "crates/macros", "crates/shared"]
+        );
+
+        let projects = discovered(&root);
+        assert!(projects.contains(&("shared".to_string(), Some("app".to_string()))));
+        assert!(projects.contains(&("macros".to_string(), Some("app".to_string()))));
+    }
+
+    #[test]
+    fn test_nested_workspaces() {
+        let root = fixture("nested");
+        let projects = discovered(&root);
+
+        assert!(projects.contains(&("outer-member".to_string(), Some("nested".to_string()))));
+        assert!(projects.contains(&("inner-root".to_string(), Some("inner-root".to_string()))));
+        assert!(projects.contains(&("inner-member".to_string(), Some("inner-root".to_string()))));
+    }
+
+    #[test]
+    fn test_package_workspace_back_pointer() {
+        let root = fixture("back_pointer");
+        let projects = discovered(&root.join("ws"));
+
+        // The member lives outside the root directory and points back at it
+        let index = {
+            let mut index = WorkspaceIndex::new();
+            let manifest = root.join("ws/Cargo.toml");
+            index.insert(&manifest, &read_manifest(&manifest).unwrap());
+            index
+        };
+        let member = root.join("external/Cargo.toml");
+        let workspace = index
+            .workspace_for(&member, &read_manifest(&member).unwrap())
+            .unwrap();
+        assert_eq!(workspace.root, normalize(&root.join("ws")));
+        assert!(workspace.contains(&member));
+        assert_eq!(
+            projects,
+            [("ws-main".to_string(), Some("ws-main".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_scanning_inside_workspace_finds_root() {
+        let root = fixture("glob");
+        let projects = discovered(&root.join("crates"));
+
+        assert_eq!(
+            projects,
+            [
+                ("alpha".to_string(), Some("glob".to_string())),
+                ("beta".to_string(), Some("glob".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_members_inherit_package_fields_and_dependencies() {
+        let projects = find_rust_projects(fixture("inherited").to_str().unwrap());
+        let app = &projects[0];
+
+        assert_eq!(app.version, "1.2.0");
+        assert_eq!(app.authors, ["Jane Doe"]);
+        assert_eq!(app.edition.as_deref(), Some("2021"));
+        assert_eq!(app.rust_version.as_deref(), Some("1.80"));
+        assert_eq!(app.license.as_deref(), Some("MIT OR Apache-2.0"));
+
+        let serde = app.dependencies.iter().find(|d| d.name == "serde").unwrap();
+        assert_eq!(serde.requirement.as_deref(), Some("1.0.200"));
+        // Member features add to the workspace ones
+        assert_eq!(serde.features, ["derive", "rc"]);
+        assert!(!serde.is_major_bump("1.0.215"));
+
+        // An exact requirement makes any newer version need a Cargo.toml change
+        let tokio = app.dependencies.iter().find(|d| d.name == "tokio").unwrap();
+        assert!(tokio.is_major_bump("1.41.0"));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("/a/b/./c/../d")),
+            PathBuf::from("/a/b/d")
+        );
+    }
+}